@@ -0,0 +1,26 @@
+// nChat Core — locale-aware string ordering (ICU4X collation)
+//
+// Channel and member lists sorted by byte order put "Émile" after "zebra"
+// and scatter CJK names; the Unicode Collation Algorithm with the user's
+// locale tailoring sorts them the way people expect.
+
+use icu_collator::{Collator, CollatorOptions, Numeric};
+use icu_locid::Locale;
+
+/// Collator for `locale` (a BCP 47 tag such as `de`, `sv-SE`, `zh-Hans`).
+/// Digit runs compare numerically so `general-2` sorts before `general-10`.
+pub fn collator(locale: &str) -> Result<Collator, String> {
+    let locale: Locale = locale
+        .parse()
+        .map_err(|e| format!("invalid locale {locale}: {e}"))?;
+    let mut options = CollatorOptions::new();
+    options.numeric = Some(Numeric::On);
+    Collator::try_new(&(&locale).into(), options).map_err(|e| e.to_string())
+}
+
+/// Sort `strings` in place for `locale`.
+pub fn sort(strings: &mut [String], locale: &str) -> Result<(), String> {
+    let collator = collator(locale)?;
+    strings.sort_by(|a, b| collator.compare(a, b));
+    Ok(())
+}
@@ -0,0 +1,218 @@
+// Passphrase-encrypted key backup envelope.
+//
+// The key store is sealed with XChaCha20-Poly1305 under a key derived from
+// the user's passphrase with Argon2id. The KDF parameters travel with the
+// envelope so they can be raised later without breaking old backups. The
+// envelope also carries an HMAC-SHA256 over its fields, keyed from the same
+// passphrase, so the server can store it for integrity checks that nobody
+// without the passphrase can forge. `ServerBackup` is the same envelope in
+// the `/api/keys/backup` wire format.
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::{engine::general_purpose::STANDARD as B64, Engine};
+use chacha20poly1305::{
+    aead::{rand_core::RngCore, Aead, AeadCore, KeyInit, OsRng},
+    XChaCha20Poly1305, XNonce,
+};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+const ENVELOPE_VERSION: u32 = 1;
+const SALT_LEN: usize = 16;
+
+/// Label separating the MAC key from the encryption key.
+const MAC_KEY_LABEL: &[u8] = b"nchat-key-backup-mac";
+
+/// Argon2id cost: 64 MiB, 3 passes, 1 lane.
+const DEFAULT_M_COST_KIB: u32 = 64 * 1024;
+const DEFAULT_T_COST: u32 = 3;
+const DEFAULT_P_COST: u32 = 1;
+
+/// Highest Argon2id cost accepted from a stored backup, so a hostile one
+/// cannot make key derivation allocate gigabytes or run for minutes.
+const MAX_M_COST_KIB: u32 = 256 * 1024;
+const MAX_T_COST: u32 = 10;
+const MAX_P_COST: u32 = 4;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BackupEnvelope {
+    pub version: u32,
+    pub m_cost_kib: u32,
+    pub t_cost: u32,
+    pub p_cost: u32,
+    /// Base64 fields.
+    pub salt: String,
+    pub nonce: String,
+    pub ciphertext: String,
+    /// HMAC-SHA256 over the fields above.
+    pub mac: String,
+}
+
+/// Argon2id parameters as the server stores them.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct KdfParams {
+    pub m_cost_kib: u32,
+    pub t_cost: u32,
+    pub p_cost: u32,
+}
+
+/// A backup as `POST /api/keys/backup` takes it and `GET` returns it.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerBackup {
+    pub key_version: u32,
+    /// Base64 fields.
+    pub encrypted_data: String,
+    pub salt: String,
+    pub iv: String,
+    pub hmac: String,
+    pub kdf: KdfParams,
+}
+
+impl From<BackupEnvelope> for ServerBackup {
+    fn from(envelope: BackupEnvelope) -> Self {
+        ServerBackup {
+            key_version: envelope.version,
+            encrypted_data: envelope.ciphertext,
+            salt: envelope.salt,
+            iv: envelope.nonce,
+            hmac: envelope.mac,
+            kdf: KdfParams {
+                m_cost_kib: envelope.m_cost_kib,
+                t_cost: envelope.t_cost,
+                p_cost: envelope.p_cost,
+            },
+        }
+    }
+}
+
+impl From<ServerBackup> for BackupEnvelope {
+    fn from(backup: ServerBackup) -> Self {
+        BackupEnvelope {
+            version: backup.key_version,
+            m_cost_kib: backup.kdf.m_cost_kib,
+            t_cost: backup.kdf.t_cost,
+            p_cost: backup.kdf.p_cost,
+            salt: backup.salt,
+            nonce: backup.iv,
+            ciphertext: backup.encrypted_data,
+            mac: backup.hmac,
+        }
+    }
+}
+
+fn derive_key(
+    passphrase: &str,
+    salt: &[u8],
+    m_cost_kib: u32,
+    t_cost: u32,
+    p_cost: u32,
+) -> Result<[u8; 32], String> {
+    let params = Params::new(m_cost_kib, t_cost, p_cost, Some(32)).map_err(|e| e.to_string())?;
+    let mut key = [0u8; 32];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| e.to_string())?;
+    Ok(key)
+}
+
+/// HMAC-SHA256 of the envelope fields under a key derived from the
+/// passphrase key.
+fn envelope_mac(
+    key: &[u8; 32],
+    version: u32,
+    salt: &[u8],
+    nonce: &[u8],
+    ciphertext: &[u8],
+) -> HmacSha256 {
+    let mut kdf = <HmacSha256 as Mac>::new_from_slice(key).expect("HMAC accepts any key length");
+    kdf.update(MAC_KEY_LABEL);
+    let mac_key = kdf.finalize().into_bytes();
+
+    let mut mac =
+        <HmacSha256 as Mac>::new_from_slice(&mac_key).expect("HMAC accepts any key length");
+    mac.update(&version.to_be_bytes());
+    for field in [salt, nonce, ciphertext] {
+        mac.update(&(field.len() as u64).to_be_bytes());
+        mac.update(field);
+    }
+    mac
+}
+
+/// Encrypt `plaintext` under `passphrase`.
+pub fn seal(passphrase: &str, plaintext: &[u8]) -> Result<BackupEnvelope, String> {
+    if passphrase.chars().count() < 8 {
+        return Err("passphrase must be at least 8 characters".into());
+    }
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let key = derive_key(
+        passphrase,
+        &salt,
+        DEFAULT_M_COST_KIB,
+        DEFAULT_T_COST,
+        DEFAULT_P_COST,
+    )?;
+
+    let cipher = XChaCha20Poly1305::new_from_slice(&key).map_err(|e| e.to_string())?;
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext)
+        .map_err(|_| "encryption failed".to_string())?;
+    let mac = envelope_mac(&key, ENVELOPE_VERSION, &salt, &nonce, &ciphertext)
+        .finalize()
+        .into_bytes();
+
+    Ok(BackupEnvelope {
+        version: ENVELOPE_VERSION,
+        m_cost_kib: DEFAULT_M_COST_KIB,
+        t_cost: DEFAULT_T_COST,
+        p_cost: DEFAULT_P_COST,
+        salt: B64.encode(salt),
+        nonce: B64.encode(nonce),
+        ciphertext: B64.encode(ciphertext),
+        mac: B64.encode(mac),
+    })
+}
+
+/// Decrypt an envelope after checking its MAC. A wrong passphrase and a
+/// tampered blob both fail authentication and are reported the same way.
+pub fn open(passphrase: &str, envelope: &BackupEnvelope) -> Result<Vec<u8>, String> {
+    if envelope.version != ENVELOPE_VERSION {
+        return Err(format!("unsupported backup version {}", envelope.version));
+    }
+    if envelope.m_cost_kib > MAX_M_COST_KIB
+        || envelope.t_cost > MAX_T_COST
+        || envelope.p_cost > MAX_P_COST
+    {
+        return Err("backup key derivation parameters are too costly".into());
+    }
+    let salt = B64.decode(&envelope.salt).map_err(|e| e.to_string())?;
+    let nonce = B64.decode(&envelope.nonce).map_err(|e| e.to_string())?;
+    let ciphertext = B64
+        .decode(&envelope.ciphertext)
+        .map_err(|e| e.to_string())?;
+    if nonce.len() != 24 {
+        return Err("malformed backup nonce".into());
+    }
+
+    let key = derive_key(
+        passphrase,
+        &salt,
+        envelope.m_cost_kib,
+        envelope.t_cost,
+        envelope.p_cost,
+    )?;
+    let mac = B64.decode(&envelope.mac).map_err(|e| e.to_string())?;
+    envelope_mac(&key, envelope.version, &salt, &nonce, &ciphertext)
+        .verify_slice(&mac)
+        .map_err(|_| "wrong passphrase or corrupted backup".to_string())?;
+    let cipher = XChaCha20Poly1305::new_from_slice(&key).map_err(|e| e.to_string())?;
+    cipher
+        .decrypt(XNonce::from_slice(&nonce), ciphertext.as_ref())
+        .map_err(|_| "wrong passphrase or corrupted backup".to_string())
+}
@@ -0,0 +1,91 @@
+// Fingerprint generation for out-of-band device verification.
+//
+// Numeric codes follow Signal's safety-number construction (iterated
+// SHA-512, 5-digit groups); emoji codes use the 64-entry Matrix SAS table
+// so users comparing on different clients see the same symbols.
+
+use serde::Serialize;
+use sha2::{Digest, Sha512};
+
+/// Version prefix mixed into every digest so the format can change later.
+const FINGERPRINT_VERSION: u16 = 0;
+
+/// Hash iterations; matches Signal's cost so brute-forcing collisions
+/// stays expensive.
+const ITERATIONS: usize = 5200;
+
+/// Number of emoji shown to the user (7 × 6 bits = 42 bits).
+const EMOJI_COUNT: usize = 7;
+
+#[rustfmt::skip]
+const SAS_EMOJI: [(&str, &str); 64] = [
+    ("🐶", "Dog"), ("🐱", "Cat"), ("🦁", "Lion"), ("🐎", "Horse"),
+    ("🦄", "Unicorn"), ("🐷", "Pig"), ("🐘", "Elephant"), ("🐰", "Rabbit"),
+    ("🐼", "Panda"), ("🐓", "Rooster"), ("🐧", "Penguin"), ("🐢", "Turtle"),
+    ("🐟", "Fish"), ("🐙", "Octopus"), ("🦋", "Butterfly"), ("🌷", "Flower"),
+    ("🌳", "Tree"), ("🌵", "Cactus"), ("🍄", "Mushroom"), ("🌏", "Globe"),
+    ("🌙", "Moon"), ("☁️", "Cloud"), ("🔥", "Fire"), ("🍌", "Banana"),
+    ("🍎", "Apple"), ("🍓", "Strawberry"), ("🌽", "Corn"), ("🍕", "Pizza"),
+    ("🎂", "Cake"), ("❤️", "Heart"), ("😀", "Smiley"), ("🤖", "Robot"),
+    ("🎩", "Hat"), ("👓", "Glasses"), ("🔧", "Spanner"), ("🎅", "Santa"),
+    ("👍", "Thumbs Up"), ("☂️", "Umbrella"), ("⌛", "Hourglass"), ("⏰", "Clock"),
+    ("🎁", "Gift"), ("💡", "Light Bulb"), ("📕", "Book"), ("✏️", "Pencil"),
+    ("📎", "Paperclip"), ("✂️", "Scissors"), ("🔒", "Lock"), ("🔑", "Key"),
+    ("🔨", "Hammer"), ("☎️", "Telephone"), ("🏁", "Flag"), ("🚂", "Train"),
+    ("🚲", "Bicycle"), ("✈️", "Aeroplane"), ("🚀", "Rocket"), ("🏆", "Trophy"),
+    ("⚽", "Ball"), ("🎸", "Guitar"), ("🎺", "Trumpet"), ("🔔", "Bell"),
+    ("⚓", "Anchor"), ("🎧", "Headphones"), ("📁", "Folder"), ("📌", "Pin"),
+];
+
+#[derive(Serialize, Clone)]
+pub struct EmojiCode {
+    pub emoji: &'static str,
+    pub name: &'static str,
+}
+
+/// Iterated digest of (version, identity key, owner id).
+fn digest(public_key: &[u8], owner: &str) -> Vec<u8> {
+    let mut hash = {
+        let mut h = Sha512::new();
+        h.update(FINGERPRINT_VERSION.to_be_bytes());
+        h.update(public_key);
+        h.update(owner.as_bytes());
+        h.finalize().to_vec()
+    };
+    for _ in 1..ITERATIONS {
+        let mut h = Sha512::new();
+        h.update(&hash);
+        h.update(public_key);
+        hash = h.finalize().to_vec();
+    }
+    hash
+}
+
+/// 30-digit numeric fingerprint, formatted as six groups of five digits.
+pub fn numeric(public_key: &[u8], owner: &str) -> String {
+    digest(public_key, owner)
+        .chunks(5)
+        .take(6)
+        .map(|chunk| {
+            let n = chunk.iter().fold(0u64, |acc, b| (acc << 8) | *b as u64);
+            format!("{:05}", n % 100_000)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Emoji fingerprint: consecutive 6-bit indices into the SAS table.
+pub fn emoji(public_key: &[u8], owner: &str) -> Vec<EmojiCode> {
+    let hash = digest(public_key, owner);
+    let bits = hash
+        .iter()
+        .take(6)
+        .fold(0u64, |acc, b| (acc << 8) | *b as u64);
+    (0..EMOJI_COUNT)
+        .map(|i| {
+            let index = (bits >> (48 - 6 * (i + 1))) & 0x3F;
+            let (emoji, name) = SAS_EMOJI[index as usize];
+            EmojiCode { emoji, name }
+        })
+        .collect()
+}
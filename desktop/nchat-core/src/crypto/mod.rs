@@ -0,0 +1,5 @@
+// nChat Core — E2EE support primitives: key backup envelopes and device
+// fingerprints.
+
+pub mod backup;
+pub mod fingerprint;
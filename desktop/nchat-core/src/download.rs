@@ -0,0 +1,86 @@
+// nChat Core — resumable, verified file downloads
+//
+// A download is written to `<dest>.part` and resumes from that file's
+// length with an HTTP Range request after an interruption. The file is
+// moved into place only once its SHA-256 matches the expected digest.
+
+use sha2::{Digest, Sha256};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+fn part_path(dest: &Path) -> PathBuf {
+    let mut part = dest.as_os_str().to_owned();
+    part.push(".part");
+    PathBuf::from(part)
+}
+
+fn sha256_file(path: &Path) -> std::io::Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buf = [0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect())
+}
+
+/// Download `url` to `dest`, verifying it against `sha256` (hex).
+/// `on_progress` receives the bytes on disk so far and the total size when
+/// the server reports it.
+pub async fn fetch(
+    client: &reqwest::Client,
+    url: &str,
+    dest: &Path,
+    sha256: &str,
+    mut on_progress: impl FnMut(u64, Option<u64>),
+) -> Result<(), String> {
+    let part = part_path(dest);
+    let mut offset = std::fs::metadata(&part).map(|m| m.len()).unwrap_or(0);
+
+    let mut request = client.get(url);
+    if offset > 0 {
+        request = request.header(reqwest::header::RANGE, format!("bytes={offset}-"));
+    }
+    let mut res = request.send().await.map_err(|e| e.to_string())?;
+
+    match res.status() {
+        // The partial file already holds everything; just verify it.
+        reqwest::StatusCode::RANGE_NOT_SATISFIABLE if offset > 0 => {}
+        status if status.is_success() => {
+            let resumed = status == reqwest::StatusCode::PARTIAL_CONTENT;
+            if !resumed {
+                offset = 0;
+            }
+            let total = res.content_length().map(|len| len + offset);
+            let mut file = std::fs::OpenOptions::new()
+                .create(true)
+                .write(true)
+                .append(resumed)
+                .truncate(!resumed)
+                .open(&part)
+                .map_err(|e| e.to_string())?;
+            while let Some(chunk) = res.chunk().await.map_err(|e| e.to_string())? {
+                file.write_all(&chunk).map_err(|e| e.to_string())?;
+                offset += chunk.len() as u64;
+                on_progress(offset, total);
+            }
+            file.sync_all().map_err(|e| e.to_string())?;
+        }
+        status => return Err(format!("download failed with HTTP {status}")),
+    }
+
+    let digest = sha256_file(&part).map_err(|e| e.to_string())?;
+    if !digest.eq_ignore_ascii_case(sha256) {
+        let _ = std::fs::remove_file(&part);
+        return Err(format!("checksum mismatch for {url}"));
+    }
+    std::fs::rename(&part, dest).map_err(|e| e.to_string())
+}
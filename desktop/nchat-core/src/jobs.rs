@@ -0,0 +1,106 @@
+// nChat Core — schedules for background jobs
+//
+// The shell runs the timers; this module decides what is due. Disappearing
+// messages carry persisted deadlines that fire in order and survive
+// restarts, and the opt-in digest fires once a day at a local time of day.
+
+use chrono::{NaiveDateTime, NaiveTime};
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Unix time in milliseconds.
+pub fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PendingExpiry {
+    pub message_id: String,
+    pub channel_id: String,
+    /// Unix time in milliseconds.
+    pub expires_at: u64,
+    #[serde(default)]
+    pub attachments: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ExpiredMessage {
+    pub message_id: String,
+    pub channel_id: String,
+    pub expired_at: u64,
+}
+
+impl PendingExpiry {
+    pub fn expired(self, now: u64) -> ExpiredMessage {
+        ExpiredMessage {
+            message_id: self.message_id,
+            channel_id: self.channel_id,
+            expired_at: now,
+        }
+    }
+}
+
+/// Add a deadline. Re-scheduling a message replaces its previous deadline.
+pub fn schedule_expiry(pending: &mut Vec<PendingExpiry>, entry: PendingExpiry) {
+    pending.retain(|p| p.message_id != entry.message_id);
+    pending.push(entry);
+}
+
+/// Split `pending` into the entries due at `now` and the rest.
+pub fn due_expiries(
+    pending: Vec<PendingExpiry>,
+    now: u64,
+) -> (Vec<PendingExpiry>, Vec<PendingExpiry>) {
+    pending.into_iter().partition(|p| p.expires_at <= now)
+}
+
+/// The earliest deadline in `pending`, if any.
+pub fn next_expiry(pending: &[PendingExpiry]) -> Option<u64> {
+    pending.iter().map(|p| p.expires_at).min()
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DigestSettings {
+    pub enabled: bool,
+    /// Local time of day, `HH:MM`.
+    pub time: String,
+}
+
+impl Default for DigestSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            time: "08:00".into(),
+        }
+    }
+}
+
+impl DigestSettings {
+    pub fn validate(&self) -> Result<(), String> {
+        parse_time(&self.time)
+            .map(|_| ())
+            .ok_or_else(|| format!("invalid digest time {}; expected HH:MM", self.time))
+    }
+}
+
+fn parse_time(time: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(time, "%H:%M").ok()
+}
+
+/// Whether the digest is due at local time `now`, given the day it was
+/// last shown (`YYYY-MM-DD`). Returns the day to record as shown.
+pub fn digest_due(
+    digest: &DigestSettings,
+    now: NaiveDateTime,
+    last_shown: Option<&str>,
+) -> Option<String> {
+    if !digest.enabled {
+        return None;
+    }
+    let at = parse_time(&digest.time)?;
+    let today = now.date().to_string();
+    (now.time() >= at && last_shown != Some(today.as_str())).then_some(today)
+}
@@ -0,0 +1,31 @@
+// nChat Core — OS keychain access for session credentials
+
+/// Service name under which all nChat secrets are stored.
+const SERVICE: &str = "org.nself.chat";
+
+/// Store a secret for `account` in the platform keychain
+/// (Keychain on macOS, Credential Manager on Windows, Secret Service on Linux).
+pub fn store_secret(account: &str, secret: &str) -> Result<(), String> {
+    keyring::Entry::new(SERVICE, account)
+        .and_then(|entry| entry.set_password(secret))
+        .map_err(|e| e.to_string())
+}
+
+/// Read the secret for `account`, or `None` if nothing is stored.
+pub fn read_secret(account: &str) -> Result<Option<String>, String> {
+    let entry = keyring::Entry::new(SERVICE, account).map_err(|e| e.to_string())?;
+    match entry.get_password() {
+        Ok(secret) => Ok(Some(secret)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Remove the secret for `account`. Missing entries are not an error.
+pub fn delete_secret(account: &str) -> Result<(), String> {
+    let entry = keyring::Entry::new(SERVICE, account).map_err(|e| e.to_string())?;
+    match entry.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(e.to_string()),
+    }
+}
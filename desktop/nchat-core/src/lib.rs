@@ -0,0 +1,20 @@
+// nChat Core — chat-agnostic native subsystems
+//
+// Everything here is plain Rust with no dependency on Tauri, so the
+// desktop shell and the mobile entry point can share it: collation,
+// backup encryption and device fingerprints, verified downloads, keychain
+// access, message quoting, settings and portable configuration,
+// notification rules and background-job schedules. The shell is the
+// binding layer: it provides the storage, windows, timers and events and
+// calls into this crate. The message cache and outbox live in the web
+// client and are not part of the native shell.
+
+pub mod collation;
+pub mod crypto;
+pub mod download;
+pub mod jobs;
+#[cfg(feature = "keychain")]
+pub mod keychain;
+pub mod notifications;
+pub mod quote;
+pub mod settings;
@@ -0,0 +1,80 @@
+// nChat Core — notification rules during focus sessions
+//
+// A focus session holds every non-urgent notification until it ends, then
+// a single recap summarizes what was held. The shell persists the session
+// and the held buffer and shows the notifications; the rules for how long
+// a session may last, what is held and what the recap names live here.
+
+use serde::{Deserialize, Serialize};
+
+/// Longest focus session, in minutes.
+pub const MAX_MINUTES: u32 = 8 * 60;
+/// Oldest held notifications are dropped beyond this.
+pub const MAX_HELD: usize = 200;
+/// Titles named in the recap body.
+pub const RECAP_TITLES: usize = 3;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct FocusSession {
+    /// Unix time in milliseconds.
+    pub started_at: u64,
+    pub ends_at: u64,
+    /// Status to restore when the session ends.
+    pub previous_status: String,
+}
+
+impl FocusSession {
+    /// A session of `minutes` starting at `now`. Restarting a running
+    /// session passes the status from before the first one.
+    pub fn new(now: u64, minutes: u32, previous_status: String) -> Result<Self, String> {
+        if minutes == 0 || minutes > MAX_MINUTES {
+            return Err(format!("focus sessions last 1 to {MAX_MINUTES} minutes"));
+        }
+        Ok(Self {
+            started_at: now,
+            ends_at: now + u64::from(minutes) * 60_000,
+            previous_status,
+        })
+    }
+
+    pub fn has_ended(&self, now: u64) -> bool {
+        self.ends_at <= now
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct HeldNotification {
+    pub title: String,
+    pub body: Option<String>,
+    pub held_at: u64,
+}
+
+/// Whether a notification is held rather than shown. Urgent ones always
+/// get through.
+pub fn should_hold(session: Option<&FocusSession>, urgent: bool) -> bool {
+    session.is_some() && !urgent
+}
+
+/// Add a notification to the held buffer, dropping the oldest past
+/// `MAX_HELD`.
+pub fn hold(held: &mut Vec<HeldNotification>, notification: HeldNotification) {
+    held.push(notification);
+    if held.len() > MAX_HELD {
+        held.drain(..held.len() - MAX_HELD);
+    }
+}
+
+/// Distinct titles for the recap, most recent first, at most
+/// `RECAP_TITLES`.
+pub fn recap_titles(held: &[HeldNotification]) -> Vec<&str> {
+    let mut titles: Vec<&str> = Vec::new();
+    for n in held.iter().rev() {
+        if titles.len() == RECAP_TITLES {
+            break;
+        }
+        if !titles.contains(&n.title.as_str()) {
+            titles.push(&n.title);
+        }
+    }
+    titles
+}
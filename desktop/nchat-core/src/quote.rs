@@ -0,0 +1,89 @@
+// nChat Core — message quotes for pasting elsewhere
+//
+// Selected messages are rendered as quote blocks with author and time
+// attribution, in Markdown and HTML, so they paste cleanly into email
+// clients and documents.
+
+use chrono::{DateTime, Local};
+use serde::Deserialize;
+
+/// A message as selected in the conversation view.
+#[derive(Deserialize)]
+pub struct QuotedMessage {
+    pub author: String,
+    /// Unix time in milliseconds.
+    pub sent_at: i64,
+    pub body: String,
+}
+
+/// Text flavor placed on the clipboard next to the HTML rendering.
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum QuoteFormat {
+    Markdown,
+    Html,
+}
+
+fn local_time(ms: i64) -> Option<DateTime<Local>> {
+    DateTime::from_timestamp_millis(ms).map(|t| t.with_timezone(&Local))
+}
+
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// One `>`-prefixed block per message, separated by blank lines.
+pub fn markdown(messages: &[QuotedMessage]) -> String {
+    messages
+        .iter()
+        .map(|m| {
+            let mut block = format!("> **{}**", m.author.replace('*', "\\*"));
+            if let Some(time) = local_time(m.sent_at) {
+                block.push_str(&format!(" — {}", time.format("%Y-%m-%d %H:%M")));
+            }
+            for line in m.body.lines() {
+                block.push_str("\n>");
+                if !line.is_empty() {
+                    block.push(' ');
+                    block.push_str(line);
+                }
+            }
+            block
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// One `<blockquote>` per message; the body is escaped, not interpreted.
+pub fn html(messages: &[QuotedMessage]) -> String {
+    messages
+        .iter()
+        .map(|m| {
+            let mut attribution = format!("<strong>{}</strong>", escape_html(&m.author));
+            if let Some(time) = local_time(m.sent_at) {
+                attribution.push_str(&format!(
+                    " <time datetime=\"{}\">{}</time>",
+                    time.to_rfc3339(),
+                    time.format("%Y-%m-%d %H:%M")
+                ));
+            }
+            let body = m
+                .body
+                .lines()
+                .map(escape_html)
+                .collect::<Vec<_>>()
+                .join("<br>");
+            format!("<blockquote><p>{attribution}</p><p>{body}</p></blockquote>")
+        })
+        .collect()
+}
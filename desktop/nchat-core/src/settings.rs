@@ -0,0 +1,139 @@
+// nChat Core — persisted native settings and portable configuration
+//
+// Settings are JSON values under string keys. Each shell brings its own
+// storage by implementing `Store` (the desktop app wraps tauri-plugin-store)
+// and reads and writes typed values through the helpers here. A portable
+// configuration is the user-facing part of the settings as one versioned
+// JSON file, for IT-distributed defaults and for moving preferences between
+// machines.
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Key-value storage for settings. Writes are flushed before they return.
+pub trait Store {
+    fn get(&self, key: &str) -> Option<Value>;
+    fn set(&self, key: &str, value: Value) -> Result<(), String>;
+    /// Missing keys are not an error.
+    fn delete(&self, key: &str) -> Result<(), String>;
+    fn entries(&self) -> Vec<(String, Value)>;
+
+    fn has(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+}
+
+/// Read and deserialize a setting. Returns `None` if the key is missing or
+/// the stored value no longer matches `T` (e.g. after a schema change).
+pub fn load<T: DeserializeOwned>(store: &(impl Store + ?Sized), key: &str) -> Option<T> {
+    store
+        .get(key)
+        .and_then(|value| serde_json::from_value(value).ok())
+}
+
+/// Serialize a setting and write it.
+pub fn save<T: Serialize>(
+    store: &(impl Store + ?Sized),
+    key: &str,
+    value: &T,
+) -> Result<(), String> {
+    let json = serde_json::to_value(value).map_err(|e| e.to_string())?;
+    store.set(key, json)
+}
+
+/// Bumped when the configuration file layout changes incompatibly.
+pub const CONFIG_VERSION: u32 = 1;
+
+/// Settings that describe this machine or this session rather than the
+/// user's preferences; they are never exported or overwritten by an import.
+pub const LOCAL_KEYS: &[&str] = &[
+    "crypto.devices",
+    "debug.remoteSession",
+    "deeplink.audit",
+    "digest.lastShown",
+    "downloads.channelDirs",
+    "drafts",
+    "ephemeral.expired",
+    "ephemeral.pending",
+    "focus.held",
+    "focus.session",
+    "integrations",
+    "kiosk.enabled",
+    "legacy.migrated",
+    "network.proxy",
+    "renderer.autoFallback",
+    "renderer.failedLaunches",
+    "renderer.launchPending",
+    "session.current",
+    "session.windows",
+    "startup.lastLocation",
+    "tray.unavailableNotified",
+    "updater.skippedVersion",
+    "updater.snoozedUntil",
+    "updater.source",
+    "window.mode",
+    "windows.popouts",
+];
+
+#[derive(Serialize, Deserialize)]
+pub struct AppConfig {
+    pub version: u32,
+    pub app_version: String,
+    /// Preference keys as stored in the settings store (shortcuts live under
+    /// `menu.accelerators`, tray options under `tray.*`, and so on).
+    pub settings: Map<String, Value>,
+}
+
+#[derive(Serialize, Default)]
+pub struct ConfigImportReport {
+    pub imported: Vec<String>,
+    pub skipped: Vec<String>,
+}
+
+/// Whether `key` belongs in a portable configuration: not machine-local and
+/// not credential-like.
+pub fn is_portable(key: &str) -> bool {
+    let lower = key.to_ascii_lowercase();
+    !LOCAL_KEYS.contains(&key)
+        && !lower.contains("token")
+        && !lower.contains("password")
+        && !lower.contains("secret")
+}
+
+/// The portable settings in `store`, stamped with the running app version.
+pub fn export_config(store: &(impl Store + ?Sized), app_version: &str) -> AppConfig {
+    AppConfig {
+        version: CONFIG_VERSION,
+        app_version: app_version.to_string(),
+        settings: store
+            .entries()
+            .into_iter()
+            .filter(|(key, _)| is_portable(key))
+            .collect(),
+    }
+}
+
+/// Apply a configuration over the current settings. Keys that are
+/// machine-local or look like credentials are reported as skipped.
+pub fn import_config(
+    store: &(impl Store + ?Sized),
+    config: AppConfig,
+) -> Result<ConfigImportReport, String> {
+    if config.version > CONFIG_VERSION {
+        return Err(format!(
+            "configuration version {} is newer than this app supports ({CONFIG_VERSION})",
+            config.version
+        ));
+    }
+
+    let mut report = ConfigImportReport::default();
+    for (key, value) in config.settings {
+        if is_portable(&key) {
+            store.set(&key, value)?;
+            report.imported.push(key);
+        } else {
+            report.skipped.push(key);
+        }
+    }
+    Ok(report)
+}
@@ -0,0 +1,20 @@
+use nchat_core::collation;
+
+#[test]
+fn sorts_accents_with_their_base_letter() {
+    let mut names = vec!["zebra".to_string(), "Émile".to_string(), "anna".to_string()];
+    collation::sort(&mut names, "fr").unwrap();
+    assert_eq!(names, ["anna", "Émile", "zebra"]);
+}
+
+#[test]
+fn compares_digit_runs_numerically() {
+    let mut channels = vec!["general-10".to_string(), "general-2".to_string()];
+    collation::sort(&mut channels, "en").unwrap();
+    assert_eq!(channels, ["general-2", "general-10"]);
+}
+
+#[test]
+fn rejects_invalid_locales() {
+    assert!(collation::collator("not a locale!").is_err());
+}
@@ -0,0 +1,87 @@
+use nchat_core::crypto::{backup, fingerprint};
+
+#[test]
+fn backup_round_trips() {
+    let envelope = backup::seal("correct horse", b"key store").unwrap();
+    assert_eq!(
+        backup::open("correct horse", &envelope).unwrap(),
+        b"key store"
+    );
+}
+
+#[test]
+fn backup_rejects_wrong_passphrase() {
+    let envelope = backup::seal("correct horse", b"key store").unwrap();
+    assert!(backup::open("battery staple", &envelope).is_err());
+}
+
+#[test]
+fn backup_rejects_a_tampered_mac() {
+    let mut envelope = backup::seal("correct horse", b"key store").unwrap();
+    let other = backup::seal("correct horse", b"key store").unwrap();
+    envelope.mac = other.mac;
+    assert!(backup::open("correct horse", &envelope).is_err());
+}
+
+#[test]
+fn backup_rejects_costly_key_derivation() {
+    let mut envelope = backup::seal("correct horse", b"key store").unwrap();
+    envelope.m_cost_kib = 4 * 1024 * 1024;
+    assert!(backup::open("correct horse", &envelope).is_err());
+}
+
+#[test]
+fn backup_round_trips_through_the_server_format() {
+    let envelope = backup::seal("correct horse", b"key store").unwrap();
+    let uploaded = serde_json::to_value(backup::ServerBackup::from(envelope)).unwrap();
+    let mut keys: Vec<&str> = uploaded
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(String::as_str)
+        .collect();
+    keys.sort_unstable();
+    assert_eq!(
+        keys,
+        ["encryptedData", "hmac", "iv", "kdf", "keyVersion", "salt"]
+    );
+    assert!(uploaded["kdf"]["mCostKib"].is_u64());
+
+    // The server returns the stored fields alongside its own.
+    let mut listed = uploaded.clone();
+    listed["id"] = "bak_1".into();
+    listed["createdAt"] = "2026-01-01T00:00:00.000Z".into();
+    let restored: backup::ServerBackup = serde_json::from_value(listed).unwrap();
+    assert_eq!(
+        backup::open("correct horse", &restored.into()).unwrap(),
+        b"key store"
+    );
+}
+
+#[test]
+fn backup_without_hmac_is_rejected() {
+    let envelope = backup::seal("correct horse", b"key store").unwrap();
+    let mut uploaded = serde_json::to_value(backup::ServerBackup::from(envelope)).unwrap();
+    uploaded.as_object_mut().unwrap().remove("hmac");
+    assert!(serde_json::from_value::<backup::ServerBackup>(uploaded).is_err());
+}
+
+#[test]
+fn backup_requires_a_long_passphrase() {
+    assert!(backup::seal("short", b"key store").is_err());
+}
+
+#[test]
+fn fingerprints_are_stable_and_owner_bound() {
+    let key = [7u8; 32];
+    let numeric = fingerprint::numeric(&key, "@ada:example.org");
+    assert_eq!(numeric, fingerprint::numeric(&key, "@ada:example.org"));
+    assert_ne!(numeric, fingerprint::numeric(&key, "@bob:example.org"));
+
+    let groups: Vec<&str> = numeric.split(' ').collect();
+    assert_eq!(groups.len(), 6);
+    assert!(groups
+        .iter()
+        .all(|g| g.len() == 5 && g.chars().all(|c| c.is_ascii_digit())));
+    assert_eq!(fingerprint::emoji(&key, "@ada:example.org").len(), 7);
+}
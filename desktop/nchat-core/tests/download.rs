@@ -0,0 +1,126 @@
+use nchat_core::download;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+
+const BODY: &[u8] = b"nchat-core download fixture";
+
+/// SHA-256 of `BODY`.
+const BODY_SHA256: &str = "65144172db3548c6f65b9e6040ce4058c9cddb04ac098b70ffb79f7f3b9259ab";
+
+/// Serve `BODY` for a single request, honouring a `Range: bytes=N-` header.
+/// Returns the URL and a receiver for the request head.
+fn serve_once() -> (String, mpsc::Receiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/file", listener.local_addr().unwrap());
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut head = Vec::new();
+        let mut byte = [0u8; 1];
+        while !head.ends_with(b"\r\n\r\n") {
+            stream.read_exact(&mut byte).unwrap();
+            head.push(byte[0]);
+        }
+        let head = String::from_utf8(head).unwrap();
+        let offset = head
+            .lines()
+            .find_map(|line| {
+                line.to_ascii_lowercase()
+                    .strip_prefix("range: bytes=")
+                    .map(String::from)
+            })
+            .and_then(|range| range.trim_end_matches('-').parse::<usize>().ok());
+        let response = match offset {
+            Some(offset) => {
+                let rest = &BODY[offset..];
+                let mut res = format!(
+                    "HTTP/1.1 206 Partial Content\r\nContent-Length: {}\r\nContent-Range: bytes {}-{}/{}\r\nConnection: close\r\n\r\n",
+                    rest.len(),
+                    offset,
+                    BODY.len() - 1,
+                    BODY.len()
+                )
+                .into_bytes();
+                res.extend_from_slice(rest);
+                res
+            }
+            None => {
+                let mut res = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    BODY.len()
+                )
+                .into_bytes();
+                res.extend_from_slice(BODY);
+                res
+            }
+        };
+        stream.write_all(&response).unwrap();
+        tx.send(head).unwrap();
+    });
+    (url, rx)
+}
+
+fn scratch(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("nchat-core-download-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let dest = dir.join(name);
+    let _ = std::fs::remove_file(&dest);
+    let _ = std::fs::remove_file(dir.join(format!("{name}.part")));
+    dest
+}
+
+fn part(dest: &Path) -> PathBuf {
+    PathBuf::from(format!("{}.part", dest.display()))
+}
+
+#[tokio::test]
+async fn fetch_writes_the_verified_file_and_reports_progress() {
+    let (url, _) = serve_once();
+    let dest = scratch("full.bin");
+    let mut last = (0, None);
+    download::fetch(
+        &reqwest::Client::new(),
+        &url,
+        &dest,
+        BODY_SHA256,
+        |done, total| {
+            last = (done, total);
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(std::fs::read(&dest).unwrap(), BODY);
+    assert!(!part(&dest).exists());
+    assert_eq!(last, (BODY.len() as u64, Some(BODY.len() as u64)));
+}
+
+#[tokio::test]
+async fn fetch_resumes_from_the_partial_file() {
+    let (url, head) = serve_once();
+    let dest = scratch("resume.bin");
+    std::fs::write(part(&dest), &BODY[..10]).unwrap();
+    download::fetch(&reqwest::Client::new(), &url, &dest, BODY_SHA256, |_, _| {})
+        .await
+        .unwrap();
+    assert!(head
+        .recv()
+        .unwrap()
+        .to_ascii_lowercase()
+        .contains("range: bytes=10-"));
+    assert_eq!(std::fs::read(&dest).unwrap(), BODY);
+}
+
+#[tokio::test]
+async fn fetch_rejects_a_checksum_mismatch() {
+    let (url, _) = serve_once();
+    let dest = scratch("mismatch.bin");
+    let wrong = "0".repeat(64);
+    let err = download::fetch(&reqwest::Client::new(), &url, &dest, &wrong, |_, _| {})
+        .await
+        .unwrap_err();
+    assert!(err.contains("checksum mismatch"));
+    assert!(!dest.exists());
+    assert!(!part(&dest).exists());
+}
@@ -0,0 +1,59 @@
+use chrono::NaiveDate;
+use nchat_core::jobs::{self, DigestSettings, PendingExpiry};
+
+fn pending(id: &str, expires_at: u64) -> PendingExpiry {
+    PendingExpiry {
+        message_id: id.into(),
+        channel_id: "general".into(),
+        expires_at,
+        attachments: Vec::new(),
+    }
+}
+
+#[test]
+fn rescheduling_replaces_the_previous_deadline() {
+    let mut list = vec![pending("a", 10), pending("b", 20)];
+    jobs::schedule_expiry(&mut list, pending("a", 30));
+    assert_eq!(list.len(), 2);
+    assert_eq!(jobs::next_expiry(&list), Some(20));
+}
+
+#[test]
+fn due_expiries_split_at_now() {
+    let list = vec![pending("a", 10), pending("b", 20), pending("c", 30)];
+    let (due, rest) = jobs::due_expiries(list, 20);
+    let due: Vec<String> = due.into_iter().map(|p| p.expired(20).message_id).collect();
+    assert_eq!(due, ["a", "b"]);
+    assert_eq!(jobs::next_expiry(&rest), Some(30));
+    assert_eq!(jobs::next_expiry(&[]), None);
+}
+
+#[test]
+fn digest_time_must_be_hh_mm() {
+    let mut digest = DigestSettings::default();
+    assert!(digest.validate().is_ok());
+    digest.time = "25:00".into();
+    assert!(digest.validate().is_err());
+}
+
+#[test]
+fn digest_is_due_once_a_day_after_its_time() {
+    let digest = DigestSettings {
+        enabled: true,
+        time: "08:30".into(),
+    };
+    let day = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+    let early = day.and_hms_opt(8, 0, 0).unwrap();
+    let late = day.and_hms_opt(9, 0, 0).unwrap();
+
+    assert_eq!(jobs::digest_due(&digest, early, None), None);
+    assert_eq!(
+        jobs::digest_due(&digest, late, None).as_deref(),
+        Some("2026-03-02")
+    );
+    assert_eq!(jobs::digest_due(&digest, late, Some("2026-03-02")), None);
+    assert_eq!(
+        jobs::digest_due(&DigestSettings::default(), late, None),
+        None
+    );
+}
@@ -0,0 +1,50 @@
+use nchat_core::notifications::{self, FocusSession, HeldNotification, MAX_HELD, MAX_MINUTES};
+
+fn held(title: &str, at: u64) -> HeldNotification {
+    HeldNotification {
+        title: title.into(),
+        body: None,
+        held_at: at,
+    }
+}
+
+#[test]
+fn sessions_are_bounded() {
+    assert!(FocusSession::new(0, 0, "online".into()).is_err());
+    assert!(FocusSession::new(0, MAX_MINUTES + 1, "online".into()).is_err());
+    let session = FocusSession::new(1_000, 25, "away".into()).unwrap();
+    assert_eq!(session.ends_at, 1_000 + 25 * 60_000);
+    assert!(!session.has_ended(session.ends_at - 1));
+    assert!(session.has_ended(session.ends_at));
+}
+
+#[test]
+fn only_non_urgent_notifications_are_held_during_a_session() {
+    let session = FocusSession::new(0, 10, "online".into()).unwrap();
+    assert!(notifications::should_hold(Some(&session), false));
+    assert!(!notifications::should_hold(Some(&session), true));
+    assert!(!notifications::should_hold(None, false));
+}
+
+#[test]
+fn holding_drops_the_oldest_past_the_limit() {
+    let mut buffer = Vec::new();
+    for i in 0..MAX_HELD as u64 + 5 {
+        notifications::hold(&mut buffer, held("n", i));
+    }
+    assert_eq!(buffer.len(), MAX_HELD);
+    assert_eq!(buffer[0].held_at, 5);
+}
+
+#[test]
+fn recap_names_distinct_titles_most_recent_first() {
+    let buffer = [
+        held("ada", 1),
+        held("bob", 2),
+        held("ada", 3),
+        held("cy", 4),
+        held("dee", 5),
+    ];
+    assert_eq!(notifications::recap_titles(&buffer), ["dee", "cy", "ada"]);
+    assert!(notifications::recap_titles(&[]).is_empty());
+}
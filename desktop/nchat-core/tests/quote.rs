@@ -0,0 +1,31 @@
+use nchat_core::quote::{self, QuotedMessage};
+
+fn message(author: &str, body: &str) -> QuotedMessage {
+    QuotedMessage {
+        author: author.into(),
+        sent_at: 0,
+        body: body.into(),
+    }
+}
+
+#[test]
+fn markdown_quotes_every_line() {
+    let text = quote::markdown(&[message("ada", "first\n\nthird")]);
+    let lines: Vec<&str> = text.lines().collect();
+    assert!(lines[0].starts_with("> **ada**"));
+    assert_eq!(&lines[1..], ["> first", ">", "> third"]);
+}
+
+#[test]
+fn markdown_separates_messages_with_a_blank_line() {
+    let text = quote::markdown(&[message("ada", "one"), message("bob", "two")]);
+    assert!(text.contains("> one\n\n> **bob**"));
+}
+
+#[test]
+fn html_escapes_author_and_body() {
+    let html = quote::html(&[message("<ada>", "a & <b>")]);
+    assert!(html.contains("<strong>&lt;ada&gt;</strong>"));
+    assert!(html.contains("a &amp; &lt;b&gt;"));
+    assert!(!html.contains("<b>"));
+}
@@ -0,0 +1,89 @@
+use nchat_core::settings::{self, AppConfig, Store, CONFIG_VERSION};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+#[derive(Default)]
+struct MemoryStore(Mutex<BTreeMap<String, Value>>);
+
+impl Store for MemoryStore {
+    fn get(&self, key: &str) -> Option<Value> {
+        self.0.lock().unwrap().get(key).cloned()
+    }
+
+    fn set(&self, key: &str, value: Value) -> Result<(), String> {
+        self.0.lock().unwrap().insert(key.to_string(), value);
+        Ok(())
+    }
+
+    fn delete(&self, key: &str) -> Result<(), String> {
+        self.0.lock().unwrap().remove(key);
+        Ok(())
+    }
+
+    fn entries(&self) -> Vec<(String, Value)> {
+        self.0
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect()
+    }
+}
+
+#[test]
+fn typed_values_round_trip() {
+    let store = MemoryStore::default();
+    settings::save(&store, "tray.enabled", &true).unwrap();
+    assert_eq!(settings::load::<bool>(&store, "tray.enabled"), Some(true));
+    assert!(store.has("tray.enabled"));
+}
+
+#[test]
+fn mismatched_values_load_as_missing() {
+    let store = MemoryStore::default();
+    settings::save(&store, "digest", &"not a struct").unwrap();
+    assert_eq!(settings::load::<u32>(&store, "digest"), None);
+}
+
+#[test]
+fn export_leaves_out_local_and_credential_keys() {
+    let store = MemoryStore::default();
+    settings::save(&store, "menu.accelerators", &json!({ "find": "Ctrl+F" })).unwrap();
+    settings::save(&store, "focus.session", &json!({})).unwrap();
+    settings::save(&store, "sync.apiToken", &"abc").unwrap();
+
+    let config = settings::export_config(&store, "1.2.3");
+    assert_eq!(config.version, CONFIG_VERSION);
+    assert_eq!(config.app_version, "1.2.3");
+    let keys: Vec<&String> = config.settings.keys().collect();
+    assert_eq!(keys, ["menu.accelerators"]);
+}
+
+#[test]
+fn import_skips_keys_that_are_not_portable() {
+    let store = MemoryStore::default();
+    let mut values = Map::new();
+    values.insert("tray.closeBehavior".into(), json!("hide"));
+    values.insert("kiosk.enabled".into(), json!(true));
+    let config = AppConfig {
+        version: CONFIG_VERSION,
+        app_version: "1.0.0".into(),
+        settings: values,
+    };
+
+    let report = settings::import_config(&store, config).unwrap();
+    assert_eq!(report.imported, ["tray.closeBehavior"]);
+    assert_eq!(report.skipped, ["kiosk.enabled"]);
+    assert!(!store.has("kiosk.enabled"));
+}
+
+#[test]
+fn import_rejects_newer_configurations() {
+    let config = AppConfig {
+        version: CONFIG_VERSION + 1,
+        app_version: "9.0.0".into(),
+        settings: Map::new(),
+    };
+    assert!(settings::import_config(&MemoryStore::default(), config).is_err());
+}
@@ -0,0 +1,88 @@
+// nChat Desktop — About window
+//
+// Help → About nChat opens a small fixed-size window rendering the
+// frontend's `#/about` route. Everything it shows comes from here, so
+// support can trust the build details a user reads out.
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
+
+/// Label of the About window; there is at most one.
+pub const ABOUT_WINDOW: &str = "about";
+
+/// Frontend route rendered in the About window.
+const ABOUT_ROUTE: &str = "index.html#/about";
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AboutInfo {
+    pub name: String,
+    pub version: String,
+    /// Short hash of the commit the app was built from.
+    pub commit: &'static str,
+    /// Build date as `YYYY-MM-DD` (UTC).
+    pub build_date: String,
+    pub tauri_version: &'static str,
+    /// Version of the system webview (WebView2, WKWebView, WebKitGTK).
+    pub webview_version: Option<String>,
+    pub os: &'static str,
+    pub arch: &'static str,
+    pub licenses: Vec<License>,
+}
+
+#[derive(Serialize)]
+pub struct License {
+    pub component: &'static str,
+    pub license: &'static str,
+}
+
+/// Build and runtime details for the About window.
+pub fn info(app: &AppHandle) -> AboutInfo {
+    let built_at: i64 = env!("NCHAT_BUILD_TIME").parse().unwrap_or(0);
+    let build_date = chrono::DateTime::from_timestamp(built_at, 0)
+        .map(|at| at.format("%Y-%m-%d").to_string())
+        .unwrap_or_default();
+    let package = app.package_info();
+    AboutInfo {
+        name: package.name.clone(),
+        version: package.version.to_string(),
+        commit: env!("NCHAT_GIT_COMMIT"),
+        build_date,
+        tauri_version: tauri::VERSION,
+        webview_version: tauri::webview_version().ok(),
+        os: std::env::consts::OS,
+        arch: std::env::consts::ARCH,
+        licenses: vec![
+            License {
+                component: "nChat Desktop",
+                license: env!("CARGO_PKG_LICENSE"),
+            },
+            License {
+                component: "Tauri",
+                license: "Apache-2.0 OR MIT",
+            },
+            License {
+                component: "Sentry SDK",
+                license: "MIT",
+            },
+        ],
+    }
+}
+
+/// Show the About window, creating it on first use.
+pub fn open(app: &AppHandle) -> Result<(), String> {
+    let win = match app.get_webview_window(ABOUT_WINDOW) {
+        Some(win) => win,
+        None => WebviewWindowBuilder::new(app, ABOUT_WINDOW, WebviewUrl::App(ABOUT_ROUTE.into()))
+            .title(format!("About {}", app.package_info().name))
+            .inner_size(420.0, 360.0)
+            .resizable(false)
+            .minimizable(false)
+            .maximizable(false)
+            .center()
+            .build()
+            .map_err(|e| e.to_string())?,
+    };
+    win.show().map_err(|e| e.to_string())?;
+    win.set_focus().map_err(|e| e.to_string())
+}
@@ -0,0 +1,251 @@
+// nChat Desktop — conversation cold storage
+//
+// Archiving a conversation moves its cached messages (handed over by the
+// frontend, which then drops them from its live cache) and its attachment
+// folder into one zstd-compressed tar file. Archives are left out of search
+// unless the user opts in, which keeps the live cache small and fast.
+
+use serde::Serialize;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+
+use crate::state::settings;
+
+/// Settings key for including archives in search.
+const SEARCHABLE_KEY: &str = "archive.searchable";
+
+/// Entry holding the messages; written first so search can stop early.
+const MESSAGES_ENTRY: &str = "messages.json";
+
+/// Cache folder holding one attachment folder per conversation.
+const ATTACHMENTS_DIR: &str = "attachments";
+
+const ARCHIVE_EXTENSION: &str = "tar.zst";
+const COMPRESSION_LEVEL: i32 = 10;
+
+/// Cap on search results across all archives.
+const MAX_SEARCH_HITS: usize = 100;
+
+#[derive(Serialize)]
+pub struct ArchivedConversation {
+    pub channel_id: String,
+    /// Unix time in seconds.
+    pub archived_at: u64,
+    pub size_bytes: u64,
+}
+
+#[derive(Serialize)]
+pub struct ArchiveHit {
+    pub channel_id: String,
+    pub message: serde_json::Value,
+}
+
+fn check_channel_id(channel_id: &str) -> Result<(), String> {
+    let valid = !channel_id.is_empty()
+        && channel_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'));
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("invalid channel id: {channel_id}"))
+    }
+}
+
+fn archives_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join("archives"))
+        .map_err(|e| e.to_string())
+}
+
+fn archive_path(app: &AppHandle, channel_id: &str) -> Result<PathBuf, String> {
+    check_channel_id(channel_id)?;
+    Ok(archives_dir(app)?.join(format!("{channel_id}.{ARCHIVE_EXTENSION}")))
+}
+
+fn cache_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path().app_cache_dir().map_err(|e| e.to_string())
+}
+
+/// Move a conversation's messages and attachments into cold storage.
+pub fn archive(
+    app: &AppHandle,
+    channel_id: &str,
+    messages: &serde_json::Value,
+) -> Result<ArchivedConversation, String> {
+    let path = archive_path(app, channel_id)?;
+    if path.exists() {
+        return Err(format!("{channel_id} is already archived"));
+    }
+    std::fs::create_dir_all(archives_dir(app)?).map_err(|e| e.to_string())?;
+    let attachments = cache_dir(app)?.join(ATTACHMENTS_DIR).join(channel_id);
+
+    // Write to a temporary file so a failure never leaves a truncated
+    // archive behind, and only delete the originals once it is complete.
+    let part = path.with_extension("part");
+    let write = || -> std::io::Result<()> {
+        let file = std::fs::File::create(&part)?;
+        let encoder = zstd::stream::write::Encoder::new(file, COMPRESSION_LEVEL)?;
+        let mut tar = tar::Builder::new(encoder);
+
+        let json = serde_json::to_vec(messages)?;
+        let mut header = tar::Header::new_gnu();
+        header.set_size(json.len() as u64);
+        header.set_mode(0o600);
+        header.set_cksum();
+        tar.append_data(&mut header, MESSAGES_ENTRY, json.as_slice())?;
+
+        if attachments.is_dir() {
+            tar.append_dir_all(Path::new(ATTACHMENTS_DIR).join(channel_id), &attachments)?;
+        }
+        tar.into_inner()?.finish()?.sync_all()
+    };
+    if let Err(e) = write() {
+        let _ = std::fs::remove_file(&part);
+        return Err(e.to_string());
+    }
+    std::fs::rename(&part, &path).map_err(|e| e.to_string())?;
+
+    if attachments.is_dir() {
+        if let Err(e) = std::fs::remove_dir_all(&attachments) {
+            log::warn!("[nchat-desktop] archived {channel_id} but kept attachments: {e}");
+        }
+    }
+
+    Ok(ArchivedConversation {
+        channel_id: channel_id.to_string(),
+        archived_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+        size_bytes: std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0),
+    })
+}
+
+/// Restore the attachments to the cache, delete the archive and return
+/// the messages for the frontend to put back into its live cache.
+pub fn unarchive(app: &AppHandle, channel_id: &str) -> Result<serde_json::Value, String> {
+    let path = archive_path(app, channel_id)?;
+    let file = std::fs::File::open(&path).map_err(|e| e.to_string())?;
+    let decoder = zstd::stream::read::Decoder::new(file).map_err(|e| e.to_string())?;
+    let mut tar = tar::Archive::new(decoder);
+    let cache = cache_dir(app)?;
+
+    let mut messages = serde_json::Value::Array(Vec::new());
+    for entry in tar.entries().map_err(|e| e.to_string())? {
+        let mut entry = entry.map_err(|e| e.to_string())?;
+        let entry_path = entry.path().map_err(|e| e.to_string())?.into_owned();
+        if entry_path == Path::new(MESSAGES_ENTRY) {
+            messages = serde_json::from_reader(&mut entry).map_err(|e| e.to_string())?;
+        } else if entry_path.starts_with(ATTACHMENTS_DIR) {
+            // `unpack_in` refuses paths that would escape the cache.
+            entry.unpack_in(&cache).map_err(|e| e.to_string())?;
+        }
+    }
+
+    std::fs::remove_file(&path).map_err(|e| e.to_string())?;
+    Ok(messages)
+}
+
+/// Conversations currently in cold storage.
+pub fn list(app: &AppHandle) -> Vec<ArchivedConversation> {
+    let Ok(entries) =
+        archives_dir(app).and_then(|d| std::fs::read_dir(d).map_err(|e| e.to_string()))
+    else {
+        return Vec::new();
+    };
+    let suffix = format!(".{ARCHIVE_EXTENSION}");
+    let mut archives: Vec<ArchivedConversation> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let channel_id = name.strip_suffix(&suffix)?.to_string();
+            let meta = entry.metadata().ok()?;
+            Some(ArchivedConversation {
+                channel_id,
+                archived_at: meta
+                    .modified()
+                    .ok()?
+                    .duration_since(UNIX_EPOCH)
+                    .ok()?
+                    .as_secs(),
+                size_bytes: meta.len(),
+            })
+        })
+        .collect();
+    archives.sort_by(|a, b| a.channel_id.cmp(&b.channel_id));
+    archives
+}
+
+pub fn searchable(app: &AppHandle) -> bool {
+    settings::load(app, SEARCHABLE_KEY).unwrap_or(false)
+}
+
+pub fn set_searchable(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    settings::save(app, SEARCHABLE_KEY, &enabled)
+}
+
+/// The messages of one archive, without unpacking its attachments.
+fn read_messages(path: &Path) -> std::io::Result<Vec<serde_json::Value>> {
+    let decoder = zstd::stream::read::Decoder::new(std::fs::File::open(path)?)?;
+    let mut tar = tar::Archive::new(decoder);
+    for entry in tar.entries()? {
+        let mut entry = entry?;
+        if entry.path()? == Path::new(MESSAGES_ENTRY) {
+            let mut json = Vec::new();
+            entry.read_to_end(&mut json)?;
+            return Ok(serde_json::from_slice(&json).unwrap_or_default());
+        }
+    }
+    Ok(Vec::new())
+}
+
+fn contains_text(value: &serde_json::Value, needle: &str) -> bool {
+    match value {
+        serde_json::Value::String(s) => s.to_lowercase().contains(needle),
+        serde_json::Value::Array(items) => items.iter().any(|v| contains_text(v, needle)),
+        serde_json::Value::Object(map) => map.values().any(|v| contains_text(v, needle)),
+        _ => false,
+    }
+}
+
+/// Case-insensitive search through archived messages. Archives are only
+/// searched when the user has opted in.
+pub fn search(app: &AppHandle, query: &str) -> Result<Vec<ArchiveHit>, String> {
+    if !searchable(app) {
+        return Err("archived conversations are not searchable".into());
+    }
+    let needle = query.trim().to_lowercase();
+    if needle.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut hits = Vec::new();
+    for archived in list(app) {
+        let path = archive_path(app, &archived.channel_id)?;
+        let messages = match read_messages(&path) {
+            Ok(messages) => messages,
+            Err(e) => {
+                log::warn!(
+                    "[nchat-desktop] skipping unreadable archive {}: {e}",
+                    path.display()
+                );
+                continue;
+            }
+        };
+        for message in messages {
+            if contains_text(&message, &needle) {
+                hits.push(ArchiveHit {
+                    channel_id: archived.channel_id.clone(),
+                    message,
+                });
+                if hits.len() >= MAX_SEARCH_HITS {
+                    return Ok(hits);
+                }
+            }
+        }
+    }
+    Ok(hits)
+}
@@ -0,0 +1,272 @@
+// nChat Desktop — unread badge on the app icon
+//
+// `set_app_badge` shows the badge wherever the platform can: macOS puts it on
+// the Dock tile through Tauri's badge API, which sets `NSDockTile.badgeLabel`
+// in-process: no AppleScript, no automation permission, and no dependence on
+// the app's display name. Windows has no taskbar badge, so the badge is drawn
+// into a small overlay icon at runtime and shown in the corner of the main
+// window's taskbar button. On Linux the count is broadcast with the
+// `com.canonical.Unity.LauncherEntry` signal, which Ubuntu's dock, KDE Plasma
+// and Dash to Dock show on the launcher; desktops without a listener ignore
+// it. Launcher entries cannot show a dot without a count, so a dot badge goes
+// on the tray icon there instead. In tray-only mode there is no Dock or
+// taskbar entry, so every platform marks the tray icon.
+
+use serde::Deserialize;
+use std::sync::Mutex;
+use tauri::image::Image;
+use tauri::AppHandle;
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+use tauri::Manager;
+
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+use crate::windows::MAIN_WINDOW;
+
+/// The badge last set, kept to move it between the Dock or taskbar and the
+/// tray when tray-only mode changes.
+static CURRENT: Mutex<(u32, BadgeKind)> = Mutex::new((0, BadgeKind::Count));
+
+/// Fill of the tray icon mark.
+const TRAY_MARK_COLOR: [u8; 3] = [0xD9, 0x30, 0x25];
+
+/// Counts above this are shown as "99+".
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+const MAX_SHOWN: u32 = 99;
+
+/// Object path the launcher entry signal is sent from.
+#[cfg(target_os = "linux")]
+const LAUNCHER_ENTRY_PATH: &str = "/org/nself/chat/LauncherEntry";
+
+/// Session bus connection the launcher entry signals are sent on. It is kept
+/// open: some docks drop the count when the sender leaves the bus.
+#[cfg(target_os = "linux")]
+static SESSION_BUS: std::sync::OnceLock<zbus::blocking::Connection> = std::sync::OnceLock::new();
+
+/// Side of the rendered overlay icon; Windows scales it to the overlay
+/// slot (16 px at 100% scaling).
+#[cfg(target_os = "windows")]
+const OVERLAY_SIZE: u32 = 32;
+
+/// Fill of the overlay disc.
+#[cfg(target_os = "windows")]
+const OVERLAY_COLOR: [u8; 3] = [0xD9, 0x30, 0x25];
+
+/// 3×5 pixel glyphs for the overlay count, one row per entry with the
+/// leftmost pixel in bit 2.
+#[cfg(target_os = "windows")]
+const GLYPHS: [(char, [u8; 5]); 11] = [
+    ('0', [0b111, 0b101, 0b101, 0b101, 0b111]),
+    ('1', [0b010, 0b110, 0b010, 0b010, 0b111]),
+    ('2', [0b111, 0b001, 0b111, 0b100, 0b111]),
+    ('3', [0b111, 0b001, 0b111, 0b001, 0b111]),
+    ('4', [0b101, 0b101, 0b111, 0b001, 0b001]),
+    ('5', [0b111, 0b100, 0b111, 0b001, 0b111]),
+    ('6', [0b111, 0b100, 0b111, 0b101, 0b111]),
+    ('7', [0b111, 0b001, 0b010, 0b010, 0b010]),
+    ('8', [0b111, 0b101, 0b111, 0b101, 0b111]),
+    ('9', [0b111, 0b101, 0b111, 0b001, 0b111]),
+    ('+', [0b000, 0b010, 0b111, 0b010, 0b000]),
+];
+
+/// What the badge shows.
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum BadgeKind {
+    /// The unread count.
+    #[default]
+    Count,
+    /// A plain dot, for activity that is not worth a number (muted channels,
+    /// for example).
+    Dot,
+}
+
+/// Show the badge on the app icon, or on the tray icon where the app has no
+/// Dock or taskbar entry to carry it; a zero `count` removes it.
+pub fn set_app_badge(app: &AppHandle, count: u32, kind: BadgeKind) -> Result<(), String> {
+    *CURRENT.lock().unwrap() = (count, kind);
+    crate::tray::apply_icon(app);
+    let count = if on_tray(app) { 0 } else { count };
+    #[cfg(target_os = "macos")]
+    {
+        let win = app
+            .get_webview_window(MAIN_WINDOW)
+            .ok_or("main window not found")?;
+        win.set_badge_label(label(count, kind))
+            .map_err(|e| e.to_string())?;
+    }
+    #[cfg(target_os = "windows")]
+    {
+        let win = app
+            .get_webview_window(MAIN_WINDOW)
+            .ok_or("main window not found")?;
+        win.set_overlay_icon(label(count, kind).as_deref().map(overlay_icon))
+            .map_err(|e| e.to_string())?;
+    }
+    #[cfg(target_os = "linux")]
+    {
+        let count = if kind == BadgeKind::Dot { 0 } else { count };
+        update_launcher_entry(app, count).map_err(|e| e.to_string())?;
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    let _ = (app, count);
+    Ok(())
+}
+
+/// Show `count` on the app icon; zero removes the badge.
+pub fn set_count(app: &AppHandle, count: u32) -> Result<(), String> {
+    set_app_badge(app, count, BadgeKind::Count)
+}
+
+pub fn clear(app: &AppHandle) -> Result<(), String> {
+    set_count(app, 0)
+}
+
+/// Set the last badge again, after tray-only mode changed where it goes.
+pub fn refresh(app: &AppHandle) {
+    let (count, kind) = *CURRENT.lock().unwrap();
+    if let Err(e) = set_app_badge(app, count, kind) {
+        log::warn!("[nchat-desktop] failed to update the app badge: {e}");
+    }
+}
+
+/// Whether the badge goes on the tray icon rather than the app icon.
+pub fn on_tray(app: &AppHandle) -> bool {
+    let (count, kind) = *CURRENT.lock().unwrap();
+    if count == 0 || !crate::tray::is_visible(app) {
+        return false;
+    }
+    crate::tray::is_tray_only(app) || (cfg!(target_os = "linux") && kind == BadgeKind::Dot)
+}
+
+/// `icon` with a dot in its top-right corner, for a badged tray icon.
+pub fn mark_tray_icon(icon: Image<'_>) -> Image<'static> {
+    let (width, height) = (icon.width() as i32, icon.height() as i32);
+    let mut rgba = icon.rgba().to_vec();
+    let radius = width.min(height) as f64 / 5.0;
+    let (cx, cy) = (width as f64 - radius, radius);
+    for y in 0..height {
+        for x in 0..width {
+            let distance = (x as f64 + 0.5 - cx).hypot(y as f64 + 0.5 - cy);
+            let coverage = (radius - distance).clamp(0.0, 1.0);
+            if coverage > 0.0 {
+                let i = ((y * width + x) * 4) as usize;
+                let [r, g, b] = TRAY_MARK_COLOR;
+                // Blend over the glyph so the rim stays smooth.
+                for (channel, value) in [r, g, b].into_iter().enumerate() {
+                    let under = rgba[i + channel] as f64;
+                    rgba[i + channel] = (under + (value as f64 - under) * coverage) as u8;
+                }
+                rgba[i + 3] = rgba[i + 3].max((coverage * 255.0) as u8);
+            }
+        }
+    }
+    Image::new_owned(rgba, icon.width(), icon.height())
+}
+
+/// Send the count to the dock. Docks match the entry to the `.desktop` file
+/// the app was launched from: the one GLib launchers report, the Flatpak app
+/// id, or the file name the Tauri bundler installs.
+#[cfg(target_os = "linux")]
+fn update_launcher_entry(app: &AppHandle, count: u32) -> zbus::Result<()> {
+    use std::collections::HashMap;
+    use zbus::zvariant::Value;
+
+    let desktop_file = std::env::var("GIO_LAUNCHED_DESKTOP_FILE")
+        .ok()
+        .and_then(|path| {
+            let name = std::path::Path::new(&path).file_name()?;
+            Some(name.to_string_lossy().into_owned())
+        })
+        .or_else(|| {
+            std::env::var("FLATPAK_ID")
+                .ok()
+                .map(|id| format!("{id}.desktop"))
+        })
+        .unwrap_or_else(|| format!("{}.desktop", app.package_info().name));
+    let conn = match SESSION_BUS.get() {
+        Some(conn) => conn,
+        None => {
+            let conn = zbus::blocking::Connection::session()?;
+            SESSION_BUS.get_or_init(|| conn)
+        }
+    };
+    let properties = HashMap::from([
+        ("count", Value::from(i64::from(count))),
+        ("count-visible", Value::from(count > 0)),
+    ]);
+    conn.emit_signal(
+        None::<zbus::names::BusName<'_>>,
+        LAUNCHER_ENTRY_PATH,
+        "com.canonical.Unity.LauncherEntry",
+        "Update",
+        &(format!("application://{desktop_file}"), properties),
+    )
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn label(count: u32, kind: BadgeKind) -> Option<String> {
+    match count {
+        0 => None,
+        // The overlay draws no glyph for it, leaving a plain disc.
+        _ if kind == BadgeKind::Dot => Some("\u{2022}".into()),
+        n if n > MAX_SHOWN => Some(format!("{MAX_SHOWN}+")),
+        n => Some(n.to_string()),
+    }
+}
+
+/// Draw `text` in white on a red disc, as large as it fits.
+#[cfg(target_os = "windows")]
+fn overlay_icon(text: &str) -> Image<'static> {
+    let size = OVERLAY_SIZE as i32;
+    let mut rgba = vec![0u8; (OVERLAY_SIZE * OVERLAY_SIZE * 4) as usize];
+
+    // Disc with a one-pixel antialiased rim.
+    let center = (size - 1) as f64 / 2.0;
+    let radius = size as f64 / 2.0;
+    for y in 0..size {
+        for x in 0..size {
+            let distance = (x as f64 - center).hypot(y as f64 - center);
+            let coverage = (radius - distance).clamp(0.0, 1.0);
+            if coverage > 0.0 {
+                let [r, g, b] = OVERLAY_COLOR;
+                put_pixel(&mut rgba, x, y, [r, g, b, (coverage * 255.0) as u8]);
+            }
+        }
+    }
+
+    let glyphs: Vec<[u8; 5]> = text
+        .chars()
+        .filter_map(|c| GLYPHS.iter().find(|(g, _)| *g == c).map(|(_, rows)| *rows))
+        .collect();
+    let columns = (glyphs.len() * 4).saturating_sub(1) as i32;
+    if columns == 0 {
+        return Image::new_owned(rgba, OVERLAY_SIZE, OVERLAY_SIZE);
+    }
+    let scale = (22 / columns).clamp(1, 4);
+    let left = (size - columns * scale) / 2;
+    let top = (size - 5 * scale) / 2;
+    for (i, rows) in glyphs.iter().enumerate() {
+        let glyph_left = left + i as i32 * 4 * scale;
+        for (row, bits) in rows.iter().enumerate() {
+            for column in 0..3 {
+                if bits & (0b100 >> column) == 0 {
+                    continue;
+                }
+                for dy in 0..scale {
+                    for dx in 0..scale {
+                        let x = glyph_left + column * scale + dx;
+                        let y = top + row as i32 * scale + dy;
+                        put_pixel(&mut rgba, x, y, [0xFF, 0xFF, 0xFF, 0xFF]);
+                    }
+                }
+            }
+        }
+    }
+    Image::new_owned(rgba, OVERLAY_SIZE, OVERLAY_SIZE)
+}
+
+#[cfg(target_os = "windows")]
+fn put_pixel(rgba: &mut [u8], x: i32, y: i32, color: [u8; 4]) {
+    let i = ((y * OVERLAY_SIZE as i32 + x) * 4) as usize;
+    rgba[i..i + 4].copy_from_slice(&color);
+}
@@ -0,0 +1,85 @@
+// nChat Desktop — global mute and deafen shortcuts for calls
+//
+// Call users need to mute their microphone or silence the call without
+// switching to it, so both toggles have system-wide shortcuts. Pressing one
+// broadcasts `shortcut-mute-toggle` or `shortcut-deafen-toggle` to every
+// window, since the call may be in a pop-out; the window is not raised.
+// Each shortcut can be changed or turned off.
+
+use std::collections::HashMap;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutEvent, ShortcutState};
+
+use crate::state::settings;
+
+/// Call shortcuts as (action, settings key, default accelerator, event).
+/// An empty stored accelerator turns the shortcut off.
+const CALL_SHORTCUTS: &[(&str, &str, &str, &str)] = &[
+    (
+        "mute",
+        "calls.muteShortcut",
+        "CmdOrCtrl+Alt+Shift+M",
+        "shortcut-mute-toggle",
+    ),
+    (
+        "deafen",
+        "calls.deafenShortcut",
+        "CmdOrCtrl+Alt+Shift+D",
+        "shortcut-deafen-toggle",
+    ),
+];
+
+/// The configured shortcut of each action (`mute`, `deafen`); empty when
+/// turned off.
+pub fn shortcuts(app: &AppHandle) -> HashMap<String, String> {
+    CALL_SHORTCUTS
+        .iter()
+        .map(|(action, key, default, _)| {
+            let accel = settings::load(app, key).unwrap_or_else(|| default.to_string());
+            (action.to_string(), accel)
+        })
+        .collect()
+}
+
+/// Register the configured shortcuts. Another app holding one is not fatal.
+pub fn register_shortcuts(app: &AppHandle) {
+    let global = app.global_shortcut();
+    for (action, accel) in shortcuts(app) {
+        if accel.is_empty() {
+            continue;
+        }
+        if let Err(e) = global.register(accel.as_str()) {
+            log::warn!("[nchat-desktop] failed to register {action} shortcut {accel}: {e}");
+        }
+    }
+}
+
+/// Change the shortcut of `action`; an empty string turns it off.
+pub fn set_shortcut(app: &AppHandle, action: &str, accel: &str) -> Result<(), String> {
+    let (_, key, _, _) = CALL_SHORTCUTS
+        .iter()
+        .find(|(a, ..)| *a == action)
+        .ok_or_else(|| format!("unknown call shortcut: {action}"))?;
+    let accel = accel.trim();
+    let previous = shortcuts(app).remove(action).unwrap_or_default();
+    crate::shortcuts::rebind(app, &previous, accel)?;
+    settings::save(app, key, &accel)
+}
+
+/// Global shortcut handler. Returns whether the shortcut was a call one.
+pub fn on_shortcut(app: &AppHandle, shortcut: &Shortcut, event: ShortcutEvent) -> bool {
+    let configured = shortcuts(app);
+    let matched = CALL_SHORTCUTS.iter().find(|(action, ..)| {
+        configured
+            .get(*action)
+            .and_then(|accel| accel.parse::<Shortcut>().ok())
+            .is_some_and(|own| own.id() == shortcut.id())
+    });
+    let Some((_, _, _, event_name)) = matched else {
+        return false;
+    };
+    if event.state == ShortcutState::Pressed {
+        let _ = app.emit(event_name, ());
+    }
+    true
+}
@@ -0,0 +1,67 @@
+// nChat Desktop — local clock skew detection
+//
+// A wrong system clock makes TLS certificates and auth tokens look expired
+// or not yet valid, which users only ever see as "connection failed".
+// Responses from the server carry a `Date` header; when it disagrees with
+// the local clock by more than a few minutes the frontend is told so it
+// can explain the actual problem.
+
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter};
+
+/// Offset tolerated before warning; most token validators allow a few
+/// minutes of leeway.
+const SKEW_THRESHOLD_SECS: i64 = 120;
+
+/// Set while a skew is being reported, so it is emitted once rather than
+/// on every request.
+static SKEW_REPORTED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Serialize, Clone)]
+pub struct ClockSkew {
+    /// Server time minus local time; positive when the local clock is behind.
+    pub offset_secs: i64,
+    /// Message the UI can show as-is.
+    pub guidance: String,
+}
+
+fn guidance(offset_secs: i64) -> String {
+    let minutes = (offset_secs.abs() + 59) / 60;
+    let direction = if offset_secs > 0 { "behind" } else { "ahead" };
+    format!(
+        "Your computer's clock is about {minutes} minute(s) {direction}. \
+         Secure connections fail when the clock is wrong — turn on automatic \
+         date and time in your system settings, then reconnect."
+    )
+}
+
+/// Offset between the server's `Date` header and the local clock, in
+/// seconds. `None` if the header is missing or malformed.
+pub fn offset_from(res: &reqwest::Response) -> Option<i64> {
+    let header = res.headers().get(reqwest::header::DATE)?.to_str().ok()?;
+    let server = httpdate::parse_http_date(header).ok()?;
+    let server = server.duration_since(UNIX_EPOCH).ok()?.as_secs() as i64;
+    let local = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs() as i64;
+    Some(server - local)
+}
+
+/// Check a server response and emit `clock-skew-detected` when the local
+/// clock is off by more than the threshold.
+pub fn observe(app: &AppHandle, res: &reqwest::Response) -> Option<ClockSkew> {
+    let offset_secs = offset_from(res)?;
+    if offset_secs.abs() <= SKEW_THRESHOLD_SECS {
+        SKEW_REPORTED.store(false, Ordering::Relaxed);
+        return None;
+    }
+    let skew = ClockSkew {
+        offset_secs,
+        guidance: guidance(offset_secs),
+    };
+    if !SKEW_REPORTED.swap(true, Ordering::Relaxed) {
+        log::warn!("[nchat-desktop] local clock is off by {offset_secs}s");
+        let _ = app.emit("clock-skew-detected", skew.clone());
+    }
+    Some(skew)
+}
@@ -1,6 +1,12 @@
 use tauri::{AppHandle, Manager};
 use tauri_plugin_autostart::ManagerExt;
 
+use crate::about::{self, AboutInfo};
+use crate::badge::{self, BadgeKind};
+use crate::jumplist::{self, RecentConversation};
+use crate::progress::{self, ProgressSource, ProgressState};
+use crate::thumbbar::{self, CallControls};
+
 #[tauri::command]
 pub fn app_get_version(app: AppHandle) -> String {
     app.package_info().version.to_string()
@@ -27,6 +33,12 @@ pub fn app_get_path(app: AppHandle, name: String) -> Result<String, String> {
     Ok(dir.to_string_lossy().to_string())
 }
 
+/// Version, commit, build date and component licenses for the About window.
+#[tauri::command]
+pub fn get_about_info(app: AppHandle) -> AboutInfo {
+    about::info(&app)
+}
+
 #[tauri::command]
 pub fn toggle_autostart(app: AppHandle, enabled: bool) -> Result<(), String> {
     let autostart = app.autolaunch();
@@ -37,38 +49,60 @@ pub fn toggle_autostart(app: AppHandle, enabled: bool) -> Result<(), String> {
     }
 }
 
-/// T24 — macOS dock badge: set unread count badge on the dock icon.
-/// On non-macOS platforms this is a no-op (returns Ok(())).
+/// T24 — unread count badge on the app icon. Kept for older frontends;
+/// same as `set_app_badge` with the count kind.
 #[tauri::command]
-pub fn app_set_badge_count(_app: AppHandle, count: u32) -> Result<(), String> {
-    #[cfg(target_os = "macos")]
-    {
-        let label = if count == 0 {
-            String::new()
-        } else {
-            count.to_string()
-        };
-        // Tauri 2 exposes badge via the objc runtime through the app handle.
-        // Use NSApp.dockTile.badgeLabel via cocoa if available; fall back gracefully.
-        unsafe {
-            use objc2::runtime::AnyObject;
-            use objc2::{class, msg_send};
-            let app: *mut AnyObject = msg_send![class!(NSApplication), sharedApplication];
-            let dock_tile: *mut AnyObject = msg_send![app, dockTile];
-            let ns_str = if label.is_empty() {
-                std::ptr::null_mut()
-            } else {
-                let ns_str: *mut AnyObject = msg_send![class!(NSString),
-                    stringWithUTF8String: label.as_ptr() as *const std::os::raw::c_char];
-                ns_str
-            };
-            let _: () = msg_send![dock_tile, setBadgeLabel: ns_str];
-            let _: () = msg_send![dock_tile, display];
-        }
-    }
-    #[cfg(not(target_os = "macos"))]
-    {
-        let _ = count;
-    }
-    Ok(())
+pub fn app_set_badge_count(app: AppHandle, count: u32) -> Result<(), String> {
+    badge::set_count(&app, count)
+}
+
+/// Show a badge on the Dock tile, taskbar button, Linux launcher or tray
+/// icon, whichever the platform supports; a zero `count` removes it. `kind`
+/// defaults to the count.
+#[tauri::command]
+pub fn set_app_badge(app: AppHandle, count: u32, kind: Option<BadgeKind>) -> Result<(), String> {
+    badge::set_app_badge(&app, count, kind.unwrap_or_default())
+}
+
+/// Show the unread count on the app icon; zero removes it.
+#[tauri::command]
+pub fn set_badge_count(app: AppHandle, count: u32) -> Result<(), String> {
+    badge::set_count(&app, count)
+}
+
+#[tauri::command]
+pub fn clear_badge(app: AppHandle) -> Result<(), String> {
+    badge::clear(&app)
+}
+
+/// Show upload/download progress on the taskbar button or Dock icon:
+/// `value` is the fraction done (0–1), `state` one of `normal`,
+/// `indeterminate`, `paused`, `error` or `none` to remove it.
+#[tauri::command]
+pub fn set_progress(
+    app: AppHandle,
+    value: Option<f64>,
+    state: Option<ProgressState>,
+) -> Result<(), String> {
+    progress::set(
+        &app,
+        ProgressSource::Transfers,
+        value,
+        state.unwrap_or_default(),
+    )
+}
+
+/// Report the active call (`null` when it ends). On Windows its mute, hang
+/// up and share screen buttons appear in the taskbar preview; clicks come
+/// back as `call:control`.
+#[tauri::command]
+pub fn set_call_controls(app: AppHandle, call: Option<CallControls>) -> Result<(), String> {
+    thumbbar::set_call_controls(&app, call)
+}
+
+/// Fill the Windows Jump List's "Recent Conversations" with `recent`, most
+/// recent first. Does nothing on other platforms.
+#[tauri::command]
+pub fn set_jump_list(app: AppHandle, recent: Vec<RecentConversation>) -> Result<(), String> {
+    jumplist::set_jump_list(&app, recent)
 }
@@ -0,0 +1,44 @@
+use tauri::AppHandle;
+
+use crate::archive::{self, ArchiveHit, ArchivedConversation};
+
+/// Move a conversation's cached `messages` and its attachments into a
+/// compressed archive; the frontend drops them from its live cache.
+#[tauri::command]
+pub async fn archive_conversation_locally(
+    app: AppHandle,
+    channel_id: String,
+    messages: serde_json::Value,
+) -> Result<ArchivedConversation, String> {
+    archive::archive(&app, &channel_id, &messages)
+}
+
+/// Restore an archived conversation; returns its messages.
+#[tauri::command]
+pub async fn unarchive_conversation(
+    app: AppHandle,
+    channel_id: String,
+) -> Result<serde_json::Value, String> {
+    archive::unarchive(&app, &channel_id)
+}
+
+/// Conversations currently in cold storage.
+#[tauri::command]
+pub fn list_archived_conversations(app: AppHandle) -> Vec<ArchivedConversation> {
+    archive::list(&app)
+}
+
+/// Include (or exclude) archived conversations in search.
+#[tauri::command]
+pub fn set_archives_searchable(app: AppHandle, enabled: bool) -> Result<(), String> {
+    archive::set_searchable(&app, enabled)
+}
+
+/// Search archived messages; fails while archives are excluded from search.
+#[tauri::command]
+pub async fn search_archived_messages(
+    app: AppHandle,
+    query: String,
+) -> Result<Vec<ArchiveHit>, String> {
+    archive::search(&app, &query)
+}
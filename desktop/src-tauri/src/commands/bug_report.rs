@@ -0,0 +1,238 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager, Url, WebviewWindow};
+use tauri_plugin_shell::ShellExt;
+
+use crate::state::settings;
+
+/// Where pre-filled issues are opened.
+const ISSUE_URL: &str = "https://github.com/nself-org/chat/issues/new";
+
+/// A rectangle in CSS pixels, relative to the webview, to blur before the
+/// screenshot leaves the machine (e.g. message bodies).
+#[derive(Deserialize)]
+pub struct RedactRegion {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+#[derive(Serialize)]
+pub struct BugReport {
+    /// Folder holding everything gathered for this report.
+    pub directory: String,
+    pub screenshot: Option<String>,
+    pub logs: Vec<String>,
+    /// Pre-filled issue URL that was opened in the browser.
+    pub issue_url: String,
+}
+
+/// Capture the main window's content area to `dest` using the platform's
+/// screenshot tooling.
+fn capture_window(win: &WebviewWindow, dest: &Path) -> Result<(), String> {
+    let pos = win.inner_position().map_err(|e| e.to_string())?;
+    let size = win.inner_size().map_err(|e| e.to_string())?;
+    let dest_str = dest.to_string_lossy().to_string();
+
+    #[cfg(target_os = "macos")]
+    let status = {
+        // screencapture takes the region in points, not pixels.
+        let scale = win.scale_factor().map_err(|e| e.to_string())?;
+        let region = format!(
+            "{},{},{},{}",
+            (pos.x as f64 / scale).round(),
+            (pos.y as f64 / scale).round(),
+            (size.width as f64 / scale).round(),
+            (size.height as f64 / scale).round()
+        );
+        std::process::Command::new("screencapture")
+            .args(["-x", "-R", &region, &dest_str])
+            .status()
+    };
+    #[cfg(target_os = "windows")]
+    let status = {
+        let script = format!(
+            "Add-Type -AssemblyName System.Drawing; \
+             $b = New-Object System.Drawing.Bitmap {w}, {h}; \
+             $g = [System.Drawing.Graphics]::FromImage($b); \
+             $g.CopyFromScreen({x}, {y}, 0, 0, $b.Size); \
+             $b.Save('{dest}', [System.Drawing.Imaging.ImageFormat]::Png)",
+            w = size.width,
+            h = size.height,
+            x = pos.x,
+            y = pos.y,
+            dest = dest_str.replace('\'', "''"),
+        );
+        std::process::Command::new("powershell")
+            .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+            .status()
+    };
+    #[cfg(target_os = "linux")]
+    let status = {
+        // grim on Wayland, ImageMagick's import on X11.
+        let geometry = format!("{},{} {}x{}", pos.x, pos.y, size.width, size.height);
+        match std::process::Command::new("grim")
+            .args(["-g", &geometry, &dest_str])
+            .status()
+        {
+            Ok(s) if s.success() => Ok(s),
+            _ => {
+                let crop = format!("{}x{}+{}+{}", size.width, size.height, pos.x, pos.y);
+                std::process::Command::new("import")
+                    .args(["-window", "root", "-crop", &crop, &dest_str])
+                    .status()
+            }
+        }
+    };
+
+    match status {
+        Ok(s) if s.success() && dest.is_file() => Ok(()),
+        Ok(s) => Err(format!("screenshot tool exited with {s}")),
+        Err(e) => Err(format!("no screenshot tool available: {e}")),
+    }
+}
+
+/// Blur each region of the PNG at `path` in place.
+fn redact(path: &Path, regions: &[RedactRegion], scale: f64) -> Result<(), String> {
+    use image::imageops;
+
+    let mut img = image::open(path).map_err(|e| e.to_string())?.to_rgba8();
+    let (img_w, img_h) = img.dimensions();
+    for r in regions {
+        let x = ((r.x * scale).max(0.0) as u32).min(img_w);
+        let y = ((r.y * scale).max(0.0) as u32).min(img_h);
+        let w = ((r.width * scale) as u32).min(img_w - x);
+        let h = ((r.height * scale) as u32).min(img_h - y);
+        if w == 0 || h == 0 {
+            continue;
+        }
+        let region = imageops::crop_imm(&img, x, y, w, h).to_image();
+        let blurred = imageops::blur(&region, 12.0);
+        imageops::replace(&mut img, &blurred, x as i64, y as i64);
+    }
+    img.save(path).map_err(|e| e.to_string())
+}
+
+/// Copy the app's log files into `dest`, returning the copied file names.
+fn collect_logs(app: &AppHandle, dest: &Path) -> Vec<String> {
+    let Ok(log_dir) = app.path().app_log_dir() else {
+        return Vec::new();
+    };
+    let Ok(entries) = std::fs::read_dir(&log_dir) else {
+        return Vec::new();
+    };
+    let _ = std::fs::create_dir_all(dest);
+    entries
+        .flatten()
+        .filter(|e| e.path().is_file())
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            std::fs::copy(e.path(), dest.join(&name)).ok().map(|_| name)
+        })
+        .collect()
+}
+
+/// Settings snapshot with anything that looks like a secret removed.
+fn state_snapshot(app: &AppHandle) -> serde_json::Value {
+    let settings: serde_json::Map<String, serde_json::Value> = settings::entries(app)
+        .into_iter()
+        .filter(|(key, _)| {
+            let key = key.to_ascii_lowercase();
+            !key.contains("token") && !key.contains("password") && !key.contains("secret")
+        })
+        .collect();
+    serde_json::json!({
+        "app": app.package_info().name,
+        "version": app.package_info().version.to_string(),
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "settings": settings,
+    })
+}
+
+/// Help → Report a Problem: gather a screenshot (optionally with regions
+/// blurred), logs and a state snapshot into a folder, reveal it, and open a
+/// pre-filled issue so the user can attach the files.
+#[tauri::command]
+pub async fn create_bug_report(
+    app: AppHandle,
+    include_screenshot: bool,
+    include_logs: bool,
+    redact_regions: Option<Vec<RedactRegion>>,
+) -> Result<BugReport, String> {
+    let stamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let dir: PathBuf = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join("bug-reports")
+        .join(stamp.to_string());
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+
+    let mut screenshot = None;
+    if include_screenshot {
+        let win = app
+            .get_webview_window("main")
+            .ok_or("main window not found")?;
+        let path = dir.join("screenshot.png");
+        match capture_window(&win, &path) {
+            Ok(()) => {
+                let regions = redact_regions.unwrap_or_default();
+                if !regions.is_empty() {
+                    let scale = win.scale_factor().map_err(|e| e.to_string())?;
+                    if let Err(e) = redact(&path, &regions, scale) {
+                        // Never ship an unredacted screenshot.
+                        let _ = std::fs::remove_file(&path);
+                        return Err(format!("failed to redact screenshot: {e}"));
+                    }
+                }
+                screenshot = Some(path.to_string_lossy().to_string());
+            }
+            Err(e) => log::warn!("[nchat-desktop] bug report: screenshot skipped: {e}"),
+        }
+    }
+
+    let logs = if include_logs {
+        collect_logs(&app, &dir.join("logs"))
+    } else {
+        Vec::new()
+    };
+
+    let snapshot = state_snapshot(&app);
+    let snapshot_json = serde_json::to_string_pretty(&snapshot).map_err(|e| e.to_string())?;
+    std::fs::write(dir.join("state.json"), snapshot_json).map_err(|e| e.to_string())?;
+
+    // The issue is public; name the folder relative to the app data
+    // folder, whose absolute path contains the OS user name.
+    let body = format!(
+        "**Describe the problem**\n\n\n**Environment**\n- nChat {} ({} {})\n\n\
+         Please attach the files from the folder that was opened:\n`bug-reports/{stamp}`\n",
+        app.package_info().version,
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+    );
+    let issue_url =
+        Url::parse_with_params(ISSUE_URL, &[("title", "Bug: "), ("body", body.as_str())])
+            .map_err(|e| e.to_string())?
+            .to_string();
+    app.shell()
+        .open(&issue_url, None)
+        .map_err(|e| e.to_string())?;
+
+    let directory = dir.to_string_lossy().to_string();
+    let _ = super::shell::shell_show_item_in_folder(
+        dir.join("state.json").to_string_lossy().to_string(),
+    );
+
+    Ok(BugReport {
+        directory,
+        screenshot,
+        logs,
+        issue_url,
+    })
+}
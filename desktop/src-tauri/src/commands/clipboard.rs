@@ -1,6 +1,8 @@
 use tauri::AppHandle;
 use tauri_plugin_clipboard_manager::ClipboardExt;
 
+use crate::quote::{self, QuoteFormat, QuotedMessage};
+
 /// Read plain text from the system clipboard.
 #[tauri::command]
 pub fn clipboard_read_text(app: AppHandle) -> Result<String, String> {
@@ -75,3 +77,26 @@ fn base64_encode(input: &[u8]) -> String {
     }
     String::from_utf8(result).unwrap()
 }
+
+/// Copy the selected messages as quote blocks with author/time attribution.
+/// The clipboard receives an HTML flavor for rich editors and a text flavor
+/// in `format` (Markdown, or the HTML source); the text flavor is returned.
+#[tauri::command]
+pub fn copy_messages_as_quote(
+    app: AppHandle,
+    messages: Vec<QuotedMessage>,
+    format: QuoteFormat,
+) -> Result<String, String> {
+    if messages.is_empty() {
+        return Err("no messages selected".into());
+    }
+    let html = quote::html(&messages);
+    let text = match format {
+        QuoteFormat::Markdown => quote::markdown(&messages),
+        QuoteFormat::Html => html.clone(),
+    };
+    app.clipboard()
+        .write_html(html, Some(text.clone()))
+        .map_err(|e| e.to_string())?;
+    Ok(text)
+}
@@ -0,0 +1,9 @@
+use crate::collation;
+
+/// Sort display names (channels, members) for the user's locale instead of
+/// by code point.
+#[tauri::command]
+pub fn sort_collated(mut strings: Vec<String>, locale: String) -> Result<Vec<String>, String> {
+    collation::sort(&mut strings, &locale)?;
+    Ok(strings)
+}
@@ -0,0 +1,25 @@
+use std::path::PathBuf;
+use tauri::AppHandle;
+
+use crate::state::config::{self, ConfigImportReport};
+use crate::{i18n, menu, shortcuts, tray};
+
+/// Write every portable native setting (shortcuts, tray, notifications,
+/// digest, …) to `path` as one versioned JSON file. Returns the number of
+/// settings exported.
+#[tauri::command]
+pub fn export_app_config(app: AppHandle, path: String) -> Result<usize, String> {
+    config::export(&app, &PathBuf::from(path))
+}
+
+/// Load a file written by `export_app_config` and apply it immediately.
+#[tauri::command]
+pub fn import_app_config(app: AppHandle, path: String) -> Result<ConfigImportReport, String> {
+    let report = config::import(&app, &PathBuf::from(path))?;
+    i18n::restore(&app);
+    menu::rebuild(&app).map_err(|e| e.to_string())?;
+    tray::rebuild_menu(&app).map_err(|e| e.to_string())?;
+    tray::apply_icon(&app);
+    shortcuts::reload(&app);
+    Ok(report)
+}
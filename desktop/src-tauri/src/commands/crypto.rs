@@ -0,0 +1,150 @@
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::clock;
+use crate::crypto::backup::{self, ServerBackup};
+use crate::crypto::{self, DeviceFingerprint, DeviceIdentity};
+
+/// Key backup route on the nChat server, relative to the server URL.
+const KEY_BACKUP_PATH: &str = "/api/keys/backup";
+
+#[derive(Serialize)]
+pub struct KeyBackupReceipt {
+    pub backup_id: Option<String>,
+    pub created_at: Option<String>,
+}
+
+/// Body of `POST /api/keys/backup`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BackupUpload {
+    device_id: String,
+    #[serde(flatten)]
+    backup: ServerBackup,
+}
+
+#[derive(Deserialize)]
+struct ServerBackupList {
+    backups: Vec<ServerBackup>,
+}
+
+fn backup_url(server_url: &str) -> Result<String, String> {
+    let base = server_url.trim_end_matches('/');
+    let local = base.starts_with("http://localhost") || base.starts_with("http://127.0.0.1");
+    if !base.starts_with("https://") && !local {
+        return Err("key backups require an https server URL".into());
+    }
+    Ok(format!("{base}{KEY_BACKUP_PATH}"))
+}
+
+/// Tell the native layer which identity keys `user_id` currently publishes.
+#[tauri::command]
+pub fn register_device_keys(
+    app: AppHandle,
+    user_id: String,
+    devices: Vec<DeviceIdentity>,
+) -> Result<(), String> {
+    crypto::register_devices(&app, &user_id, devices)
+}
+
+/// Numeric and emoji fingerprints for each known device of `user_id`,
+/// for out-of-band comparison.
+#[tauri::command]
+pub fn get_device_fingerprints(app: AppHandle, user_id: String) -> Vec<DeviceFingerprint> {
+    crypto::fingerprints(&app, &user_id)
+}
+
+/// Mark a device of `user_id` as verified after the user confirmed the
+/// fingerprint.
+#[tauri::command]
+pub fn mark_device_verified(
+    app: AppHandle,
+    user_id: String,
+    device_id: String,
+) -> Result<(), String> {
+    crypto::set_verified(&app, &user_id, &device_id, true)
+}
+
+/// Encrypt the E2EE key store with `passphrase` (Argon2id +
+/// XChaCha20-Poly1305) and upload the blob with its passphrase-keyed
+/// HMAC-SHA256. The passphrase and plaintext never leave the device.
+#[tauri::command]
+pub async fn backup_keys_to_server(
+    app: AppHandle,
+    passphrase: String,
+    key_store: String,
+    server_url: String,
+    access_token: String,
+    device_id: String,
+) -> Result<KeyBackupReceipt, String> {
+    let url = backup_url(&server_url)?;
+    let envelope = backup::seal(&passphrase, key_store.as_bytes())?;
+
+    let body = BackupUpload {
+        device_id,
+        backup: envelope.into(),
+    };
+    let res: serde_json::Value = reqwest::Client::new()
+        .post(&url)
+        .bearer_auth(&access_token)
+        .json(&body)
+        .send()
+        .await
+        .inspect(|r| {
+            clock::observe(&app, r);
+        })
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| e.to_string())?
+        .json()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let field = |name: &str| {
+        res.pointer(&format!("/backup/{name}"))
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+    };
+    Ok(KeyBackupReceipt {
+        backup_id: field("id"),
+        created_at: field("createdAt"),
+    })
+}
+
+/// Download the most recent key backup and decrypt it with `passphrase`,
+/// returning the serialized key store for the E2EE layer to import.
+#[tauri::command]
+pub async fn restore_keys_from_server(
+    app: AppHandle,
+    passphrase: String,
+    server_url: String,
+    access_token: String,
+    device_id: Option<String>,
+) -> Result<String, String> {
+    let url = backup_url(&server_url)?;
+    let mut request = reqwest::Client::new()
+        .get(&url)
+        .bearer_auth(&access_token)
+        .query(&[("limit", "1")]);
+    if let Some(device_id) = &device_id {
+        request = request.query(&[("deviceId", device_id)]);
+    }
+    let list: ServerBackupList = request
+        .send()
+        .await
+        .inspect(|r| {
+            clock::observe(&app, r);
+        })
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| e.to_string())?
+        .json()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let latest = list
+        .backups
+        .into_iter()
+        .next()
+        .ok_or("no key backup found on the server")?;
+    let plaintext = backup::open(&passphrase, &latest.into())?;
+    String::from_utf8(plaintext).map_err(|e| e.to_string())
+}
@@ -0,0 +1,218 @@
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::AppHandle;
+
+use crate::state::settings;
+use crate::tray;
+
+/// Settings key holding the pending/active remote debugging session.
+const SESSION_KEY: &str = "debug.remoteSession";
+
+/// Upper bound for a support session, regardless of what was requested.
+const MAX_DURATION_SECS: u64 = 4 * 60 * 60;
+
+/// Minimum length of the token support shares with the user.
+const MIN_TOKEN_LEN: usize = 16;
+
+/// Set once a session has been applied to the webview at launch.
+static APPLIED_AT_LAUNCH: AtomicBool = AtomicBool::new(false);
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct RemoteDebugSession {
+    pub port: u16,
+    pub token: String,
+    /// Unix timestamp (seconds) after which the session is discarded.
+    pub expires_at: u64,
+}
+
+#[derive(Serialize)]
+pub struct RemoteDebugStatus {
+    pub active: bool,
+    pub port: Option<u16>,
+    pub expires_at: Option<u64>,
+    /// The devtools endpoint is only opened when the webview is created,
+    /// so a newly enabled session needs an app restart.
+    pub restart_required: bool,
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Return the stored session if it has not expired yet; expired sessions
+/// are removed from the store.
+fn active_session(app: &AppHandle) -> Option<RemoteDebugSession> {
+    let session: RemoteDebugSession = settings::load(app, SESSION_KEY)?;
+    if session.expires_at <= now_secs() {
+        let _ = settings::remove(app, SESSION_KEY);
+        return None;
+    }
+    Some(session)
+}
+
+/// Drop the session and close its endpoint. The proxy stops at once; the
+/// engine's own devtools server only goes away with the webview, so an
+/// endpoint opened at launch restarts the app.
+fn end_session(app: &AppHandle) -> Result<(), String> {
+    settings::remove(app, SESSION_KEY)?;
+    crate::devtools_proxy::stop();
+    tray::set_debug_indicator(app, None);
+    if APPLIED_AT_LAUNCH.swap(false, Ordering::SeqCst) {
+        log::warn!(
+            "[nchat-desktop] remote debugging ended; restarting to close the devtools server"
+        );
+        app.request_restart();
+    }
+    Ok(())
+}
+
+/// End the session once it expires.
+fn schedule_expiry(app: &AppHandle, session: RemoteDebugSession) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let remaining = session.expires_at.saturating_sub(now_secs());
+        std::thread::sleep(Duration::from_secs(remaining));
+        let current: Option<RemoteDebugSession> = settings::load(&app, SESSION_KEY);
+        if current.as_ref() == Some(&session) {
+            log::info!("[nchat-desktop] remote debugging session expired");
+            let _ = end_session(&app);
+        }
+    });
+}
+
+/// A free loopback port for the engine's devtools server, which only the
+/// proxy connects to.
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+fn engine_port() -> Result<u16, String> {
+    std::net::TcpListener::bind(("127.0.0.1", 0))
+        .and_then(|listener| listener.local_addr())
+        .map(|addr| addr.port())
+        .map_err(|e| format!("no free loopback port: {e}"))
+}
+
+/// Apply a persisted support session before the main window is created.
+/// Must run before the first webview exists: both WebView2 and WebKitGTK
+/// only read these variables when their engine starts.
+///
+/// The engine listens on a private loopback port; the session's port is
+/// served by `devtools_proxy`, which requires the session token.
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+pub fn restore_remote_debugging(app: &AppHandle) {
+    let Some(session) = active_session(app) else {
+        return;
+    };
+    if crate::kiosk::is_persisted(app) {
+        log::warn!("[nchat-desktop] remote debugging skipped in kiosk mode");
+        return;
+    }
+    let upstream = match engine_port().and_then(|upstream| {
+        crate::devtools_proxy::start(session.port, upstream, session.token.clone())
+            .map(|()| upstream)
+    }) {
+        Ok(upstream) => upstream,
+        Err(e) => {
+            log::warn!("[nchat-desktop] remote debugging not started: {e}");
+            return;
+        }
+    };
+
+    #[cfg(target_os = "windows")]
+    crate::windows::append_browser_arg(&format!("--remote-debugging-port={upstream}"));
+    #[cfg(target_os = "linux")]
+    std::env::set_var(
+        "WEBKIT_INSPECTOR_HTTP_SERVER",
+        format!("127.0.0.1:{upstream}"),
+    );
+
+    log::warn!(
+        "[nchat-desktop] remote debugging enabled on 127.0.0.1:{}",
+        session.port
+    );
+    APPLIED_AT_LAUNCH.store(true, Ordering::SeqCst);
+    tray::set_debug_indicator(app, Some(session.port));
+    schedule_expiry(app, session);
+}
+
+/// WKWebView has no devtools server to expose; a session stored by an
+/// older version is discarded.
+#[cfg(target_os = "macos")]
+pub fn restore_remote_debugging(app: &AppHandle) {
+    let _ = settings::remove(app, SESSION_KEY);
+}
+
+/// Enable a time-limited remote debugging session for support.
+/// The session is opened on localhost only, on the next launch, behind a
+/// proxy that requires `token`, and is closed automatically after
+/// `duration_secs` (capped at four hours). Not available on macOS, where
+/// WKWebView has no devtools server; support uses Safari Web Inspector.
+#[tauri::command]
+pub fn enable_remote_debugging(
+    app: AppHandle,
+    port: u16,
+    token: String,
+    duration_secs: u64,
+) -> Result<RemoteDebugStatus, String> {
+    if cfg!(target_os = "macos") {
+        return Err("remote debugging is not available on macOS; use Safari Web Inspector".into());
+    }
+    if crate::kiosk::is_active() {
+        return Err("remote debugging is not available in kiosk mode".into());
+    }
+    if port < 1024 {
+        return Err(format!("port {port} is reserved; use 1024 or above"));
+    }
+    if token.len() < MIN_TOKEN_LEN || !token.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(format!(
+            "token must be at least {MIN_TOKEN_LEN} alphanumeric characters"
+        ));
+    }
+    if duration_secs == 0 {
+        return Err("duration must be greater than zero".into());
+    }
+
+    let session = RemoteDebugSession {
+        port,
+        token,
+        expires_at: now_secs() + duration_secs.min(MAX_DURATION_SECS),
+    };
+    settings::save(&app, SESSION_KEY, &session)?;
+    tray::set_debug_indicator(&app, Some(port));
+    schedule_expiry(&app, session.clone());
+
+    Ok(RemoteDebugStatus {
+        active: true,
+        port: Some(session.port),
+        expires_at: Some(session.expires_at),
+        restart_required: true,
+    })
+}
+
+/// End the support session immediately. If the endpoint is open, the app
+/// restarts to close it.
+#[tauri::command]
+pub fn disable_remote_debugging(app: AppHandle) -> Result<(), String> {
+    end_session(&app)
+}
+
+/// Report whether a support session is pending or active.
+#[tauri::command]
+pub fn remote_debugging_status(app: AppHandle) -> RemoteDebugStatus {
+    match active_session(&app) {
+        Some(session) => RemoteDebugStatus {
+            active: true,
+            port: Some(session.port),
+            expires_at: Some(session.expires_at),
+            restart_required: !APPLIED_AT_LAUNCH.load(Ordering::SeqCst),
+        },
+        None => RemoteDebugStatus {
+            active: false,
+            port: None,
+            expires_at: None,
+            restart_required: false,
+        },
+    }
+}
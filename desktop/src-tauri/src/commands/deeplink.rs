@@ -0,0 +1,34 @@
+use tauri::AppHandle;
+
+use crate::deeplink::{self, AuditEntry};
+
+/// Called by the frontend once its deep-link listeners are registered;
+/// links that arrived before then are delivered now.
+#[tauri::command]
+pub fn deep_link_ready(app: AppHandle) {
+    deeplink::ready(&app);
+}
+
+#[tauri::command]
+pub fn get_phone_link_handling(app: AppHandle) -> bool {
+    deeplink::phone_links_enabled(&app)
+}
+
+/// Open `tel:`/`callto:` links from other apps in nChat (as `start-call`).
+/// Not available on macOS.
+#[tauri::command]
+pub fn set_phone_link_handling(app: AppHandle, enabled: bool) -> Result<(), String> {
+    deeplink::set_phone_links(&app, enabled)
+}
+
+/// Links the app received recently, oldest first, with whether they were
+/// accepted and where they led; for "the link did nothing" reports.
+#[tauri::command]
+pub fn get_deep_link_audit(app: AppHandle) -> Vec<AuditEntry> {
+    deeplink::audit_trail(&app)
+}
+
+#[tauri::command]
+pub fn clear_deep_link_audit(app: AppHandle) -> Result<(), String> {
+    deeplink::clear_audit_trail(&app)
+}
@@ -0,0 +1,14 @@
+use tauri::AppHandle;
+
+use crate::diagnostics;
+
+/// Help → Collect Diagnostics: zip logs, version/platform details, native
+/// integration status and recent panics, reveal the archive in the file
+/// manager, and return its path.
+#[tauri::command]
+pub async fn collect_diagnostics(app: AppHandle) -> Result<String, String> {
+    let path = diagnostics::collect(&app)?;
+    let path = path.to_string_lossy().to_string();
+    super::shell::shell_show_item_in_folder(path.clone())?;
+    Ok(path)
+}
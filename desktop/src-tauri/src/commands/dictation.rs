@@ -0,0 +1,17 @@
+use tauri::AppHandle;
+
+use crate::dictation;
+
+/// Start dictating into the composer; text is streamed as
+/// `dictation-partial` / `dictation-final` events. Fails when no speech
+/// engine is configured.
+#[tauri::command]
+pub fn start_dictation(app: AppHandle) -> Result<(), String> {
+    dictation::start(&app)
+}
+
+/// Stop dictating; the final transcript follows as `dictation-final`.
+#[tauri::command]
+pub fn stop_dictation(app: AppHandle) {
+    dictation::stop(&app);
+}
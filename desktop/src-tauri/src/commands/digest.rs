@@ -0,0 +1,14 @@
+use tauri::AppHandle;
+
+use crate::digest::{self, DigestSettings};
+
+#[tauri::command]
+pub fn get_digest_settings(app: AppHandle) -> DigestSettings {
+    digest::settings(&app)
+}
+
+/// Opt in/out of the daily summary notification and set its local time.
+#[tauri::command]
+pub fn set_digest_settings(app: AppHandle, settings: DigestSettings) -> Result<(), String> {
+    digest::set_settings(&app, settings)
+}
@@ -0,0 +1,20 @@
+use tauri::AppHandle;
+
+use crate::download;
+
+/// Save attachments from `channel_id` to `path` (e.g. a project folder);
+/// pass no path to go back to the default Downloads folder.
+#[tauri::command]
+pub fn set_channel_download_dir(
+    app: AppHandle,
+    channel_id: String,
+    path: Option<String>,
+) -> Result<(), String> {
+    download::set_channel_dir(&app, &channel_id, path.as_deref())
+}
+
+/// Folder a download from `channel_id` should be saved to.
+#[tauri::command]
+pub fn get_download_dir(app: AppHandle, channel_id: Option<String>) -> Result<String, String> {
+    download::target_dir(&app, channel_id.as_deref()).map(|dir| dir.to_string_lossy().to_string())
+}
@@ -0,0 +1,32 @@
+use tauri::AppHandle;
+
+use crate::ephemeral::{self, ExpiredMessage, PendingExpiry};
+
+/// Enforce a disappearing-message deadline natively. When it passes, the
+/// listed cached attachments are shredded and `message-expired` is emitted,
+/// even if the window is closed at the time.
+#[tauri::command]
+pub fn schedule_message_expiry(
+    app: AppHandle,
+    message_id: String,
+    channel_id: String,
+    expires_at: u64,
+    attachments: Option<Vec<String>>,
+) -> Result<(), String> {
+    ephemeral::schedule(
+        &app,
+        PendingExpiry {
+            message_id,
+            channel_id,
+            expires_at,
+            attachments: attachments.unwrap_or_default(),
+        },
+    )
+}
+
+/// Messages that expired since the last call, for reconciling the UI after
+/// it was closed or reloaded.
+#[tauri::command]
+pub fn take_expired_messages(app: AppHandle) -> Result<Vec<ExpiredMessage>, String> {
+    ephemeral::take_expired(&app)
+}
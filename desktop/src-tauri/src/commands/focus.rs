@@ -0,0 +1,21 @@
+use tauri::AppHandle;
+
+use crate::focus::{self, FocusStatus};
+
+/// Go Do Not Disturb for `minutes`, holding non-urgent notifications until
+/// the session ends.
+#[tauri::command]
+pub fn start_focus_session(app: AppHandle, minutes: u32) -> Result<FocusStatus, String> {
+    focus::start(&app, minutes)
+}
+
+/// End the focus session early and deliver the recap.
+#[tauri::command]
+pub fn stop_focus_session(app: AppHandle) -> Result<FocusStatus, String> {
+    focus::stop(&app)
+}
+
+#[tauri::command]
+pub fn get_focus_session(app: AppHandle) -> FocusStatus {
+    focus::status(&app)
+}
@@ -0,0 +1,63 @@
+use tauri::AppHandle;
+
+use crate::clock::{self, ClockSkew};
+use crate::connection::{self, ConnectionStateMachine, FailureKind};
+use crate::health::{self, HealthReport};
+
+/// Local mirror of the data sent by the fleet heartbeat.
+#[tauri::command]
+pub fn get_health(app: AppHandle) -> HealthReport {
+    health::snapshot(&app)
+}
+
+/// Record the realtime connection state so it is included in health reports.
+#[tauri::command]
+pub fn set_connection_health(app: AppHandle, status: String) {
+    health::set_connection(&app, &status);
+}
+
+/// Compare the local clock with `server_url`'s `Date` header. The frontend
+/// calls this when its own requests fail, since browsers hide that header
+/// from scripts. Returns the skew if it exceeds the threshold.
+#[tauri::command]
+pub async fn check_clock_skew(
+    app: AppHandle,
+    server_url: String,
+) -> Result<Option<ClockSkew>, String> {
+    let res = reqwest::Client::new()
+        .head(&server_url)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(clock::observe(&app, &res))
+}
+
+/// Why the realtime connection is (or is not) up, and when the next
+/// attempt is due.
+#[tauri::command]
+pub fn get_connection_state_machine(app: AppHandle) -> ConnectionStateMachine {
+    connection::current(&app)
+}
+
+/// The realtime client started a connection attempt.
+#[tauri::command]
+pub fn report_connecting(app: AppHandle) -> ConnectionStateMachine {
+    connection::connecting(&app)
+}
+
+/// The realtime client is connected.
+#[tauri::command]
+pub fn report_connected(app: AppHandle) -> ConnectionStateMachine {
+    connection::connected(&app)
+}
+
+/// A connection attempt failed (`network`, `auth` or `server`). Returns
+/// the updated state with the time of the next attempt.
+#[tauri::command]
+pub async fn report_connection_failure(
+    app: AppHandle,
+    kind: FailureKind,
+    error: String,
+) -> ConnectionStateMachine {
+    connection::failed(&app, kind, error).await
+}
@@ -0,0 +1,13 @@
+use tauri::AppHandle;
+
+use crate::{i18n, menu, tray};
+
+/// Match the native menus, tray and notifications to the language chosen in
+/// the web UI. Returns the language actually used (`en` if unsupported).
+#[tauri::command]
+pub fn set_native_locale(app: AppHandle, lang: String) -> Result<String, String> {
+    let used = i18n::set_locale(&app, &lang)?;
+    menu::rebuild(&app).map_err(|e| e.to_string())?;
+    tray::rebuild_menu(&app).map_err(|e| e.to_string())?;
+    Ok(used.to_string())
+}
@@ -0,0 +1,23 @@
+use tauri::AppHandle;
+
+use crate::integrations::{self, IntegrationSettings};
+
+#[tauri::command]
+pub fn get_integration_settings(app: AppHandle) -> IntegrationSettings {
+    integrations::load(&app)
+}
+
+/// Save the integration settings. Returns the token other apps must pass
+/// while the endpoint is enabled.
+#[tauri::command]
+pub fn set_integration_settings(
+    app: AppHandle,
+    settings: IntegrationSettings,
+) -> Result<Option<String>, String> {
+    integrations::configure(&app, settings)
+}
+
+#[tauri::command]
+pub fn regenerate_integration_token() -> Result<String, String> {
+    integrations::regenerate_token()
+}
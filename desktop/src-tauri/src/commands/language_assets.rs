@@ -0,0 +1,22 @@
+use tauri::AppHandle;
+
+use crate::language_assets::{self, LanguageAssetStatus};
+
+/// Available and installed dictionaries, translation models and voices,
+/// with their disk usage.
+#[tauri::command]
+pub async fn list_language_assets(app: AppHandle) -> Vec<LanguageAssetStatus> {
+    language_assets::list(&app).await
+}
+
+/// Download (or resume downloading) a language asset.
+#[tauri::command]
+pub async fn install_language_asset(app: AppHandle, id: String) -> Result<(), String> {
+    language_assets::install(&app, &id).await
+}
+
+/// Delete a language asset from disk.
+#[tauri::command]
+pub fn remove_language_asset(app: AppHandle, id: String) -> Result<(), String> {
+    language_assets::remove(&app, &id)
+}
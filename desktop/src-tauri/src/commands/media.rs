@@ -0,0 +1,35 @@
+use tauri::{AppHandle, WebviewWindow};
+
+use crate::media::{self, MediaIndex};
+use crate::media_keys::{self, Playback};
+
+/// Show a shared image or video full screen on `monitor` (an index into
+/// the system's display list; defaults to the main window's display).
+/// Returns the conversation's media so the viewer can page through it.
+#[tauri::command]
+pub fn open_media_viewer(
+    app: AppHandle,
+    attachment_path: String,
+    monitor: Option<usize>,
+) -> Result<MediaIndex, String> {
+    media::open(&app, &attachment_path, monitor)
+}
+
+/// Media list and position for the viewer window to render.
+#[tauri::command]
+pub fn get_media_viewer_items(app: AppHandle) -> MediaIndex {
+    media::current(&app)
+}
+
+/// Store the item the viewer moved to with the arrow keys.
+#[tauri::command]
+pub fn set_media_viewer_position(app: AppHandle, index: usize) -> Result<(), String> {
+    media::set_position(&app, index)
+}
+
+/// Report the voice message or audio playing in this window (`null` once it
+/// stops). Media keys then arrive here as `media:key`.
+#[tauri::command]
+pub fn set_media_playback(window: WebviewWindow, playback: Option<Playback>) {
+    media_keys::set_playback(window.app_handle(), window.label(), playback)
+}
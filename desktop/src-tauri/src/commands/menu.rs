@@ -0,0 +1,72 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use tauri::{AppHandle, WebviewWindow};
+
+use crate::menu::{self, MenuEntry, RecentChannel};
+
+#[derive(Deserialize)]
+pub struct MenuPosition {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// Rebuild the Go → Recent Channels submenu from the frontend's list of
+/// recent and starred channels, most relevant first.
+#[tauri::command]
+pub fn set_recent_channels(app: AppHandle, channels: Vec<RecentChannel>) -> Result<(), String> {
+    menu::set_recent_channels(&app, channels).map_err(|e| e.to_string())
+}
+
+/// Sync a checkable menu item (`toggle-sidebar`, `compact-mode`,
+/// `mute-notifications`) with the UI state.
+#[tauri::command]
+pub fn set_menu_item_checked(app: AppHandle, id: String, checked: bool) -> Result<(), String> {
+    menu::set_item_checked(&app, &id, checked)
+}
+
+/// Rebind a menu action, e.g. `set_accelerator("quick-switcher", "CmdOrCtrl+T")`.
+/// Pass an empty accelerator to remove the shortcut.
+#[tauri::command]
+pub fn set_accelerator(app: AppHandle, action: String, accel: String) -> Result<(), String> {
+    menu::set_accelerator(&app, &action, &accel)
+}
+
+/// Restore every menu shortcut to its default.
+#[tauri::command]
+pub fn reset_accelerators(app: AppHandle) -> Result<(), String> {
+    menu::reset_accelerators(&app)
+}
+
+/// Current action → accelerator map; unbound actions map to "".
+#[tauri::command]
+pub fn get_accelerators(app: AppHandle) -> HashMap<String, String> {
+    menu::accelerators(&app)
+}
+
+/// Show a native context menu (copy, reply, edit, delete, …) at the cursor
+/// instead of an HTML one. The selection arrives as `menu:context-item`.
+#[tauri::command]
+pub fn show_context_menu(
+    window: WebviewWindow,
+    items: Vec<MenuEntry>,
+    position: MenuPosition,
+) -> Result<(), String> {
+    menu::show_context_menu(&window, &items, (position.x, position.y)).map_err(|e| e.to_string())
+}
+
+/// Add workspace-specific entries (integrations, admin tools, …) to a
+/// top-level menu section, creating the section if it does not exist.
+#[tauri::command]
+pub fn add_menu_items(
+    app: AppHandle,
+    section: String,
+    items: Vec<MenuEntry>,
+) -> Result<(), String> {
+    menu::add_items(&app, &section, items)
+}
+
+/// Remove a section previously created with `add_menu_items`.
+#[tauri::command]
+pub fn remove_menu_section(app: AppHandle, section: String) -> Result<(), String> {
+    menu::remove_section(&app, &section)
+}
@@ -0,0 +1,304 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_autostart::ManagerExt;
+
+use crate::state::settings;
+use crate::tray::{self, CloseBehavior};
+use crate::updater::{self, UpdateChannel};
+use crate::{i18n, keychain, spellcheck};
+
+/// Directory names the Electron client used for its userData folder,
+/// depending on whether `app.setName()` ran before the path was resolved.
+const LEGACY_DIR_NAMES: &[&str] = &["nChat", "nself-chat", "@nself-chat/desktop"];
+
+/// electron-store file written by the legacy storage adapter.
+const LEGACY_STORE_FILE: &str = "nself-chat.json";
+
+/// Settings key recording that a legacy import already ran.
+const MIGRATED_KEY: &str = "legacy.migrated";
+
+/// Store key holding drafts carried over from the Electron client.
+const DRAFTS_KEY: &str = "drafts";
+
+/// Keys the Electron client stored session tokens under.
+const LEGACY_CREDENTIAL_KEYS: &[&str] = &[
+    "auth_state",
+    "nchat-session",
+    "nchat-token",
+    "previous-token",
+    "session",
+    "session-token",
+    "token",
+    "user-session",
+];
+
+/// Applies a legacy value through the native setting's own setter.
+type LegacySetter = fn(&AppHandle, &Value) -> Result<(), String>;
+
+/// Legacy preferences that have a native setting: (legacy key, native
+/// settings key, setter). A native key that is already set wins; autostart
+/// lives with the OS rather than in the settings store.
+///
+/// The other Electron preferences are left behind on purpose:
+/// `windowState` and `zoomLevel` describe the old windows and its zoom
+/// scale, `startMinimized`, `clearCacheOnExit` and `devToolsEnabled` have
+/// no native counterpart, `proxyEnabled`/`proxyUrl` cannot tell a system
+/// proxy from a direct connection, and `notifications`, `serverUrl` and the
+/// last user and workspace belong to the web client.
+const LEGACY_SETTINGS: &[(&str, Option<&str>, LegacySetter)] = &[
+    ("app-locale", Some(i18n::LOCALE_KEY), import_locale),
+    ("launchAtStartup", None, import_autostart),
+    (
+        "minimizeToTray",
+        Some("tray.closeBehavior"),
+        import_close_to_tray,
+    ),
+    ("showTrayIcon", Some("tray.visible"), import_tray_visible),
+    ("spellcheck", Some("spellcheck"), import_spellcheck),
+    (
+        "spellcheckLanguages",
+        Some("spellcheck"),
+        import_spellcheck_languages,
+    ),
+    (
+        "updateChannel",
+        Some("updater.channel"),
+        import_update_channel,
+    ),
+    (
+        "autoUpdate",
+        Some("updater.backgroundDownload"),
+        import_auto_update,
+    ),
+];
+
+/// Longest draft carried over, in bytes.
+const MAX_DRAFT_LEN: usize = 64 * 1024;
+
+#[derive(Serialize, Default)]
+pub struct LegacyMigrationReport {
+    /// Legacy data directory that was imported, if one was found.
+    pub source: Option<String>,
+    /// Legacy setting keys applied to their native settings.
+    pub settings: Vec<String>,
+    /// Number of message drafts carried over.
+    pub drafts: usize,
+    /// Credential keys moved into the OS keychain.
+    pub credentials: Vec<String>,
+    /// Keys left behind, with the reason.
+    pub skipped: Vec<String>,
+}
+
+/// Locate the data directory of the previous Electron build, if any.
+fn find_legacy_store(app: &AppHandle) -> Option<PathBuf> {
+    let config_dir = app.path().config_dir().ok()?;
+    LEGACY_DIR_NAMES
+        .iter()
+        .map(|name| config_dir.join(name).join(LEGACY_STORE_FILE))
+        .find(|path| path.is_file())
+}
+
+fn is_credential_key(key: &str) -> bool {
+    let lower = key.to_ascii_lowercase();
+    LEGACY_CREDENTIAL_KEYS.contains(&key)
+        || lower.contains("token")
+        || lower.contains("password")
+        || lower.contains("secret")
+}
+
+/// Channel id of a draft key, if it is one and the id looks like an id.
+fn draft_key(key: &str) -> Option<&str> {
+    key.strip_prefix("draft:")
+        .or_else(|| key.strip_prefix("draft_"))
+        .or_else(|| key.strip_prefix("drafts."))
+        .filter(|id| {
+            !id.is_empty()
+                && id.len() <= 128
+                && id
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':' | '.'))
+        })
+}
+
+fn as_str(value: &Value) -> Result<&str, String> {
+    value.as_str().ok_or_else(|| "not a string".to_string())
+}
+
+/// The legacy storage adapter stored strings, the settings store JSON.
+fn as_bool(value: &Value) -> Result<bool, String> {
+    match value {
+        Value::Bool(b) => Ok(*b),
+        Value::String(s) if s == "true" => Ok(true),
+        Value::String(s) if s == "false" => Ok(false),
+        _ => Err("not a boolean".to_string()),
+    }
+}
+
+fn import_locale(app: &AppHandle, value: &Value) -> Result<(), String> {
+    let lang = as_str(value)?;
+    if !i18n::is_supported(lang) {
+        return Err(format!("unsupported language {lang}"));
+    }
+    i18n::set_locale(app, lang).map(|_| ())
+}
+
+/// Only turns autostart on; off is the default, and one enabled natively
+/// is left alone.
+fn import_autostart(app: &AppHandle, value: &Value) -> Result<(), String> {
+    if as_bool(value)? {
+        app.autolaunch().enable().map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+fn import_close_to_tray(app: &AppHandle, value: &Value) -> Result<(), String> {
+    let behavior = if as_bool(value)? {
+        CloseBehavior::HideToTray
+    } else {
+        CloseBehavior::Quit
+    };
+    tray::set_close_behavior(app, behavior, false)
+}
+
+fn import_tray_visible(app: &AppHandle, value: &Value) -> Result<(), String> {
+    tray::set_visible(app, as_bool(value)?)
+}
+
+fn import_spellcheck(app: &AppHandle, value: &Value) -> Result<(), String> {
+    spellcheck::set_enabled(app, as_bool(value)?)
+}
+
+fn import_spellcheck_languages(app: &AppHandle, value: &Value) -> Result<(), String> {
+    let languages: Vec<String> =
+        serde_json::from_value(value.clone()).map_err(|_| "not a list of languages")?;
+    spellcheck::set_languages(app, languages)
+}
+
+fn import_update_channel(app: &AppHandle, value: &Value) -> Result<(), String> {
+    let channel = match as_str(value)? {
+        "stable" => UpdateChannel::Stable,
+        "beta" => UpdateChannel::Beta,
+        "alpha" => UpdateChannel::Nightly,
+        other => return Err(format!("unknown update channel {other}")),
+    };
+    updater::set_channel(app, channel)
+}
+
+/// Electron downloaded updates on its own; the closest native setting is
+/// background download.
+fn import_auto_update(app: &AppHandle, value: &Value) -> Result<(), String> {
+    updater::set_background_download(app, as_bool(value)?)
+}
+
+/// Rewrite the legacy store without the credentials now in the keychain,
+/// so no plaintext copy of them stays behind. Credentials that could not
+/// be migrated are kept, so the session is not lost.
+fn scrub_legacy_store(
+    path: &Path,
+    entries: &Map<String, Value>,
+    migrated: &[String],
+) -> Result<(), String> {
+    let kept: Map<String, Value> = entries
+        .iter()
+        .filter(|(key, _)| !migrated.contains(key))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    if kept.is_empty() {
+        return std::fs::remove_file(path).map_err(|e| e.to_string());
+    }
+    let json = serde_json::to_vec_pretty(&kept).map_err(|e| e.to_string())?;
+    std::fs::write(path, json).map_err(|e| e.to_string())
+}
+
+/// Import settings, drafts and cached credentials from the old Electron
+/// client. Only known keys are imported: preferences through their native
+/// setters, drafts when they are text, and credentials into the OS
+/// keychain, after which they are scrubbed from the legacy file. Existing
+/// native settings always win over legacy values, and the import only runs
+/// once unless `force` is set, which also retries credentials the keychain
+/// refused.
+#[tauri::command]
+pub fn import_legacy_data(
+    app: AppHandle,
+    force: Option<bool>,
+) -> Result<LegacyMigrationReport, String> {
+    let mut report = LegacyMigrationReport::default();
+
+    if !force.unwrap_or(false) && settings::load::<bool>(&app, MIGRATED_KEY).unwrap_or(false) {
+        return Ok(report);
+    }
+
+    let Some(path) = find_legacy_store(&app) else {
+        return Ok(report);
+    };
+    report.source = path.parent().map(|dir| dir.to_string_lossy().to_string());
+
+    let raw = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let entries: Map<String, Value> = serde_json::from_str(&raw).map_err(|e| e.to_string())?;
+
+    let mut drafts: Map<String, Value> = settings::load(&app, DRAFTS_KEY).unwrap_or_default();
+    // Checked up front: two legacy keys may share one native setting.
+    let set_natively: Vec<&str> = LEGACY_SETTINGS
+        .iter()
+        .filter_map(|(_, native_key, _)| *native_key)
+        .filter(|native_key| settings::contains(&app, native_key))
+        .collect();
+
+    for (key, value) in &entries {
+        let key = key.clone();
+        if is_credential_key(&key) {
+            match value.as_str() {
+                Some(secret) if !secret.is_empty() => match keychain::store_secret(&key, secret) {
+                    Ok(()) => report.credentials.push(key),
+                    Err(e) => report.skipped.push(format!("{key}: keychain error: {e}")),
+                },
+                _ => report
+                    .skipped
+                    .push(format!("{key}: not a string credential")),
+            }
+        } else if let Some(channel) = draft_key(&key) {
+            match value.as_str() {
+                Some(text) if text.len() <= MAX_DRAFT_LEN => {
+                    if !drafts.contains_key(channel) {
+                        drafts.insert(channel.to_string(), Value::from(text));
+                        report.drafts += 1;
+                    }
+                }
+                _ => report.skipped.push(format!("{key}: not a text draft")),
+            }
+        } else if let Some((_, native_key, set)) =
+            LEGACY_SETTINGS.iter().find(|(legacy, ..)| *legacy == key)
+        {
+            if native_key.is_some_and(|native_key| set_natively.contains(&native_key)) {
+                report.skipped.push(format!("{key}: already set natively"));
+                continue;
+            }
+            match set(&app, value) {
+                Ok(()) => report.settings.push(key),
+                Err(e) => report.skipped.push(format!("{key}: {e}")),
+            }
+        } else {
+            report.skipped.push(format!("{key}: not a known setting"));
+        }
+    }
+
+    if report.drafts > 0 {
+        settings::save(&app, DRAFTS_KEY, &drafts)?;
+    }
+    if !report.credentials.is_empty() {
+        if let Err(e) = scrub_legacy_store(&path, &entries, &report.credentials) {
+            log::warn!("[nchat-desktop] failed to scrub the legacy store: {e}");
+        }
+    }
+    settings::save(&app, MIGRATED_KEY, &true)?;
+
+    log::info!(
+        "[nchat-desktop] legacy import: {} settings, {} drafts, {} credentials",
+        report.settings.len(),
+        report.drafts,
+        report.credentials.len()
+    );
+    Ok(report)
+}
@@ -1,7 +1,38 @@
 pub mod app;
+pub mod archive;
+pub mod bug_report;
 pub mod clipboard;
+pub mod collation;
+pub mod config;
+pub mod crypto;
+pub mod debug;
+pub mod deeplink;
+pub mod diagnostics;
+pub mod dictation;
+pub mod digest;
+pub mod download;
 pub mod drag;
+pub mod ephemeral;
+pub mod focus;
+pub mod health;
+pub mod i18n;
+pub mod integrations;
+pub mod language_assets;
+pub mod media;
+pub mod menu;
+pub mod migrate;
 pub mod notification;
+pub mod proxy;
+pub mod renderer;
+pub mod screen_share;
+pub mod screenshot;
+pub mod sessions;
 pub mod shell;
+pub mod shortcuts;
+pub mod spellcheck;
+pub mod startup;
+pub mod stats;
+pub mod switcher;
+pub mod tray;
 pub mod update;
 pub mod window;
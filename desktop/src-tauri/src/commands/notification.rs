@@ -2,11 +2,16 @@ use serde::Deserialize;
 use tauri::AppHandle;
 use tauri_plugin_notification::NotificationExt;
 
+use crate::focus;
+
 #[derive(Deserialize)]
 pub struct NotificationOptions {
     pub title: String,
     pub body: Option<String>,
     pub icon: Option<String>,
+    /// Shown even during a focus session.
+    #[serde(default)]
+    pub urgent: bool,
 }
 
 #[tauri::command]
@@ -14,6 +19,9 @@ pub fn notification_show(
     app: AppHandle,
     options: NotificationOptions,
 ) -> Result<(), String> {
+    if !options.urgent && focus::hold(&app, &options.title, options.body.as_deref()) {
+        return Ok(());
+    }
     let mut builder = app.notification().builder().title(&options.title);
     if let Some(body) = &options.body {
         builder = builder.body(body);
@@ -0,0 +1,19 @@
+use tauri::AppHandle;
+
+use crate::proxy::{self, ProxySettings};
+
+#[tauri::command]
+pub fn get_proxy_settings(app: AppHandle) -> ProxySettings {
+    proxy::load(&app)
+}
+
+/// Save the proxy used for updates and other native requests. Pass
+/// `password` to replace the stored one, or an empty string to clear it.
+#[tauri::command]
+pub fn set_proxy_settings(
+    app: AppHandle,
+    settings: ProxySettings,
+    password: Option<String>,
+) -> Result<(), String> {
+    proxy::configure(&app, settings, password)
+}
@@ -0,0 +1,23 @@
+use tauri::AppHandle;
+
+use crate::renderer::{self, RendererInfo};
+
+/// Webview version and GPU/fallback status for the diagnostics panel.
+#[tauri::command]
+pub fn get_renderer_info(app: AppHandle) -> RendererInfo {
+    renderer::info(&app)
+}
+
+/// Persistently disable (or re-enable) GPU acceleration. Takes effect on the
+/// next launch.
+#[tauri::command]
+pub fn set_gpu_disabled(app: AppHandle, disabled: bool) -> Result<(), String> {
+    renderer::set_gpu_disabled(&app, disabled)
+}
+
+/// Called by the frontend once the first view has rendered, so the launch
+/// is not counted towards the software-rendering fallback.
+#[tauri::command]
+pub fn report_renderer_ready(app: AppHandle) -> Result<(), String> {
+    renderer::mark_ready(&app)
+}
@@ -0,0 +1,40 @@
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::{policy, sessions};
+
+/// Watermark the frontend must composite onto every outgoing screen-share
+/// frame while the policy is in effect.
+#[derive(Serialize)]
+pub struct ScreenShareWatermark {
+    pub text: String,
+    pub opacity: f32,
+}
+
+/// Resolve the admin-mandated screen-share watermark for the signed-in
+/// user, or `None` when the deployment does not require one.
+///
+/// The name and email come from the server through the native session, and
+/// the template and opacity from the machine policy, so the page can
+/// neither change the text nor soften it. Screen capture runs inside the
+/// webview (`getDisplayMedia`), though, so there is no native frame
+/// pipeline to draw into: the call layer composites the overlay onto its
+/// canvas track itself. This makes the watermark hard to spoof, not
+/// impossible to skip for a page that does not draw it.
+#[tauri::command]
+pub async fn get_screen_share_watermark(
+    app: AppHandle,
+) -> Result<Option<ScreenShareWatermark>, String> {
+    let Some(watermark) = policy::current().screen_share_watermark.as_ref() else {
+        return Ok(None);
+    };
+    let identity = sessions::identity(&app).await?;
+    let name = identity.display_name.as_deref().unwrap_or(&identity.email);
+    Ok(Some(ScreenShareWatermark {
+        text: watermark
+            .template
+            .replace("{name}", name)
+            .replace("{email}", &identity.email),
+        opacity: watermark.opacity.clamp(0.0, 1.0),
+    }))
+}
@@ -0,0 +1,21 @@
+use tauri::AppHandle;
+
+use crate::screenshot;
+
+/// Start a region capture, as the shortcut does; the file arrives as
+/// `attach-screenshot`.
+#[tauri::command]
+pub fn capture_screenshot(app: AppHandle) -> Result<(), String> {
+    screenshot::capture(&app)
+}
+
+#[tauri::command]
+pub fn get_screenshot_shortcut(app: AppHandle) -> String {
+    screenshot::shortcut(&app)
+}
+
+/// Change the system-wide screenshot shortcut; an empty string turns it off.
+#[tauri::command]
+pub fn set_screenshot_shortcut(app: AppHandle, accel: String) -> Result<(), String> {
+    screenshot::set_shortcut(&app, &accel)
+}
@@ -0,0 +1,45 @@
+use tauri::AppHandle;
+
+use crate::sessions::{self, Session};
+
+/// Hand the signed-in session to the native layer; the access token is
+/// stored in the OS keychain.
+#[tauri::command]
+pub fn set_current_session(
+    app: AppHandle,
+    server_url: String,
+    session_id: String,
+    access_token: String,
+) -> Result<(), String> {
+    sessions::set_current(&app, server_url, session_id, access_token)
+}
+
+/// Forget the session on this device (local sign-out).
+#[tauri::command]
+pub fn clear_current_session(app: AppHandle) -> Result<(), String> {
+    sessions::clear(&app)
+}
+
+/// Every device signed in to the account, with this one marked `current`.
+#[tauri::command]
+pub async fn list_sessions(app: AppHandle) -> Result<Vec<Session>, String> {
+    sessions::list(&app).await
+}
+
+#[tauri::command]
+pub async fn revoke_session(app: AppHandle, id: String) -> Result<(), String> {
+    sessions::revoke(&app, &id).await
+}
+
+/// Called when the realtime client receives a "session revoked" push.
+#[tauri::command]
+pub fn handle_session_revoked(app: AppHandle) {
+    sessions::revoked(&app);
+}
+
+/// "Sign in again" in the re-auth window: bring the main window back,
+/// reloaded so it starts at sign-in.
+#[tauri::command]
+pub fn begin_reauth(app: AppHandle) -> Result<(), String> {
+    sessions::begin_reauth(&app)
+}
@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tauri::AppHandle;
+
+use crate::call_shortcuts;
+use crate::shortcut_profile::{self, ConflictResolution, ShortcutImportReport};
+use crate::shortcuts::{self, CapturedKey, GlobalBinding, ShortcutCapture};
+use crate::workspace_shortcuts::{self, WorkspaceShortcuts};
+
+/// Record the next shortcut pressed on the settings page, which forwards its
+/// key presses with `shortcut_capture_key`. `action` is the binding being
+/// changed. Resolves with the normalized accelerator and its conflicts, or
+/// `null` if the user pressed Escape.
+#[tauri::command]
+pub async fn begin_shortcut_capture(
+    app: AppHandle,
+    action: Option<String>,
+) -> Result<Option<ShortcutCapture>, String> {
+    shortcuts::capture(&app, action.as_deref()).await
+}
+
+/// Forward a `keydown` to the running capture; true once it ended the
+/// capture.
+#[tauri::command]
+pub fn shortcut_capture_key(key: CapturedKey) -> bool {
+    shortcuts::capture_key(&key)
+}
+
+#[tauri::command]
+pub fn get_global_shortcuts(app: AppHandle) -> Vec<GlobalBinding> {
+    shortcuts::global_bindings(&app)
+}
+
+/// Bind a system-wide shortcut to a frontend action, e.g.
+/// `{ accelerator: "CmdOrCtrl+Alt+N", action: "new-message", showWindow: true }`.
+/// Pressing it sends `shortcut:action` with the action to the main window.
+/// `suspend` (`never`, `when-focused` or `while-typing`) lets the app's own
+/// shortcuts have the keys while its main window has focus.
+#[tauri::command]
+pub fn register_shortcut(app: AppHandle, binding: GlobalBinding) -> Result<(), String> {
+    shortcuts::bind(&app, binding)
+}
+
+#[tauri::command]
+pub fn unregister_shortcut(app: AppHandle, action: String) -> Result<(), String> {
+    shortcuts::unbind(&app, &action)
+}
+
+/// Report whether a text field in the main window has focus, for bindings
+/// suspended `while-typing`.
+#[tauri::command]
+pub fn set_typing(app: AppHandle, typing: bool) {
+    shortcuts::set_typing(&app, typing)
+}
+
+/// The global call shortcuts by action (`mute`, `deafen`); "" when off.
+#[tauri::command]
+pub fn get_call_shortcuts(app: AppHandle) -> HashMap<String, String> {
+    call_shortcuts::shortcuts(&app)
+}
+
+/// Change the global `mute` or `deafen` shortcut; an empty string turns it
+/// off. Pressing them sends `shortcut-mute-toggle` and
+/// `shortcut-deafen-toggle`.
+#[tauri::command]
+pub fn set_call_shortcut(app: AppHandle, action: String, accel: String) -> Result<(), String> {
+    call_shortcuts::set_shortcut(&app, &action, &accel)
+}
+
+/// The workspace cycling shortcuts, the modifiers of the jump shortcuts
+/// (`CmdOrCtrl` for CmdOrCtrl+1 to 9) and whether they are system-wide.
+#[tauri::command]
+pub fn get_workspace_shortcuts(app: AppHandle) -> WorkspaceShortcuts {
+    workspace_shortcuts::config(&app)
+}
+
+/// Change the `next-workspace` or `previous-workspace` shortcut; an empty
+/// string turns it off.
+#[tauri::command]
+pub fn set_workspace_shortcut(app: AppHandle, action: String, accel: String) -> Result<(), String> {
+    workspace_shortcuts::set_shortcut(&app, &action, &accel)
+}
+
+/// Change the modifiers combined with 1 to 9, e.g. `"Alt"`; an empty string
+/// turns jumping off.
+#[tauri::command]
+pub fn set_workspace_jump_modifiers(app: AppHandle, modifiers: String) -> Result<(), String> {
+    workspace_shortcuts::set_jump_modifiers(&app, &modifiers)
+}
+
+/// Keep the workspace shortcuts while other apps have focus.
+#[tauri::command]
+pub fn set_workspace_shortcuts_system_wide(app: AppHandle, enabled: bool) -> Result<(), String> {
+    workspace_shortcuts::set_system_wide(&app, enabled)
+}
+
+/// Write every menu accelerator, app shortcut and global binding to `path`
+/// as a shortcut profile. Returns the number of entries exported.
+#[tauri::command]
+pub fn export_shortcut_profile(app: AppHandle, path: String) -> Result<usize, String> {
+    shortcut_profile::export(&app, &PathBuf::from(path))
+}
+
+/// Apply a profile written by `export_shortcut_profile`. `resolution`
+/// (`keep-existing`, the default, or `use-imported`) decides entries whose
+/// keys are already taken here.
+#[tauri::command]
+pub fn import_shortcut_profile(
+    app: AppHandle,
+    path: String,
+    resolution: Option<ConflictResolution>,
+) -> Result<ShortcutImportReport, String> {
+    shortcut_profile::import(&app, &PathBuf::from(path), resolution.unwrap_or_default())
+}
@@ -0,0 +1,31 @@
+use tauri::AppHandle;
+
+use crate::menu;
+use crate::spellcheck::{self, SpellcheckSettings};
+
+/// Current spellcheck configuration.
+#[tauri::command]
+pub fn get_spellcheck_settings(app: AppHandle) -> SpellcheckSettings {
+    spellcheck::settings(&app)
+}
+
+/// Dictionary codes installed on this system (empty on Windows, where the
+/// OS input languages are used).
+#[tauri::command]
+pub fn get_spellcheck_languages() -> Vec<String> {
+    spellcheck::available_languages()
+}
+
+/// Turn spellchecking on or off in every window.
+#[tauri::command]
+pub fn set_spellcheck_enabled(app: AppHandle, enabled: bool) -> Result<(), String> {
+    spellcheck::set_enabled(&app, enabled)?;
+    menu::rebuild(&app).map_err(|e| e.to_string())
+}
+
+/// Choose the active dictionaries; an empty list uses the system default.
+#[tauri::command]
+pub fn set_spellcheck_languages(app: AppHandle, languages: Vec<String>) -> Result<(), String> {
+    spellcheck::set_languages(&app, languages)?;
+    menu::rebuild(&app).map_err(|e| e.to_string())
+}
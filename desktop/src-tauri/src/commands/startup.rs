@@ -0,0 +1,41 @@
+use tauri::AppHandle;
+
+use crate::session;
+use crate::startup::{self, Location, StartupTarget, StartupView};
+
+/// The user's startup preference.
+#[tauri::command]
+pub fn get_startup_view(app: AppHandle) -> StartupView {
+    startup::view(&app)
+}
+
+/// Choose where the app opens: `last-used`, `unified-inbox`, or a specific
+/// `channel` / `workspace` (with `id`).
+#[tauri::command]
+pub fn set_startup_view(app: AppHandle, view: StartupView) -> Result<(), String> {
+    startup::set_view(&app, view)
+}
+
+/// Report the current workspace/channel, used by the `last-used` view.
+#[tauri::command]
+pub fn set_last_location(app: AppHandle, location: Location) -> Result<(), String> {
+    startup::set_last_location(&app, location)
+}
+
+/// Called once by the frontend when it first loads: where to navigate.
+/// A launch link wins over the startup preference.
+#[tauri::command]
+pub fn take_startup_target(app: AppHandle) -> Option<StartupTarget> {
+    startup::take_target(&app)
+}
+
+#[tauri::command]
+pub fn get_session_restore(app: AppHandle) -> bool {
+    session::enabled(&app)
+}
+
+/// Reopen the windows and routes of the last session at launch.
+#[tauri::command]
+pub fn set_session_restore(app: AppHandle, enabled: bool) -> Result<(), String> {
+    session::set_enabled(&app, enabled)
+}
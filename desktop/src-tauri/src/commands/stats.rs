@@ -0,0 +1,22 @@
+use tauri::AppHandle;
+
+use crate::stats::{self, Activity, PersonalStats, StatsPeriod};
+
+/// Opt in to (or out of, deleting the data) local usage statistics.
+#[tauri::command]
+pub fn set_personal_stats_enabled(app: AppHandle, enabled: bool) -> Result<(), String> {
+    stats::set_enabled(&app, enabled)
+}
+
+/// Count a sent message or a finished call. No-op unless opted in.
+#[tauri::command]
+pub fn record_activity(app: AppHandle, activity: Activity) -> Result<(), String> {
+    stats::record(&app, activity)
+}
+
+/// Totals, per-day heatmap and most active channels for `week`, `month`,
+/// `year` or `all`.
+#[tauri::command]
+pub fn get_personal_stats(app: AppHandle, period: StatsPeriod) -> PersonalStats {
+    stats::summarize(&app, period)
+}
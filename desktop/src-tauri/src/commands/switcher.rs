@@ -0,0 +1,45 @@
+use tauri::AppHandle;
+
+use crate::double_tap::{self, DoubleTap};
+use crate::switcher;
+
+#[tauri::command]
+pub fn show_quick_switcher(app: AppHandle) -> Result<(), String> {
+    switcher::show(&app)
+}
+
+/// Dismiss the overlay (e.g. on Escape). Pass the picked result as
+/// `target` to hand it to the main window as `quick-switcher:open`.
+#[tauri::command]
+pub fn hide_quick_switcher(app: AppHandle, target: Option<String>) -> Result<(), String> {
+    switcher::hide(&app, target)
+}
+
+#[tauri::command]
+pub fn get_quick_switcher_shortcut(app: AppHandle) -> String {
+    switcher::shortcut(&app)
+}
+
+/// Change the system-wide shortcut, e.g. `"Alt+Space"`; an empty string
+/// turns it off.
+#[tauri::command]
+pub fn set_quick_switcher_shortcut(app: AppHandle, accel: String) -> Result<(), String> {
+    switcher::set_shortcut(&app, &accel)
+}
+
+#[tauri::command]
+pub fn get_quick_switcher_double_tap(app: AppHandle) -> Option<DoubleTap> {
+    double_tap::switcher_trigger(&app)
+}
+
+/// Also open the overlay on a double tap of a modifier, e.g.
+/// `{ modifier: "cmd-or-ctrl", intervalMs: 300 }`; `null` turns it off.
+/// Fails when key events cannot be read (the Input Monitoring permission on
+/// macOS; a Wayland session on Linux).
+#[tauri::command]
+pub fn set_quick_switcher_double_tap(
+    app: AppHandle,
+    trigger: Option<DoubleTap>,
+) -> Result<(), String> {
+    double_tap::set_switcher_trigger(&app, trigger)
+}
@@ -0,0 +1,74 @@
+use tauri::AppHandle;
+
+use crate::tray::{
+    self, CloseBehavior, MiddleClickAction, TrayAvailability, TrayIconTheme, TraySummary,
+};
+
+/// Reflect the user's presence in the tray menu: the item for `status`
+/// (`online`, `away`, `dnd` or `invisible`) is shown with a checkmark.
+#[tauri::command]
+pub fn set_tray_status(app: AppHandle, status: String) -> Result<(), String> {
+    tray::set_status(&app, &status)
+}
+
+/// Override the tray icon variant (`auto`, `light` or `dark`). `auto`
+/// follows the system theme, and uses a template icon on macOS.
+#[tauri::command]
+pub fn set_tray_icon_theme(app: AppHandle, theme: TrayIconTheme) -> Result<(), String> {
+    tray::set_icon_theme(&app, theme)
+}
+
+/// Show or remove the tray icon. While it is hidden, closing the main
+/// window quits the app instead of hiding it.
+#[tauri::command]
+pub fn set_tray_visible(app: AppHandle, visible: bool) -> Result<(), String> {
+    tray::set_visible(&app, visible)
+}
+
+/// Report whether the desktop session supports a tray icon, so the frontend
+/// can hide tray-related settings where they would do nothing.
+#[tauri::command]
+pub fn get_tray_availability(app: AppHandle) -> TrayAvailability {
+    tray::availability(&app)
+}
+
+/// Push the unread count, busiest channels and connection state; the tray
+/// tooltip is rendered from it natively.
+#[tauri::command]
+pub fn update_tray_state(app: AppHandle, payload: TraySummary) {
+    tray::set_summary(&app, payload);
+}
+
+/// Choose what a middle click on the tray icon does (`quick-compose`,
+/// `toggle-mute` or `none`).
+#[tauri::command]
+pub fn set_tray_middle_click_action(
+    app: AppHandle,
+    action: MiddleClickAction,
+) -> Result<(), String> {
+    tray::set_middle_click_action(&app, action)
+}
+
+/// Run as a pure menu-bar/tray utility: no Dock icon on macOS and no
+/// taskbar entry elsewhere. Fails while the tray icon is hidden.
+#[tauri::command]
+pub fn set_tray_only_mode(app: AppHandle, enabled: bool) -> Result<(), String> {
+    tray::set_tray_only(&app, enabled)
+}
+
+#[tauri::command]
+pub fn get_close_behavior(app: AppHandle) -> CloseBehavior {
+    tray::close_behavior(&app)
+}
+
+/// Choose what closing the main window does on Windows and Linux (`quit`,
+/// `hide-to-tray` or `ask`); on macOS it always hides. `close_now` also
+/// closes the window the chosen way right away.
+#[tauri::command]
+pub fn set_close_behavior(
+    app: AppHandle,
+    behavior: CloseBehavior,
+    close_now: Option<bool>,
+) -> Result<(), String> {
+    tray::set_close_behavior(&app, behavior, close_now.unwrap_or(false))
+}
@@ -1,55 +1,148 @@
-use serde::Serialize;
+use std::path::Path;
 use tauri::AppHandle;
 
-#[derive(Serialize)]
-pub struct UpdateInfo {
-    pub available: bool,
-    pub version: Option<String>,
-    pub notes: Option<String>,
-}
+use crate::updater::{
+    self, DownloadControl, RollbackInfo, UpdateChannel, UpdateInfo, UpdateSource,
+};
 
 /// T25 — update_check with semver downgrade guard.
 /// Returns Ok(UpdateInfo { available: false }) if the remote version is older
 /// than or equal to the currently running version, preventing rollback attacks.
+/// Unless `manual` is set (the user clicked "Check for Updates"), a skipped
+/// or snoozed release is not reported either.
+#[tauri::command]
+pub async fn update_check(app: AppHandle, manual: Option<bool>) -> Result<UpdateInfo, String> {
+    updater::check(&app, manual.unwrap_or(false)).await
+}
+
+/// "Remind me later": keep automatic checks quiet for `hours`. Returns the
+/// end of the snooze (Unix ms).
+#[tauri::command]
+pub fn snooze_update(app: AppHandle, hours: u32) -> Result<u64, String> {
+    updater::snooze(&app, hours)
+}
+
+/// "Skip this version": never announce `version` automatically again.
+#[tauri::command]
+pub fn skip_update_version(app: AppHandle, version: String) -> Result<(), String> {
+    updater::skip_version(&app, &version)
+}
+
+/// Download and install the pending update, reporting progress through
+/// `update-download-progress` events. Returns the installed version; the
+/// frontend offers a restart afterwards.
+#[tauri::command]
+pub async fn install_update(app: AppHandle) -> Result<String, String> {
+    updater::install(&app).await
+}
+
+/// Pause the update download in flight; `resume_update_download` picks it
+/// up where it stopped if the server supports range requests.
+#[tauri::command]
+pub fn pause_update_download(app: AppHandle) -> Result<(), String> {
+    updater::control_download(&app, DownloadControl::Pause)
+}
+
+#[tauri::command]
+pub fn resume_update_download(app: AppHandle) -> Result<(), String> {
+    updater::control_download(&app, DownloadControl::Run)
+}
+
+/// Abort the update download in flight. The pending `install_update` or
+/// `download_update` call fails with "update download canceled".
+#[tauri::command]
+pub fn cancel_update_download(app: AppHandle) -> Result<(), String> {
+    updater::control_download(&app, DownloadControl::Cancel)
+}
+
+/// Install a signed package from disk (air-gapped or offline-first
+/// deployments) and restart into it. Without `signature` it is read from
+/// the `.sig` file next to the package.
+#[tauri::command]
+pub async fn install_update_from_path(
+    app: AppHandle,
+    path: String,
+    signature: Option<String>,
+) -> Result<(), String> {
+    updater::install_from_path(&app, Path::new(&path), signature).await?;
+    app.restart()
+}
+
+/// Currently selected release channel.
+#[tauri::command]
+pub fn get_update_channel(app: AppHandle) -> UpdateChannel {
+    updater::channel(&app)
+}
+
+/// Switch between `stable`, `beta` and `nightly`, then check the new
+/// channel right away.
 #[tauri::command]
-pub async fn update_check(app: AppHandle) -> Result<UpdateInfo, String> {
-    use semver::Version;
-    use tauri_plugin_updater::UpdaterExt;
-
-    let current_ver = app.package_info().version.to_string();
-    let current = Version::parse(&current_ver).map_err(|e| e.to_string())?;
-
-    match app.updater() {
-        Ok(updater) => match updater.check().await {
-            Ok(Some(update)) => {
-                // Downgrade guard: reject if remote ≤ current
-                match Version::parse(&update.version) {
-                    Ok(remote) if remote <= current => {
-                        log::warn!(
-                            "[nchat-desktop] update check: remote {} <= current {} — ignoring",
-                            remote,
-                            current
-                        );
-                        Ok(UpdateInfo {
-                            available: false,
-                            version: None,
-                            notes: None,
-                        })
-                    }
-                    _ => Ok(UpdateInfo {
-                        available: true,
-                        version: Some(update.version.clone()),
-                        notes: update.body.clone(),
-                    }),
-                }
-            }
-            Ok(None) => Ok(UpdateInfo {
-                available: false,
-                version: None,
-                notes: None,
-            }),
-            Err(e) => Err(e.to_string()),
-        },
-        Err(e) => Err(e.to_string()),
+pub async fn set_update_channel(
+    app: AppHandle,
+    channel: UpdateChannel,
+) -> Result<UpdateInfo, String> {
+    updater::set_channel(&app, channel)?;
+    updater::check(&app, true).await
+}
+
+/// Download the available update in the background and stage it for
+/// installation on quit. Returns the staged version, if any.
+#[tauri::command]
+pub async fn download_update(app: AppHandle) -> Result<Option<String>, String> {
+    updater::stage(&app, true).await
+}
+
+/// Install the staged update now and restart into it.
+#[tauri::command]
+pub fn install_staged_update(app: AppHandle) -> Result<(), String> {
+    if !updater::install_staged(&app)? {
+        return Err("no update is staged".into());
     }
+    app.restart()
+}
+
+/// Version of the update waiting to be installed on quit, if any.
+#[tauri::command]
+pub fn get_staged_update(app: AppHandle) -> Option<String> {
+    updater::staged_version(&app)
+}
+
+/// Download updates silently and install them on quit instead of
+/// prompting.
+#[tauri::command]
+pub fn set_update_background_download(app: AppHandle, enabled: bool) -> Result<(), String> {
+    updater::set_background_download(&app, enabled)
+}
+
+/// The version `rollback_update` would return to, if a previous package is
+/// kept.
+#[tauri::command]
+pub fn get_rollback_info(app: AppHandle) -> Option<RollbackInfo> {
+    updater::rollback_info(&app)
+}
+
+/// Reinstall the previous version and restart into it. `version` must be
+/// the version the user confirmed rolling back to.
+#[tauri::command]
+pub async fn rollback_update(app: AppHandle, version: String) -> Result<(), String> {
+    updater::rollback(&app, &version).await?;
+    app.restart()
+}
+
+/// The update manifest in use and where it comes from.
+#[tauri::command]
+pub fn get_update_source(app: AppHandle) -> UpdateSource {
+    updater::source(&app)
+}
+
+/// Use a self-hosted update manifest (and, where the admin policy allows
+/// it, a public key), or reset to the channel URLs with no `endpoint`. The
+/// endpoint is checked before it is saved.
+#[tauri::command]
+pub async fn set_update_source(
+    app: AppHandle,
+    endpoint: Option<String>,
+    pubkey: Option<String>,
+) -> Result<UpdateSource, String> {
+    updater::set_source(&app, endpoint, pubkey).await
 }
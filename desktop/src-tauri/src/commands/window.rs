@@ -1,18 +1,27 @@
-use tauri::{AppHandle, Manager};
+use serde::Serialize;
+use tauri::{AppHandle, WebviewWindow};
+
+use crate::effects::{self, BackgroundEffect, EffectStatus};
+use crate::kiosk;
+use crate::preferences;
+use crate::titlebar::{self, Inset, ResizeEdge, TrafficLightStatus};
+use crate::windows::{
+    self, AttentionKind, ConversationWindow, ConversationWindowOptions, DisplayInfo, DockEdge,
+    WindowMode, WindowOptions,
+};
+
+// The window commands act on the main window unless `label` names another.
 
 #[tauri::command]
-pub fn window_minimize(app: AppHandle) -> Result<(), String> {
-    app.get_webview_window("main")
-        .ok_or("main window not found")?
+pub fn window_minimize(app: AppHandle, label: Option<String>) -> Result<(), String> {
+    windows::window(&app, label.as_deref())?
         .minimize()
         .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-pub fn window_maximize(app: AppHandle) -> Result<(), String> {
-    let win = app
-        .get_webview_window("main")
-        .ok_or("main window not found")?;
+pub fn window_maximize(app: AppHandle, label: Option<String>) -> Result<(), String> {
+    let win = windows::window(&app, label.as_deref())?;
     if win.is_maximized().map_err(|e| e.to_string())? {
         win.unmaximize().map_err(|e| e.to_string())
     } else {
@@ -21,17 +30,217 @@ pub fn window_maximize(app: AppHandle) -> Result<(), String> {
 }
 
 #[tauri::command]
-pub fn window_close(app: AppHandle) -> Result<(), String> {
-    app.get_webview_window("main")
-        .ok_or("main window not found")?
+pub fn window_close(app: AppHandle, label: Option<String>) -> Result<(), String> {
+    windows::window(&app, label.as_deref())?
         .close()
         .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-pub fn window_is_maximized(app: AppHandle) -> Result<bool, String> {
-    app.get_webview_window("main")
-        .ok_or("main window not found")?
+pub fn window_is_maximized(app: AppHandle, label: Option<String>) -> Result<bool, String> {
+    windows::window(&app, label.as_deref())?
         .is_maximized()
         .map_err(|e| e.to_string())
 }
+
+#[tauri::command]
+pub fn show_window(app: AppHandle, label: Option<String>) -> Result<(), String> {
+    windows::window(&app, label.as_deref())?
+        .show()
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn hide_window(app: AppHandle, label: Option<String>) -> Result<(), String> {
+    windows::window(&app, label.as_deref())?
+        .hide()
+        .map_err(|e| e.to_string())
+}
+
+/// Show, unminimize and focus a window.
+#[tauri::command]
+pub fn focus_window(app: AppHandle, label: Option<String>) -> Result<(), String> {
+    let win = windows::window(&app, label.as_deref())?;
+    let result = (|| {
+        win.show()?;
+        win.unminimize()?;
+        win.set_focus()
+    })();
+    result.map_err(|e| e.to_string())
+}
+
+/// Whether the window `label` has focus or, without a label, whether any
+/// app window does. Use this rather than `document.hasFocus()` to decide
+/// if the user is looking at the app.
+#[tauri::command]
+pub fn is_focused(app: AppHandle, label: Option<String>) -> Result<bool, String> {
+    match label {
+        Some(label) => windows::window(&app, Some(&label))?
+            .is_focused()
+            .map_err(|e| e.to_string()),
+        None => Ok(windows::app_focused(&app)),
+    }
+}
+
+/// Pop a DM or thread out into its own window (focusing it if it is
+/// already open).
+#[tauri::command]
+pub fn open_conversation_window(
+    app: AppHandle,
+    conversation_id: String,
+    options: Option<ConversationWindowOptions>,
+) -> Result<ConversationWindow, String> {
+    windows::open_conversation_window(&app, &conversation_id, options.unwrap_or_default())
+}
+
+#[tauri::command]
+pub fn list_conversation_windows(app: AppHandle) -> Vec<ConversationWindow> {
+    windows::conversation_windows(&app)
+}
+
+/// Pin a window (e.g. `main` or a pop-out) above other apps, or unpin it.
+#[tauri::command]
+pub fn set_always_on_top(app: AppHandle, label: String, on: bool) -> Result<(), String> {
+    windows::set_always_on_top(&app, &label, on)
+}
+
+#[derive(Serialize)]
+pub struct WindowModeInfo {
+    pub mode: WindowMode,
+    pub edge: DockEdge,
+}
+
+#[tauri::command]
+pub fn get_window_mode(app: AppHandle) -> WindowModeInfo {
+    let (mode, edge) = windows::window_mode(&app);
+    WindowModeInfo { mode, edge }
+}
+
+/// Switch the main window to the `mini` dock (optionally picking the
+/// screen edge) or back to `normal`.
+#[tauri::command]
+pub fn set_window_mode(
+    app: AppHandle,
+    mode: WindowMode,
+    edge: Option<DockEdge>,
+) -> Result<(), String> {
+    windows::set_window_mode(&app, mode, edge)
+}
+
+#[tauri::command]
+pub fn get_restore_popouts(app: AppHandle) -> bool {
+    windows::restore_popouts_enabled(&app)
+}
+
+/// Reopen pop-out windows left open at quit on the next launch.
+#[tauri::command]
+pub fn set_restore_popouts(app: AppHandle, enabled: bool) -> Result<(), String> {
+    windows::set_restore_popouts(&app, enabled)
+}
+
+/// Open an auxiliary window on an app route, e.g.
+/// `create_window("settings", "index.html#/settings", { width: 640 })`.
+/// Returns the window's label (`aux-settings`).
+#[tauri::command]
+pub fn create_window(
+    app: AppHandle,
+    label: String,
+    url: String,
+    options: Option<WindowOptions>,
+) -> Result<String, String> {
+    windows::create_window(&app, &label, &url, options.unwrap_or_default())
+}
+
+/// Open the settings window, or focus it, optionally at a section such as
+/// `notifications`.
+#[tauri::command]
+pub fn open_settings_window(app: AppHandle, section: Option<String>) -> Result<(), String> {
+    preferences::open(&app, section.as_deref())
+}
+
+/// Lock the main window full screen for a shared display. There is no
+/// command to leave: only the exit shortcut (see the kiosk policy) does.
+#[tauri::command]
+pub fn enter_kiosk_mode(app: AppHandle) -> Result<(), String> {
+    kiosk::enter(&app)
+}
+
+#[tauri::command]
+pub fn is_kiosk_mode() -> bool {
+    kiosk::is_active()
+}
+
+/// Prefix every window title with the unread count, e.g. "(3) nChat — #general".
+#[tauri::command]
+pub fn set_window_title_badge(app: AppHandle, count: u32) -> Result<(), String> {
+    windows::set_title_badge(&app, count)
+}
+
+/// Title the calling window after what it shows, e.g. `"#general"`.
+#[tauri::command]
+pub fn set_window_title(window: WebviewWindow, context: Option<String>) -> Result<(), String> {
+    windows::set_title(&window, context.as_deref())
+}
+
+#[tauri::command]
+pub fn get_window_effect(app: AppHandle) -> EffectStatus {
+    effects::status(&app)
+}
+
+/// Use a native translucent background (`auto`, `sidebar`, `mica`,
+/// `acrylic`, ...) behind the main window, or `none`.
+#[tauri::command]
+pub fn set_window_effect(app: AppHandle, effect: BackgroundEffect) -> Result<EffectStatus, String> {
+    effects::set_effect(&app, effect)
+}
+
+#[tauri::command]
+pub fn get_traffic_light_inset(app: AppHandle) -> TrafficLightStatus {
+    titlebar::traffic_lights(&app)
+}
+
+/// Place the macOS window buttons inside a custom titlebar, e.g.
+/// `{ x: 16, y: 18 }`, or restore the standard titlebar with `null`.
+#[tauri::command]
+pub fn set_traffic_light_inset(
+    app: AppHandle,
+    inset: Option<Inset>,
+) -> Result<TrafficLightStatus, String> {
+    titlebar::set_traffic_lights(&app, inset)
+}
+
+/// Call from a mousedown in a custom titlebar's drag region.
+#[tauri::command]
+pub fn start_window_drag(window: WebviewWindow) -> Result<(), String> {
+    titlebar::start_drag(&window)
+}
+
+/// Call from a mousedown on a custom resize handle, e.g. `"south-east"`.
+#[tauri::command]
+pub fn start_window_resize(window: WebviewWindow, edge: ResizeEdge) -> Result<(), String> {
+    titlebar::start_resize(&window, edge)
+}
+
+#[tauri::command]
+pub fn set_window_resize_borders(window: WebviewWindow, enabled: bool) -> Result<(), String> {
+    titlebar::set_resize_borders(&window, enabled)
+}
+
+/// Connected displays with their bounds, work areas and scale factors.
+#[tauri::command]
+pub fn get_displays(app: AppHandle) -> Result<Vec<DisplayInfo>, String> {
+    windows::displays(&app)
+}
+
+/// Flash the taskbar button / bounce the Dock icon for a window that is
+/// not focused (the main window unless `label` is given). Pass `none` to
+/// stop. Returns whether attention was requested.
+#[tauri::command]
+pub fn request_attention(
+    app: AppHandle,
+    kind: AttentionKind,
+    label: Option<String>,
+) -> Result<bool, String> {
+    let label = label.as_deref().unwrap_or(windows::MAIN_WINDOW);
+    windows::request_attention(&app, label, kind)
+}
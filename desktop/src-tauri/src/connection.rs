@@ -0,0 +1,277 @@
+// nChat Desktop — realtime reconnect policy
+//
+// The realtime client runs in the webview and reports each connection
+// attempt here. The native side decides how long to wait before the next
+// attempt and why: network failures back off quickly and are checked for a
+// captive portal by probing the signed-in server through the configured
+// proxy, authentication failures back off hard and stop after a few tries,
+// and metered connections retry less often. The resulting state
+// machine is what the status indicator explains to the user.
+
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{health, proxy, sessions};
+
+/// Liveness route of the nChat server: 200 with `{"alive": true}`. A
+/// captive portal answers with a redirect or its login page instead.
+const PROBE_PATH: &str = "/api/health/live";
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Backoff bounds per failure kind, in seconds.
+const NETWORK_BACKOFF: (u64, u64) = (1, 60);
+const SERVER_BACKOFF: (u64, u64) = (5, 300);
+const AUTH_BACKOFF: (u64, u64) = (30, 1800);
+/// While stuck behind a captive portal, re-check at this pace.
+const CAPTIVE_PORTAL_RETRY_SECS: u64 = 30;
+/// Metered connections stretch every delay by this factor.
+const METERED_FACTOR: u64 = 4;
+/// Give up after this many consecutive auth failures; the user must sign
+/// in again.
+const MAX_AUTH_ATTEMPTS: u32 = 3;
+
+#[derive(Serialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConnectionPhase {
+    #[default]
+    Connecting,
+    Connected,
+    /// Waiting for `next_retry_at` before the next attempt.
+    WaitingToRetry,
+    /// No network route at all.
+    Offline,
+    /// A login page is intercepting traffic.
+    CaptivePortal,
+    /// Credentials were rejected too often; no automatic retries.
+    AuthFailed,
+}
+
+impl ConnectionPhase {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Connecting => "connecting",
+            Self::Connected => "connected",
+            Self::WaitingToRetry => "waiting-to-retry",
+            Self::Offline => "offline",
+            Self::CaptivePortal => "captive-portal",
+            Self::AuthFailed => "auth-failed",
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum FailureKind {
+    /// Socket errors, timeouts, DNS failures.
+    Network,
+    /// 401/403 or a rejected token.
+    Auth,
+    /// 5xx or protocol errors from a reachable server.
+    Server,
+}
+
+#[derive(Serialize, Clone, Default)]
+pub struct ConnectionStateMachine {
+    pub phase: ConnectionPhase,
+    /// Consecutive failed attempts.
+    pub attempt: u32,
+    /// Unix time in milliseconds of the next attempt, if one is planned.
+    pub next_retry_at: Option<u64>,
+    /// Error reported for the last failed attempt.
+    pub last_error: Option<String>,
+    /// `None` where the platform does not say.
+    pub metered: Option<bool>,
+}
+
+#[derive(Default)]
+pub struct ConnectionState {
+    machine: Mutex<ConnectionStateMachine>,
+}
+
+enum Probe {
+    Online,
+    CaptivePortal,
+    Unreachable,
+    /// No server to probe (not signed in).
+    Unknown,
+}
+
+#[derive(Deserialize)]
+struct Liveness {
+    alive: bool,
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Check the signed-in server, so self-hosted and offline deployments are
+/// judged by their own server rather than a public endpoint.
+async fn probe(app: &AppHandle) -> Probe {
+    let Some(server_url) = sessions::server_url(app) else {
+        return Probe::Unknown;
+    };
+    let client = proxy::current(app).and_then(|config| {
+        config
+            .apply(reqwest::Client::builder())
+            .redirect(reqwest::redirect::Policy::none())
+            .timeout(PROBE_TIMEOUT)
+            .build()
+            .map_err(|e| e.to_string())
+    });
+    let Ok(client) = client else {
+        return Probe::Unknown;
+    };
+    let url = format!("{}{PROBE_PATH}", server_url.trim_end_matches('/'));
+    match client.get(url).send().await {
+        Ok(res) if res.status() == reqwest::StatusCode::OK => match res.json::<Liveness>().await {
+            Ok(liveness) if liveness.alive => Probe::Online,
+            _ => Probe::CaptivePortal,
+        },
+        Ok(_) => Probe::CaptivePortal,
+        Err(_) => Probe::Unreachable,
+    }
+}
+
+/// Ask NetworkManager whether the primary connection is metered.
+#[cfg(target_os = "linux")]
+fn metered() -> Option<bool> {
+    let conn = zbus::blocking::Connection::system().ok()?;
+    let reply = conn
+        .call_method(
+            Some("org.freedesktop.NetworkManager"),
+            "/org/freedesktop/NetworkManager",
+            Some("org.freedesktop.DBus.Properties"),
+            "Get",
+            &("org.freedesktop.NetworkManager", "Metered"),
+        )
+        .ok()?;
+    let value: zbus::zvariant::OwnedValue = reply.body().deserialize().ok()?;
+    // NMMetered: 1 = yes, 3 = guessed yes.
+    u32::try_from(value).ok().map(|m| m == 1 || m == 3)
+}
+
+/// Ask Windows for the cost of the internet connection profile.
+#[cfg(target_os = "windows")]
+fn metered() -> Option<bool> {
+    use windows::Networking::Connectivity::{NetworkCostType, NetworkInformation};
+    let cost = NetworkInformation::GetInternetConnectionProfile()
+        .ok()?
+        .GetConnectionCost()
+        .ok()?;
+    match cost.NetworkCostType().ok()? {
+        NetworkCostType::Fixed | NetworkCostType::Variable => Some(true),
+        NetworkCostType::Unrestricted => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+fn metered() -> Option<bool> {
+    None
+}
+
+/// Exponential backoff with ±20% jitter, so clients that dropped together
+/// do not reconnect together.
+fn backoff(attempt: u32, (base, cap): (u64, u64), metered: bool) -> Duration {
+    let exp = base.saturating_mul(1 << attempt.saturating_sub(1).min(16));
+    let mut delay_ms = exp.min(cap) * 1000;
+    if metered {
+        delay_ms *= METERED_FACTOR;
+    }
+    let jitter = (now_ms() % 400) as i64 - 200; // per mille
+    let ms = delay_ms as i64 + delay_ms as i64 * jitter / 1000;
+    Duration::from_millis(ms.max(0) as u64)
+}
+
+fn update(
+    app: &AppHandle,
+    change: impl FnOnce(&mut ConnectionStateMachine),
+) -> ConnectionStateMachine {
+    let snapshot = {
+        let state = app.state::<ConnectionState>();
+        let mut machine = state.machine.lock().unwrap();
+        change(&mut machine);
+        machine.clone()
+    };
+    health::set_connection(app, snapshot.phase.as_str());
+    let _ = app.emit("connection-state", snapshot.clone());
+    snapshot
+}
+
+pub fn current(app: &AppHandle) -> ConnectionStateMachine {
+    app.state::<ConnectionState>()
+        .machine
+        .lock()
+        .unwrap()
+        .clone()
+}
+
+/// The frontend started an attempt.
+pub fn connecting(app: &AppHandle) -> ConnectionStateMachine {
+    update(app, |m| {
+        m.phase = ConnectionPhase::Connecting;
+        m.next_retry_at = None;
+    })
+}
+
+/// The realtime connection is up; reset the backoff.
+pub fn connected(app: &AppHandle) -> ConnectionStateMachine {
+    update(app, |m| {
+        *m = ConnectionStateMachine {
+            phase: ConnectionPhase::Connected,
+            metered: m.metered,
+            ..Default::default()
+        };
+    })
+}
+
+/// Record a failed attempt and plan the next one. The frontend waits until
+/// `next_retry_at`; without one, it waits for the user (or for
+/// `connecting` after a sign-in).
+pub async fn failed(app: &AppHandle, kind: FailureKind, error: String) -> ConnectionStateMachine {
+    let metered = tauri::async_runtime::spawn_blocking(metered)
+        .await
+        .ok()
+        .flatten();
+    let attempt = current(app).attempt + 1;
+
+    let (phase, delay) = match kind {
+        FailureKind::Auth if attempt >= MAX_AUTH_ATTEMPTS => (ConnectionPhase::AuthFailed, None),
+        FailureKind::Auth => (
+            ConnectionPhase::WaitingToRetry,
+            Some(backoff(attempt, AUTH_BACKOFF, false)),
+        ),
+        FailureKind::Server => (
+            ConnectionPhase::WaitingToRetry,
+            Some(backoff(attempt, SERVER_BACKOFF, metered == Some(true))),
+        ),
+        FailureKind::Network => match probe(app).await {
+            Probe::CaptivePortal => (
+                ConnectionPhase::CaptivePortal,
+                Some(Duration::from_secs(CAPTIVE_PORTAL_RETRY_SECS)),
+            ),
+            Probe::Unreachable => (
+                ConnectionPhase::Offline,
+                Some(backoff(attempt, NETWORK_BACKOFF, metered == Some(true))),
+            ),
+            Probe::Online | Probe::Unknown => (
+                ConnectionPhase::WaitingToRetry,
+                Some(backoff(attempt, NETWORK_BACKOFF, metered == Some(true))),
+            ),
+        },
+    };
+
+    update(app, |m| {
+        m.phase = phase;
+        m.attempt = attempt;
+        m.next_retry_at = delay.map(|d| now_ms() + d.as_millis() as u64);
+        m.last_error = Some(error);
+        m.metered = metered;
+    })
+}
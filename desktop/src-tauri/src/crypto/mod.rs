@@ -0,0 +1,101 @@
+// nChat Desktop — native crypto helpers for E2EE device verification.
+//
+// Key material stays in the web E2EE layer; the native side only keeps the
+// public identity keys it is shown, and which of them the user verified.
+
+pub use nchat_core::crypto::{backup, fingerprint};
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::AppHandle;
+
+use crate::state::settings;
+use fingerprint::EmojiCode;
+
+/// Store key for the device directory (user id → device id → record).
+/// Device ids are only unique per user, so both are needed to find one.
+const DEVICES_KEY: &str = "crypto.devices";
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DeviceRecord {
+    pub public_key: Vec<u8>,
+    pub verified: bool,
+}
+
+type DeviceDirectory = HashMap<String, HashMap<String, DeviceRecord>>;
+
+#[derive(Deserialize)]
+pub struct DeviceIdentity {
+    pub device_id: String,
+    pub public_key: Vec<u8>,
+}
+
+#[derive(Serialize)]
+pub struct DeviceFingerprint {
+    pub device_id: String,
+    pub user_id: String,
+    /// Six groups of five digits, e.g. "05218 33170 …".
+    pub numeric: String,
+    pub emoji: Vec<EmojiCode>,
+    pub verified: bool,
+}
+
+fn load_devices(app: &AppHandle) -> DeviceDirectory {
+    settings::load(app, DEVICES_KEY).unwrap_or_default()
+}
+
+/// Record the identity keys currently published for `user_id`. A device
+/// whose key changed loses its verified flag.
+pub fn register_devices(
+    app: &AppHandle,
+    user_id: &str,
+    devices: Vec<DeviceIdentity>,
+) -> Result<(), String> {
+    let mut all = load_devices(app);
+    let known = all.entry(user_id.to_string()).or_default();
+    for device in devices {
+        let verified = known
+            .get(&device.device_id)
+            .is_some_and(|r| r.verified && r.public_key == device.public_key);
+        known.insert(
+            device.device_id,
+            DeviceRecord {
+                public_key: device.public_key,
+                verified,
+            },
+        );
+    }
+    settings::save(app, DEVICES_KEY, &all)
+}
+
+pub fn fingerprints(app: &AppHandle, user_id: &str) -> Vec<DeviceFingerprint> {
+    let mut list: Vec<DeviceFingerprint> = load_devices(app)
+        .remove(user_id)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(device_id, r)| DeviceFingerprint {
+            numeric: fingerprint::numeric(&r.public_key, user_id),
+            emoji: fingerprint::emoji(&r.public_key, user_id),
+            device_id,
+            user_id: user_id.to_string(),
+            verified: r.verified,
+        })
+        .collect();
+    list.sort_by(|a, b| a.device_id.cmp(&b.device_id));
+    list
+}
+
+pub fn set_verified(
+    app: &AppHandle,
+    user_id: &str,
+    device_id: &str,
+    verified: bool,
+) -> Result<(), String> {
+    let mut all = load_devices(app);
+    let record = all
+        .get_mut(user_id)
+        .and_then(|devices| devices.get_mut(device_id))
+        .ok_or_else(|| format!("unknown device: {device_id} of {user_id}"))?;
+    record.verified = verified;
+    settings::save(app, DEVICES_KEY, &all)
+}
@@ -0,0 +1,666 @@
+// nChat Desktop — `nchat://` and universal link routing
+//
+// Links that arrive before the frontend has registered its listeners (a
+// cold start, or while the main window does not exist yet) are queued and
+// replayed once it calls `deep_link_ready`.
+//
+// Every navigable link reaches the frontend as one `deep-link-parsed` event
+// carrying the route, its ids and the query parameters, e.g.
+// `nchat://channel/123?message=456` becomes
+// `{ "route": "channel", "id": "123", "params": { "message": "456" } }`.
+//
+// Links can be scoped to a workspace by prefixing the route, e.g.
+// `nchat://workspace/<ws>/channel/<id>/thread/<thread>`, and every
+// navigation event carries `workspace_id` (or `null`) so multi-workspace
+// users land in the right one.
+//
+// Invites (`nchat://invite/<code>`, or `https://<server>/invite/<code>` as
+// opened through a universal link) are checked here and sent as
+// `invite-received` instead, so the frontend can go straight to the join
+// flow.
+//
+// Call links (`nchat://call/<room>?video=1&muted=1`, e.g. from a calendar
+// invite) are sent as `join-call` with the camera/microphone preferences.
+//
+// Compose links (`nchat://compose?to=<user>&text=<msg>&attach=<path>`) let
+// other tools share into nChat. They are sent as `compose-prefill`; each
+// attachment must be an existing file inside the user's document, media,
+// download or desktop folders (or the app's own data) and is dropped
+// otherwise, so a link cannot pull arbitrary files into a message.
+//
+// Status links (`nchat://status`, or `nchat://status/<status>` with one of
+// the tray's statuses) are emitted as `status-requested`, for the frontend
+// to open its status picker or set the status; the Windows Jump List uses
+// them.
+//
+// Phone links: when the user turns it on, the app registers for `tel:` and
+// `callto:` (Windows and Linux; macOS only allows this at build time) and
+// turns them into `start-call` with the number or handle to ring.
+//
+// Universal links (macOS only): `https://<host>/<route>/...` on an allowed
+// host (the hosted service, plus any the admin policy adds for self-hosted
+// servers) go through the same routes and carry the server's origin. macOS
+// hands them over through the associated-domains entitlement. Windows (for
+// unpackaged apps) and Linux have no per-domain association, and claiming
+// every `https` link would make the app the browser, so there such links
+// open in the browser; only the `nchat://` form opens the app.
+//
+// Links come from outside the app, so nothing reaches the webview before
+// `validate` has accepted it; rejected links are logged without their
+// contents.
+//
+// Every link the app receives is also kept in a short audit trail (with
+// secrets in the query masked), so "clicking the link did nothing" can be
+// traced to a rejection or a route.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, Manager, Url};
+use tauri_plugin_deep_link::DeepLinkExt;
+
+use crate::integrations;
+use crate::policy;
+use crate::state::settings;
+use crate::windows::MAIN_WINDOW;
+
+/// Settings key for the audit trail of received links.
+const AUDIT_KEY: &str = "deeplink.audit";
+const AUDIT_LEN: usize = 200;
+/// Longest link stored in the audit trail.
+const AUDIT_URL_LEN: usize = 512;
+/// Links queued before the frontend is ready; the oldest are dropped (and
+/// recorded as such) beyond this.
+const PENDING_LEN: usize = AUDIT_LEN;
+
+/// Hosts of `nchat://` links, i.e. the routes the app knows.
+const ROUTES: &[&str] = &[
+    "notify",
+    "channel",
+    "chat",
+    "workspace",
+    "call",
+    "invite",
+    "compose",
+    "user",
+    "status",
+];
+
+/// Phone-style schemes the app can handle, on request.
+const PHONE_SCHEMES: &[&str] = &["tel", "callto"];
+
+/// Hosts whose `https` links open in the app, besides those in the policy.
+const UNIVERSAL_LINK_HOSTS: &[&str] = &["chat.nself.org"];
+
+/// Longest link accepted, in bytes. Leaves room for a percent-encoded
+/// integration notification body.
+const MAX_LINK_LEN: usize = 16 * 1024;
+const MAX_SEGMENTS: usize = 6;
+const MAX_PARAMS: usize = 16;
+/// Largest file a compose link may attach.
+const MAX_ATTACHMENT_SIZE: u64 = 100 * 1024 * 1024;
+/// Longest decoded query value.
+const MAX_PARAM_LEN: usize = 4096;
+
+/// A parsed `nchat://` link.
+#[derive(Clone, PartialEq)]
+pub enum DeepLink {
+    /// A place in the app for the frontend to open.
+    Open(ParsedLink),
+    /// `nchat://notify?token=…`, sent by other local apps.
+    Notify(Url),
+}
+
+impl DeepLink {
+    /// Links a startup target cannot express: ones that do something
+    /// rather than pick a place to open, and universal links, which need
+    /// their server. They are routed at launch instead.
+    pub fn routed_at_launch(&self) -> bool {
+        match self {
+            Self::Notify(_) => true,
+            Self::Open(link) => {
+                link.server.is_some()
+                    || matches!(
+                        link.route,
+                        Route::Channel {
+                            thread_id: Some(_),
+                            ..
+                        } | Route::User { .. }
+                            | Route::Call { .. }
+                            | Route::Compose { .. }
+                            | Route::Dial { .. }
+                            | Route::Status { .. }
+                    )
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Clone, PartialEq)]
+#[serde(tag = "route", rename_all = "kebab-case")]
+pub enum Route {
+    /// `nchat://channel/<id>[/thread/<thread>]` (or the older
+    /// `nchat://chat/<id>`)
+    Channel {
+        id: String,
+        thread_id: Option<String>,
+    },
+    /// `nchat://user/<id>`: a direct conversation.
+    User { id: String },
+    /// `nchat://workspace/<id>`
+    Workspace { id: String },
+    /// `nchat://invite/<code>` or `https://<server>/invite/<code>`
+    Invite { code: String },
+    /// `nchat://call/<room>?video=1&muted=1`
+    Call {
+        room: String,
+        /// Join with the camera on.
+        video: bool,
+        /// Join with the microphone muted.
+        muted: bool,
+    },
+    /// `tel:<number>` or `callto:<number or handle>`
+    Dial { target: String },
+    /// `nchat://compose?to=<user>&text=<msg>&attach=<path>`
+    Compose {
+        to: Option<String>,
+        text: Option<String>,
+        /// Canonical paths of the files to attach, after the scope check.
+        attachments: Vec<String>,
+    },
+    /// `nchat://status[/<status>]`: without a status, open the picker.
+    Status { status: Option<String> },
+}
+
+impl Route {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Channel { .. } => "channel",
+            Self::User { .. } => "user",
+            Self::Workspace { .. } => "workspace",
+            Self::Invite { .. } => "invite",
+            Self::Call { .. } => "call",
+            Self::Dial { .. } => "dial",
+            Self::Compose { .. } => "compose",
+            Self::Status { .. } => "status",
+        }
+    }
+}
+
+/// One received link in the audit trail.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AuditEntry {
+    /// Unix time in milliseconds.
+    pub at: u64,
+    /// The link as received, with secrets masked and long links cut.
+    pub url: String,
+    pub route: Option<String>,
+    pub accepted: bool,
+    /// Why the link was rejected.
+    pub reason: Option<String>,
+}
+
+/// Payload of `deep-link-parsed`.
+#[derive(Serialize, Clone, PartialEq)]
+pub struct ParsedLink {
+    #[serde(flatten)]
+    pub route: Route,
+    /// Workspace the route is scoped to, from a `workspace/<ws>/` prefix.
+    pub workspace_id: Option<String>,
+    /// Origin of a universal link; the frontend opens the route on that
+    /// server.
+    pub server: Option<String>,
+    /// Query parameters such as `message`, `ts` or `highlight`.
+    pub params: BTreeMap<String, String>,
+}
+
+#[derive(Default)]
+pub struct DeepLinkState {
+    queue: Mutex<Queue>,
+    /// Serializes read-modify-write of the audit trail.
+    audit_lock: Mutex<()>,
+}
+
+#[derive(Default)]
+struct Queue {
+    ready: bool,
+    /// Links received before the frontend was ready, oldest first.
+    pending: VecDeque<String>,
+}
+
+/// Invite codes are 6–64 URL-safe characters.
+fn valid_invite_code(code: &str) -> bool {
+    (6..=64).contains(&code.len())
+        && code
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'))
+}
+
+/// Ids and tokens are used as-is, so only URL-safe characters are allowed.
+fn valid_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= 128
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':'))
+}
+
+/// A `1`/`true`/`yes` query flag; absent means off.
+fn query_flag(url: &Url, name: &str) -> bool {
+    url.query_pairs()
+        .any(|(key, value)| key == name && matches!(value.as_ref(), "1" | "true" | "yes"))
+}
+
+fn universal_link_host(host: &str) -> bool {
+    UNIVERSAL_LINK_HOSTS.contains(&host)
+        || policy::current()
+            .deep_link_hosts
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(host))
+}
+
+/// Check a raw link before anything in it is used: only known schemes
+/// and routes, bounded sizes, URL-safe ids and simple query keys pass.
+/// Credentials and fragments are dropped.
+fn validate(raw: &str) -> Result<Url, &'static str> {
+    if raw.len() > MAX_LINK_LEN {
+        return Err("link too long");
+    }
+    let mut url = Url::parse(raw).map_err(|_| "malformed link")?;
+    match url.scheme() {
+        "nchat" if ROUTES.contains(&url.host_str().unwrap_or_default()) => {}
+        "nchat" => return Err("unknown route"),
+        "https" if url.host_str().is_some_and(universal_link_host) => {}
+        "https" => return Err("host not allowed"),
+        scheme if PHONE_SCHEMES.contains(&scheme) => return Ok(url),
+        _ => return Err("unexpected scheme"),
+    }
+    let _ = url.set_username("");
+    let _ = url.set_password(None);
+    url.set_fragment(None);
+
+    let segments: Vec<&str> = url
+        .path_segments()
+        .map(|segments| segments.filter(|s| !s.is_empty()).collect())
+        .unwrap_or_default();
+    if segments.len() > MAX_SEGMENTS || !segments.iter().all(|s| valid_id(s)) {
+        return Err("invalid path");
+    }
+    let mut params = 0;
+    for (key, value) in url.query_pairs() {
+        params += 1;
+        let key_ok = !key.is_empty()
+            && key.len() <= 64
+            && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if params > MAX_PARAMS || !key_ok || value.len() > MAX_PARAM_LEN {
+            return Err("invalid query");
+        }
+    }
+    Ok(url)
+}
+
+/// Parse a link without recording it; see `receive`.
+pub fn parse(url: &str) -> Option<DeepLink> {
+    parse_link(url).ok()
+}
+
+/// The number or handle in a `tel:`/`callto:` link, without visual
+/// separators' encoding or `;`-parameters.
+fn dial_target(url: &Url) -> Result<String, &'static str> {
+    let raw = url.path().trim_start_matches('/');
+    let target = raw
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .replace("%20", " ");
+    let valid = !target.trim().is_empty()
+        && target.len() <= 64
+        && target
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || " +-.()_@".contains(c));
+    if !valid {
+        return Err("invalid call target");
+    }
+    Ok(target.trim().to_string())
+}
+
+fn parse_link(url: &str) -> Result<DeepLink, &'static str> {
+    let url = validate(url)?;
+    if PHONE_SCHEMES.contains(&url.scheme()) {
+        let route = Route::Dial {
+            target: dial_target(&url)?,
+        };
+        return Ok(DeepLink::Open(ParsedLink {
+            route,
+            workspace_id: None,
+            server: None,
+            params: BTreeMap::new(),
+        }));
+    }
+    let mut segments: Vec<&str> = url
+        .path_segments()
+        .map(|segments| segments.filter(|s| !s.is_empty()).collect())
+        .unwrap_or_default();
+    let server = match url.scheme() {
+        // For `nchat://` links the route is the host.
+        "nchat" => {
+            segments.insert(0, url.host_str().unwrap_or_default());
+            None
+        }
+        _ => Some(url.origin().ascii_serialization()),
+    };
+    let workspace_id = match segments.as_slice() {
+        ["workspace", ws, ..] => Some(ws.to_string()),
+        _ => None,
+    };
+    if workspace_id.is_some() && segments.len() > 2 {
+        segments.drain(..2);
+    }
+    let route = match (server.is_some(), segments.as_slice()) {
+        (false, ["notify"]) => return Ok(DeepLink::Notify(url)),
+        (false, ["chat", id]) | (_, ["channel", id]) => Route::Channel {
+            id: id.to_string(),
+            thread_id: None,
+        },
+        (_, ["channel", id, "thread", thread]) => Route::Channel {
+            id: id.to_string(),
+            thread_id: Some(thread.to_string()),
+        },
+        (_, ["user", id]) => Route::User { id: id.to_string() },
+        (_, ["workspace", id]) => Route::Workspace { id: id.to_string() },
+        (_, ["call", room]) => Route::Call {
+            room: room.to_string(),
+            video: query_flag(&url, "video"),
+            muted: query_flag(&url, "muted"),
+        },
+        (_, ["invite", code]) if valid_invite_code(code) => Route::Invite {
+            code: code.to_string(),
+        },
+        (_, ["invite", _]) => return Err("malformed invite code"),
+        (false, ["compose"]) => {
+            let values = |name: &str| -> Vec<String> {
+                url.query_pairs()
+                    .filter(|(key, _)| key == name)
+                    .map(|(_, value)| value.into_owned())
+                    .collect()
+            };
+            let to = values("to").pop();
+            if !to
+                .as_deref()
+                .is_none_or(|to| valid_id(to.trim_start_matches('@')))
+            {
+                return Err("invalid recipient");
+            }
+            Route::Compose {
+                to,
+                text: values("text").pop(),
+                attachments: values("attach"),
+            }
+        }
+        (false, ["status"]) => Route::Status { status: None },
+        (false, ["status", status]) => {
+            if !crate::tray::STATUSES.iter().any(|(s, _)| s == status) {
+                return Err("unknown status");
+            }
+            Route::Status {
+                status: Some(status.to_string()),
+            }
+        }
+        _ => return Err("unknown route"),
+    };
+    let params = url
+        .query_pairs()
+        .into_owned()
+        .filter(|(key, _)| {
+            // Compose fields are in the route; raw paths stay out of the payload.
+            !matches!(route, Route::Compose { .. })
+                || !matches!(key.as_str(), "to" | "text" | "attach")
+        })
+        .collect();
+    Ok(DeepLink::Open(ParsedLink {
+        route,
+        workspace_id,
+        server,
+        params,
+    }))
+}
+
+/// Resolve a compose attachment (a path or `file://` URL) to a canonical
+/// path, if it is a file the user could reasonably share: inside their
+/// documents, downloads, desktop or media folders, and not in a hidden
+/// directory. The app's data and cache are never allowed: they hold
+/// settings, exports and diagnostics a link must not slip into a message.
+fn shared_file(app: &AppHandle, raw: &str) -> Option<String> {
+    let path = match Url::parse(raw) {
+        Ok(url) if url.scheme() == "file" => url.to_file_path().ok()?,
+        _ => PathBuf::from(raw),
+    };
+    let checked = path.canonicalize().ok().filter(|path| {
+        let resolver = app.path();
+        let roots = [
+            resolver.document_dir(),
+            resolver.download_dir(),
+            resolver.desktop_dir(),
+            resolver.picture_dir(),
+            resolver.video_dir(),
+            resolver.audio_dir(),
+        ];
+        let inside = roots
+            .into_iter()
+            .flatten()
+            .filter_map(|root| root.canonicalize().ok())
+            .find_map(|root| path.strip_prefix(&root).ok().map(Path::to_path_buf));
+        let Some(relative) = inside else {
+            return false;
+        };
+        let hidden = relative
+            .components()
+            .any(|c| c.as_os_str().to_string_lossy().starts_with('.'));
+        let size_ok = std::fs::metadata(path)
+            .is_ok_and(|meta| meta.is_file() && meta.len() <= MAX_ATTACHMENT_SIZE);
+        !hidden && size_ok
+    });
+    if checked.is_none() {
+        log::warn!("[nchat-desktop] compose link: dropped attachment outside the allowed folders");
+    }
+    checked.map(|path| path.to_string_lossy().to_string())
+}
+
+/// `raw` with credentials dropped, secret-looking query values masked and
+/// the result cut to `AUDIT_URL_LEN`.
+fn redact(raw: &str) -> String {
+    let redacted = match Url::parse(raw) {
+        Ok(mut url) => {
+            let _ = url.set_password(None);
+            let pairs: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+            if !pairs.is_empty() {
+                let mut query = url.query_pairs_mut();
+                query.clear();
+                for (key, value) in &pairs {
+                    let lower = key.to_ascii_lowercase();
+                    let secret = ["token", "password", "secret", "key"]
+                        .iter()
+                        .any(|s| lower.contains(s));
+                    query.append_pair(key, if secret { "***" } else { value });
+                }
+            }
+            url.to_string()
+        }
+        // Malformed links are kept without their query.
+        Err(_) => raw.split('?').next().unwrap_or_default().to_string(),
+    };
+    match redacted.char_indices().nth(AUDIT_URL_LEN) {
+        Some((end, _)) => format!("{}…", &redacted[..end]),
+        None => redacted,
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Parse a link the app received and add it to the audit trail.
+pub fn receive(app: &AppHandle, url: &str) -> Option<DeepLink> {
+    let result = parse_link(url);
+    let entry = AuditEntry {
+        at: now_ms(),
+        url: redact(url),
+        route: result.as_ref().ok().map(|link| match link {
+            DeepLink::Notify(_) => "notify".to_string(),
+            DeepLink::Open(link) => link.route.name().to_string(),
+        }),
+        accepted: result.is_ok(),
+        reason: result.as_ref().err().map(|reason| reason.to_string()),
+    };
+    record(app, entry);
+    match result {
+        Ok(link) => Some(link),
+        Err(reason) => {
+            // The link itself may carry tokens, so only the reason is logged.
+            log::warn!("[nchat-desktop] rejected deep link: {reason}");
+            None
+        }
+    }
+}
+
+/// Append to the audit trail, keeping the last `AUDIT_LEN` entries.
+fn record(app: &AppHandle, entry: AuditEntry) {
+    let state = app.state::<DeepLinkState>();
+    let _guard = state.audit_lock.lock().unwrap();
+    let mut trail = audit_trail(app);
+    trail.push(entry);
+    let excess = trail.len().saturating_sub(AUDIT_LEN);
+    trail.drain(..excess);
+    if let Err(e) = settings::save(app, AUDIT_KEY, &trail) {
+        log::warn!("[nchat-desktop] failed to record deep link: {e}");
+    }
+}
+
+/// Received links, oldest first.
+pub fn audit_trail(app: &AppHandle) -> Vec<AuditEntry> {
+    settings::load(app, AUDIT_KEY).unwrap_or_default()
+}
+
+pub fn clear_audit_trail(app: &AppHandle) -> Result<(), String> {
+    settings::remove(app, AUDIT_KEY)
+}
+
+/// Handle a link the app received: record it, then hand it over.
+pub fn route(app: &AppHandle, url: &str) {
+    if let Some(link) = receive(app, url) {
+        dispatch(app, url, link);
+    }
+}
+
+/// Bring the main window forward and hand a link to the frontend, or queue
+/// it until the frontend is ready.
+pub fn dispatch(app: &AppHandle, url: &str, link: DeepLink) {
+    let mut link = match link {
+        // Integration requests are handled natively and must not steal focus.
+        DeepLink::Notify(url) => return integrations::handle_notify(app, &url),
+        DeepLink::Open(link) => link,
+    };
+    let win = app.get_webview_window(MAIN_WINDOW);
+    {
+        let state = app.state::<DeepLinkState>();
+        let mut queue = state.queue.lock().unwrap();
+        if !queue.ready || win.is_none() {
+            queue.pending.push_back(url.to_string());
+            let dropped = if queue.pending.len() > PENDING_LEN {
+                queue.pending.pop_front()
+            } else {
+                None
+            };
+            drop(queue);
+            if let Some(dropped) = dropped {
+                log::warn!("[nchat-desktop] deep link queue full, dropped the oldest link");
+                record(
+                    app,
+                    AuditEntry {
+                        at: now_ms(),
+                        url: redact(&dropped),
+                        route: None,
+                        accepted: false,
+                        reason: Some("dropped: too many links before the app was ready".into()),
+                    },
+                );
+            }
+            return;
+        }
+    }
+    if let Some(win) = win {
+        let _ = win.show();
+        let _ = win.set_focus();
+        if let Route::Compose { attachments, .. } = &mut link.route {
+            *attachments = attachments
+                .iter()
+                .filter_map(|raw| shared_file(app, raw))
+                .collect();
+        }
+        let event = match link.route {
+            Route::Invite { .. } => "invite-received",
+            Route::Call { .. } => "join-call",
+            Route::Compose { .. } => "compose-prefill",
+            Route::Dial { .. } => "start-call",
+            Route::Status { .. } => "status-requested",
+            _ => "deep-link-parsed",
+        };
+        let _ = win.emit(event, link);
+    }
+}
+
+/// The frontend has registered its deep-link listeners: deliver the links
+/// queued so far and route later ones directly.
+pub fn ready(app: &AppHandle) {
+    let pending = {
+        let state = app.state::<DeepLinkState>();
+        let mut queue = state.queue.lock().unwrap();
+        queue.ready = true;
+        std::mem::take(&mut queue.pending)
+    };
+    for url in pending {
+        // Already recorded when it arrived.
+        if let Ok(link) = parse_link(&url) {
+            dispatch(app, &url, link);
+        }
+    }
+}
+
+/// A phone link passed as the only launch argument (Windows/Linux, where
+/// the app registers for them). The deep-link plugin only picks up
+/// `nchat://` arguments.
+pub fn launch_argument() -> Option<String> {
+    if cfg!(target_os = "macos") {
+        return None;
+    }
+    let mut args = std::env::args().skip(1);
+    let arg = args.next().filter(|_| args.next().is_none())?;
+    let scheme = arg.split(':').next().unwrap_or_default();
+    PHONE_SCHEMES.contains(&scheme).then_some(arg)
+}
+
+/// Whether the app is registered for `tel:`/`callto:` links.
+pub fn phone_links_enabled(app: &AppHandle) -> bool {
+    PHONE_SCHEMES
+        .iter()
+        .all(|scheme| app.deep_link().is_registered(scheme).unwrap_or(false))
+}
+
+/// Register or unregister the app for `tel:`/`callto:` links. macOS only
+/// supports schemes declared at build time.
+pub fn set_phone_links(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    for scheme in PHONE_SCHEMES {
+        let result = if enabled {
+            app.deep_link().register(scheme)
+        } else {
+            app.deep_link().unregister(scheme)
+        };
+        result.map_err(|e| format!("failed to update {scheme}: links: {e}"))?;
+    }
+    Ok(())
+}
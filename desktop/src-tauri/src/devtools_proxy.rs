@@ -0,0 +1,217 @@
+// nChat Desktop — authenticating proxy in front of the devtools endpoint
+//
+// Neither WebView2 nor WebKitGTK can protect their devtools server, so during
+// a support session the engine listens on a private loopback port and this
+// proxy owns the port support was given. The first request of a connection
+// must carry the session token, either as `Authorization: Bearer <token>` or
+// as a `token` query parameter (devtools front ends cannot set headers on the
+// WebSocket they open); it is stripped from the query before the request is
+// forwarded, and the rest of the connection is relayed unchanged. Anything
+// else is answered with 401. `stop` closes the listener and every relayed
+// connection.
+
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Largest request head accepted before the token is checked.
+const MAX_HEAD_LEN: usize = 16 * 1024;
+
+/// How long a client has to send its request head.
+const HEAD_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How often the accept loop checks whether it was stopped.
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Both ends of each relayed connection, so `stop` can close them.
+type Connections = Arc<Mutex<HashMap<u64, [TcpStream; 2]>>>;
+
+struct Proxy {
+    stopped: Arc<AtomicBool>,
+    connections: Connections,
+}
+
+static PROXY: Mutex<Option<Proxy>> = Mutex::new(None);
+
+static NEXT_CONNECTION: AtomicU64 = AtomicU64::new(0);
+
+/// Listen on 127.0.0.1:`port` and relay authenticated connections to the
+/// engine's devtools server on 127.0.0.1:`upstream`. Replaces a running
+/// proxy.
+pub fn start(port: u16, upstream: u16, token: String) -> Result<(), String> {
+    stop();
+    let listener = TcpListener::bind(("127.0.0.1", port))
+        .map_err(|e| format!("cannot listen on 127.0.0.1:{port}: {e}"))?;
+    listener
+        .set_nonblocking(true)
+        .map_err(|e| format!("cannot listen on 127.0.0.1:{port}: {e}"))?;
+
+    let stopped = Arc::new(AtomicBool::new(false));
+    let connections: Connections = Arc::default();
+    *PROXY.lock().unwrap() = Some(Proxy {
+        stopped: stopped.clone(),
+        connections: connections.clone(),
+    });
+
+    let token = Arc::new(token);
+    std::thread::spawn(move || {
+        while !stopped.load(Ordering::SeqCst) {
+            match listener.accept() {
+                Ok((client, _)) => {
+                    let token = token.clone();
+                    let connections = connections.clone();
+                    std::thread::spawn(move || {
+                        if let Err(e) = serve(client, upstream, &token, &connections) {
+                            log::warn!("[nchat-desktop] devtools proxy connection failed: {e}");
+                        }
+                    });
+                }
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                    std::thread::sleep(POLL_INTERVAL);
+                }
+                Err(e) => {
+                    log::warn!("[nchat-desktop] devtools proxy stopped: {e}");
+                    break;
+                }
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Close the listener and every connection relayed so far.
+pub fn stop() {
+    let Some(proxy) = PROXY.lock().unwrap().take() else {
+        return;
+    };
+    proxy.stopped.store(true, Ordering::SeqCst);
+    for (_, ends) in proxy.connections.lock().unwrap().drain() {
+        for stream in ends {
+            let _ = stream.shutdown(Shutdown::Both);
+        }
+    }
+}
+
+fn serve(
+    mut client: TcpStream,
+    upstream: u16,
+    token: &str,
+    connections: &Connections,
+) -> io::Result<()> {
+    client.set_nonblocking(false)?;
+    client.set_read_timeout(Some(HEAD_TIMEOUT))?;
+    let (head, rest) = read_head(&mut client)?;
+    let Some(head) = authorize(&head, token) else {
+        client.write_all(
+            b"HTTP/1.1 401 Unauthorized\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        )?;
+        return Ok(());
+    };
+    client.set_read_timeout(None)?;
+
+    let mut server = TcpStream::connect(("127.0.0.1", upstream))?;
+    server.write_all(head.as_bytes())?;
+    server.write_all(&rest)?;
+    let id = NEXT_CONNECTION.fetch_add(1, Ordering::Relaxed);
+    connections
+        .lock()
+        .unwrap()
+        .insert(id, [client.try_clone()?, server.try_clone()?]);
+
+    let (mut client_read, mut server_write) = (client.try_clone()?, server.try_clone()?);
+    let outbound = std::thread::spawn(move || {
+        let _ = io::copy(&mut client_read, &mut server_write);
+        let _ = server_write.shutdown(Shutdown::Write);
+    });
+    let _ = io::copy(&mut server, &mut client);
+    let _ = client.shutdown(Shutdown::Both);
+    let _ = server.shutdown(Shutdown::Both);
+    let _ = outbound.join();
+    connections.lock().unwrap().remove(&id);
+    Ok(())
+}
+
+/// Read up to the end of the request head; returns the head and whatever
+/// followed it in the same reads.
+fn read_head(stream: &mut TcpStream) -> io::Result<(String, Vec<u8>)> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 2048];
+    loop {
+        if let Some(end) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            let rest = buf.split_off(end + 4);
+            let head = String::from_utf8(buf).map_err(|_| {
+                io::Error::new(io::ErrorKind::InvalidData, "request head is not UTF-8")
+            })?;
+            return Ok((head, rest));
+        }
+        if buf.len() > MAX_HEAD_LEN {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "request head too large",
+            ));
+        }
+        let n = stream.read(&mut chunk)?;
+        if n == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+}
+
+/// Check the token of a request head and return the head to forward, with
+/// the `token` query parameter removed; `None` when the token is missing or
+/// wrong.
+fn authorize(head: &str, token: &str) -> Option<String> {
+    let (request_line, headers) = head.split_once("\r\n")?;
+    let mut parts = request_line.splitn(3, ' ');
+    let (method, target, version) = (parts.next()?, parts.next()?, parts.next()?);
+
+    let bearer = headers.split("\r\n").any(|line| {
+        let Some((name, value)) = line.split_once(':') else {
+            return false;
+        };
+        name.trim().eq_ignore_ascii_case("authorization")
+            && value
+                .trim()
+                .strip_prefix("Bearer ")
+                .is_some_and(|given| matches(given.trim(), token))
+    });
+
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let mut in_query = false;
+    let kept: Vec<&str> = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .filter(|pair| match pair.split_once('=') {
+            Some(("token", given)) => {
+                in_query |= matches(given, token);
+                false
+            }
+            _ => true,
+        })
+        .collect();
+    if !bearer && !in_query {
+        return None;
+    }
+
+    let target = if kept.is_empty() {
+        path.to_string()
+    } else {
+        format!("{path}?{}", kept.join("&"))
+    };
+    Some(format!("{method} {target} {version}\r\n{headers}"))
+}
+
+/// Compare in constant time so the token cannot be guessed byte by byte.
+fn matches(given: &str, token: &str) -> bool {
+    let (given, token) = (given.as_bytes(), token.as_bytes());
+    given.len() == token.len()
+        && given
+            .iter()
+            .zip(token)
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
@@ -0,0 +1,234 @@
+// nChat Desktop — diagnostics bundle
+//
+// Help → Collect Diagnostics zips the logs, version and platform details,
+// the state of the native integrations and any recent panics, so users can
+// attach a single file to a bug report. Everything logged through the `log`
+// macros goes to a file in the log folder, rotated by size so the folder
+// stays small; the bundle takes every file there. Panics are appended to
+// their own file by a hook installed at startup (Sentry only sees them
+// when a DSN is configured).
+
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_autostart::ManagerExt;
+use tauri_plugin_notification::NotificationExt;
+use zip::write::SimpleFileOptions;
+
+/// File in the log folder collecting panic messages.
+const PANIC_LOG: &str = "panics.log";
+
+/// Only the tail of the panic log goes into a bundle.
+const MAX_PANIC_LOG_BYTES: u64 = 256 * 1024;
+
+static PANIC_LOG_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+/// File in the log folder the app logs to; older ones get `.1`, `.2`, ...
+const APP_LOG: &str = "nchat-desktop.log";
+
+/// Size at which the app log is rotated.
+const MAX_APP_LOG_BYTES: u64 = 1024 * 1024;
+
+/// Rotated app logs kept next to the current one.
+const ROTATED_APP_LOGS: usize = 3;
+
+struct LogFile {
+    path: PathBuf,
+    file: File,
+    len: u64,
+}
+
+impl LogFile {
+    fn open(path: PathBuf) -> std::io::Result<Self> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)?;
+        let len = file.metadata()?.len();
+        Ok(Self { path, file, len })
+    }
+
+    /// Shift `nchat-desktop.log.N` up by one, dropping the oldest, and
+    /// start a new file.
+    fn rotate(&mut self) -> std::io::Result<()> {
+        let rotated = |n: usize| PathBuf::from(format!("{}.{n}", self.path.display()));
+        let _ = std::fs::remove_file(rotated(ROTATED_APP_LOGS));
+        for n in (1..ROTATED_APP_LOGS).rev() {
+            let _ = std::fs::rename(rotated(n), rotated(n + 1));
+        }
+        std::fs::rename(&self.path, rotated(1))?;
+        *self = Self::open(self.path.clone())?;
+        Ok(())
+    }
+}
+
+struct FileLogger {
+    file: Mutex<LogFile>,
+}
+
+impl log::Log for FileLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &log::Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let line = format!(
+            "{} {:<5} {}: {}\n",
+            chrono::Local::now().format("%Y-%m-%dT%H:%M:%S%.3f%:z"),
+            record.level(),
+            record.target(),
+            record.args()
+        );
+        #[cfg(debug_assertions)]
+        eprint!("{line}");
+        let mut log = self.file.lock().unwrap();
+        if log.len >= MAX_APP_LOG_BYTES {
+            if let Err(e) = log.rotate() {
+                eprintln!("[nchat-desktop] failed to rotate the log: {e}");
+            }
+        }
+        if log.file.write_all(line.as_bytes()).is_ok() {
+            log.len += line.len() as u64;
+        }
+    }
+
+    fn flush(&self) {
+        let _ = self.file.lock().unwrap().file.flush();
+    }
+}
+
+/// Send the `log` macros to a rotating file in the log folder. Info and
+/// above are kept, debug as well in debug builds.
+pub fn install_file_logger(app: &AppHandle) {
+    let Ok(dir) = app.path().app_log_dir() else {
+        return;
+    };
+    let _ = std::fs::create_dir_all(&dir);
+    let file = match LogFile::open(dir.join(APP_LOG)) {
+        Ok(file) => file,
+        Err(e) => {
+            eprintln!("[nchat-desktop] cannot open the log file: {e}");
+            return;
+        }
+    };
+    let logger = FileLogger {
+        file: Mutex::new(file),
+    };
+    if log::set_logger(Box::leak(Box::new(logger))).is_ok() {
+        log::set_max_level(if cfg!(debug_assertions) {
+            log::LevelFilter::Debug
+        } else {
+            log::LevelFilter::Info
+        });
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Record panics in the log folder, in addition to the existing hook.
+pub fn install_panic_hook(app: &AppHandle) {
+    let Ok(dir) = app.path().app_log_dir() else {
+        return;
+    };
+    let _ = std::fs::create_dir_all(&dir);
+    if PANIC_LOG_PATH.set(dir.join(PANIC_LOG)).is_err() {
+        return;
+    }
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if let Some(path) = PANIC_LOG_PATH.get() {
+            if let Ok(mut file) = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+            {
+                let thread = std::thread::current();
+                let _ = writeln!(
+                    file,
+                    "[{}] thread '{}' {info}",
+                    now_secs(),
+                    thread.name().unwrap_or("<unnamed>")
+                );
+            }
+        }
+        previous(info);
+    }));
+}
+
+/// Version, platform and the state of each native integration.
+fn system_info(app: &AppHandle) -> serde_json::Value {
+    serde_json::json!({
+        "health": crate::health::snapshot(app),
+        "renderer": crate::renderer::info(app),
+        "tray": crate::tray::availability(app),
+        "autostart": app.autolaunch().is_enabled().map_err(|e| e.to_string()),
+        "notifications": app
+            .notification()
+            .permission_state()
+            .map(|state| state.to_string())
+            .map_err(|e| e.to_string()),
+        "spellcheck": crate::spellcheck::settings(app),
+        "managed": crate::policy::current().heartbeat.is_some(),
+    })
+}
+
+/// Last `MAX_PANIC_LOG_BYTES` of the panic log.
+fn panic_log_tail(path: &Path) -> Option<Vec<u8>> {
+    let data = std::fs::read(path).ok()?;
+    let start = data.len().saturating_sub(MAX_PANIC_LOG_BYTES as usize);
+    Some(data[start..].to_vec())
+}
+
+/// Write the bundle and return its path.
+pub fn collect(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join("diagnostics");
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let path = dir.join(format!("nchat-diagnostics-{}.zip", now_secs()));
+
+    let file = std::fs::File::create(&path).map_err(|e| e.to_string())?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    let info = serde_json::to_vec_pretty(&system_info(app)).map_err(|e| e.to_string())?;
+    zip.start_file("system.json", options)
+        .map_err(|e| e.to_string())?;
+    zip.write_all(&info).map_err(|e| e.to_string())?;
+
+    if let Ok(log_dir) = app.path().app_log_dir() {
+        let entries = std::fs::read_dir(&log_dir).into_iter().flatten().flatten();
+        for entry in entries {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let contents = if name == PANIC_LOG {
+                panic_log_tail(&entry.path())
+            } else if entry.path().is_file() {
+                std::fs::read(entry.path()).ok()
+            } else {
+                None
+            };
+            let Some(contents) = contents else {
+                continue;
+            };
+            zip.start_file(format!("logs/{name}"), options)
+                .map_err(|e| e.to_string())?;
+            zip.write_all(&contents).map_err(|e| e.to_string())?;
+        }
+    }
+
+    zip.finish().map_err(|e| e.to_string())?;
+    Ok(path)
+}
@@ -0,0 +1,112 @@
+// nChat Desktop — composer dictation
+//
+// Recognized text is streamed to the composer as `dictation-partial`
+// (running transcript) and `dictation-final` events. Recognition runs in a
+// local speech-to-text program configured by policy, so audio never leaves
+// the machine; it prints one recognized segment per line. Without one,
+// dictation is unsupported on every platform and `start` says so (the OS
+// dictation features type into fields themselves and cannot feed the
+// composer's events).
+
+use serde::Serialize;
+use std::io::{BufRead, BufReader};
+use std::process::{Child, Command, Stdio};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::policy;
+
+#[derive(Default)]
+pub struct DictationState {
+    child: Mutex<Option<Child>>,
+}
+
+#[derive(Serialize, Clone)]
+struct DictationText {
+    text: String,
+}
+
+fn emit(app: &AppHandle, event: &str, text: &str) {
+    if let Some(win) = app.get_webview_window("main") {
+        let _ = win.emit(
+            event,
+            DictationText {
+                text: text.to_string(),
+            },
+        );
+    }
+}
+
+pub fn is_running(app: &AppHandle) -> bool {
+    app.state::<DictationState>()
+        .child
+        .lock()
+        .unwrap()
+        .is_some()
+}
+
+/// Whether a speech engine is configured, i.e. dictation can work.
+pub fn is_available() -> bool {
+    policy::current().dictation.is_some()
+}
+
+/// Start dictation. Calling it while a session is running is a no-op.
+pub fn start(app: &AppHandle) -> Result<(), String> {
+    let Some(engine) = policy::current().dictation.clone() else {
+        return Err("dictation is unsupported: no speech recognition engine is configured".into());
+    };
+
+    let state = app.state::<DictationState>();
+    let mut slot = state.child.lock().unwrap();
+    if slot.is_some() {
+        return Ok(());
+    }
+    let mut child = Command::new(&engine.program)
+        .args(&engine.args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("failed to start {}: {e}", engine.program))?;
+    let stdout = child.stdout.take().ok_or("speech engine has no stdout")?;
+    *slot = Some(child);
+
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let mut transcript = String::new();
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            let segment = line.trim();
+            if segment.is_empty() {
+                continue;
+            }
+            if !transcript.is_empty() {
+                transcript.push(' ');
+            }
+            transcript.push_str(segment);
+            emit(&app, "dictation-partial", &transcript);
+        }
+        // stdout closes when the engine exits or `stop` killed it.
+        let child = app.state::<DictationState>().child.lock().unwrap().take();
+        if let Some(mut child) = child {
+            let _ = child.wait();
+        }
+        emit(&app, "dictation-final", &transcript);
+    });
+    Ok(())
+}
+
+/// Stop the engine; the reader thread then emits `dictation-final`.
+pub fn stop(app: &AppHandle) {
+    if let Some(child) = app.state::<DictationState>().child.lock().unwrap().as_mut() {
+        let _ = child.kill();
+    }
+}
+
+/// Menu/shortcut entry point.
+pub fn toggle(app: &AppHandle) {
+    if is_running(app) {
+        stop(app);
+    } else if let Err(e) = start(app) {
+        log::warn!("[nchat-desktop] dictation unavailable: {e}");
+    }
+}
@@ -0,0 +1,145 @@
+// nChat Desktop — opt-in morning digest notification
+//
+// Once a day, at the configured local time, the unread summary the
+// frontend keeps pushing for the tray (unread per channel, mentions, DMs)
+// is condensed into a single notification, greeting by the local time of
+// day in the language the web UI picked. Desktop notifications cannot
+// carry action buttons or report a click or dismissal, so "Open inbox" is
+// the click itself: when the window gains focus shortly after the digest
+// was shown, the frontend is asked to open the inbox. Past that window the
+// digest counts as dismissed and focus is left alone. When the digest is
+// due is decided by `nchat_core::jobs`.
+
+use chrono::{Local, Timelike};
+use nchat_core::jobs;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::i18n::{t, tf};
+use crate::state::settings;
+use crate::tray::{self, TraySummary};
+
+const SETTINGS_KEY: &str = "digest";
+const LAST_SHOWN_KEY: &str = "digest.lastShown";
+
+/// How often the schedule is checked. Polling keeps the job correct across
+/// sleep/wake and time-zone changes.
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Channels named in the notification body.
+const DIGEST_CHANNELS: usize = 3;
+
+/// How long after the digest is shown focusing the app counts as clicking
+/// it; roughly how long the notification stays on screen.
+const PENDING_OPEN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// When the last digest was shown, until the user comes back or it times
+/// out.
+static PENDING_OPEN: Mutex<Option<Instant>> = Mutex::new(None);
+
+pub use nchat_core::jobs::DigestSettings;
+
+pub fn settings(app: &AppHandle) -> DigestSettings {
+    settings::load(app, SETTINGS_KEY).unwrap_or_default()
+}
+
+pub fn set_settings(app: &AppHandle, digest: DigestSettings) -> Result<(), String> {
+    digest.validate()?;
+    settings::save(app, SETTINGS_KEY, &digest)
+}
+
+/// Notification body for `summary`, or `None` when there is nothing to
+/// report.
+fn render(summary: &TraySummary) -> Option<String> {
+    if summary.unread == 0 && summary.mentions == 0 && summary.direct_messages == 0 {
+        return None;
+    }
+    let mut lines = vec![tf(
+        "digest.counts",
+        &[
+            ("unread", &summary.unread),
+            ("mentions", &summary.mentions),
+            ("dms", &summary.direct_messages),
+        ],
+    )];
+    let mut channels: Vec<_> = summary
+        .top_channels
+        .iter()
+        .filter(|c| c.unread > 0)
+        .collect();
+    channels.sort_by(|a, b| b.unread.cmp(&a.unread));
+    let channels: Vec<String> = channels
+        .iter()
+        .take(DIGEST_CHANNELS)
+        .map(|c| format!("#{} ({})", c.name, c.unread))
+        .collect();
+    if !channels.is_empty() {
+        lines.push(channels.join(", "));
+    }
+    lines.push(t("digest.open").into());
+    Some(lines.join("\n"))
+}
+
+/// Greeting for the local hour, e.g. "Good afternoon — here's what you
+/// missed".
+fn title(hour: u32) -> &'static str {
+    match hour {
+        5..=11 => t("digest.title_morning"),
+        12..=17 => t("digest.title_afternoon"),
+        _ => t("digest.title_evening"),
+    }
+}
+
+/// Show today's digest if it is due and has not been shown yet.
+fn check(app: &AppHandle) {
+    let now = Local::now();
+    let last_shown: Option<String> = settings::load(app, LAST_SHOWN_KEY);
+    let Some(today) = jobs::digest_due(&settings(app), now.naive_local(), last_shown.as_deref())
+    else {
+        return;
+    };
+    let _ = settings::save(app, LAST_SHOWN_KEY, &today);
+
+    let Some(body) = render(&tray::summary(app)) else {
+        return;
+    };
+    let shown = app
+        .notification()
+        .builder()
+        .title(title(now.hour()))
+        .body(body)
+        .show();
+    match shown {
+        Ok(()) => *PENDING_OPEN.lock().unwrap() = Some(Instant::now()),
+        Err(e) => log::warn!("[nchat-desktop] digest notification failed: {e}"),
+    }
+}
+
+/// Called when the main window gains focus: if the user came back through
+/// the digest, ask the frontend to open the inbox. Any focus clears the
+/// pending digest, so a later unrelated one never opens the inbox.
+pub fn on_focus(app: &AppHandle) {
+    let Some(shown) = PENDING_OPEN.lock().unwrap().take() else {
+        return;
+    };
+    if shown.elapsed() > PENDING_OPEN_TIMEOUT {
+        return;
+    }
+    if let Some(win) = app.get_webview_window("main") {
+        let _ = win.emit("digest:open-inbox", ());
+    }
+}
+
+/// Start the digest scheduler. The first check waits one interval so the
+/// frontend has pushed a fresh unread summary.
+pub fn start(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(CHECK_INTERVAL).await;
+            check(&app);
+        }
+    });
+}
@@ -0,0 +1,542 @@
+// nChat Desktop — double-tap modifier trigger for the quick switcher
+//
+// The quick switcher can also open on a quick double tap of a modifier key
+// (e.g. Ctrl, Ctrl), which the global shortcut plugin cannot express since it
+// only registers key combinations. Instead, key events are read natively and
+// run through a small state machine: press, release, press again within the
+// interval, with no other key in between. Only modifier presses are looked
+// at; everything else just resets the gesture.
+//
+// macOS uses a listen-only event tap, which needs the Input Monitoring
+// permission. Windows uses a low-level keyboard hook. Linux selects
+// XInput2 raw key events on the X11 root window, which any X client may
+// do; Wayland offers no way to watch modifier keys, and reading
+// /dev/input would need the `input` group and expose every keystroke to
+// all of the user's processes, so there the trigger is unsupported. The
+// listener only runs while a trigger is set.
+
+use serde::{Deserialize, Serialize};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+use crate::state::settings;
+
+/// Settings key holding the quick switcher's trigger; absent when off.
+const SWITCHER_KEY: &str = "switcher.doubleTap";
+
+const DEFAULT_INTERVAL_MS: u64 = 300;
+
+/// Accepted range for the interval between the two taps.
+const MIN_INTERVAL_MS: u64 = 100;
+const MAX_INTERVAL_MS: u64 = 1000;
+
+/// The configured gesture and its progress.
+static DETECTOR: Mutex<Option<Detector>> = Mutex::new(None);
+
+/// Receives the gesture from the listener threads.
+static APP: OnceLock<AppHandle> = OnceLock::new();
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum Modifier {
+    Ctrl,
+    /// Command on macOS, the Windows or Super key elsewhere.
+    Cmd,
+    Alt,
+    Shift,
+    /// Command on macOS, Ctrl elsewhere.
+    CmdOrCtrl,
+}
+
+impl Modifier {
+    /// The physical key this stands for on the current platform.
+    fn resolve(self) -> Modifier {
+        match self {
+            Modifier::CmdOrCtrl if cfg!(target_os = "macos") => Modifier::Cmd,
+            Modifier::CmdOrCtrl => Modifier::Ctrl,
+            other => other,
+        }
+    }
+}
+
+/// A double tap of `modifier`, the second tap within `interval_ms` of
+/// the first.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DoubleTap {
+    pub modifier: Modifier,
+    #[serde(default = "default_interval")]
+    pub interval_ms: u64,
+}
+
+fn default_interval() -> u64 {
+    DEFAULT_INTERVAL_MS
+}
+
+/// A key as the platform listeners report it.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Key {
+    Modifier(Modifier),
+    Other,
+}
+
+#[derive(Clone, Copy)]
+enum State {
+    Idle,
+    /// First press, at the given time.
+    Pressed(Instant),
+    /// First release, at the given time.
+    Released(Instant),
+    /// The gesture fired; waiting for the key to go up again.
+    Fired,
+}
+
+struct Detector {
+    modifier: Modifier,
+    interval: Duration,
+    state: State,
+}
+
+impl Detector {
+    fn new(trigger: DoubleTap) -> Self {
+        Detector {
+            modifier: trigger.modifier.resolve(),
+            interval: Duration::from_millis(trigger.interval_ms),
+            state: State::Idle,
+        }
+    }
+
+    /// Advance the gesture with a key going down (or repeating) or up.
+    /// Returns true when it completes.
+    fn feed(&mut self, key: Key, down: bool, now: Instant) -> bool {
+        if key != Key::Modifier(self.modifier) {
+            // Ctrl+C, or another modifier joining in, is not a tap.
+            if down {
+                self.state = State::Idle;
+            }
+            return false;
+        }
+        let within = |at: Instant| now.duration_since(at) <= self.interval;
+        let (state, fired) = match (self.state, down) {
+            // Auto-repeat of a held key.
+            (State::Pressed(at), true) => (State::Pressed(at), false),
+            (State::Fired, true) => (State::Fired, false),
+            (State::Released(at), true) if within(at) => (State::Fired, true),
+            (_, true) => (State::Pressed(now), false),
+            // Held too long to count as a tap.
+            (State::Pressed(at), false) if within(at) => (State::Released(now), false),
+            (_, false) => (State::Idle, false),
+        };
+        self.state = state;
+        fired
+    }
+}
+
+/// The quick switcher's double-tap trigger, if set.
+pub fn switcher_trigger(app: &AppHandle) -> Option<DoubleTap> {
+    settings::load(app, SWITCHER_KEY)
+}
+
+/// Set or clear (`None`) the quick switcher's double-tap trigger. Fails
+/// without saving when key events cannot be read, e.g. for lack of a
+/// permission.
+pub fn set_switcher_trigger(app: &AppHandle, trigger: Option<DoubleTap>) -> Result<(), String> {
+    let Some(mut trigger) = trigger else {
+        imp::stop();
+        DETECTOR.lock().unwrap().take();
+        return settings::remove(app, SWITCHER_KEY);
+    };
+    trigger.interval_ms = trigger.interval_ms.clamp(MIN_INTERVAL_MS, MAX_INTERVAL_MS);
+    start(app, trigger)?;
+    settings::save(app, SWITCHER_KEY, &trigger)
+}
+
+/// Start listening at launch if a trigger is set.
+pub fn restore(app: &AppHandle) {
+    if let Some(trigger) = switcher_trigger(app) {
+        if let Err(e) = start(app, trigger) {
+            log::warn!("[nchat-desktop] double-tap trigger unavailable: {e}");
+        }
+    }
+}
+
+fn start(app: &AppHandle, trigger: DoubleTap) -> Result<(), String> {
+    let _ = APP.set(app.clone());
+    imp::start()?;
+    *DETECTOR.lock().unwrap() = Some(Detector::new(trigger));
+    Ok(())
+}
+
+/// Called by the platform listeners for every key event. Must return
+/// quickly; on Windows it runs inside the keyboard hook.
+fn on_key(key: Key, down: bool) {
+    let fired = DETECTOR
+        .lock()
+        .unwrap()
+        .as_mut()
+        .is_some_and(|detector| detector.feed(key, down, Instant::now()));
+    // A shortcut being recorded or kiosk mode take precedence, like for the
+    // switcher's global shortcut.
+    if !fired || crate::kiosk::is_active() || crate::shortcuts::is_capturing() {
+        return;
+    }
+    if let Some(app) = APP.get() {
+        let handle = app.clone();
+        let _ = app.run_on_main_thread(move || crate::switcher::toggle(&handle));
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod imp {
+    use std::ffi::c_void;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    use super::{on_key, Key, Modifier};
+
+    type TapCallback = extern "C" fn(*mut c_void, u32, *mut c_void, *mut c_void) -> *mut c_void;
+
+    #[link(name = "CoreGraphics", kind = "framework")]
+    extern "C" {
+        fn CGEventTapCreate(
+            tap: u32,
+            place: u32,
+            options: u32,
+            events_of_interest: u64,
+            callback: TapCallback,
+            user_info: *mut c_void,
+        ) -> *mut c_void;
+        fn CGEventTapEnable(tap: *mut c_void, enable: bool);
+        fn CGEventGetIntegerValueField(event: *mut c_void, field: u32) -> i64;
+        fn CGEventGetFlags(event: *mut c_void) -> u64;
+        fn CGRequestListenEventAccess() -> bool;
+    }
+
+    #[link(name = "CoreFoundation", kind = "framework")]
+    extern "C" {
+        static kCFRunLoopCommonModes: *const c_void;
+        fn CFMachPortCreateRunLoopSource(
+            allocator: *const c_void,
+            port: *mut c_void,
+            order: isize,
+        ) -> *mut c_void;
+        fn CFMachPortInvalidate(port: *mut c_void);
+        fn CFRunLoopGetCurrent() -> *mut c_void;
+        fn CFRunLoopAddSource(run_loop: *mut c_void, source: *mut c_void, mode: *const c_void);
+        fn CFRunLoopRun();
+        fn CFRunLoopStop(run_loop: *mut c_void);
+        fn CFRelease(object: *const c_void);
+    }
+
+    const SESSION_EVENT_TAP: u32 = 1;
+    const HEAD_INSERT_EVENT_TAP: u32 = 0;
+    const TAP_OPTION_LISTEN_ONLY: u32 = 1;
+
+    const KEY_DOWN: u32 = 10;
+    const FLAGS_CHANGED: u32 = 12;
+    const TAP_DISABLED_BY_TIMEOUT: u32 = 0xFFFF_FFFE;
+    const TAP_DISABLED_BY_USER_INPUT: u32 = 0xFFFF_FFFF;
+
+    const KEYCODE_FIELD: u32 = 9;
+
+    /// Modifier key codes with the event flag that is set while they are
+    /// down.
+    const MODIFIER_KEYS: &[(i64, Modifier, u64)] = &[
+        (0x3B, Modifier::Ctrl, 0x0004_0000),
+        (0x3E, Modifier::Ctrl, 0x0004_0000),
+        (0x37, Modifier::Cmd, 0x0010_0000),
+        (0x36, Modifier::Cmd, 0x0010_0000),
+        (0x3A, Modifier::Alt, 0x0008_0000),
+        (0x3D, Modifier::Alt, 0x0008_0000),
+        (0x38, Modifier::Shift, 0x0002_0000),
+        (0x3C, Modifier::Shift, 0x0002_0000),
+    ];
+
+    /// The tap, to re-enable it when macOS turns it off, and the run loop
+    /// serving it, to stop it. Raw pointers so they can live in statics.
+    static TAP: AtomicUsize = AtomicUsize::new(0);
+    static RUN_LOOP: Mutex<Option<usize>> = Mutex::new(None);
+
+    extern "C" fn on_event(
+        _proxy: *mut c_void,
+        event_type: u32,
+        event: *mut c_void,
+        _user_info: *mut c_void,
+    ) -> *mut c_void {
+        match event_type {
+            KEY_DOWN => on_key(Key::Other, true),
+            FLAGS_CHANGED => {
+                let (code, flags) = unsafe {
+                    (
+                        CGEventGetIntegerValueField(event, KEYCODE_FIELD),
+                        CGEventGetFlags(event),
+                    )
+                };
+                if let Some((_, modifier, mask)) = MODIFIER_KEYS.iter().find(|(c, ..)| *c == code) {
+                    on_key(Key::Modifier(*modifier), flags & mask != 0);
+                }
+            }
+            // A slow callback or secure input turned the tap off.
+            TAP_DISABLED_BY_TIMEOUT | TAP_DISABLED_BY_USER_INPUT => {
+                let tap = TAP.load(Ordering::SeqCst);
+                if tap != 0 {
+                    unsafe { CGEventTapEnable(tap as *mut c_void, true) };
+                }
+            }
+            _ => {}
+        }
+        event
+    }
+
+    pub fn start() -> Result<(), String> {
+        let mut run_loop = RUN_LOOP.lock().unwrap();
+        if run_loop.is_some() {
+            return Ok(());
+        }
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || unsafe {
+            // Shows the Input Monitoring prompt the first time.
+            CGRequestListenEventAccess();
+            let mask = (1u64 << KEY_DOWN) | (1u64 << FLAGS_CHANGED);
+            let tap = CGEventTapCreate(
+                SESSION_EVENT_TAP,
+                HEAD_INSERT_EVENT_TAP,
+                TAP_OPTION_LISTEN_ONLY,
+                mask,
+                on_event,
+                std::ptr::null_mut(),
+            );
+            if tap.is_null() {
+                let _ = tx.send(Err(
+                    "nChat needs the Input Monitoring permission (System Settings → Privacy & \
+                     Security)"
+                        .to_string(),
+                ));
+                return;
+            }
+            TAP.store(tap as usize, Ordering::SeqCst);
+            let source = CFMachPortCreateRunLoopSource(std::ptr::null(), tap, 0);
+            let current = CFRunLoopGetCurrent();
+            CFRunLoopAddSource(current, source, kCFRunLoopCommonModes);
+            CGEventTapEnable(tap, true);
+            let _ = tx.send(Ok(current as usize));
+            // Runs until `stop`.
+            CFRunLoopRun();
+            TAP.store(0, Ordering::SeqCst);
+            CFMachPortInvalidate(tap);
+            CFRelease(source);
+            CFRelease(tap);
+        });
+        let current = rx.recv().map_err(|e| e.to_string())??;
+        *run_loop = Some(current);
+        Ok(())
+    }
+
+    pub fn stop() {
+        if let Some(run_loop) = RUN_LOOP.lock().unwrap().take() {
+            unsafe { CFRunLoopStop(run_loop as *mut c_void) };
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod imp {
+    use std::sync::Mutex;
+    use windows::Win32::Foundation::{LPARAM, LRESULT, WPARAM};
+    use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+    use windows::Win32::System::Threading::GetCurrentThreadId;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        CallNextHookEx, GetMessageW, PostThreadMessageW, SetWindowsHookExW, UnhookWindowsHookEx,
+        KBDLLHOOKSTRUCT, MSG, WH_KEYBOARD_LL, WM_KEYDOWN, WM_QUIT, WM_SYSKEYDOWN,
+    };
+
+    use super::{on_key, Key, Modifier};
+
+    /// Virtual-key codes of the modifiers, left and right.
+    const MODIFIER_KEYS: &[(u32, Modifier)] = &[
+        (0xA2, Modifier::Ctrl),
+        (0xA3, Modifier::Ctrl),
+        (0x5B, Modifier::Cmd),
+        (0x5C, Modifier::Cmd),
+        (0xA4, Modifier::Alt),
+        (0xA5, Modifier::Alt),
+        (0xA0, Modifier::Shift),
+        (0xA1, Modifier::Shift),
+    ];
+
+    /// Thread running the hook's message loop.
+    static THREAD: Mutex<Option<u32>> = Mutex::new(None);
+
+    unsafe extern "system" fn on_event(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+        if code >= 0 {
+            let info = &*(lparam.0 as *const KBDLLHOOKSTRUCT);
+            let down = matches!(wparam.0 as u32, WM_KEYDOWN | WM_SYSKEYDOWN);
+            let key = MODIFIER_KEYS
+                .iter()
+                .find(|(vk, _)| *vk == info.vkCode)
+                .map_or(Key::Other, |(_, modifier)| Key::Modifier(*modifier));
+            on_key(key, down);
+        }
+        CallNextHookEx(None, code, wparam, lparam)
+    }
+
+    pub fn start() -> Result<(), String> {
+        let mut thread = THREAD.lock().unwrap();
+        if thread.is_some() {
+            return Ok(());
+        }
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || unsafe {
+            let module = GetModuleHandleW(None).ok().map(Into::into);
+            let hook = match SetWindowsHookExW(WH_KEYBOARD_LL, Some(on_event), module, 0) {
+                Ok(hook) => hook,
+                Err(e) => {
+                    let _ = tx.send(Err(e.to_string()));
+                    return;
+                }
+            };
+            let _ = tx.send(Ok(GetCurrentThreadId()));
+            // The hook is called from this loop; `stop` ends it.
+            let mut msg = MSG::default();
+            while GetMessageW(&mut msg, None, 0, 0).0 > 0 {}
+            let _ = UnhookWindowsHookEx(hook);
+        });
+        let id = rx.recv().map_err(|e| e.to_string())??;
+        *thread = Some(id);
+        Ok(())
+    }
+
+    pub fn stop() {
+        if let Some(id) = THREAD.lock().unwrap().take() {
+            unsafe {
+                let _ = PostThreadMessageW(id, WM_QUIT, WPARAM(0), LPARAM(0));
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod imp {
+    use std::error::Error;
+    use std::sync::{Arc, Mutex};
+    use x11rb::connection::Connection;
+    use x11rb::protocol::xinput::{self, ConnectionExt as _};
+    use x11rb::protocol::xproto::{
+        AtomEnum, ClientMessageEvent, ConnectionExt as _, CreateWindowAux, EventMask, Window,
+        WindowClass,
+    };
+    use x11rb::protocol::Event;
+    use x11rb::rust_connection::RustConnection;
+    use x11rb::COPY_DEPTH_FROM_PARENT;
+
+    use super::{on_key, Key, Modifier};
+
+    /// X key codes of the modifiers (evdev codes + 8), left and right.
+    const MODIFIER_KEYS: &[(u32, Modifier)] = &[
+        (37, Modifier::Ctrl),
+        (105, Modifier::Ctrl),
+        (133, Modifier::Cmd),
+        (134, Modifier::Cmd),
+        (64, Modifier::Alt),
+        (108, Modifier::Alt),
+        (50, Modifier::Shift),
+        (62, Modifier::Shift),
+    ];
+
+    /// The listener's connection, and the hidden window `stop` wakes it
+    /// through.
+    static LISTENER: Mutex<Option<(Arc<RustConnection>, Window)>> = Mutex::new(None);
+
+    /// Connect and select raw key events on the root window; they report
+    /// keys wherever the focus is, without reading the devices.
+    fn listen() -> Result<(RustConnection, Window), Box<dyn Error>> {
+        let (conn, screen) = x11rb::connect(None)?;
+        let root = conn.setup().roots[screen].root;
+        conn.xinput_xi_query_version(2, 0)?.reply()?;
+        let mask = xinput::XIEventMask::RAW_KEY_PRESS | xinput::XIEventMask::RAW_KEY_RELEASE;
+        conn.xinput_xi_select_events(
+            root,
+            &[xinput::EventMask {
+                deviceid: xinput::Device::ALL_MASTER.into(),
+                mask: vec![mask],
+            }],
+        )?
+        .check()?;
+        let wake = conn.generate_id()?;
+        conn.create_window(
+            COPY_DEPTH_FROM_PARENT,
+            wake,
+            root,
+            0,
+            0,
+            1,
+            1,
+            0,
+            WindowClass::INPUT_ONLY,
+            0,
+            &CreateWindowAux::new(),
+        )?
+        .check()?;
+        Ok((conn, wake))
+    }
+
+    fn read_events(conn: &RustConnection, wake: Window) {
+        loop {
+            let (code, down) = match conn.wait_for_event() {
+                Ok(Event::XinputRawKeyPress(event)) => (event.detail, true),
+                Ok(Event::XinputRawKeyRelease(event)) => (event.detail, false),
+                Ok(Event::ClientMessage(event)) if event.window == wake => break,
+                Ok(_) => continue,
+                Err(_) => break,
+            };
+            let key = MODIFIER_KEYS
+                .iter()
+                .find(|(c, _)| *c == code)
+                .map_or(Key::Other, |(_, modifier)| Key::Modifier(*modifier));
+            on_key(key, down);
+        }
+        let _ = conn.destroy_window(wake);
+        let _ = conn.flush();
+    }
+
+    pub fn start() -> Result<(), String> {
+        let mut listener = LISTENER.lock().unwrap();
+        if listener.is_some() {
+            return Ok(());
+        }
+        if std::env::var("XDG_SESSION_TYPE").is_ok_and(|session| session == "wayland") {
+            return Err(
+                "double-tap triggers need an X11 session; Wayland does not let apps watch \
+                 modifier keys"
+                    .into(),
+            );
+        }
+        let (conn, wake) =
+            listen().map_err(|e| format!("cannot watch the keyboard through X11: {e}"))?;
+        let conn = Arc::new(conn);
+        let reader = conn.clone();
+        std::thread::spawn(move || read_events(&reader, wake));
+        *listener = Some((conn, wake));
+        Ok(())
+    }
+
+    pub fn stop() {
+        if let Some((conn, wake)) = LISTENER.lock().unwrap().take() {
+            // Sent with no mask, the event goes to the window's creator:
+            // the listener itself.
+            let event = ClientMessageEvent::new(32, wake, AtomEnum::NONE, [0u32; 5]);
+            let _ = conn.send_event(false, wake, EventMask::NO_EVENT, event);
+            let _ = conn.flush();
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+mod imp {
+    pub fn start() -> Result<(), String> {
+        Err("double-tap triggers are not supported on this platform".into())
+    }
+
+    pub fn stop() {}
+}
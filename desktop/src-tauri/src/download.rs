@@ -0,0 +1,52 @@
+// nChat Desktop — download folders
+//
+// Attachments are saved to the system Downloads folder unless the user
+// picked a folder for the conversation they come from. The transfer itself
+// is `nchat_core::download::fetch`.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+use crate::state::settings;
+
+pub use nchat_core::download::fetch;
+
+/// Settings key mapping conversation ids to download folders.
+const CHANNEL_DIRS_KEY: &str = "downloads.channelDirs";
+
+fn channel_dirs(app: &AppHandle) -> HashMap<String, String> {
+    settings::load(app, CHANNEL_DIRS_KEY).unwrap_or_default()
+}
+
+/// Send a conversation's downloads to `dir`, or back to the default with
+/// `None`. The folder must exist.
+pub fn set_channel_dir(app: &AppHandle, channel_id: &str, dir: Option<&str>) -> Result<(), String> {
+    let mut dirs = channel_dirs(app);
+    match dir {
+        Some(dir) => {
+            let path = Path::new(dir);
+            if !path.is_absolute() || !path.is_dir() {
+                return Err(format!("not an existing folder: {dir}"));
+            }
+            dirs.insert(channel_id.to_string(), dir.to_string());
+        }
+        None => {
+            dirs.remove(channel_id);
+        }
+    }
+    settings::save(app, CHANNEL_DIRS_KEY, &dirs)
+}
+
+/// Folder for a download from `channel_id`: the conversation's override
+/// while that folder still exists, otherwise the system Downloads folder.
+pub fn target_dir(app: &AppHandle, channel_id: Option<&str>) -> Result<PathBuf, String> {
+    let custom = channel_id
+        .and_then(|id| channel_dirs(app).remove(id))
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_dir());
+    match custom {
+        Some(dir) => Ok(dir),
+        None => app.path().download_dir().map_err(|e| e.to_string()),
+    }
+}
@@ -0,0 +1,131 @@
+// nChat Desktop — native window background effects
+//
+// Lets the frosted sidebar design show the desktop through the main window:
+// NSVisualEffectView materials on macOS, Mica and acrylic on Windows. An
+// effect needs a transparent webview, and transparency can only be chosen
+// when the window is created, so turning effects on from a launch without
+// them takes effect after a restart. Linux has no compositor-independent
+// equivalent and keeps the opaque background.
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::utils::config::WindowEffectsConfig;
+use tauri::window::{Effect, EffectState, EffectsBuilder};
+use tauri::{AppHandle, Emitter, Manager, WebviewWindow};
+
+use crate::state::settings;
+use crate::windows::MAIN_WINDOW;
+
+/// Settings key holding the chosen background effect.
+const EFFECT_KEY: &str = "window.effect";
+
+/// Set when the main window was created with a transparent background.
+static TRANSPARENT_AT_LAUNCH: AtomicBool = AtomicBool::new(false);
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum BackgroundEffect {
+    /// Opaque background.
+    #[default]
+    None,
+    /// The platform's own look: the sidebar material on macOS, Mica on
+    /// Windows (Windows 11 only; Windows 10 needs `Acrylic`).
+    Auto,
+    /// macOS materials.
+    Sidebar,
+    UnderWindow,
+    HudWindow,
+    /// Windows 11.
+    Mica,
+    /// Windows 10 and 11.
+    Acrylic,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectStatus {
+    pub effect: BackgroundEffect,
+    /// The main window was created opaque; the effect shows after a restart.
+    pub restart_required: bool,
+}
+
+/// The native effect for `effect` on this platform, if it has one. Tauri
+/// only applies the first effect it is given, so there is no fallback list.
+fn platform_effect(effect: BackgroundEffect) -> Option<Effect> {
+    use BackgroundEffect as B;
+    if cfg!(target_os = "macos") {
+        match effect {
+            B::Auto | B::Sidebar => Some(Effect::Sidebar),
+            B::UnderWindow => Some(Effect::UnderWindowBackground),
+            B::HudWindow => Some(Effect::HudWindow),
+            _ => None,
+        }
+    } else if cfg!(target_os = "windows") {
+        match effect {
+            B::Auto | B::Mica => Some(Effect::Mica),
+            B::Acrylic => Some(Effect::Acrylic),
+            _ => None,
+        }
+    } else {
+        None
+    }
+}
+
+pub fn effect(app: &AppHandle) -> BackgroundEffect {
+    settings::load(app, EFFECT_KEY).unwrap_or_default()
+}
+
+pub fn status(app: &AppHandle) -> EffectStatus {
+    let effect = effect(app);
+    EffectStatus {
+        effect,
+        restart_required: effect != BackgroundEffect::None
+            && !TRANSPARENT_AT_LAUNCH.load(Ordering::Relaxed),
+    }
+}
+
+/// Whether the main window should be created transparent. Called by
+/// `windows::create_main_window` before the webview exists.
+pub fn wants_transparency(app: &AppHandle) -> bool {
+    let wanted = platform_effect(effect(app)).is_some();
+    TRANSPARENT_AT_LAUNCH.store(wanted, Ordering::Relaxed);
+    wanted
+}
+
+/// Apply the stored effect to the freshly created main window.
+pub fn restore(win: &WebviewWindow) {
+    if let Err(e) = apply(win, effect(win.app_handle())) {
+        log::warn!("[nchat-desktop] failed to apply window effect: {e}");
+    }
+}
+
+fn apply(win: &WebviewWindow, effect: BackgroundEffect) -> Result<(), String> {
+    let Some(effect) = platform_effect(effect) else {
+        return win
+            .set_effects(None::<WindowEffectsConfig>)
+            .map_err(|e| e.to_string());
+    };
+    let config = EffectsBuilder::new()
+        .effect(effect)
+        .state(EffectState::FollowsWindowActiveState)
+        .build();
+    win.set_effects(config).map_err(|e| e.to_string())
+}
+
+/// Choose the main window's background effect. The window gets
+/// `window:effect` with the new status so the UI can switch to its
+/// translucent styles; an opaque window keeps its look until restarted.
+pub fn set_effect(app: &AppHandle, effect: BackgroundEffect) -> Result<EffectStatus, String> {
+    if effect != BackgroundEffect::None && platform_effect(effect).is_none() {
+        return Err(format!("{effect:?} is not available on this platform"));
+    }
+    settings::save(app, EFFECT_KEY, &effect)?;
+    let status = status(app);
+    if let Some(win) = app.get_webview_window(MAIN_WINDOW) {
+        if TRANSPARENT_AT_LAUNCH.load(Ordering::Relaxed) {
+            apply(&win, effect)?;
+        }
+        let _ = win.emit("window:effect", &status);
+    }
+    Ok(status)
+}
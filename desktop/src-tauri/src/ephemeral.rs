@@ -0,0 +1,136 @@
+// nChat Desktop — disappearing-message enforcement
+//
+// Expiry deadlines are persisted so they survive restarts; a single task
+// sleeps until the earliest one, shreds the message's cached attachments
+// and emits `message-expired`. Expirations that happen while no window is
+// open are kept until the frontend collects them. The schedule itself is
+// `nchat_core::jobs`.
+
+use nchat_core::jobs::{self, now_ms};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::Notify;
+
+use crate::state::settings;
+
+const PENDING_KEY: &str = "ephemeral.pending";
+const EXPIRED_KEY: &str = "ephemeral.expired";
+
+pub use nchat_core::jobs::{ExpiredMessage, PendingExpiry};
+
+#[derive(Default)]
+pub struct EphemeralState {
+    wake: Notify,
+    /// Serializes read-modify-write of the persisted lists.
+    lock: Mutex<()>,
+}
+
+/// Only files inside the app's own data/cache directories may be shredded.
+fn allowed_path(app: &AppHandle, path: &Path) -> Option<PathBuf> {
+    let path = path.canonicalize().ok()?;
+    let roots = [app.path().app_data_dir(), app.path().app_cache_dir()];
+    roots
+        .into_iter()
+        .flatten()
+        .filter_map(|root| root.canonicalize().ok())
+        .any(|root| path.starts_with(root))
+        .then_some(path)
+}
+
+/// Overwrite a file with zeros before unlinking it.
+fn shred(path: &Path) -> std::io::Result<()> {
+    let len = std::fs::metadata(path)?.len();
+    let mut file = std::fs::OpenOptions::new().write(true).open(path)?;
+    let zeros = [0u8; 64 * 1024];
+    let mut remaining = len;
+    while remaining > 0 {
+        let n = remaining.min(zeros.len() as u64) as usize;
+        file.write_all(&zeros[..n])?;
+        remaining -= n as u64;
+    }
+    file.sync_all()?;
+    drop(file);
+    std::fs::remove_file(path)
+}
+
+/// Register a deadline for a message. Re-scheduling a message replaces its
+/// previous deadline.
+pub fn schedule(app: &AppHandle, entry: PendingExpiry) -> Result<(), String> {
+    let state = app.state::<EphemeralState>();
+    {
+        let _guard = state.lock.lock().unwrap();
+        let mut pending: Vec<PendingExpiry> = settings::load(app, PENDING_KEY).unwrap_or_default();
+        jobs::schedule_expiry(&mut pending, entry);
+        settings::save(app, PENDING_KEY, &pending)?;
+    }
+    state.wake.notify_one();
+    Ok(())
+}
+
+/// Expired messages the frontend has not collected yet; clears the list.
+pub fn take_expired(app: &AppHandle) -> Result<Vec<ExpiredMessage>, String> {
+    let state = app.state::<EphemeralState>();
+    let _guard = state.lock.lock().unwrap();
+    let expired: Vec<ExpiredMessage> = settings::load(app, EXPIRED_KEY).unwrap_or_default();
+    settings::remove(app, EXPIRED_KEY)?;
+    Ok(expired)
+}
+
+/// Fire every overdue entry and return the next deadline, if any.
+fn expire_due(app: &AppHandle) -> Option<u64> {
+    let state = app.state::<EphemeralState>();
+    let _guard = state.lock.lock().unwrap();
+    let now = now_ms();
+    let pending: Vec<PendingExpiry> = settings::load(app, PENDING_KEY).unwrap_or_default();
+    let (due, rest) = jobs::due_expiries(pending, now);
+    if due.is_empty() {
+        return jobs::next_expiry(&rest);
+    }
+
+    let mut expired: Vec<ExpiredMessage> = settings::load(app, EXPIRED_KEY).unwrap_or_default();
+    for entry in due {
+        for attachment in &entry.attachments {
+            match allowed_path(app, Path::new(attachment)) {
+                Some(path) => {
+                    if let Err(e) = shred(&path) {
+                        log::warn!("[nchat-desktop] failed to shred {}: {e}", path.display());
+                    }
+                }
+                None => {
+                    log::warn!("[nchat-desktop] refusing to shred {attachment}: outside app data")
+                }
+            }
+        }
+        let msg = entry.expired(now);
+        let _ = app.emit("message-expired", msg.clone());
+        expired.push(msg);
+    }
+    let _ = settings::save(app, PENDING_KEY, &rest);
+    let _ = settings::save(app, EXPIRED_KEY, &expired);
+    jobs::next_expiry(&rest)
+}
+
+/// Start the expiry task. Deadlines that passed while the app was closed
+/// fire immediately.
+pub fn start(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let next = expire_due(&app);
+            let state = app.state::<EphemeralState>();
+            match next {
+                Some(deadline) => {
+                    let wait = Duration::from_millis(deadline.saturating_sub(now_ms()));
+                    tokio::select! {
+                        _ = tokio::time::sleep(wait) => {}
+                        _ = state.wake.notified() => {}
+                    }
+                }
+                None => state.wake.notified().await,
+            }
+        }
+    });
+}
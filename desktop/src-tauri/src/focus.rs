@@ -0,0 +1,182 @@
+// nChat Desktop — time-boxed focus sessions
+//
+// A focus session sets the presence status to Do Not Disturb, holds every
+// non-urgent notification and dims the tray icon. When the time is up (or
+// the session is stopped early) the previous status comes back and a single
+// recap notification summarizes what was held. Everything runs natively, so
+// sessions keep working while the window is closed to the tray, and a
+// session that was running when the app quit resumes at the next launch.
+// The rules for sessions and held notifications are
+// `nchat_core::notifications`.
+
+use nchat_core::jobs::now_ms;
+use nchat_core::notifications::{self, FocusSession, HeldNotification};
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::i18n::{t, tf};
+use crate::state::settings;
+use crate::tray;
+use crate::windows::MAIN_WINDOW;
+
+const SESSION_KEY: &str = "focus.session";
+const HELD_KEY: &str = "focus.held";
+
+#[derive(Serialize, Clone)]
+pub struct FocusStatus {
+    pub active: bool,
+    pub ends_at: Option<u64>,
+    pub held: usize,
+}
+
+/// Serializes session changes and the held buffer.
+#[derive(Default)]
+pub struct FocusState {
+    lock: Mutex<()>,
+}
+
+fn session(app: &AppHandle) -> Option<FocusSession> {
+    settings::load(app, SESSION_KEY)
+}
+
+pub fn is_active(app: &AppHandle) -> bool {
+    session(app).is_some()
+}
+
+pub fn status(app: &AppHandle) -> FocusStatus {
+    let session = session(app);
+    let held: Vec<HeldNotification> = settings::load(app, HELD_KEY).unwrap_or_default();
+    FocusStatus {
+        active: session.is_some(),
+        ends_at: session.map(|s| s.ends_at),
+        held: held.len(),
+    }
+}
+
+/// Set the tray and presence status, and tell the frontend so it can
+/// publish the presence change.
+fn apply_status(app: &AppHandle, status: &str) {
+    let _ = tray::set_status(app, status);
+    if let Some(win) = app.get_webview_window(MAIN_WINDOW) {
+        let _ = win.emit("tray:set-status", status.to_string());
+    }
+}
+
+/// End the session when `ends_at` passes. The session is looked up again
+/// on wake-up, so a stopped or replaced session is left alone.
+fn schedule_end(app: &AppHandle, ends_at: u64) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let wait = Duration::from_millis(ends_at.saturating_sub(now_ms()));
+        tokio::time::sleep(wait).await;
+        if session(&app).is_some_and(|s| s.ends_at == ends_at) {
+            if let Err(e) = stop(&app) {
+                log::warn!("[nchat-desktop] failed to end focus session: {e}");
+            }
+        }
+    });
+}
+
+/// Start (or extend) a focus session lasting `minutes`.
+pub fn start(app: &AppHandle, minutes: u32) -> Result<FocusStatus, String> {
+    let state = app.state::<FocusState>();
+    let ends_at = {
+        let _guard = state.lock.lock().unwrap();
+        // Restarting keeps the status from before the first session.
+        let previous_status = match session(app) {
+            Some(running) => running.previous_status,
+            None => {
+                settings::remove(app, HELD_KEY)?;
+                tray::status(app)
+            }
+        };
+        let session = FocusSession::new(now_ms(), minutes, previous_status)?;
+        settings::save(app, SESSION_KEY, &session)?;
+        session.ends_at
+    };
+    apply_status(app, "dnd");
+    tray::apply_icon(app);
+    schedule_end(app, ends_at);
+    if let Some(win) = app.get_webview_window(MAIN_WINDOW) {
+        let _ = win.emit("focus:started", ends_at);
+    }
+    Ok(status(app))
+}
+
+/// Hold a notification if a focus session is running. Returns whether it
+/// was held; the caller shows it otherwise.
+pub fn hold(app: &AppHandle, title: &str, body: Option<&str>) -> bool {
+    let state = app.state::<FocusState>();
+    let _guard = state.lock.lock().unwrap();
+    if !notifications::should_hold(session(app).as_ref(), false) {
+        return false;
+    }
+    let mut held: Vec<HeldNotification> = settings::load(app, HELD_KEY).unwrap_or_default();
+    notifications::hold(
+        &mut held,
+        HeldNotification {
+            title: title.to_string(),
+            body: body.map(str::to_string),
+            held_at: now_ms(),
+        },
+    );
+    let _ = settings::save(app, HELD_KEY, &held);
+    true
+}
+
+fn recap(held: &[HeldNotification]) -> String {
+    if held.is_empty() {
+        return t("focus.recap_none").to_string();
+    }
+    let mut lines = vec![tf("focus.recap", &[("n", &held.len())])];
+    lines.push(notifications::recap_titles(held).join(", "));
+    lines.join("\n")
+}
+
+/// End the running session: restore the previous status and icon, and
+/// deliver the recap. The held notifications go to the frontend with
+/// `focus:ended`.
+pub fn stop(app: &AppHandle) -> Result<FocusStatus, String> {
+    let state = app.state::<FocusState>();
+    let (session, held) = {
+        let _guard = state.lock.lock().unwrap();
+        let Some(session) = session(app) else {
+            return Ok(status(app));
+        };
+        let held: Vec<HeldNotification> = settings::load(app, HELD_KEY).unwrap_or_default();
+        settings::remove(app, SESSION_KEY)?;
+        settings::remove(app, HELD_KEY)?;
+        (session, held)
+    };
+
+    apply_status(app, &session.previous_status);
+    tray::apply_icon(app);
+    let _ = app
+        .notification()
+        .builder()
+        .title(t("focus.ended"))
+        .body(recap(&held))
+        .show();
+    if let Some(win) = app.get_webview_window(MAIN_WINDOW) {
+        let _ = win.emit("focus:ended", held);
+    }
+    Ok(status(app))
+}
+
+/// Resume a session that was running when the app quit; one that expired
+/// meanwhile ends right away with its recap.
+pub fn restore(app: &AppHandle) {
+    let Some(session) = session(app) else {
+        return;
+    };
+    if session.has_ended(now_ms()) {
+        let _ = stop(app);
+        return;
+    }
+    apply_status(app, "dnd");
+    tray::apply_icon(app);
+    schedule_end(app, session.ends_at);
+}
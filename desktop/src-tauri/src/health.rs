@@ -0,0 +1,96 @@
+// nChat Desktop — health snapshot and fleet heartbeat
+
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+use crate::clock;
+use crate::policy;
+use crate::state::settings;
+
+/// Floor for the heartbeat interval so a bad policy can't flood the endpoint.
+const MIN_HEARTBEAT_SECS: u64 = 60;
+
+#[derive(Serialize, Clone)]
+pub struct HealthReport {
+    pub app_version: String,
+    pub update_channel: String,
+    /// Last connection state reported by the frontend (`connected`,
+    /// `connecting`, `offline`, ...).
+    pub connection: String,
+    pub os: &'static str,
+    pub arch: &'static str,
+    pub uptime_secs: u64,
+    /// True when a heartbeat policy is in effect.
+    pub managed: bool,
+}
+
+pub struct HealthState {
+    connection: Mutex<String>,
+    started: Instant,
+}
+
+impl Default for HealthState {
+    fn default() -> Self {
+        Self {
+            connection: Mutex::new("unknown".into()),
+            started: Instant::now(),
+        }
+    }
+}
+
+pub fn set_connection(app: &AppHandle, status: &str) {
+    *app.state::<HealthState>().connection.lock().unwrap() = status.to_string();
+}
+
+pub fn snapshot(app: &AppHandle) -> HealthReport {
+    let state = app.state::<HealthState>();
+    HealthReport {
+        app_version: app.package_info().version.to_string(),
+        update_channel: settings::load(app, "updater.channel")
+            .unwrap_or_else(|| "stable".to_string()),
+        connection: state.connection.lock().unwrap().clone(),
+        os: std::env::consts::OS,
+        arch: std::env::consts::ARCH,
+        uptime_secs: state.started.elapsed().as_secs(),
+        managed: policy::current().heartbeat.is_some(),
+    }
+}
+
+/// Start posting `HealthReport`s to the policy endpoint. No-op unless an
+/// admin policy enables the heartbeat.
+pub fn start_heartbeat(app: &AppHandle) {
+    let Some(heartbeat) = policy::current().heartbeat.clone() else {
+        return;
+    };
+    if !heartbeat.endpoint.starts_with("https://") {
+        log::warn!(
+            "[nchat-desktop] heartbeat disabled: endpoint must use https ({})",
+            heartbeat.endpoint
+        );
+        return;
+    }
+
+    let app = app.clone();
+    let interval = Duration::from_secs(heartbeat.interval_secs.max(MIN_HEARTBEAT_SECS));
+    tauri::async_runtime::spawn(async move {
+        let client = reqwest::Client::new();
+        loop {
+            let report = snapshot(&app);
+            let result = client
+                .post(&heartbeat.endpoint)
+                .json(&report)
+                .send()
+                .await
+                .inspect(|res| {
+                    clock::observe(&app, res);
+                })
+                .and_then(|res| res.error_for_status());
+            if let Err(e) = result {
+                log::warn!("[nchat-desktop] heartbeat failed: {e}");
+            }
+            tokio::time::sleep(interval).await;
+        }
+    });
+}
@@ -0,0 +1,693 @@
+// nChat Desktop — translations for native chrome (menus, tray, notifications)
+//
+// The web UI owns its own translations; these tables only cover strings
+// the native layer renders itself. Missing languages and keys fall back to
+// English.
+
+use std::sync::RwLock;
+use tauri::AppHandle;
+
+use crate::state::settings;
+
+/// Settings key holding the language picked in the web UI.
+pub const LOCALE_KEY: &str = "native.locale";
+
+const EN: &[(&str, &str)] = &[
+    ("menu.file", "File"),
+    ("menu.new_conversation", "New Conversation"),
+    ("menu.preferences", "Preferences…"),
+    ("menu.quit", "Quit nChat"),
+    ("menu.edit", "Edit"),
+    ("menu.undo", "Undo"),
+    ("menu.redo", "Redo"),
+    ("menu.cut", "Cut"),
+    ("menu.copy", "Copy"),
+    ("menu.paste", "Paste"),
+    ("menu.select_all", "Select All"),
+    ("menu.find", "Find…"),
+    ("menu.spelling", "Spelling"),
+    ("menu.language_settings", "Language Settings…"),
+    ("menu.check_spelling", "Check Spelling While Typing"),
+    ("menu.dictation", "Dictate Message"),
+    ("menu.view", "View"),
+    ("menu.show_sidebar", "Show Sidebar"),
+    ("menu.compact_mode", "Compact Mode"),
+    ("menu.mute_notifications", "Mute Notifications"),
+    ("menu.fullscreen", "Toggle Full Screen"),
+    ("menu.always_on_top", "Always on Top"),
+    ("menu.actual_size", "Actual Size"),
+    ("menu.zoom_in", "Zoom In"),
+    ("menu.zoom_out", "Zoom Out"),
+    ("menu.go", "Go"),
+    ("menu.jump_to", "Jump to…"),
+    ("menu.recent_channels", "Recent Channels"),
+    ("menu.no_recent_channels", "No Recent Channels"),
+    ("menu.window", "Window"),
+    ("menu.minimize", "Minimize"),
+    ("menu.zoom", "Zoom"),
+    ("menu.bring_all_to_front", "Bring All to Front"),
+    ("menu.help", "Help"),
+    ("menu.about", "About nChat"),
+    ("menu.report_problem", "Report a Problem…"),
+    ("menu.collect_diagnostics", "Collect Diagnostics"),
+    ("menu.rollback_update", "Roll Back to Previous Version…"),
+    ("tray.show", "Show nChat"),
+    ("tray.status.online", "Online"),
+    ("tray.status.away", "Away"),
+    ("tray.status.dnd", "Do Not Disturb"),
+    ("tray.status.invisible", "Invisible"),
+    ("tray.unread_one", "nChat — 1 unread message"),
+    ("tray.unread_many", "nChat — {n} unread messages"),
+    ("tray.connection", "Connection: {state}"),
+    ("tray.debug", "Remote debugging on port {port}"),
+    (
+        "tray.unavailable",
+        "Your desktop has no system tray, so closing the window will quit nChat.",
+    ),
+    ("digest.title_morning", "Good morning — here's what you missed"),
+    ("digest.title_afternoon", "Good afternoon — here's what you missed"),
+    ("digest.title_evening", "Good evening — here's what you missed"),
+    (
+        "digest.counts",
+        "{unread} unread · {mentions} mention(s) · {dms} direct message(s)",
+    ),
+    ("digest.open", "Click to open your inbox."),
+    (
+        "session.revoked",
+        "Your session was signed out from another device. Sign in again to continue.",
+    ),
+    ("focus.ended", "Focus session ended"),
+    (
+        "focus.recap",
+        "{n} notification(s) arrived while you were focusing.",
+    ),
+    (
+        "focus.recap_none",
+        "Nothing arrived while you were focusing.",
+    ),
+    ("call.mute", "Mute"),
+    ("call.unmute", "Unmute"),
+    ("call.hang_up", "Hang Up"),
+    ("call.share_screen", "Share Screen"),
+    ("call.stop_sharing", "Stop Sharing"),
+    ("jumplist.new_message", "New Message"),
+    ("jumplist.set_status", "Set Status"),
+    ("jumplist.recent", "Recent Conversations"),
+    ("close.prompt_title", "Close nChat?"),
+    (
+        "close.prompt",
+        "Keep nChat running in the tray so you still get messages, or quit it? You can change this later in Preferences.",
+    ),
+    ("close.keep_running", "Keep Running"),
+    ("close.quit", "Quit"),
+];
+
+const DE: &[(&str, &str)] = &[
+    ("menu.file", "Datei"),
+    ("menu.new_conversation", "Neue Unterhaltung"),
+    ("menu.preferences", "Einstellungen…"),
+    ("menu.quit", "nChat beenden"),
+    ("menu.edit", "Bearbeiten"),
+    ("menu.undo", "Widerrufen"),
+    ("menu.redo", "Wiederholen"),
+    ("menu.cut", "Ausschneiden"),
+    ("menu.copy", "Kopieren"),
+    ("menu.paste", "Einsetzen"),
+    ("menu.select_all", "Alles auswählen"),
+    ("menu.find", "Suchen…"),
+    ("menu.spelling", "Rechtschreibung"),
+    ("menu.language_settings", "Spracheinstellungen…"),
+    (
+        "menu.check_spelling",
+        "Rechtschreibung während der Eingabe prüfen",
+    ),
+    ("menu.dictation", "Nachricht diktieren"),
+    ("menu.view", "Darstellung"),
+    ("menu.show_sidebar", "Seitenleiste anzeigen"),
+    ("menu.compact_mode", "Kompaktmodus"),
+    ("menu.mute_notifications", "Mitteilungen stummschalten"),
+    ("menu.fullscreen", "Vollbildmodus"),
+    ("menu.always_on_top", "Immer im Vordergrund"),
+    ("menu.actual_size", "Originalgröße"),
+    ("menu.zoom_in", "Vergrößern"),
+    ("menu.zoom_out", "Verkleinern"),
+    ("menu.go", "Gehe zu"),
+    ("menu.jump_to", "Springen zu…"),
+    ("menu.recent_channels", "Letzte Kanäle"),
+    ("menu.no_recent_channels", "Keine letzten Kanäle"),
+    ("menu.window", "Fenster"),
+    ("menu.minimize", "Minimieren"),
+    ("menu.zoom", "Zoomen"),
+    ("menu.bring_all_to_front", "Alle nach vorne bringen"),
+    ("menu.help", "Hilfe"),
+    ("menu.about", "Über nChat"),
+    ("menu.report_problem", "Problem melden…"),
+    ("menu.collect_diagnostics", "Diagnosedaten sammeln"),
+    ("menu.rollback_update", "Auf vorherige Version zurücksetzen…"),
+    ("tray.show", "nChat anzeigen"),
+    ("tray.status.online", "Online"),
+    ("tray.status.away", "Abwesend"),
+    ("tray.status.dnd", "Nicht stören"),
+    ("tray.status.invisible", "Unsichtbar"),
+    ("tray.unread_one", "nChat — 1 ungelesene Nachricht"),
+    ("tray.unread_many", "nChat — {n} ungelesene Nachrichten"),
+    ("tray.connection", "Verbindung: {state}"),
+    ("tray.debug", "Remote-Debugging auf Port {port}"),
+    (
+        "tray.unavailable",
+        "Dein Desktop hat keinen Infobereich, daher beendet das Schließen des Fensters nChat.",
+    ),
+    ("digest.title_morning", "Guten Morgen – das hast du verpasst"),
+    ("digest.title_afternoon", "Guten Tag – das hast du verpasst"),
+    ("digest.title_evening", "Guten Abend – das hast du verpasst"),
+    (
+        "digest.counts",
+        "{unread} ungelesen · {mentions} Erwähnung(en) · {dms} Direktnachricht(en)",
+    ),
+    ("digest.open", "Klicke, um deinen Posteingang zu öffnen."),
+    (
+        "session.revoked",
+        "Deine Sitzung wurde auf einem anderen Gerät abgemeldet. Melde dich erneut an, um fortzufahren.",
+    ),
+    ("focus.ended", "Fokuszeit beendet"),
+    ("focus.recap", "{n} Benachrichtigung(en) kamen während deiner Fokuszeit an."),
+    ("focus.recap_none", "Während deiner Fokuszeit kam nichts an."),
+    ("call.mute", "Stummschalten"),
+    ("call.unmute", "Stummschaltung aufheben"),
+    ("call.hang_up", "Auflegen"),
+    ("call.share_screen", "Bildschirm teilen"),
+    ("call.stop_sharing", "Teilen beenden"),
+    ("jumplist.new_message", "Neue Nachricht"),
+    ("jumplist.set_status", "Status festlegen"),
+    ("jumplist.recent", "Letzte Unterhaltungen"),
+    ("close.prompt_title", "nChat schließen?"),
+    (
+        "close.prompt",
+        "nChat im Infobereich weiterlaufen lassen, damit Nachrichten weiter ankommen, oder beenden? Das lässt sich später in den Einstellungen ändern.",
+    ),
+    ("close.keep_running", "Weiterlaufen lassen"),
+    ("close.quit", "Beenden"),
+];
+
+const ES: &[(&str, &str)] = &[
+    ("menu.file", "Archivo"),
+    ("menu.new_conversation", "Nueva conversación"),
+    ("menu.preferences", "Preferencias…"),
+    ("menu.quit", "Salir de nChat"),
+    ("menu.edit", "Edición"),
+    ("menu.undo", "Deshacer"),
+    ("menu.redo", "Rehacer"),
+    ("menu.cut", "Cortar"),
+    ("menu.copy", "Copiar"),
+    ("menu.paste", "Pegar"),
+    ("menu.select_all", "Seleccionar todo"),
+    ("menu.find", "Buscar…"),
+    ("menu.spelling", "Ortografía"),
+    ("menu.language_settings", "Configuración de idioma…"),
+    ("menu.check_spelling", "Revisar ortografía mientras escribe"),
+    ("menu.dictation", "Dictar mensaje"),
+    ("menu.view", "Ver"),
+    ("menu.show_sidebar", "Mostrar barra lateral"),
+    ("menu.compact_mode", "Modo compacto"),
+    ("menu.mute_notifications", "Silenciar notificaciones"),
+    ("menu.fullscreen", "Pantalla completa"),
+    ("menu.always_on_top", "Siempre visible"),
+    ("menu.actual_size", "Tamaño real"),
+    ("menu.zoom_in", "Acercar"),
+    ("menu.zoom_out", "Alejar"),
+    ("menu.go", "Ir"),
+    ("menu.jump_to", "Ir a…"),
+    ("menu.recent_channels", "Canales recientes"),
+    ("menu.no_recent_channels", "No hay canales recientes"),
+    ("menu.window", "Ventana"),
+    ("menu.minimize", "Minimizar"),
+    ("menu.zoom", "Zoom"),
+    ("menu.bring_all_to_front", "Traer todo al frente"),
+    ("menu.help", "Ayuda"),
+    ("menu.about", "Acerca de nChat"),
+    ("menu.report_problem", "Informar de un problema…"),
+    ("menu.collect_diagnostics", "Recopilar diagnósticos"),
+    ("menu.rollback_update", "Volver a la versión anterior…"),
+    ("tray.show", "Mostrar nChat"),
+    ("tray.status.online", "En línea"),
+    ("tray.status.away", "Ausente"),
+    ("tray.status.dnd", "No molestar"),
+    ("tray.status.invisible", "Invisible"),
+    ("tray.unread_one", "nChat — 1 mensaje sin leer"),
+    ("tray.unread_many", "nChat — {n} mensajes sin leer"),
+    ("tray.connection", "Conexión: {state}"),
+    ("tray.debug", "Depuración remota en el puerto {port}"),
+    (
+        "tray.unavailable",
+        "Tu escritorio no tiene bandeja del sistema, así que cerrar la ventana cerrará nChat.",
+    ),
+    ("digest.title_morning", "Buenos días: esto es lo que te perdiste"),
+    ("digest.title_afternoon", "Buenas tardes: esto es lo que te perdiste"),
+    ("digest.title_evening", "Buenas noches: esto es lo que te perdiste"),
+    (
+        "digest.counts",
+        "{unread} sin leer · {mentions} mención(es) · {dms} mensaje(s) directo(s)",
+    ),
+    ("digest.open", "Haz clic para abrir tu bandeja de entrada."),
+    (
+        "session.revoked",
+        "Tu sesión se cerró desde otro dispositivo. Inicia sesión de nuevo para continuar.",
+    ),
+    ("focus.ended", "Sesión de concentración terminada"),
+    (
+        "focus.recap",
+        "Llegaron {n} notificación(es) mientras te concentrabas.",
+    ),
+    (
+        "focus.recap_none",
+        "No llegó nada mientras te concentrabas.",
+    ),
+    ("call.mute", "Silenciar"),
+    ("call.unmute", "Activar micrófono"),
+    ("call.hang_up", "Colgar"),
+    ("call.share_screen", "Compartir pantalla"),
+    ("call.stop_sharing", "Dejar de compartir"),
+    ("jumplist.new_message", "Nuevo mensaje"),
+    ("jumplist.set_status", "Establecer estado"),
+    ("jumplist.recent", "Conversaciones recientes"),
+    ("close.prompt_title", "¿Cerrar nChat?"),
+    (
+        "close.prompt",
+        "¿Mantener nChat en la bandeja para seguir recibiendo mensajes o salir? Puedes cambiarlo más tarde en Preferencias.",
+    ),
+    ("close.keep_running", "Mantener abierto"),
+    ("close.quit", "Salir"),
+];
+
+const FR: &[(&str, &str)] = &[
+    ("menu.file", "Fichier"),
+    ("menu.new_conversation", "Nouvelle conversation"),
+    ("menu.preferences", "Préférences…"),
+    ("menu.quit", "Quitter nChat"),
+    ("menu.edit", "Édition"),
+    ("menu.undo", "Annuler"),
+    ("menu.redo", "Rétablir"),
+    ("menu.cut", "Couper"),
+    ("menu.copy", "Copier"),
+    ("menu.paste", "Coller"),
+    ("menu.select_all", "Tout sélectionner"),
+    ("menu.find", "Rechercher…"),
+    ("menu.spelling", "Orthographe"),
+    ("menu.language_settings", "Paramètres de langue…"),
+    (
+        "menu.check_spelling",
+        "Vérifier l’orthographe lors de la saisie",
+    ),
+    ("menu.dictation", "Dicter un message"),
+    ("menu.view", "Présentation"),
+    ("menu.show_sidebar", "Afficher la barre latérale"),
+    ("menu.compact_mode", "Mode compact"),
+    ("menu.mute_notifications", "Désactiver les notifications"),
+    ("menu.fullscreen", "Plein écran"),
+    ("menu.always_on_top", "Toujours au premier plan"),
+    ("menu.actual_size", "Taille réelle"),
+    ("menu.zoom_in", "Zoom avant"),
+    ("menu.zoom_out", "Zoom arrière"),
+    ("menu.go", "Aller"),
+    ("menu.jump_to", "Aller à…"),
+    ("menu.recent_channels", "Canaux récents"),
+    ("menu.no_recent_channels", "Aucun canal récent"),
+    ("menu.window", "Fenêtre"),
+    ("menu.minimize", "Réduire"),
+    ("menu.zoom", "Zoom"),
+    ("menu.bring_all_to_front", "Tout ramener au premier plan"),
+    ("menu.help", "Aide"),
+    ("menu.about", "À propos de nChat"),
+    ("menu.report_problem", "Signaler un problème…"),
+    ("menu.collect_diagnostics", "Collecter les diagnostics"),
+    ("menu.rollback_update", "Revenir à la version précédente…"),
+    ("tray.show", "Afficher nChat"),
+    ("tray.status.online", "En ligne"),
+    ("tray.status.away", "Absent"),
+    ("tray.status.dnd", "Ne pas déranger"),
+    ("tray.status.invisible", "Invisible"),
+    ("tray.unread_one", "nChat — 1 message non lu"),
+    ("tray.unread_many", "nChat — {n} messages non lus"),
+    ("tray.connection", "Connexion : {state}"),
+    ("tray.debug", "Débogage à distance sur le port {port}"),
+    (
+        "tray.unavailable",
+        "Votre bureau n'a pas de zone de notification : fermer la fenêtre quittera nChat.",
+    ),
+    ("digest.title_morning", "Bonjour — voici ce que vous avez manqué"),
+    ("digest.title_afternoon", "Bonjour — voici ce que vous avez manqué"),
+    ("digest.title_evening", "Bonsoir — voici ce que vous avez manqué"),
+    (
+        "digest.counts",
+        "{unread} non lus · {mentions} mention(s) · {dms} message(s) direct(s)",
+    ),
+    (
+        "digest.open",
+        "Cliquez pour ouvrir votre boîte de réception.",
+    ),
+    (
+        "session.revoked",
+        "Votre session a été fermée depuis un autre appareil. Reconnectez-vous pour continuer.",
+    ),
+    ("focus.ended", "Session de concentration terminée"),
+    (
+        "focus.recap",
+        "{n} notification(s) sont arrivées pendant votre concentration.",
+    ),
+    (
+        "focus.recap_none",
+        "Rien n'est arrivé pendant votre concentration.",
+    ),
+    ("call.mute", "Couper le micro"),
+    ("call.unmute", "Réactiver le micro"),
+    ("call.hang_up", "Raccrocher"),
+    ("call.share_screen", "Partager l’écran"),
+    ("call.stop_sharing", "Arrêter le partage"),
+    ("jumplist.new_message", "Nouveau message"),
+    ("jumplist.set_status", "Définir le statut"),
+    ("jumplist.recent", "Conversations récentes"),
+    ("close.prompt_title", "Fermer nChat ?"),
+    (
+        "close.prompt",
+        "Laisser nChat actif dans la zone de notification pour continuer à recevoir les messages, ou le quitter ? Vous pourrez modifier ce choix dans les Préférences.",
+    ),
+    ("close.keep_running", "Laisser actif"),
+    ("close.quit", "Quitter"),
+];
+
+const PT: &[(&str, &str)] = &[
+    ("menu.file", "Arquivo"),
+    ("menu.new_conversation", "Nova conversa"),
+    ("menu.preferences", "Preferências…"),
+    ("menu.quit", "Sair do nChat"),
+    ("menu.edit", "Editar"),
+    ("menu.undo", "Desfazer"),
+    ("menu.redo", "Refazer"),
+    ("menu.cut", "Recortar"),
+    ("menu.copy", "Copiar"),
+    ("menu.paste", "Colar"),
+    ("menu.select_all", "Selecionar tudo"),
+    ("menu.find", "Buscar…"),
+    ("menu.spelling", "Ortografia"),
+    ("menu.language_settings", "Configurações de idioma…"),
+    ("menu.check_spelling", "Verificar ortografia ao digitar"),
+    ("menu.dictation", "Ditar mensagem"),
+    ("menu.view", "Visualizar"),
+    ("menu.show_sidebar", "Mostrar barra lateral"),
+    ("menu.compact_mode", "Modo compacto"),
+    ("menu.mute_notifications", "Silenciar notificações"),
+    ("menu.fullscreen", "Tela cheia"),
+    ("menu.always_on_top", "Sempre visível"),
+    ("menu.actual_size", "Tamanho real"),
+    ("menu.zoom_in", "Ampliar"),
+    ("menu.zoom_out", "Reduzir"),
+    ("menu.go", "Ir"),
+    ("menu.jump_to", "Ir para…"),
+    ("menu.recent_channels", "Canais recentes"),
+    ("menu.no_recent_channels", "Nenhum canal recente"),
+    ("menu.window", "Janela"),
+    ("menu.minimize", "Minimizar"),
+    ("menu.zoom", "Zoom"),
+    ("menu.bring_all_to_front", "Trazer tudo para a frente"),
+    ("menu.help", "Ajuda"),
+    ("menu.about", "Sobre o nChat"),
+    ("menu.report_problem", "Relatar um problema…"),
+    ("menu.collect_diagnostics", "Coletar diagnósticos"),
+    ("menu.rollback_update", "Voltar para a versão anterior…"),
+    ("tray.show", "Mostrar nChat"),
+    ("tray.status.online", "Online"),
+    ("tray.status.away", "Ausente"),
+    ("tray.status.dnd", "Não perturbe"),
+    ("tray.status.invisible", "Invisível"),
+    ("tray.unread_one", "nChat — 1 mensagem não lida"),
+    ("tray.unread_many", "nChat — {n} mensagens não lidas"),
+    ("tray.connection", "Conexão: {state}"),
+    ("tray.debug", "Depuração remota na porta {port}"),
+    (
+        "tray.unavailable",
+        "Sua área de trabalho não tem bandeja do sistema, então fechar a janela encerrará o nChat.",
+    ),
+    ("digest.title_morning", "Bom dia — veja o que você perdeu"),
+    ("digest.title_afternoon", "Boa tarde — veja o que você perdeu"),
+    ("digest.title_evening", "Boa noite — veja o que você perdeu"),
+    (
+        "digest.counts",
+        "{unread} não lidas · {mentions} menção(ões) · {dms} mensagem(ns) direta(s)",
+    ),
+    ("digest.open", "Clique para abrir sua caixa de entrada."),
+    (
+        "session.revoked",
+        "Sua sessão foi encerrada em outro dispositivo. Entre novamente para continuar.",
+    ),
+    ("focus.ended", "Sessão de foco encerrada"),
+    (
+        "focus.recap",
+        "{n} notificação(ões) chegaram enquanto você estava focado.",
+    ),
+    (
+        "focus.recap_none",
+        "Nada chegou enquanto você estava focado.",
+    ),
+    ("call.mute", "Silenciar"),
+    ("call.unmute", "Ativar microfone"),
+    ("call.hang_up", "Desligar"),
+    ("call.share_screen", "Compartilhar tela"),
+    ("call.stop_sharing", "Parar de compartilhar"),
+    ("jumplist.new_message", "Nova mensagem"),
+    ("jumplist.set_status", "Definir status"),
+    ("jumplist.recent", "Conversas recentes"),
+    ("close.prompt_title", "Fechar o nChat?"),
+    (
+        "close.prompt",
+        "Manter o nChat na bandeja para continuar recebendo mensagens ou sair? Você pode mudar isso depois em Preferências.",
+    ),
+    ("close.keep_running", "Manter aberto"),
+    ("close.quit", "Sair"),
+];
+
+const JA: &[(&str, &str)] = &[
+    ("menu.file", "ファイル"),
+    ("menu.new_conversation", "新しい会話"),
+    ("menu.preferences", "設定…"),
+    ("menu.quit", "nChat を終了"),
+    ("menu.edit", "編集"),
+    ("menu.undo", "取り消す"),
+    ("menu.redo", "やり直す"),
+    ("menu.cut", "カット"),
+    ("menu.copy", "コピー"),
+    ("menu.paste", "ペースト"),
+    ("menu.select_all", "すべてを選択"),
+    ("menu.find", "検索…"),
+    ("menu.spelling", "スペル"),
+    ("menu.language_settings", "言語の設定…"),
+    ("menu.check_spelling", "入力中にスペルをチェック"),
+    ("menu.dictation", "メッセージを音声入力"),
+    ("menu.view", "表示"),
+    ("menu.show_sidebar", "サイドバーを表示"),
+    ("menu.compact_mode", "コンパクトモード"),
+    ("menu.mute_notifications", "通知をミュート"),
+    ("menu.fullscreen", "フルスクリーン"),
+    ("menu.always_on_top", "常に手前に表示"),
+    ("menu.actual_size", "実際のサイズ"),
+    ("menu.zoom_in", "拡大"),
+    ("menu.zoom_out", "縮小"),
+    ("menu.go", "移動"),
+    ("menu.jump_to", "ジャンプ…"),
+    ("menu.recent_channels", "最近のチャンネル"),
+    ("menu.no_recent_channels", "最近のチャンネルはありません"),
+    ("menu.window", "ウインドウ"),
+    ("menu.minimize", "最小化"),
+    ("menu.zoom", "拡大/縮小"),
+    ("menu.bring_all_to_front", "すべてを手前に移動"),
+    ("menu.help", "ヘルプ"),
+    ("menu.about", "nChat について"),
+    ("menu.report_problem", "問題を報告…"),
+    ("menu.collect_diagnostics", "診断情報を収集"),
+    ("menu.rollback_update", "以前のバージョンに戻す…"),
+    ("tray.show", "nChat を表示"),
+    ("tray.status.online", "オンライン"),
+    ("tray.status.away", "離席中"),
+    ("tray.status.dnd", "応答不可"),
+    ("tray.status.invisible", "オフライン表示"),
+    ("tray.unread_one", "nChat — 未読メッセージ 1 件"),
+    ("tray.unread_many", "nChat — 未読メッセージ {n} 件"),
+    ("tray.connection", "接続: {state}"),
+    ("tray.debug", "ポート {port} でリモートデバッグ中"),
+    (
+        "tray.unavailable",
+        "デスクトップにシステムトレイがないため、ウインドウを閉じると nChat が終了します。",
+    ),
+    ("digest.title_morning", "おはようございます — 見逃した内容"),
+    ("digest.title_afternoon", "こんにちは — 見逃した内容"),
+    ("digest.title_evening", "こんばんは — 見逃した内容"),
+    (
+        "digest.counts",
+        "未読 {unread} 件 · メンション {mentions} 件 · ダイレクトメッセージ {dms} 件",
+    ),
+    ("digest.open", "クリックして受信トレイを開きます。"),
+    ("session.revoked", "別のデバイスからセッションがサインアウトされました。続行するには再度サインインしてください。"),
+    ("focus.ended", "集中セッションが終了しました"),
+    ("focus.recap", "集中している間に {n} 件の通知が届きました。"),
+    ("focus.recap_none", "集中している間に届いた通知はありません。"),
+    ("call.mute", "ミュート"),
+    ("call.unmute", "ミュート解除"),
+    ("call.hang_up", "通話を終了"),
+    ("call.share_screen", "画面を共有"),
+    ("call.stop_sharing", "共有を停止"),
+    ("jumplist.new_message", "新規メッセージ"),
+    ("jumplist.set_status", "ステータスを設定"),
+    ("jumplist.recent", "最近の会話"),
+    ("close.prompt_title", "nChat を閉じますか？"),
+    (
+        "close.prompt",
+        "メッセージを受け取れるよう nChat をトレイで実行し続けますか、それとも終了しますか？ この設定は後から環境設定で変更できます。",
+    ),
+    ("close.keep_running", "実行を続ける"),
+    ("close.quit", "終了"),
+];
+
+const ZH: &[(&str, &str)] = &[
+    ("menu.file", "文件"),
+    ("menu.new_conversation", "新建对话"),
+    ("menu.preferences", "偏好设置…"),
+    ("menu.quit", "退出 nChat"),
+    ("menu.edit", "编辑"),
+    ("menu.undo", "撤销"),
+    ("menu.redo", "重做"),
+    ("menu.cut", "剪切"),
+    ("menu.copy", "复制"),
+    ("menu.paste", "粘贴"),
+    ("menu.select_all", "全选"),
+    ("menu.find", "查找…"),
+    ("menu.spelling", "拼写"),
+    ("menu.language_settings", "语言设置…"),
+    ("menu.check_spelling", "键入时检查拼写"),
+    ("menu.dictation", "听写消息"),
+    ("menu.view", "显示"),
+    ("menu.show_sidebar", "显示侧边栏"),
+    ("menu.compact_mode", "紧凑模式"),
+    ("menu.mute_notifications", "静音通知"),
+    ("menu.fullscreen", "全屏"),
+    ("menu.always_on_top", "窗口置顶"),
+    ("menu.actual_size", "实际大小"),
+    ("menu.zoom_in", "放大"),
+    ("menu.zoom_out", "缩小"),
+    ("menu.go", "前往"),
+    ("menu.jump_to", "跳转到…"),
+    ("menu.recent_channels", "最近的频道"),
+    ("menu.no_recent_channels", "没有最近的频道"),
+    ("menu.window", "窗口"),
+    ("menu.minimize", "最小化"),
+    ("menu.zoom", "缩放"),
+    ("menu.bring_all_to_front", "前置全部窗口"),
+    ("menu.help", "帮助"),
+    ("menu.about", "关于 nChat"),
+    ("menu.report_problem", "报告问题…"),
+    ("menu.collect_diagnostics", "收集诊断信息"),
+    ("menu.rollback_update", "回退到上一版本…"),
+    ("tray.show", "显示 nChat"),
+    ("tray.status.online", "在线"),
+    ("tray.status.away", "离开"),
+    ("tray.status.dnd", "请勿打扰"),
+    ("tray.status.invisible", "隐身"),
+    ("tray.unread_one", "nChat — 1 条未读消息"),
+    ("tray.unread_many", "nChat — {n} 条未读消息"),
+    ("tray.connection", "连接：{state}"),
+    ("tray.debug", "远程调试端口 {port}"),
+    (
+        "tray.unavailable",
+        "你的桌面没有系统托盘，关闭窗口将退出 nChat。",
+    ),
+    ("digest.title_morning", "早上好 — 这是你错过的消息"),
+    ("digest.title_afternoon", "下午好 — 这是你错过的消息"),
+    ("digest.title_evening", "晚上好 — 这是你错过的消息"),
+    (
+        "digest.counts",
+        "{unread} 条未读 · {mentions} 条提及 · {dms} 条私信",
+    ),
+    ("digest.open", "点击打开收件箱。"),
+    (
+        "session.revoked",
+        "您的会话已在另一台设备上退出。请重新登录以继续。",
+    ),
+    ("focus.ended", "专注时段已结束"),
+    ("focus.recap", "专注期间收到 {n} 条通知。"),
+    ("focus.recap_none", "专注期间没有收到任何通知。"),
+    ("call.mute", "静音"),
+    ("call.unmute", "取消静音"),
+    ("call.hang_up", "挂断"),
+    ("call.share_screen", "共享屏幕"),
+    ("call.stop_sharing", "停止共享"),
+    ("jumplist.new_message", "新消息"),
+    ("jumplist.set_status", "设置状态"),
+    ("jumplist.recent", "最近的会话"),
+    ("close.prompt_title", "关闭 nChat？"),
+    (
+        "close.prompt",
+        "让 nChat 在托盘中继续运行以便接收消息，还是退出？之后可在偏好设置中更改。",
+    ),
+    ("close.keep_running", "继续运行"),
+    ("close.quit", "退出"),
+];
+
+type Table = &'static [(&'static str, &'static str)];
+
+/// Supported languages by primary subtag.
+const LANGUAGES: &[(&str, Table)] = &[
+    ("en", EN),
+    ("de", DE),
+    ("es", ES),
+    ("fr", FR),
+    ("pt", PT),
+    ("ja", JA),
+    ("zh", ZH),
+];
+
+static CURRENT: RwLock<Table> = RwLock::new(EN);
+
+/// Supported language matching `lang`'s primary subtag (`pt-BR` → `pt`).
+fn language(lang: &str) -> Option<(&'static str, Table)> {
+    let primary = lang
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    LANGUAGES.iter().find(|(code, _)| *code == primary).copied()
+}
+
+/// Translate `key` into the current native language.
+pub fn t(key: &str) -> &'static str {
+    let lookup = |table: Table| table.iter().find(|(k, _)| *k == key).map(|(_, v)| *v);
+    lookup(*CURRENT.read().unwrap())
+        .or_else(|| lookup(EN))
+        .unwrap_or("")
+}
+
+/// Translate `key` and substitute `{name}` placeholders.
+pub fn tf(key: &str, args: &[(&str, &dyn std::fmt::Display)]) -> String {
+    args.iter().fold(t(key).to_string(), |text, (name, value)| {
+        text.replace(&format!("{{{name}}}"), &value.to_string())
+    })
+}
+
+/// Whether the native chrome has strings for `lang`.
+pub fn is_supported(lang: &str) -> bool {
+    language(lang).is_some()
+}
+
+/// Switch the native language (a BCP 47 tag such as `de` or `pt-BR`).
+/// Unsupported languages fall back to English. Returns the language used.
+pub fn set_locale(app: &AppHandle, lang: &str) -> Result<&'static str, String> {
+    let (code, table) = language(lang).unwrap_or(("en", EN));
+    *CURRENT.write().unwrap() = table;
+    settings::save(app, LOCALE_KEY, &lang)?;
+    Ok(code)
+}
+
+/// Apply the persisted language before any native chrome is built.
+pub fn restore(app: &AppHandle) {
+    let lang: Option<String> = settings::load(app, LOCALE_KEY);
+    if let Some((_, table)) = lang.as_deref().and_then(language) {
+        *CURRENT.write().unwrap() = table;
+    }
+}
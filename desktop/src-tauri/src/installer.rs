@@ -0,0 +1,211 @@
+// nChat Desktop — installing a verified package without the updater plugin
+//
+// The updater plugin only installs packages it fetched itself in answer to
+// a manifest. Rollbacks and offline installs already have the package and
+// have checked its signature (see `updater.rs`), so they install the bytes
+// here instead, the way the bundler's formats expect:
+//
+// - Windows: the NSIS `-setup.exe` or `.msi` (bare or zipped) is written to
+//   a temporary folder and run passively; the app exits so it can be
+//   replaced.
+// - macOS: the `.app.tar.gz` is unpacked next to the running bundle, which
+//   is then swapped for it.
+// - Linux: an `.AppImage.tar.gz` replaces the running AppImage; `.deb` and
+//   `.rpm` packages are handed to dpkg or rpm through pkexec.
+//
+// Except on Windows, the new version runs after a restart.
+
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+use std::path::Path;
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+use std::path::PathBuf;
+
+/// A scratch folder for `version`'s package, emptied first.
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+fn scratch_dir(version: &str) -> Result<PathBuf, String> {
+    let dir = std::env::temp_dir().join(format!("nchat-desktop-{version}-install"));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir)
+}
+
+/// Unpack a `.tar.gz` package into `dest`.
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn unpack(bytes: &[u8], dest: &Path) -> Result<(), String> {
+    tar::Archive::new(flate2::read::GzDecoder::new(bytes))
+        .unpack(dest)
+        .map_err(|e| format!("cannot unpack the package: {e}"))
+}
+
+/// Install `bytes`, a package of `version` whose signature was checked.
+#[cfg(target_os = "windows")]
+pub fn install(bytes: &[u8], version: &str) -> Result<(), String> {
+    use std::io::Cursor;
+    use windows::core::{HSTRING, PCWSTR};
+    use windows::Win32::UI::Shell::ShellExecuteW;
+    use windows::Win32::UI::WindowsAndMessaging::SW_SHOW;
+
+    let dir = scratch_dir(version)?;
+    let installer = if bytes.starts_with(b"PK\x03\x04") {
+        zip::ZipArchive::new(Cursor::new(bytes))
+            .and_then(|mut archive| archive.extract(&dir))
+            .map_err(|e| format!("cannot unpack the package: {e}"))?;
+        std::fs::read_dir(&dir)
+            .map_err(|e| e.to_string())?
+            .flatten()
+            .map(|entry| entry.path())
+            .find(|path| {
+                path.extension()
+                    .is_some_and(|ext| ext == "exe" || ext == "msi")
+            })
+            .ok_or("the package contains no installer")?
+    } else {
+        let ext = if bytes.starts_with(b"MZ") {
+            "exe"
+        } else if bytes.starts_with(&[0xD0, 0xCF, 0x11, 0xE0]) {
+            "msi"
+        } else {
+            return Err("the package is not a Windows installer".into());
+        };
+        let path = dir.join(format!("nchat-desktop-{version}-setup.{ext}"));
+        std::fs::write(&path, bytes).map_err(|e| e.to_string())?;
+        path
+    };
+
+    let (file, parameters) = if installer.extension().is_some_and(|ext| ext == "msi") {
+        let system = std::env::var("SYSTEMROOT").unwrap_or_else(|_| "C:\\Windows".into());
+        (
+            PathBuf::from(system).join("System32\\msiexec.exe"),
+            format!(
+                "/i \"{}\" /passive /promptrestart AUTOLAUNCHAPP=True",
+                installer.display()
+            ),
+        )
+    } else {
+        (installer, "/P /R /UPDATE".to_string())
+    };
+    let (file, parameters) = (HSTRING::from(file.as_os_str()), HSTRING::from(parameters));
+    let started = unsafe {
+        ShellExecuteW(
+            None,
+            &HSTRING::from("open"),
+            &file,
+            &parameters,
+            PCWSTR::null(),
+            SW_SHOW,
+        )
+    };
+    // Values above 32 mean success.
+    if started.0 as isize <= 32 {
+        return Err("the installer could not be started".into());
+    }
+    std::process::exit(0);
+}
+
+/// Install `bytes`, a package of `version` whose signature was checked.
+#[cfg(target_os = "macos")]
+pub fn install(bytes: &[u8], version: &str) -> Result<(), String> {
+    let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+    // <name>.app/Contents/MacOS/<binary>
+    let bundle = exe
+        .ancestors()
+        .nth(3)
+        .filter(|path| path.extension().is_some_and(|ext| ext == "app"))
+        .ok_or("the app is not running from an application bundle")?
+        .to_path_buf();
+    let parent = bundle.parent().ok_or("the app bundle has no folder")?;
+
+    // Unpack on the same volume so the swap is two renames.
+    let dir = parent.join(format!(".nchat-desktop-{version}-install"));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("cannot write to {}: {e}", parent.display()))?;
+    let result = unpack(bytes, &dir).and_then(|_| swap_bundle(&dir, &bundle));
+    let _ = std::fs::remove_dir_all(&dir);
+    result
+}
+
+/// Replace `bundle` with the `.app` unpacked in `dir`, putting the old one
+/// back if that fails.
+#[cfg(target_os = "macos")]
+fn swap_bundle(dir: &Path, bundle: &Path) -> Result<(), String> {
+    let new = std::fs::read_dir(dir)
+        .map_err(|e| e.to_string())?
+        .flatten()
+        .map(|entry| entry.path())
+        .find(|path| path.extension().is_some_and(|ext| ext == "app"))
+        .ok_or("the package contains no application bundle")?;
+    let old = dir.join("previous.app");
+    std::fs::rename(bundle, &old).map_err(|e| e.to_string())?;
+    if let Err(e) = std::fs::rename(&new, bundle) {
+        let _ = std::fs::rename(&old, bundle);
+        return Err(e.to_string());
+    }
+    Ok(())
+}
+
+/// Install `bytes`, a package of `version` whose signature was checked.
+#[cfg(target_os = "linux")]
+pub fn install(bytes: &[u8], version: &str) -> Result<(), String> {
+    let package_manager = if bytes.starts_with(b"!<arch>\n") {
+        Some(("dpkg", "-i", "deb"))
+    } else if bytes.starts_with(&[0xED, 0xAB, 0xEE, 0xDB]) {
+        Some(("rpm", "-U", "rpm"))
+    } else {
+        None
+    };
+    if let Some((tool, flag, ext)) = package_manager {
+        let path = scratch_dir(version)?.join(format!("nchat-desktop-{version}.{ext}"));
+        std::fs::write(&path, bytes).map_err(|e| e.to_string())?;
+        let status = std::process::Command::new("pkexec")
+            .args([tool, flag])
+            .arg(&path)
+            .status()
+            .map_err(|e| format!("cannot run pkexec: {e}"))?;
+        let _ = std::fs::remove_file(&path);
+        return if status.success() {
+            Ok(())
+        } else {
+            Err(format!("{tool} failed ({status})"))
+        };
+    }
+
+    let appimage = std::env::var_os("APPIMAGE")
+        .map(PathBuf::from)
+        .ok_or("this installation cannot be updated from an AppImage package")?;
+    let dir = scratch_dir(version)?;
+    unpack(bytes, &dir)?;
+    let new = std::fs::read_dir(&dir)
+        .map_err(|e| e.to_string())?
+        .flatten()
+        .map(|entry| entry.path())
+        .find(|path| path.extension().is_some_and(|ext| ext == "AppImage"))
+        .ok_or("the package contains no AppImage")?;
+    replace_file(&new, &appimage)?;
+    let _ = std::fs::remove_dir_all(&dir);
+    Ok(())
+}
+
+/// Copy `new` next to `target` with its permissions, then rename it over
+/// `target`, so a failed copy leaves the running file intact.
+#[cfg(target_os = "linux")]
+fn replace_file(new: &Path, target: &Path) -> Result<(), String> {
+    let mut staged = target.as_os_str().to_owned();
+    staged.push(".update");
+    let staged = PathBuf::from(staged);
+    let permissions = std::fs::metadata(target)
+        .map_err(|e| e.to_string())?
+        .permissions();
+    std::fs::copy(new, &staged)
+        .and_then(|_| std::fs::set_permissions(&staged, permissions))
+        .and_then(|_| std::fs::rename(&staged, target))
+        .map_err(|e| {
+            let _ = std::fs::remove_file(&staged);
+            format!("cannot replace {}: {e}", target.display())
+        })
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+pub fn install(_bytes: &[u8], _version: &str) -> Result<(), String> {
+    Err("installing packages is not supported on this platform".into())
+}
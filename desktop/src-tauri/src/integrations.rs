@@ -0,0 +1,184 @@
+// nChat Desktop — incoming local notifications from other apps
+//
+// Opt-in. Once enabled, other desktop tools can open
+// `nchat://notify?token=…&title=…&body=…[&target=conversation]` to show a
+// notification through nChat, or to post into the conversation the user
+// designated for integrations. The token lives in the OS keychain and
+// requests are rate limited so a misbehaving script cannot flood the user.
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chacha20poly1305::aead::{rand_core::RngCore, OsRng};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, Url};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::focus;
+use crate::keychain;
+use crate::state::settings;
+use crate::windows::MAIN_WINDOW;
+
+const SETTINGS_KEY: &str = "integrations";
+const TOKEN_ACCOUNT: &str = "integrations-token";
+
+/// At most this many requests are accepted per window.
+const RATE_LIMIT: usize = 10;
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+const MAX_TITLE_LEN: usize = 200;
+const MAX_BODY_LEN: usize = 4000;
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrationSettings {
+    pub enabled: bool,
+    /// Conversation that `target=conversation` requests are posted into.
+    pub channel_id: Option<String>,
+}
+
+#[derive(Default)]
+pub struct IntegrationState {
+    recent: Mutex<VecDeque<Instant>>,
+}
+
+/// Payload of `integration:message`; the frontend posts it into
+/// `channel_id` as the integrations bot.
+#[derive(Serialize, Clone)]
+pub struct IntegrationMessage {
+    pub channel_id: String,
+    pub source: Option<String>,
+    pub title: String,
+    pub body: Option<String>,
+}
+
+pub fn load(app: &AppHandle) -> IntegrationSettings {
+    settings::load(app, SETTINGS_KEY).unwrap_or_default()
+}
+
+/// Enable or disable the endpoint. Enabling for the first time creates a
+/// token; the returned value is the token other apps must send.
+pub fn configure(app: &AppHandle, new: IntegrationSettings) -> Result<Option<String>, String> {
+    settings::save(app, SETTINGS_KEY, &new)?;
+    if !new.enabled {
+        return Ok(None);
+    }
+    match keychain::read_secret(TOKEN_ACCOUNT)? {
+        Some(token) => Ok(Some(token)),
+        None => regenerate_token().map(Some),
+    }
+}
+
+/// Replace the token, invalidating whatever other apps were configured
+/// with.
+pub fn regenerate_token() -> Result<String, String> {
+    let mut bytes = [0u8; 24];
+    OsRng.fill_bytes(&mut bytes);
+    let token = URL_SAFE_NO_PAD.encode(bytes);
+    keychain::store_secret(TOKEN_ACCOUNT, &token)?;
+    Ok(token)
+}
+
+/// Compare without short-circuiting so the token cannot be guessed byte by
+/// byte from timing.
+fn token_matches(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+fn within_rate_limit(app: &AppHandle) -> bool {
+    let state = app.state::<IntegrationState>();
+    let mut recent = state.recent.lock().unwrap();
+    let now = Instant::now();
+    while recent
+        .front()
+        .is_some_and(|t| now.duration_since(*t) > RATE_WINDOW)
+    {
+        recent.pop_front();
+    }
+    if recent.len() >= RATE_LIMIT {
+        return false;
+    }
+    recent.push_back(now);
+    true
+}
+
+fn truncate(s: &str, max: usize) -> String {
+    s.chars().take(max).collect()
+}
+
+/// Handle an `nchat://notify` link. Rejections are logged and otherwise
+/// ignored; the caller gets no response either way.
+pub fn handle_notify(app: &AppHandle, url: &Url) {
+    if let Err(e) = notify(app, url) {
+        log::warn!("[nchat-desktop] rejected integration request: {e}");
+    }
+}
+
+fn notify(app: &AppHandle, url: &Url) -> Result<(), String> {
+    let config = load(app);
+    if !config.enabled {
+        return Err("integrations are disabled".into());
+    }
+
+    let param = |name: &str| {
+        url.query_pairs()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.into_owned())
+    };
+    let expected = keychain::read_secret(TOKEN_ACCOUNT)?.ok_or("no integration token set")?;
+    if !token_matches(&param("token").unwrap_or_default(), &expected) {
+        return Err("invalid token".into());
+    }
+    if !within_rate_limit(app) {
+        return Err("rate limit exceeded".into());
+    }
+
+    let title = param("title")
+        .filter(|t| !t.trim().is_empty())
+        .ok_or("missing title")?;
+    let title = truncate(&title, MAX_TITLE_LEN);
+    let body = param("body").map(|b| truncate(&b, MAX_BODY_LEN));
+    let source = param("source").map(|s| truncate(&s, MAX_TITLE_LEN));
+
+    match param("target").as_deref() {
+        Some("conversation") => {
+            let channel_id = config
+                .channel_id
+                .ok_or("no integrations conversation configured")?;
+            let win = app
+                .get_webview_window(MAIN_WINDOW)
+                .ok_or("main window not found")?;
+            win.emit(
+                "integration:message",
+                IntegrationMessage {
+                    channel_id,
+                    source,
+                    title,
+                    body,
+                },
+            )
+            .map_err(|e| e.to_string())
+        }
+        None | Some("notification") => {
+            let title = match &source {
+                Some(source) => format!("{source}: {title}"),
+                None => title,
+            };
+            if focus::hold(app, &title, body.as_deref()) {
+                return Ok(());
+            }
+            let mut builder = app.notification().builder().title(&title);
+            if let Some(body) = &body {
+                builder = builder.body(body);
+            }
+            builder.show().map_err(|e| e.to_string())
+        }
+        Some(other) => Err(format!("unknown target {other}")),
+    }
+}
@@ -0,0 +1,134 @@
+// nChat Desktop — Windows Jump List
+//
+// Right-clicking the taskbar or Start menu entry offers "New Message" and
+// "Set Status" tasks and the conversations the frontend reports as recent.
+// Each entry starts the app with an `nchat://` link (`compose`, `status`,
+// `channel/<id>` or `user/<id>`), which goes through the usual deep-link
+// validation. Entries the user removed from the list are not added back.
+// Other platforms have no equivalent.
+
+use serde::Deserialize;
+use tauri::AppHandle;
+
+/// Longest recent-conversation list the frontend can send; Windows shows
+/// fewer (10 by default).
+const MAX_RECENT: usize = 20;
+
+/// A conversation for the "Recent Conversations" category.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentConversation {
+    pub id: String,
+    pub title: String,
+    /// A direct conversation, opened with `nchat://user/<id>`.
+    #[serde(default)]
+    pub direct: bool,
+}
+
+impl RecentConversation {
+    fn link(&self) -> String {
+        let route = if self.direct { "user" } else { "channel" };
+        format!("nchat://{route}/{}", self.id)
+    }
+}
+
+/// Rebuild the Jump List with the standard tasks and `recent`, most recent
+/// first.
+pub fn set_jump_list(app: &AppHandle, mut recent: Vec<RecentConversation>) -> Result<(), String> {
+    recent.retain(|conversation| {
+        crate::deeplink::parse(&conversation.link()).is_some()
+            && !conversation.title.trim().is_empty()
+    });
+    recent.truncate(MAX_RECENT);
+    #[cfg(target_os = "windows")]
+    app.run_on_main_thread(move || {
+        if let Err(e) = imp::commit(&recent) {
+            log::warn!("[nchat-desktop] failed to update the Jump List: {e}");
+        }
+    })
+    .map_err(|e| e.to_string())?;
+    #[cfg(not(target_os = "windows"))]
+    let _ = (app, recent);
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+mod imp {
+    use std::collections::HashSet;
+    use windows::core::{Interface, Result, HSTRING, PROPVARIANT};
+    use windows::Win32::Storage::EnhancedStorage::PKEY_Title;
+    use windows::Win32::System::Com::{CoCreateInstance, CLSCTX_INPROC_SERVER};
+    use windows::Win32::UI::Shell::Common::{IObjectArray, IObjectCollection};
+    use windows::Win32::UI::Shell::PropertiesSystem::IPropertyStore;
+    use windows::Win32::UI::Shell::{
+        DestinationList, EnumerableObjectCollection, ICustomDestinationList, IShellLinkW, ShellLink,
+    };
+
+    use super::RecentConversation;
+    use crate::i18n::t;
+
+    pub fn commit(recent: &[RecentConversation]) -> Result<()> {
+        let exe = std::env::current_exe().unwrap_or_default();
+        let exe = HSTRING::from(exe.as_os_str());
+        unsafe {
+            let list: ICustomDestinationList =
+                CoCreateInstance(&DestinationList, None, CLSCTX_INPROC_SERVER)?;
+            let mut slots = 0u32;
+            let removed: IObjectArray = list.BeginList(&mut slots)?;
+            let removed = removed_arguments(&removed);
+
+            let tasks = collection()?;
+            tasks.AddObject(&link(&exe, "nchat://compose", t("jumplist.new_message"))?)?;
+            tasks.AddObject(&link(&exe, "nchat://status", t("jumplist.set_status"))?)?;
+            list.AddUserTasks(&tasks.cast::<IObjectArray>()?)?;
+
+            let conversations = collection()?;
+            let mut added = 0;
+            for conversation in recent {
+                let arguments = conversation.link();
+                if added == slots as usize || removed.contains(&arguments) {
+                    continue;
+                }
+                conversations.AddObject(&link(&exe, &arguments, &conversation.title)?)?;
+                added += 1;
+            }
+            if added > 0 {
+                list.AppendCategory(
+                    &HSTRING::from(t("jumplist.recent")),
+                    &conversations.cast::<IObjectArray>()?,
+                )?;
+            }
+            list.CommitList()
+        }
+    }
+
+    unsafe fn collection() -> Result<IObjectCollection> {
+        CoCreateInstance(&EnumerableObjectCollection, None, CLSCTX_INPROC_SERVER)
+    }
+
+    /// A shell link that starts the app with `arguments`, titled `title`.
+    unsafe fn link(exe: &HSTRING, arguments: &str, title: &str) -> Result<IShellLinkW> {
+        let link: IShellLinkW = CoCreateInstance(&ShellLink, None, CLSCTX_INPROC_SERVER)?;
+        link.SetPath(exe)?;
+        link.SetArguments(&HSTRING::from(arguments))?;
+        link.SetIconLocation(exe, 0)?;
+        let store: IPropertyStore = link.cast()?;
+        store.SetValue(&PKEY_Title, &PROPVARIANT::from(title))?;
+        store.Commit()?;
+        Ok(link)
+    }
+
+    /// Arguments of the entries the user removed from the list.
+    unsafe fn removed_arguments(removed: &IObjectArray) -> HashSet<String> {
+        let count = removed.GetCount().unwrap_or(0);
+        (0..count)
+            .filter_map(|i| {
+                let link: IShellLinkW = removed.GetAt(i).ok()?;
+                let mut buffer = [0u16; 1024];
+                link.GetArguments(&mut buffer).ok()?;
+                let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+                Some(String::from_utf16_lossy(&buffer[..len]))
+            })
+            .collect()
+    }
+}
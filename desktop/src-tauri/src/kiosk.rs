@@ -0,0 +1,142 @@
+// nChat Desktop — presentation/kiosk mode
+//
+// Locks the main window full screen for shared displays such as lobby
+// screens and ops rooms. The menu and window decorations are removed, other
+// windows are closed, closing and quitting are refused, and a support
+// debugging session is ended. The webview can enter the mode but not leave
+// it: only the exit shortcut does, which admins can change through policy.
+// The mode is persisted, so a display that reboots comes back locked.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutEvent, ShortcutState};
+
+use crate::state::settings;
+use crate::windows::{self, WindowMode, MAIN_WINDOW};
+
+/// Settings key recording that the app was left in kiosk mode.
+const KIOSK_KEY: &str = "kiosk.enabled";
+
+/// Leaves kiosk mode unless policy sets another combination.
+const DEFAULT_EXIT_SHORTCUT: &str = "CmdOrCtrl+Alt+Shift+Q";
+
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+pub fn is_active() -> bool {
+    ACTIVE.load(Ordering::Relaxed)
+}
+
+fn exit_shortcut() -> String {
+    crate::policy::current()
+        .kiosk
+        .as_ref()
+        .and_then(|kiosk| kiosk.exit_shortcut.clone())
+        .unwrap_or_else(|| DEFAULT_EXIT_SHORTCUT.to_string())
+}
+
+/// Lock the main window full screen. The exit shortcut is registered
+/// first, so the mode is never entered without a way out.
+pub fn enter(app: &AppHandle) -> Result<(), String> {
+    if is_active() {
+        return Ok(());
+    }
+    let win = app
+        .get_webview_window(MAIN_WINDOW)
+        .ok_or("main window not found")?;
+    let exit = exit_shortcut();
+    let global = app.global_shortcut();
+    // The quick switcher would open a window above the locked one, and
+    // other shortcuts would act on a window the kiosk user cannot see.
+    global.unregister_all().map_err(|e| e.to_string())?;
+    if let Err(e) = global.register(exit.as_str()) {
+        crate::shortcuts::register_all(app);
+        return Err(format!("kiosk exit shortcut {exit} is not available: {e}"));
+    }
+    ACTIVE.store(true, Ordering::Relaxed);
+
+    if windows::window_mode(app).0 == WindowMode::Mini {
+        windows::set_window_mode(app, WindowMode::Normal, None)?;
+    }
+    // Destroyed rather than closed: close requests are refused from here on.
+    for (label, other) in app.webview_windows() {
+        if label != MAIN_WINDOW {
+            let _ = other.destroy();
+        }
+    }
+    let _ = crate::commands::debug::disable_remote_debugging(app.clone());
+
+    let result = (|| {
+        #[cfg(target_os = "macos")]
+        app.remove_menu()?;
+        #[cfg(not(target_os = "macos"))]
+        win.hide_menu()?;
+        win.set_decorations(false)?;
+        win.unminimize()?;
+        win.show()?;
+        win.set_fullscreen(true)?;
+        win.set_always_on_top(true)?;
+        win.set_focus()
+    })();
+    result.map_err(|e| e.to_string())?;
+    settings::save(app, KIOSK_KEY, &true)?;
+    let _ = win.emit("kiosk:changed", true);
+    log::info!("[nchat-desktop] kiosk mode on; {exit} leaves it");
+    Ok(())
+}
+
+/// Unlock the main window and restore the menu and shortcuts.
+fn exit(app: &AppHandle) -> Result<(), String> {
+    ACTIVE.store(false, Ordering::Relaxed);
+    settings::save(app, KIOSK_KEY, &false)?;
+    let _ = app.global_shortcut().unregister(exit_shortcut().as_str());
+    crate::shortcuts::register_all(app);
+    let win = app
+        .get_webview_window(MAIN_WINDOW)
+        .ok_or("main window not found")?;
+    let result = (|| {
+        #[cfg(target_os = "macos")]
+        crate::menu::rebuild(app)?;
+        #[cfg(not(target_os = "macos"))]
+        win.show_menu()?;
+        win.set_always_on_top(false)?;
+        win.set_fullscreen(false)?;
+        win.set_decorations(true)
+    })();
+    result.map_err(|e| e.to_string())?;
+    let _ = win.emit("kiosk:changed", false);
+    log::info!("[nchat-desktop] kiosk mode off");
+    Ok(())
+}
+
+/// Re-enter kiosk mode at launch if the app was left in it.
+pub fn restore(app: &AppHandle) {
+    if !settings::load(app, KIOSK_KEY).unwrap_or(false) {
+        return;
+    }
+    if let Err(e) = enter(app) {
+        log::warn!("[nchat-desktop] failed to restore kiosk mode: {e}");
+    }
+}
+
+/// Whether the app was left in kiosk mode, for decisions made before the
+/// main window exists.
+pub fn is_persisted(app: &AppHandle) -> bool {
+    settings::load(app, KIOSK_KEY).unwrap_or(false)
+}
+
+/// Global shortcut handler. Returns whether the shortcut was the kiosk
+/// exit, so other handlers can skip it.
+pub fn on_shortcut(app: &AppHandle, shortcut: &Shortcut, event: ShortcutEvent) -> bool {
+    let is_exit = exit_shortcut()
+        .parse::<Shortcut>()
+        .is_ok_and(|exit| exit.id() == shortcut.id());
+    if !is_active() || !is_exit {
+        return false;
+    }
+    if event.state == ShortcutState::Pressed {
+        if let Err(e) = exit(app) {
+            log::warn!("[nchat-desktop] failed to leave kiosk mode: {e}");
+        }
+    }
+    true
+}
@@ -0,0 +1,229 @@
+// nChat Desktop — downloadable language assets
+//
+// Spellcheck dictionaries, translation models and text-to-speech voices are
+// not bundled; they are listed in a catalog next to the update manifests
+// and downloaded on demand into the app data folder. An asset counts as
+// installed once all of its files are verified and its `asset.json` is
+// written, so an interrupted install resumes instead of appearing usable.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::download;
+
+/// Catalog of available assets, served alongside the update manifests.
+const CATALOG_URL: &str = "https://packages.nself.org/chat-desktop/language-assets.json";
+
+/// Written last; its presence marks a complete install.
+const MARKER_FILE: &str = "asset.json";
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum LanguageAssetKind {
+    Dictionary,
+    Translation,
+    Voice,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AssetFile {
+    pub name: String,
+    pub url: String,
+    pub sha256: String,
+    pub size: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LanguageAsset {
+    pub id: String,
+    pub kind: LanguageAssetKind,
+    /// BCP 47 tag, or a pair such as `en-de` for translation models.
+    pub language: String,
+    pub files: Vec<AssetFile>,
+}
+
+#[derive(Serialize)]
+pub struct LanguageAssetStatus {
+    #[serde(flatten)]
+    pub asset: LanguageAsset,
+    pub installed: bool,
+    /// Bytes used on disk, including partial downloads.
+    pub disk_bytes: u64,
+}
+
+#[derive(Serialize, Clone)]
+struct ProgressEvent {
+    id: String,
+    downloaded: u64,
+    total: u64,
+}
+
+/// Ids of assets currently being installed.
+#[derive(Default)]
+pub struct LanguageAssetState {
+    installing: Mutex<HashSet<String>>,
+}
+
+/// Ids and file names become path components; keep them plain.
+fn is_safe_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+fn assets_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join("language-assets"))
+        .map_err(|e| e.to_string())
+}
+
+fn asset_dir(app: &AppHandle, id: &str) -> Result<PathBuf, String> {
+    if !is_safe_name(id) {
+        return Err(format!("invalid asset id: {id}"));
+    }
+    Ok(assets_dir(app)?.join(id))
+}
+
+fn disk_bytes(dir: &std::path::Path) -> u64 {
+    std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|e| e.metadata().ok())
+                .map(|m| m.len())
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+async fn catalog() -> Result<Vec<LanguageAsset>, String> {
+    reqwest::get(CATALOG_URL)
+        .await
+        .and_then(|res| res.error_for_status())
+        .map_err(|e| e.to_string())?
+        .json()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Installed assets, read from their markers.
+fn installed(app: &AppHandle) -> Vec<LanguageAsset> {
+    let Ok(entries) = assets_dir(app).and_then(|d| std::fs::read_dir(d).map_err(|e| e.to_string()))
+    else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|e| std::fs::read_to_string(e.path().join(MARKER_FILE)).ok())
+        .filter_map(|raw| serde_json::from_str(&raw).ok())
+        .collect()
+}
+
+/// Everything in the catalog plus anything installed that has since been
+/// dropped from it. Offline, only installed assets are listed.
+pub async fn list(app: &AppHandle) -> Vec<LanguageAssetStatus> {
+    let mut assets = installed(app);
+    match catalog().await {
+        Ok(available) => {
+            let known: HashSet<String> = assets.iter().map(|a| a.id.clone()).collect();
+            assets.extend(available.into_iter().filter(|a| !known.contains(&a.id)));
+        }
+        Err(e) => log::warn!("[nchat-desktop] language asset catalog unavailable: {e}"),
+    }
+    assets.sort_by(|a, b| a.id.cmp(&b.id));
+    assets
+        .into_iter()
+        .filter_map(|asset| {
+            // Skip catalog entries whose id could not be a folder name.
+            let dir = asset_dir(app, &asset.id).ok()?;
+            Some(LanguageAssetStatus {
+                installed: dir.join(MARKER_FILE).is_file(),
+                disk_bytes: disk_bytes(&dir),
+                asset,
+            })
+        })
+        .collect()
+}
+
+/// Download and verify every file of asset `id`, emitting
+/// `language-asset:progress` as bytes arrive. Re-running after an
+/// interruption resumes the partial files.
+pub async fn install(app: &AppHandle, id: &str) -> Result<(), String> {
+    let dir = asset_dir(app, id)?;
+    if !app
+        .state::<LanguageAssetState>()
+        .installing
+        .lock()
+        .unwrap()
+        .insert(id.to_string())
+    {
+        return Err(format!("{id} is already being installed"));
+    }
+    let result = install_files(app, id, &dir).await;
+    app.state::<LanguageAssetState>()
+        .installing
+        .lock()
+        .unwrap()
+        .remove(id);
+    result
+}
+
+async fn install_files(app: &AppHandle, id: &str, dir: &std::path::Path) -> Result<(), String> {
+    let asset = catalog()
+        .await?
+        .into_iter()
+        .find(|a| a.id == id)
+        .ok_or_else(|| format!("unknown language asset: {id}"))?;
+    if let Some(file) = asset.files.iter().find(|f| !is_safe_name(&f.name)) {
+        return Err(format!("invalid file name in catalog: {}", file.name));
+    }
+    std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+
+    let client = reqwest::Client::new();
+    let total: u64 = asset.files.iter().map(|f| f.size).sum();
+    let mut done = 0;
+    for file in &asset.files {
+        let dest = dir.join(&file.name);
+        if !dest.is_file() {
+            download::fetch(&client, &file.url, &dest, &file.sha256, |bytes, _| {
+                let _ = app.emit(
+                    "language-asset:progress",
+                    ProgressEvent {
+                        id: id.to_string(),
+                        downloaded: done + bytes,
+                        total,
+                    },
+                );
+            })
+            .await?;
+        }
+        done += file.size;
+    }
+
+    let marker = serde_json::to_string_pretty(&asset).map_err(|e| e.to_string())?;
+    std::fs::write(dir.join(MARKER_FILE), marker).map_err(|e| e.to_string())
+}
+
+/// Delete an asset, including partial downloads, to free disk space.
+pub fn remove(app: &AppHandle, id: &str) -> Result<(), String> {
+    if app
+        .state::<LanguageAssetState>()
+        .installing
+        .lock()
+        .unwrap()
+        .contains(id)
+    {
+        return Err(format!("{id} is being installed"));
+    }
+    let dir = asset_dir(app, id)?;
+    match std::fs::remove_dir_all(&dir) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.to_string()),
+        _ => Ok(()),
+    }
+}
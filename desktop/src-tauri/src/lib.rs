@@ -1,11 +1,60 @@
 // nChat Desktop — Tauri 2 library root
 
+mod about;
+mod archive;
+mod badge;
+mod call_shortcuts;
+mod clock;
 mod commands;
+mod connection;
+mod crypto;
+mod deeplink;
+mod devtools_proxy;
+mod diagnostics;
+mod dictation;
+mod digest;
+mod double_tap;
+mod download;
+mod effects;
+mod ephemeral;
+mod focus;
+mod health;
+mod i18n;
+mod installer;
+mod integrations;
+mod jumplist;
+mod kiosk;
+mod language_assets;
+mod media;
+mod media_keys;
 mod menu;
+mod policy;
+mod preferences;
+mod progress;
+mod proxy;
+mod renderer;
+mod screenshot;
+#[cfg(target_os = "macos")]
+mod services;
+mod session;
+mod sessions;
+mod shortcut_profile;
+mod shortcuts;
+mod spellcheck;
+mod startup;
 mod state;
+mod stats;
+mod switcher;
+mod thumbbar;
+mod titlebar;
 mod tray;
+mod updater;
+mod windows;
+mod workspace_shortcuts;
 
-use tauri::{Emitter, Listener, WindowEvent};
+use nchat_core::{collation, keychain, quote};
+use tauri::{Listener, RunEvent, WindowEvent};
+use tauri_plugin_deep_link::DeepLinkExt;
 
 pub fn run() {
     // T28: optional crash reporting via sentry-tauri.
@@ -21,7 +70,16 @@ pub fn run() {
     });
 
     tauri::Builder::default()
-        .plugin(tauri_plugin_window_state::Builder::new().build())
+        .plugin(
+            // Pop-outs are per conversation; `windows` keeps their state.
+            tauri_plugin_window_state::Builder::new()
+                .with_filter(|label| {
+                    !label.starts_with(windows::CONVERSATION_WINDOW_PREFIX)
+                        && label != switcher::SWITCHER_WINDOW
+                        && label != about::ABOUT_WINDOW
+                })
+                .build(),
+        )
         .plugin(tauri_plugin_autostart::init(
             tauri_plugin_autostart::MacosLauncher::LaunchAgent,
             None,
@@ -31,8 +89,41 @@ pub fn run() {
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_clipboard_manager::init())
+        .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_store::Builder::new().build())
+        .plugin(
+            tauri_plugin_global_shortcut::Builder::new()
+                .with_handler(|app, shortcut, event| {
+                    let handled = kiosk::on_shortcut(app, shortcut, event)
+                        || switcher::on_shortcut(app, shortcut, event)
+                        || screenshot::on_shortcut(app, shortcut, event)
+                        || call_shortcuts::on_shortcut(app, shortcut, event)
+                        || workspace_shortcuts::on_shortcut(app, shortcut, event)
+                        || shortcuts::on_shortcut(app, shortcut, event);
+                    #[cfg(not(target_os = "linux"))]
+                    if !handled {
+                        media_keys::on_shortcut(app, shortcut, event);
+                    }
+                    #[cfg(target_os = "linux")]
+                    let _ = handled;
+                })
+                .build(),
+        )
         .plugin(sentry_tauri::plugin())
+        .manage(menu::MenuState::default())
+        .manage(tray::TrayState::default())
+        .manage(health::HealthState::default())
+        .manage(connection::ConnectionState::default())
+        .manage(ephemeral::EphemeralState::default())
+        .manage(dictation::DictationState::default())
+        .manage(stats::StatsState::default())
+        .manage(media::MediaState::default())
+        .manage(language_assets::LanguageAssetState::default())
+        .manage(updater::UpdaterState::default())
+        .manage(integrations::IntegrationState::default())
+        .manage(focus::FocusState::default())
+        .manage(deeplink::DeepLinkState::default())
+        .manage(windows::WindowRegistry::default())
         .on_menu_event(|app, event| {
             menu::handle_menu_event(app, event.id.as_ref());
         })
@@ -44,6 +135,55 @@ pub fn run() {
             commands::window::window_maximize,
             commands::window::window_close,
             commands::window::window_is_maximized,
+            commands::window::show_window,
+            commands::window::hide_window,
+            commands::window::focus_window,
+            commands::window::is_focused,
+            commands::window::open_conversation_window,
+            commands::window::list_conversation_windows,
+            commands::window::set_always_on_top,
+            commands::window::get_window_mode,
+            commands::window::set_window_mode,
+            commands::window::get_restore_popouts,
+            commands::window::set_restore_popouts,
+            commands::window::create_window,
+            commands::window::open_settings_window,
+            commands::window::enter_kiosk_mode,
+            commands::window::is_kiosk_mode,
+            commands::window::set_window_title_badge,
+            commands::window::set_window_title,
+            commands::window::get_window_effect,
+            commands::window::set_window_effect,
+            commands::window::get_traffic_light_inset,
+            commands::window::set_traffic_light_inset,
+            commands::window::start_window_drag,
+            commands::window::start_window_resize,
+            commands::window::set_window_resize_borders,
+            commands::window::get_displays,
+            commands::window::request_attention,
+            commands::switcher::show_quick_switcher,
+            commands::switcher::hide_quick_switcher,
+            commands::switcher::get_quick_switcher_shortcut,
+            commands::switcher::set_quick_switcher_shortcut,
+            commands::switcher::get_quick_switcher_double_tap,
+            commands::switcher::set_quick_switcher_double_tap,
+            commands::shortcuts::begin_shortcut_capture,
+            commands::shortcuts::shortcut_capture_key,
+            commands::shortcuts::get_global_shortcuts,
+            commands::shortcuts::register_shortcut,
+            commands::shortcuts::unregister_shortcut,
+            commands::shortcuts::set_typing,
+            commands::shortcuts::get_call_shortcuts,
+            commands::shortcuts::set_call_shortcut,
+            commands::shortcuts::get_workspace_shortcuts,
+            commands::shortcuts::set_workspace_shortcut,
+            commands::shortcuts::set_workspace_jump_modifiers,
+            commands::shortcuts::set_workspace_shortcuts_system_wide,
+            commands::shortcuts::export_shortcut_profile,
+            commands::shortcuts::import_shortcut_profile,
+            commands::screenshot::capture_screenshot,
+            commands::screenshot::get_screenshot_shortcut,
+            commands::screenshot::set_screenshot_shortcut,
             commands::shell::shell_open_external,
             commands::shell::shell_show_item_in_folder,
             commands::clipboard::clipboard_read_text,
@@ -51,63 +191,293 @@ pub fn run() {
             commands::clipboard::clipboard_read_image,
             commands::clipboard::clipboard_write_image,
             commands::clipboard::clipboard_has_image,
+            commands::clipboard::copy_messages_as_quote,
             commands::notification::notification_show,
             commands::update::update_check,
+            commands::update::snooze_update,
+            commands::update::skip_update_version,
+            commands::update::install_update,
+            commands::update::install_update_from_path,
+            commands::update::pause_update_download,
+            commands::update::resume_update_download,
+            commands::update::cancel_update_download,
+            commands::update::get_update_channel,
+            commands::update::set_update_channel,
+            commands::update::get_update_source,
+            commands::update::set_update_source,
+            commands::update::download_update,
+            commands::update::install_staged_update,
+            commands::update::get_staged_update,
+            commands::update::set_update_background_download,
+            commands::update::get_rollback_info,
+            commands::update::rollback_update,
+            commands::proxy::get_proxy_settings,
+            commands::proxy::set_proxy_settings,
+            commands::sessions::set_current_session,
+            commands::sessions::clear_current_session,
+            commands::sessions::list_sessions,
+            commands::sessions::revoke_session,
+            commands::sessions::handle_session_revoked,
+            commands::sessions::begin_reauth,
             commands::drag::drag_start_file,
+            commands::download::set_channel_download_dir,
+            commands::download::get_download_dir,
             commands::app::toggle_autostart,
             commands::app::app_set_badge_count,
+            commands::app::set_app_badge,
+            commands::app::set_badge_count,
+            commands::app::clear_badge,
+            commands::app::set_progress,
+            commands::app::set_call_controls,
+            commands::app::set_jump_list,
+            commands::app::get_about_info,
+            commands::migrate::import_legacy_data,
+            commands::debug::enable_remote_debugging,
+            commands::debug::disable_remote_debugging,
+            commands::debug::remote_debugging_status,
+            commands::tray::set_tray_status,
+            commands::tray::set_tray_icon_theme,
+            commands::tray::set_tray_visible,
+            commands::tray::get_tray_availability,
+            commands::tray::update_tray_state,
+            commands::tray::set_tray_middle_click_action,
+            commands::tray::set_tray_only_mode,
+            commands::tray::get_close_behavior,
+            commands::tray::set_close_behavior,
+            commands::bug_report::create_bug_report,
+            commands::diagnostics::collect_diagnostics,
+            commands::health::get_health,
+            commands::health::set_connection_health,
+            commands::health::check_clock_skew,
+            commands::health::get_connection_state_machine,
+            commands::health::report_connecting,
+            commands::health::report_connected,
+            commands::health::report_connection_failure,
+            commands::crypto::register_device_keys,
+            commands::crypto::get_device_fingerprints,
+            commands::crypto::mark_device_verified,
+            commands::crypto::backup_keys_to_server,
+            commands::crypto::restore_keys_from_server,
+            commands::ephemeral::schedule_message_expiry,
+            commands::ephemeral::take_expired_messages,
+            commands::screen_share::get_screen_share_watermark,
+            commands::menu::set_recent_channels,
+            commands::menu::set_menu_item_checked,
+            commands::menu::set_accelerator,
+            commands::menu::reset_accelerators,
+            commands::menu::get_accelerators,
+            commands::menu::show_context_menu,
+            commands::menu::add_menu_items,
+            commands::menu::remove_menu_section,
+            commands::media::open_media_viewer,
+            commands::media::get_media_viewer_items,
+            commands::media::set_media_viewer_position,
+            commands::media::set_media_playback,
+            commands::spellcheck::get_spellcheck_settings,
+            commands::spellcheck::get_spellcheck_languages,
+            commands::spellcheck::set_spellcheck_enabled,
+            commands::spellcheck::set_spellcheck_languages,
+            commands::language_assets::list_language_assets,
+            commands::language_assets::install_language_asset,
+            commands::language_assets::remove_language_asset,
+            commands::startup::get_startup_view,
+            commands::startup::set_startup_view,
+            commands::startup::set_last_location,
+            commands::startup::take_startup_target,
+            commands::startup::get_session_restore,
+            commands::startup::set_session_restore,
+            commands::deeplink::deep_link_ready,
+            commands::deeplink::get_deep_link_audit,
+            commands::deeplink::clear_deep_link_audit,
+            commands::deeplink::get_phone_link_handling,
+            commands::deeplink::set_phone_link_handling,
+            commands::archive::archive_conversation_locally,
+            commands::archive::unarchive_conversation,
+            commands::archive::list_archived_conversations,
+            commands::archive::set_archives_searchable,
+            commands::archive::search_archived_messages,
+            commands::collation::sort_collated,
+            commands::dictation::start_dictation,
+            commands::dictation::stop_dictation,
+            commands::digest::get_digest_settings,
+            commands::digest::set_digest_settings,
+            commands::focus::start_focus_session,
+            commands::focus::stop_focus_session,
+            commands::focus::get_focus_session,
+            commands::stats::set_personal_stats_enabled,
+            commands::stats::record_activity,
+            commands::stats::get_personal_stats,
+            commands::i18n::set_native_locale,
+            commands::integrations::get_integration_settings,
+            commands::integrations::set_integration_settings,
+            commands::integrations::regenerate_integration_token,
+            commands::config::export_app_config,
+            commands::config::import_app_config,
+            commands::renderer::get_renderer_info,
+            commands::renderer::set_gpu_disabled,
+            commands::renderer::report_renderer_ready,
         ])
-        .on_window_event(|window, event| {
-            if window.label() == "main" {
-                if let WindowEvent::CloseRequested { api, .. } = event {
-                    #[cfg(target_os = "macos")]
-                    {
-                        api.prevent_close();
-                        let _ = window.hide();
-                    }
-                    #[cfg(not(target_os = "macos"))]
-                    {
-                        let _ = api;
-                        window.app_handle().exit(0);
+        .on_window_event(|window, event| match event {
+            WindowEvent::CloseRequested { api, .. } if kiosk::is_active() => {
+                api.prevent_close();
+            }
+            WindowEvent::CloseRequested { api, .. } if window.label() == "main" => {
+                tray::on_main_close_requested(window, api);
+            }
+            WindowEvent::CloseRequested { .. }
+                if window.label() == preferences::SETTINGS_WINDOW =>
+            {
+                preferences::on_close(window.app_handle());
+            }
+            WindowEvent::CloseRequested { .. }
+                if window
+                    .label()
+                    .starts_with(windows::CONVERSATION_WINDOW_PREFIX) =>
+            {
+                windows::popout_closed(window.app_handle(), window.label());
+            }
+            WindowEvent::Moved(position) if window.label() == "main" => {
+                windows::on_main_moved(window.app_handle(), *position);
+            }
+            WindowEvent::ThemeChanged(_) if window.label() == "main" => {
+                tray::apply_icon(window.app_handle());
+            }
+            WindowEvent::Focused(false) if window.label() == switcher::SWITCHER_WINDOW => {
+                switcher::on_blur(window.app_handle());
+            }
+            WindowEvent::Focused(focused) => {
+                windows::on_focus_changed(window.app_handle(), *focused);
+                if window.label() == "main" {
+                    shortcuts::on_main_focus_changed(window.app_handle(), *focused);
+                    if *focused {
+                        digest::on_focus(window.app_handle());
                     }
                 }
+                menu::refresh_window_list(window.app_handle(), None);
+                if *focused {
+                    menu::refresh_always_on_top(window.app_handle());
+                }
             }
+            WindowEvent::Destroyed => {
+                windows::forget(window.app_handle(), window.label());
+                menu::refresh_window_list(window.app_handle(), Some(window.label()));
+            }
+            _ => {}
         })
         .setup(|app| {
+            diagnostics::install_file_logger(app.handle());
+            diagnostics::install_panic_hook(app.handle());
+            i18n::restore(app.handle());
             let menu = menu::build_menu(app.handle())?;
             app.set_menu(menu)?;
+            #[cfg(target_os = "macos")]
+            services::register(app.handle());
 
             // T13: wire deep-link handler for nchat:// scheme
             let handle = app.handle().clone();
             app.listen("deep-link://new-url", move |event| {
                 if let Ok(urls) = serde_json::from_str::<Vec<String>>(event.payload()) {
                     for url in urls {
-                        if let Some(win) = handle.get_webview_window("main") {
-                            let _ = win.show();
-                            let _ = win.set_focus();
-                            if url.starts_with("nchat://chat/") {
-                                let room = url.trim_start_matches("nchat://chat/");
-                                let _ = win.emit("deep-link:chat", room.to_string());
-                            } else if url.starts_with("nchat://invite/") {
-                                let token = url.trim_start_matches("nchat://invite/");
-                                let _ = win.emit("deep-link:invite", token.to_string());
-                            }
-                        }
+                        deeplink::route(&handle, &url);
                     }
                 }
             });
+            // A link the app was launched with is picked up by the
+            // frontend through `take_startup_target` once it has loaded.
+            let mut urls: Vec<String> = app
+                .deep_link()
+                .get_current()
+                .ok()
+                .flatten()
+                .unwrap_or_default()
+                .iter()
+                .map(|u| u.to_string())
+                .collect();
+            urls.extend(deeplink::launch_argument());
+            if !urls.is_empty() {
+                startup::set_launch_links(&urls);
+                // Integration requests, calls and universal links are routed
+                // instead; the queue delivers them once the frontend is
+                // ready.
+                for url in &urls {
+                    match deeplink::receive(app.handle(), url) {
+                        Some(link) if link.routed_at_launch() => {
+                            deeplink::dispatch(app.handle(), url, link)
+                        }
+                        _ => {}
+                    }
+                }
+            }
 
-            #[cfg(any(target_os = "macos", target_os = "windows"))]
-            tray::build_tray(app)?;
+            if tray::is_enabled(app.handle()) {
+                #[cfg(any(target_os = "macos", target_os = "windows"))]
+                tray::build_tray(app.handle())?;
 
-            #[cfg(target_os = "linux")]
-            {
-                eprintln!("[nchat-desktop] warning: system tray may not be available on this Linux session");
-                let _ = tray::build_tray(app);
+                #[cfg(target_os = "linux")]
+                {
+                    match tray::host_available() {
+                        Ok(()) => {
+                            let _ = tray::build_tray(app.handle());
+                        }
+                        Err(reason) => {
+                            eprintln!("[nchat-desktop] warning: system tray unavailable: {reason}");
+                            tray::notify_unavailable(app.handle());
+                        }
+                    }
+                }
             }
 
+            // Launch-time webview settings must be in place before the
+            // main window (declared with `create: false`) is built.
+            commands::debug::restore_remote_debugging(app.handle());
+            renderer::apply_launch_settings(app.handle());
+            windows::create_main_window(app.handle())?;
+            windows::restore_window_mode(app.handle());
+            windows::restore_popouts(app.handle());
+            session::restore(app.handle());
+            shortcuts::register_all(app.handle());
+            double_tap::restore(app.handle());
+            kiosk::restore(app.handle());
+            menu::refresh_window_list(app.handle(), None);
+            focus::restore(app.handle());
+            tray::apply_icon(app.handle());
+            tray::apply_tray_only(app.handle());
+            spellcheck::apply(app.handle());
+            health::start_heartbeat(app.handle());
+            ephemeral::start(app.handle());
+            digest::start(app.handle());
+            updater::start_background(app.handle());
+            sessions::start(app.handle());
+
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running nchat desktop");
+        .build(tauri::generate_context!())
+        .expect("error while running nchat desktop")
+        .run(|app, event| {
+            if let RunEvent::ExitRequested { code, api, .. } = &event {
+                // Only the exit shortcut unlocks kiosk mode; restarts (e.g.
+                // to install an update) still go through.
+                if kiosk::is_active() && *code != Some(tauri::RESTART_EXIT_CODE) {
+                    api.prevent_exit();
+                    return;
+                }
+                // Windows are still alive here, unlike at `Exit`.
+                windows::save_popouts(app);
+                windows::save_mini_dock(app);
+                session::snapshot(app);
+            }
+            // Closing the main window only hides it on macOS; clicking the
+            // Dock icon brings it back.
+            #[cfg(target_os = "macos")]
+            if let RunEvent::Reopen { .. } = event {
+                tray::show_main(app);
+            }
+            if let RunEvent::Exit = event {
+                renderer::on_exit(app);
+                // A staged background update replaces the app on quit.
+                if let Err(e) = updater::install_staged(app) {
+                    log::warn!("[nchat-desktop] failed to install staged update: {e}");
+                }
+            }
+        });
 }
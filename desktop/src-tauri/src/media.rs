@@ -0,0 +1,174 @@
+// nChat Desktop — fullscreen media viewer
+//
+// Shared images and videos open in a dedicated borderless window that
+// covers a chosen display. The viewer navigates across the media cached
+// for the same conversation: attachments are stored one folder per
+// conversation, so the siblings of the opened file form its index. The
+// webview loads them through the asset protocol, whose scope in
+// tauri.conf.json is limited to the attachments folder this module opens.
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+use tauri::{AppHandle, Emitter, Manager, WebviewUrl, WebviewWindow, WebviewWindowBuilder};
+
+/// Label of the viewer window; there is at most one.
+pub const VIEWER_WINDOW: &str = "media-viewer";
+
+/// Frontend route rendered in the viewer window.
+const VIEWER_ROUTE: &str = "index.html#/media-viewer";
+
+/// Folder of the app cache holding attachments, one subfolder per
+/// conversation; must match the asset protocol scope.
+const ATTACHMENTS_DIR: &str = "attachments";
+
+const MEDIA_EXTENSIONS: &[&str] = &[
+    "png", "jpg", "jpeg", "gif", "webp", "avif", "heic", "bmp", "mp4", "m4v", "mov", "webm", "mkv",
+];
+
+#[derive(Serialize, Clone, Default)]
+pub struct MediaIndex {
+    /// Absolute paths of the conversation's media, oldest first.
+    pub items: Vec<String>,
+    /// Position of the item currently shown.
+    pub index: usize,
+}
+
+#[derive(Default)]
+pub struct MediaState {
+    current: Mutex<MediaIndex>,
+}
+
+fn is_media(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| MEDIA_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+}
+
+/// `path` resolved, if it is inside the attachments folder.
+fn attachment_path(app: &AppHandle, path: &Path) -> Option<PathBuf> {
+    let root = app
+        .path()
+        .app_cache_dir()
+        .ok()?
+        .join(ATTACHMENTS_DIR)
+        .canonicalize()
+        .ok()?;
+    let path = path.canonicalize().ok()?;
+    path.starts_with(root).then_some(path)
+}
+
+/// Media files next to `path`, ordered by modification time so navigation
+/// follows the order they were received.
+fn index_for(path: &Path) -> Result<MediaIndex, String> {
+    let dir = path.parent().ok_or("attachment has no parent folder")?;
+    let mut files: Vec<(SystemTime, PathBuf)> = std::fs::read_dir(dir)
+        .map_err(|e| e.to_string())?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && is_media(p))
+        .map(|p| {
+            let modified = p
+                .metadata()
+                .and_then(|m| m.modified())
+                .unwrap_or(SystemTime::UNIX_EPOCH);
+            (modified, p)
+        })
+        .collect();
+    files.sort();
+    let index = files.iter().position(|(_, p)| p == path).unwrap_or(0);
+    Ok(MediaIndex {
+        items: files
+            .into_iter()
+            .map(|(_, p)| p.to_string_lossy().to_string())
+            .collect(),
+        index,
+    })
+}
+
+/// Move `win` onto the given display (by position in the system's monitor
+/// list) and make it cover it. Without a monitor, the main window's display
+/// is used.
+fn place_on_monitor(
+    app: &AppHandle,
+    win: &WebviewWindow,
+    monitor: Option<usize>,
+) -> Result<(), String> {
+    let target = match monitor {
+        Some(i) => app
+            .available_monitors()
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .nth(i)
+            .ok_or_else(|| format!("no monitor at index {i}"))?,
+        None => app
+            .get_webview_window(crate::windows::MAIN_WINDOW)
+            .and_then(|main| main.current_monitor().ok().flatten())
+            .or_else(|| app.primary_monitor().ok().flatten())
+            .ok_or("no monitor available")?,
+    };
+    // Fullscreen always applies to the display the window is on, so leave
+    // fullscreen, move, then re-enter.
+    win.set_fullscreen(false).map_err(|e| e.to_string())?;
+    win.set_position(*target.position())
+        .map_err(|e| e.to_string())?;
+    win.set_size(*target.size()).map_err(|e| e.to_string())?;
+    win.set_fullscreen(true).map_err(|e| e.to_string())
+}
+
+/// Open `attachment` in the viewer on `monitor`, reusing the viewer window
+/// if it is already open. The viewer reads the index with `current` on load
+/// and receives `media-viewer:update` when it changes afterwards.
+pub fn open(
+    app: &AppHandle,
+    attachment: &str,
+    monitor: Option<usize>,
+) -> Result<MediaIndex, String> {
+    let path = attachment_path(app, Path::new(attachment))
+        .ok_or("attachment is not in the app's media cache")?;
+    if !is_media(&path) {
+        return Err("attachment is not an image or video".into());
+    }
+    let media = index_for(&path)?;
+    *app.state::<MediaState>().current.lock().unwrap() = media.clone();
+
+    let win = match app.get_webview_window(VIEWER_WINDOW) {
+        Some(win) => {
+            let _ = win.emit("media-viewer:update", media.clone());
+            win
+        }
+        None => {
+            let win =
+                WebviewWindowBuilder::new(app, VIEWER_WINDOW, WebviewUrl::App(VIEWER_ROUTE.into()))
+                    .title("nChat")
+                    .decorations(false)
+                    .visible(false)
+                    .build()
+                    .map_err(|e| e.to_string())?;
+            crate::tray::apply_tray_only(app);
+            win
+        }
+    };
+    place_on_monitor(app, &win, monitor)?;
+    win.show().map_err(|e| e.to_string())?;
+    win.set_focus().map_err(|e| e.to_string())?;
+    Ok(media)
+}
+
+/// The media list and position the viewer should show.
+pub fn current(app: &AppHandle) -> MediaIndex {
+    app.state::<MediaState>().current.lock().unwrap().clone()
+}
+
+/// Record the viewer's position after keyboard navigation, so reopening
+/// it resumes where the user left off.
+pub fn set_position(app: &AppHandle, index: usize) -> Result<(), String> {
+    let state = app.state::<MediaState>();
+    let mut current = state.current.lock().unwrap();
+    if index >= current.items.len() {
+        return Err(format!("media index {index} out of range"));
+    }
+    current.index = index;
+    Ok(())
+}
@@ -0,0 +1,404 @@
+// nChat Desktop — media keys for voice message playback
+//
+// While a voice message or audio attachment plays, the keyboard's play/pause,
+// next, previous and stop keys control it. The frontend reports playback
+// with `set_media_playback` and receives the keys as `media:key`
+// (`play-pause`, `play`, `pause`, `stop`, `next` or `previous`) in the window
+// that reported it. On Linux the app appears as an MPRIS player, which is
+// how desktops route media keys there and show it in their media controls;
+// elsewhere the keys are taken as global shortcuts. Both are released when
+// playback ends, so other players get the keys back.
+
+use serde::Deserialize;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+#[cfg(not(target_os = "linux"))]
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutEvent, ShortcutState};
+
+use crate::windows::MAIN_WINDOW;
+
+/// Media keys taken as global shortcuts, with the action each sends.
+#[cfg(not(target_os = "linux"))]
+const MEDIA_SHORTCUTS: &[(&str, &str)] = &[
+    ("MediaPlayPause", "play-pause"),
+    ("MediaStop", "stop"),
+    ("MediaTrackNext", "next"),
+    ("MediaTrackPrevious", "previous"),
+];
+
+/// Well-known bus name of the MPRIS player.
+#[cfg(target_os = "linux")]
+const MPRIS_NAME: &str = "org.mpris.MediaPlayer2.nchat";
+
+#[cfg(target_os = "linux")]
+const MPRIS_PATH: &str = "/org/mpris/MediaPlayer2";
+
+/// The playing audio and the window playing it.
+static CURRENT: Mutex<Option<(Playback, String)>> = Mutex::new(None);
+
+/// The MPRIS player's bus connection; dropping it removes the player.
+#[cfg(target_os = "linux")]
+static MPRIS: Mutex<Option<zbus::blocking::Connection>> = Mutex::new(None);
+
+/// Audio the frontend is playing.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Playback {
+    /// Shown by the desktop's media controls, e.g. "Voice message".
+    pub title: String,
+    /// Who sent it.
+    #[serde(default)]
+    pub sender: Option<String>,
+    pub playing: bool,
+    #[serde(default)]
+    pub duration_ms: Option<u64>,
+    #[serde(default)]
+    pub has_next: bool,
+    #[serde(default)]
+    pub has_previous: bool,
+}
+
+/// Report the audio playing in `window`, or `None` once it stops.
+pub fn set_playback(app: &AppHandle, window: &str, playback: Option<Playback>) {
+    let active = playback.is_some();
+    let was_active = {
+        let mut current = CURRENT.lock().unwrap();
+        let was_active = current.is_some();
+        *current = playback.map(|playback| (playback, window.to_string()));
+        was_active
+    };
+    match (was_active, active) {
+        (false, true) => register(app),
+        (true, false) => unregister(app),
+        #[cfg(target_os = "linux")]
+        (true, true) => mpris::changed(),
+        _ => {}
+    }
+}
+
+/// Send `action` to the window playing the audio.
+fn send(app: &AppHandle, action: &str) {
+    let label = CURRENT
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|(_, label)| label.clone());
+    let win = label
+        .and_then(|label| app.get_webview_window(&label))
+        .or_else(|| app.get_webview_window(MAIN_WINDOW));
+    if let Some(win) = win {
+        let _ = win.emit("media:key", action);
+    }
+}
+
+/// Take the media keys again after the global shortcuts were all released,
+/// if something is playing. The MPRIS player is not affected.
+pub fn register_shortcuts(app: &AppHandle) {
+    #[cfg(not(target_os = "linux"))]
+    if CURRENT.lock().unwrap().is_some() {
+        register(app);
+    }
+    #[cfg(target_os = "linux")]
+    let _ = app;
+}
+
+#[cfg(not(target_os = "linux"))]
+fn register(app: &AppHandle) {
+    let global = app.global_shortcut();
+    for (key, _) in MEDIA_SHORTCUTS {
+        if global.is_registered(*key) {
+            continue;
+        }
+        if let Err(e) = global.register(*key) {
+            log::warn!("[nchat-desktop] failed to take media key {key}: {e}");
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn unregister(app: &AppHandle) {
+    let global = app.global_shortcut();
+    for (key, _) in MEDIA_SHORTCUTS {
+        let _ = global.unregister(*key);
+    }
+}
+
+/// Global shortcut handler. Returns whether the shortcut was a media key.
+#[cfg(not(target_os = "linux"))]
+pub fn on_shortcut(app: &AppHandle, shortcut: &Shortcut, event: ShortcutEvent) -> bool {
+    let action = MEDIA_SHORTCUTS.iter().find_map(|(key, action)| {
+        key.parse::<Shortcut>()
+            .is_ok_and(|own| own.id() == shortcut.id())
+            .then_some(*action)
+    });
+    let Some(action) = action else {
+        return false;
+    };
+    if event.state == ShortcutState::Pressed {
+        send(app, action);
+    }
+    true
+}
+
+#[cfg(target_os = "linux")]
+fn register(app: &AppHandle) {
+    match mpris::connect(app) {
+        Ok(conn) => *MPRIS.lock().unwrap() = Some(conn),
+        Err(e) => log::warn!("[nchat-desktop] failed to register the MPRIS player: {e}"),
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn unregister(_app: &AppHandle) {
+    MPRIS.lock().unwrap().take();
+}
+
+#[cfg(target_os = "linux")]
+mod mpris {
+    use std::collections::HashMap;
+    use tauri::{AppHandle, Manager};
+    use zbus::zvariant::{ObjectPath, Value};
+
+    use super::{send, Playback, CURRENT, MPRIS, MPRIS_NAME, MPRIS_PATH};
+    use crate::windows::MAIN_WINDOW;
+
+    const PLAYER_INTERFACE: &str = "org.mpris.MediaPlayer2.Player";
+
+    pub fn connect(app: &AppHandle) -> zbus::Result<zbus::blocking::Connection> {
+        zbus::blocking::connection::Builder::session()?
+            .name(MPRIS_NAME)?
+            .serve_at(MPRIS_PATH, Root { app: app.clone() })?
+            .serve_at(MPRIS_PATH, Player { app: app.clone() })?
+            .build()
+    }
+
+    fn playback() -> Option<Playback> {
+        CURRENT
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|(playback, _)| playback.clone())
+    }
+
+    fn status(playback: &Option<Playback>) -> &'static str {
+        match playback {
+            Some(playback) if playback.playing => "Playing",
+            Some(_) => "Paused",
+            None => "Stopped",
+        }
+    }
+
+    fn metadata(playback: &Option<Playback>) -> HashMap<String, Value<'static>> {
+        let mut metadata = HashMap::new();
+        let Some(playback) = playback else {
+            return metadata;
+        };
+        let track = ObjectPath::from_static_str_unchecked("/org/nself/chat/track");
+        metadata.insert("mpris:trackid".to_string(), Value::from(track));
+        metadata.insert(
+            "xesam:title".to_string(),
+            Value::from(playback.title.clone()),
+        );
+        if let Some(sender) = &playback.sender {
+            metadata.insert(
+                "xesam:artist".to_string(),
+                Value::from(vec![sender.clone()]),
+            );
+        }
+        if let Some(duration) = playback.duration_ms {
+            // Microseconds.
+            let length = i64::try_from(duration.saturating_mul(1000)).unwrap_or(i64::MAX);
+            metadata.insert("mpris:length".to_string(), Value::from(length));
+        }
+        metadata
+    }
+
+    /// Tell the desktop the status, metadata or controls changed.
+    pub fn changed() {
+        let connection = MPRIS.lock().unwrap();
+        let Some(conn) = connection.as_ref() else {
+            return;
+        };
+        let playback = playback();
+        let (has_next, has_previous) = playback
+            .as_ref()
+            .map_or((false, false), |p| (p.has_next, p.has_previous));
+        let properties = HashMap::from([
+            ("PlaybackStatus", Value::from(status(&playback))),
+            ("Metadata", Value::from(metadata(&playback))),
+            ("CanGoNext", Value::from(has_next)),
+            ("CanGoPrevious", Value::from(has_previous)),
+        ]);
+        let result = conn.emit_signal(
+            None::<zbus::names::BusName<'_>>,
+            MPRIS_PATH,
+            "org.freedesktop.DBus.Properties",
+            "PropertiesChanged",
+            &(PLAYER_INTERFACE, properties, Vec::<String>::new()),
+        );
+        if let Err(e) = result {
+            log::warn!("[nchat-desktop] failed to update the MPRIS player: {e}");
+        }
+    }
+
+    struct Root {
+        app: AppHandle,
+    }
+
+    #[zbus::interface(name = "org.mpris.MediaPlayer2")]
+    impl Root {
+        fn raise(&self) {
+            if let Some(win) = self.app.get_webview_window(MAIN_WINDOW) {
+                let _ = win.show();
+                let _ = win.unminimize();
+                let _ = win.set_focus();
+            }
+        }
+
+        fn quit(&self) {}
+
+        #[zbus(property)]
+        fn can_quit(&self) -> bool {
+            false
+        }
+
+        #[zbus(property)]
+        fn can_raise(&self) -> bool {
+            true
+        }
+
+        #[zbus(property)]
+        fn has_track_list(&self) -> bool {
+            false
+        }
+
+        #[zbus(property)]
+        fn identity(&self) -> String {
+            self.app.package_info().name.clone()
+        }
+
+        #[zbus(property)]
+        fn supported_uri_schemes(&self) -> Vec<String> {
+            Vec::new()
+        }
+
+        #[zbus(property)]
+        fn supported_mime_types(&self) -> Vec<String> {
+            Vec::new()
+        }
+    }
+
+    struct Player {
+        app: AppHandle,
+    }
+
+    #[zbus::interface(name = "org.mpris.MediaPlayer2.Player")]
+    impl Player {
+        fn play_pause(&self) {
+            send(&self.app, "play-pause");
+        }
+
+        fn play(&self) {
+            send(&self.app, "play");
+        }
+
+        fn pause(&self) {
+            send(&self.app, "pause");
+        }
+
+        fn stop(&self) {
+            send(&self.app, "stop");
+        }
+
+        fn next(&self) {
+            send(&self.app, "next");
+        }
+
+        fn previous(&self) {
+            send(&self.app, "previous");
+        }
+
+        /// Seeking is not offered (`CanSeek` is false).
+        fn seek(&self, _offset: i64) {}
+
+        fn set_position(&self, _track: ObjectPath<'_>, _position: i64) {}
+
+        fn open_uri(&self, _uri: &str) {}
+
+        #[zbus(property)]
+        fn playback_status(&self) -> String {
+            status(&playback()).to_string()
+        }
+
+        #[zbus(property)]
+        fn loop_status(&self) -> String {
+            "None".to_string()
+        }
+
+        #[zbus(property)]
+        fn rate(&self) -> f64 {
+            1.0
+        }
+
+        #[zbus(property)]
+        fn minimum_rate(&self) -> f64 {
+            1.0
+        }
+
+        #[zbus(property)]
+        fn maximum_rate(&self) -> f64 {
+            1.0
+        }
+
+        #[zbus(property)]
+        fn shuffle(&self) -> bool {
+            false
+        }
+
+        #[zbus(property)]
+        fn metadata(&self) -> HashMap<String, Value<'static>> {
+            metadata(&playback())
+        }
+
+        #[zbus(property)]
+        fn volume(&self) -> f64 {
+            1.0
+        }
+
+        /// Not tracked; reported as the start.
+        #[zbus(property)]
+        fn position(&self) -> i64 {
+            0
+        }
+
+        #[zbus(property)]
+        fn can_go_next(&self) -> bool {
+            playback().is_some_and(|p| p.has_next)
+        }
+
+        #[zbus(property)]
+        fn can_go_previous(&self) -> bool {
+            playback().is_some_and(|p| p.has_previous)
+        }
+
+        #[zbus(property)]
+        fn can_play(&self) -> bool {
+            true
+        }
+
+        #[zbus(property)]
+        fn can_pause(&self) -> bool {
+            true
+        }
+
+        #[zbus(property)]
+        fn can_seek(&self) -> bool {
+            false
+        }
+
+        #[zbus(property)]
+        fn can_control(&self) -> bool {
+            true
+        }
+    }
+}
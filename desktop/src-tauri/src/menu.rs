@@ -1,45 +1,700 @@
 // nChat Desktop — native menu builder (Tauri 2)
 
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
 use tauri::{
-    menu::{MenuBuilder, PredefinedMenuItem, SubmenuBuilder},
-    AppHandle, Emitter, Manager, Wry,
+    menu::{
+        CheckMenuItem, MenuBuilder, MenuItem, MenuItemKind, PredefinedMenuItem, Submenu,
+        SubmenuBuilder,
+    },
+    AppHandle, Emitter, EventTarget, LogicalPosition, Manager, WebviewWindow, Wry,
 };
+use tauri_plugin_global_shortcut::Shortcut;
+
+use crate::i18n::t;
+use crate::state::settings;
+
+/// Settings key holding the user's accelerator overrides (action → accel).
+/// An empty string unbinds the action.
+const ACCELERATORS_KEY: &str = "menu.accelerators";
+
+/// Remappable menu actions and their default accelerators.
+const DEFAULT_ACCELERATORS: &[(&str, &str)] = &[
+    ("new-conversation", "CmdOrCtrl+N"),
+    ("preferences", "CmdOrCtrl+,"),
+    ("find", "CmdOrCtrl+F"),
+    ("dictation", "CmdOrCtrl+Shift+Space"),
+    ("toggle-sidebar", "CmdOrCtrl+Shift+D"),
+    ("zoom-in", "CmdOrCtrl+="),
+    ("zoom-out", "CmdOrCtrl+-"),
+    ("actual-size", "CmdOrCtrl+0"),
+    ("compact-mode", ""),
+    ("always-on-top", ""),
+    ("mute-notifications", "CmdOrCtrl+Shift+M"),
+    ("quick-switcher", "CmdOrCtrl+K"),
+    ("report-problem", ""),
+    ("collect-diagnostics", ""),
+    ("rollback-update", ""),
+];
+
+/// Check items mirroring UI toggles, with their initial state. The
+/// frontend keeps them in sync through `set_menu_item_checked`.
+const CHECK_ITEMS: &[(&str, bool)] = &[
+    ("toggle-sidebar", true),
+    ("compact-mode", false),
+    ("mute-notifications", false),
+];
+
+const MODIFIERS: &[&str] = &[
+    "cmdorctrl",
+    "commandorcontrol",
+    "cmd",
+    "command",
+    "ctrl",
+    "control",
+    "alt",
+    "option",
+    "shift",
+    "super",
+];
+
+/// Id of the Go submenu and its runtime-populated "Recent Channels" child.
+const GO_MENU_ID: &str = "go";
+const RECENT_MENU_ID: &str = "go-recent";
+
+/// Prefix for recent-channel item ids; the channel id follows it.
+const RECENT_ITEM_PREFIX: &str = "go-channel:";
+
+/// Id of the Window submenu, whose tail lists the open windows.
+const WINDOW_MENU_ID: &str = "window";
+
+/// Prefix for open-window item ids; the window label follows it.
+const WINDOW_ITEM_PREFIX: &str = "win:";
+
+/// Prefix for context-menu item ids; the frontend's item id follows it.
+const CONTEXT_ITEM_PREFIX: &str = "ctx:";
+
+/// Prefix for Edit → Spelling language items; the dictionary code follows.
+const SPELL_LANGUAGE_PREFIX: &str = "spell-lang:";
+
+/// Prefix for custom section ids; items append `ITEM_SEPARATOR` and their id.
+const CUSTOM_PREFIX: &str = "ext:";
+const ITEM_SEPARATOR: char = '\u{1f}';
+
+/// Only the first nine entries get a Cmd/Ctrl+digit accelerator.
+const MAX_ACCELERATED: usize = 9;
+
+#[derive(Deserialize, Clone)]
+pub struct RecentChannel {
+    pub id: String,
+    pub name: String,
+}
+
+/// One entry of a frontend-defined menu (context menus, custom sections).
+#[derive(Deserialize, Clone)]
+pub struct MenuEntry {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub label: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Render a separator; `id` and `label` are ignored.
+    #[serde(default)]
+    pub separator: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Runtime menu contents that must survive a menu rebuild.
+#[derive(Default)]
+pub struct MenuState {
+    recent: Mutex<Vec<RecentChannel>>,
+    checked: Mutex<HashMap<String, bool>>,
+    /// Frontend-defined top-level sections, in insertion order.
+    custom: Mutex<Vec<(String, Vec<MenuEntry>)>>,
+    /// Label of the window the last context menu was shown in.
+    context_window: Mutex<Option<String>>,
+}
+
+#[derive(Serialize, Clone)]
+struct CustomMenuEvent {
+    section: String,
+    id: String,
+}
+
+/// Accelerator currently bound to `action`, honouring user overrides.
+fn accelerator(overrides: &HashMap<String, String>, action: &str) -> Option<String> {
+    let accel = match overrides.get(action) {
+        Some(accel) => accel.as_str(),
+        None => DEFAULT_ACCELERATORS
+            .iter()
+            .find(|(id, _)| *id == action)
+            .map(|(_, accel)| *accel)?,
+    };
+    (!accel.is_empty()).then(|| accel.to_string())
+}
+
+/// Menu item for a remappable action.
+fn action_item(
+    app: &AppHandle,
+    overrides: &HashMap<String, String>,
+    action: &str,
+    text: &str,
+) -> tauri::Result<MenuItem<Wry>> {
+    MenuItem::with_id(app, action, text, true, accelerator(overrides, action))
+}
+
+/// Check item for a remappable toggle, showing the last known UI state.
+fn check_item(
+    app: &AppHandle,
+    overrides: &HashMap<String, String>,
+    action: &str,
+    text: &str,
+) -> tauri::Result<CheckMenuItem<Wry>> {
+    let checked = app
+        .state::<MenuState>()
+        .checked
+        .lock()
+        .unwrap()
+        .get(action)
+        .copied()
+        .or_else(|| {
+            CHECK_ITEMS
+                .iter()
+                .find(|(id, _)| *id == action)
+                .map(|(_, checked)| *checked)
+        })
+        .unwrap_or(false);
+    let accel = accelerator(overrides, action);
+    CheckMenuItem::with_id(app, action, text, true, checked, accel)
+}
+
+/// Reject strings the native menu would silently drop, e.g. `Cmd+`,
+/// `Hyper+K` or `Ctrl+Foo`. The key is checked with the shortcut parser,
+/// which shares its grammar with the menu's.
+fn validate_accelerator(accel: &str) -> Result<(), String> {
+    let mut parts: Vec<&str> = accel.split('+').map(str::trim).collect();
+    let key = parts.pop().unwrap_or_default();
+    let modifiers_ok = parts
+        .iter()
+        .all(|m| MODIFIERS.contains(&m.to_ascii_lowercase().as_str()));
+    if key.is_empty() || !modifiers_ok {
+        return Err(format!("invalid accelerator: {accel}"));
+    }
+    accel
+        .parse::<Shortcut>()
+        .map_err(|e| format!("invalid accelerator {accel}: {e}"))?;
+    Ok(())
+}
+
+fn recent_placeholder(app: &AppHandle) -> tauri::Result<MenuItem<Wry>> {
+    MenuItem::with_id(
+        app,
+        "go-recent-empty",
+        t("menu.no_recent_channels"),
+        false,
+        None::<&str>,
+    )
+}
+
+/// Edit → Spelling: the spellcheck toggle and one check item per installed
+/// dictionary. On Windows the languages are the system's, shown checked
+/// and read-only, with a link to the Windows settings to change them.
+fn spelling_submenu(app: &AppHandle) -> tauri::Result<Submenu<Wry>> {
+    let config = crate::spellcheck::settings(app);
+    let submenu = SubmenuBuilder::new(app, t("menu.spelling"))
+        .item(&CheckMenuItem::with_id(
+            app,
+            "spellcheck",
+            t("menu.check_spelling"),
+            true,
+            config.enabled,
+            None::<&str>,
+        )?)
+        .build()?;
+    let languages = crate::spellcheck::available_languages();
+    let choosable = !cfg!(target_os = "windows");
+    if !languages.is_empty() || !choosable {
+        submenu.append(&PredefinedMenuItem::separator(app)?)?;
+    }
+    for code in languages {
+        let checked = !choosable || config.languages.contains(&code);
+        submenu.append(&CheckMenuItem::with_id(
+            app,
+            format!("{SPELL_LANGUAGE_PREFIX}{code}"),
+            &code,
+            config.enabled && choosable,
+            checked,
+            None::<&str>,
+        )?)?;
+    }
+    #[cfg(target_os = "windows")]
+    submenu.append(&MenuItem::with_id(
+        app,
+        "spell-language-settings",
+        t("menu.language_settings"),
+        true,
+        None::<&str>,
+    )?)?;
+    Ok(submenu)
+}
 
 /// Build the native application menu for all platforms.
 /// Returns a fully configured `Menu<Wry>` ready to pass to `Builder::menu()`.
 pub fn build_menu(app: &AppHandle) -> tauri::Result<tauri::menu::Menu<Wry>> {
-    let file_menu = SubmenuBuilder::new(app, "File")
-        .text("new-conversation", "New Conversation")
+    let keys: HashMap<String, String> = settings::load(app, ACCELERATORS_KEY).unwrap_or_default();
+    let new_conversation = action_item(app, &keys, "new-conversation", t("menu.new_conversation"))?;
+    let preferences = action_item(app, &keys, "preferences", t("menu.preferences"))?;
+    let find = action_item(app, &keys, "find", t("menu.find"))?;
+    let dictation = action_item(app, &keys, "dictation", t("menu.dictation"))?;
+    dictation.set_enabled(crate::dictation::is_available())?;
+    let toggle_sidebar = check_item(app, &keys, "toggle-sidebar", t("menu.show_sidebar"))?;
+    let compact_mode = check_item(app, &keys, "compact-mode", t("menu.compact_mode"))?;
+    let mute = check_item(
+        app,
+        &keys,
+        "mute-notifications",
+        t("menu.mute_notifications"),
+    )?;
+    // Follows the focused window rather than a UI toggle.
+    let on_top = focused_window(app)
+        .and_then(|win| win.is_always_on_top().ok())
+        .unwrap_or(false);
+    let always_on_top = CheckMenuItem::with_id(
+        app,
+        "always-on-top",
+        t("menu.always_on_top"),
+        true,
+        on_top,
+        accelerator(&keys, "always-on-top"),
+    )?;
+    let zoom_in = action_item(app, &keys, "zoom-in", t("menu.zoom_in"))?;
+    let zoom_out = action_item(app, &keys, "zoom-out", t("menu.zoom_out"))?;
+    let actual_size = action_item(app, &keys, "actual-size", t("menu.actual_size"))?;
+    let quick_switcher = action_item(app, &keys, "quick-switcher", t("menu.jump_to"))?;
+    let report_problem = action_item(app, &keys, "report-problem", t("menu.report_problem"))?;
+    let collect_diagnostics = action_item(
+        app,
+        &keys,
+        "collect-diagnostics",
+        t("menu.collect_diagnostics"),
+    )?;
+    let rollback = action_item(app, &keys, "rollback-update", t("menu.rollback_update"))?;
+    let about = MenuItem::with_id(app, "about", t("menu.about"), true, None::<&str>)?;
+    rollback.set_enabled(crate::updater::rollback_info(app).is_some())?;
+    let spelling = spelling_submenu(app)?;
+
+    let file_menu = SubmenuBuilder::new(app, t("menu.file"))
+        .item(&new_conversation)
         .item(&PredefinedMenuItem::separator(app)?)
-        .text("preferences", "Preferences…")
+        .item(&preferences)
         .item(&PredefinedMenuItem::separator(app)?)
-        .quit()
+        .quit_with_text(t("menu.quit"))
         .build()?;
 
-    let edit_menu = SubmenuBuilder::new(app, "Edit")
-        .undo()
-        .redo()
+    let edit_menu = SubmenuBuilder::new(app, t("menu.edit"))
+        .undo_with_text(t("menu.undo"))
+        .redo_with_text(t("menu.redo"))
+        .item(&PredefinedMenuItem::separator(app)?)
+        .cut_with_text(t("menu.cut"))
+        .copy_with_text(t("menu.copy"))
+        .paste_with_text(t("menu.paste"))
+        .select_all_with_text(t("menu.select_all"))
         .item(&PredefinedMenuItem::separator(app)?)
-        .cut()
-        .copy()
-        .paste()
-        .select_all()
+        .item(&find)
+        .item(&spelling)
+        .item(&dictation)
         .build()?;
 
-    let view_menu = SubmenuBuilder::new(app, "View")
-        .text("toggle-sidebar", "Toggle Sidebar")
-        .fullscreen()
+    let view_menu = SubmenuBuilder::new(app, t("menu.view"))
+        .item(&toggle_sidebar)
+        .item(&compact_mode)
+        .item(&PredefinedMenuItem::separator(app)?)
+        .item(&mute)
+        .item(&PredefinedMenuItem::separator(app)?)
+        .item(&actual_size)
+        .item(&zoom_in)
+        .item(&zoom_out)
+        .item(&PredefinedMenuItem::separator(app)?)
+        .item(&always_on_top)
+        .fullscreen_with_text(t("menu.fullscreen"))
+        .build()?;
+
+    let window_menu = SubmenuBuilder::with_id(app, WINDOW_MENU_ID, t("menu.window"))
+        .minimize_with_text(t("menu.minimize"))
+        .text("zoom", t("menu.zoom"))
+        .item(&PredefinedMenuItem::separator(app)?)
+        .text("bring-to-front", t("menu.bring_all_to_front"))
+        .item(&PredefinedMenuItem::separator(app)?)
+        .build()?;
+    fill_window_list(app, &window_menu, None)?;
+
+    let recent_menu =
+        SubmenuBuilder::with_id(app, RECENT_MENU_ID, t("menu.recent_channels")).build()?;
+    let recent = app.state::<MenuState>().recent.lock().unwrap().clone();
+    fill_recent(app, &recent_menu, &recent)?;
+
+    let go_menu = SubmenuBuilder::with_id(app, GO_MENU_ID, t("menu.go"))
+        .item(&quick_switcher)
+        .item(&PredefinedMenuItem::separator(app)?)
+        .item(&recent_menu)
         .build()?;
 
-    let window_menu = SubmenuBuilder::new(app, "Window")
-        .minimize()
+    let help_menu = SubmenuBuilder::new(app, t("menu.help"))
+        .item(&report_problem)
+        .item(&collect_diagnostics)
+        .item(&PredefinedMenuItem::separator(app)?)
+        .item(&rollback)
         .item(&PredefinedMenuItem::separator(app)?)
-        .text("bring-to-front", "Bring All to Front")
+        .item(&about)
         .build()?;
 
-    MenuBuilder::new(app)
-        .items(&[&file_menu, &edit_menu, &view_menu, &window_menu])
-        .build()
+    let custom = app.state::<MenuState>().custom.lock().unwrap().clone();
+    let custom_menus = custom
+        .iter()
+        .map(|(section, entries)| custom_section(app, section, entries))
+        .collect::<tauri::Result<Vec<_>>>()?;
+
+    let mut builder =
+        MenuBuilder::new(app).items(&[&file_menu, &edit_menu, &view_menu, &go_menu, &window_menu]);
+    for submenu in &custom_menus {
+        builder = builder.item(submenu);
+    }
+    builder.item(&help_menu).build()
+}
+
+/// Submenu for a frontend-defined section, placed before Help.
+fn custom_section(
+    app: &AppHandle,
+    section: &str,
+    entries: &[MenuEntry],
+) -> tauri::Result<Submenu<Wry>> {
+    let submenu =
+        SubmenuBuilder::with_id(app, format!("{CUSTOM_PREFIX}{section}"), section).build()?;
+    for entry in entries {
+        if entry.separator {
+            submenu.append(&PredefinedMenuItem::separator(app)?)?;
+        } else {
+            let id = format!("{CUSTOM_PREFIX}{section}{ITEM_SEPARATOR}{}", entry.id);
+            submenu.append(&MenuItem::with_id(
+                app,
+                id,
+                &entry.label,
+                entry.enabled,
+                None::<&str>,
+            )?)?;
+        }
+    }
+    Ok(submenu)
+}
+
+/// Append entries to a frontend-defined top-level section (e.g. installed
+/// integrations or admin tools), creating it if needed. Clicks arrive in
+/// the focused window as `menu:custom-item` events with the section and
+/// entry id.
+pub fn add_items(app: &AppHandle, section: &str, entries: Vec<MenuEntry>) -> Result<(), String> {
+    if section.is_empty() || section.contains(ITEM_SEPARATOR) {
+        return Err(format!("invalid menu section name: {section:?}"));
+    }
+    {
+        let state = app.state::<MenuState>();
+        let mut custom = state.custom.lock().unwrap();
+        match custom.iter_mut().find(|(name, _)| name == section) {
+            Some((_, existing)) => existing.extend(entries),
+            None => custom.push((section.to_string(), entries)),
+        }
+    }
+    rebuild(app).map_err(|e| e.to_string())
+}
+
+/// Drop a frontend-defined section and everything in it.
+pub fn remove_section(app: &AppHandle, section: &str) -> Result<(), String> {
+    app.state::<MenuState>()
+        .custom
+        .lock()
+        .unwrap()
+        .retain(|(name, _)| name != section);
+    rebuild(app).map_err(|e| e.to_string())
+}
+
+/// Depth-first lookup of a menu item by id across all submenus.
+fn find_item(items: Vec<MenuItemKind<Wry>>, id: &str) -> Option<MenuItemKind<Wry>> {
+    items.into_iter().find_map(|item| {
+        if item.id() == id {
+            return Some(item);
+        }
+        let children = item.as_submenu()?.items().ok()?;
+        find_item(children, id)
+    })
+}
+
+fn recent_submenu(app: &AppHandle) -> Option<Submenu<Wry>> {
+    // `Menu::get` only looks at top-level items, so walk down by hand.
+    let go = app.menu()?.get(GO_MENU_ID)?;
+    let recent = go.as_submenu()?.get(RECENT_MENU_ID)?;
+    recent.as_submenu().cloned()
+}
+
+fn fill_recent(
+    app: &AppHandle,
+    submenu: &Submenu<Wry>,
+    channels: &[RecentChannel],
+) -> tauri::Result<()> {
+    for item in submenu.items()? {
+        submenu.remove(&item)?;
+    }
+    if channels.is_empty() {
+        return submenu.append(&recent_placeholder(app)?);
+    }
+    for (i, channel) in channels.iter().enumerate() {
+        let accelerator = (i < MAX_ACCELERATED).then(|| recent_channel_accelerator(i + 1));
+        let item = MenuItem::with_id(
+            app,
+            format!("{RECENT_ITEM_PREFIX}{}", channel.id),
+            &channel.name,
+            true,
+            accelerator,
+        )?;
+        submenu.append(&item)?;
+    }
+    Ok(())
+}
+
+/// Replace the open-window entries at the end of the Window menu, checking
+/// the focused one. `closing` is left out: it may still be registered while
+/// its `Destroyed` event is handled.
+fn fill_window_list(
+    app: &AppHandle,
+    submenu: &Submenu<Wry>,
+    closing: Option<&str>,
+) -> tauri::Result<()> {
+    for item in submenu.items()? {
+        if item.id().as_ref().starts_with(WINDOW_ITEM_PREFIX) {
+            submenu.remove(&item)?;
+        }
+    }
+    let mut windows: Vec<_> = app
+        .webview_windows()
+        .into_values()
+        .filter(|win| {
+            Some(win.label()) != closing && win.label() != crate::switcher::SWITCHER_WINDOW
+        })
+        .collect();
+    windows.sort_by(|a, b| a.label().cmp(b.label()));
+    for win in windows {
+        let title = win.title().unwrap_or_else(|_| win.label().to_string());
+        let item = CheckMenuItem::with_id(
+            app,
+            format!("{WINDOW_ITEM_PREFIX}{}", win.label()),
+            title,
+            true,
+            win.is_focused().unwrap_or(false),
+            None::<&str>,
+        )?;
+        submenu.append(&item)?;
+    }
+    Ok(())
+}
+
+/// Refresh the Window menu's list of open windows after one was created,
+/// focused or closed.
+pub fn refresh_window_list(app: &AppHandle, closing: Option<&str>) {
+    let submenu = app
+        .menu()
+        .and_then(|menu| menu.get(WINDOW_MENU_ID))
+        .and_then(|item| item.as_submenu().cloned());
+    if let Some(submenu) = submenu {
+        if let Err(e) = fill_window_list(app, &submenu, closing) {
+            log::warn!("[nchat-desktop] failed to update Window menu: {e}");
+        }
+    }
+}
+
+/// Show whether the focused window is pinned in View → Always on Top.
+pub fn refresh_always_on_top(app: &AppHandle) {
+    let Some(win) = focused_window(app) else {
+        return;
+    };
+    let on_top = win.is_always_on_top().unwrap_or(false);
+    let item = app
+        .menu()
+        .and_then(|menu| find_item(menu.items().ok()?, "always-on-top"));
+    if let Some(check) = item.as_ref().and_then(|item| item.as_check_menuitem()) {
+        let _ = check.set_checked(on_top);
+    }
+}
+
+/// The window the user is working in, falling back to the main window.
+fn focused_window(app: &AppHandle) -> Option<WebviewWindow> {
+    app.webview_windows()
+        .into_values()
+        .find(|win| win.is_focused().unwrap_or(false))
+        .or_else(|| app.get_webview_window("main"))
+}
+
+/// Send `event` to `win` alone. `Emitter::emit` broadcasts to every window
+/// whichever handle it is called on, so pop-outs and settings would act on
+/// clicks meant for another window.
+pub(crate) fn emit_to_window<S: Serialize + Clone>(win: &WebviewWindow, event: &str, payload: S) {
+    let _ = win.emit_to(EventTarget::webview_window(win.label()), event, payload);
+}
+
+/// Replace the Go → Recent Channels entries. The first nine channels are
+/// bound to Cmd/Ctrl+1..9.
+pub fn set_recent_channels(app: &AppHandle, channels: Vec<RecentChannel>) -> tauri::Result<()> {
+    *app.state::<MenuState>().recent.lock().unwrap() = channels.clone();
+    match recent_submenu(app) {
+        Some(submenu) => fill_recent(app, &submenu, &channels),
+        None => Ok(()),
+    }
+}
+
+/// Pop up a native context menu at `position` (logical pixels from the
+/// window's top-left corner). The chosen item is reported to that window
+/// as a `menu:context-item` event carrying its id.
+pub fn show_context_menu(
+    window: &WebviewWindow,
+    items: &[MenuEntry],
+    position: (f64, f64),
+) -> tauri::Result<()> {
+    let app = window.app_handle();
+    *app.state::<MenuState>().context_window.lock().unwrap() = Some(window.label().to_string());
+    let menu = tauri::menu::Menu::new(app)?;
+    for item in items {
+        if item.separator {
+            menu.append(&PredefinedMenuItem::separator(app)?)?;
+        } else {
+            let id = format!("{CONTEXT_ITEM_PREFIX}{}", item.id);
+            menu.append(&MenuItem::with_id(
+                app,
+                id,
+                &item.label,
+                item.enabled,
+                None::<&str>,
+            )?)?;
+        }
+    }
+    window.popup_menu_at(&menu, LogicalPosition::new(position.0, position.1))
+}
+
+/// Reflect UI state in a check item. The state is kept so it survives menu
+/// rebuilds.
+pub fn set_item_checked(app: &AppHandle, id: &str, checked: bool) -> Result<(), String> {
+    if !CHECK_ITEMS.iter().any(|(item, _)| *item == id) {
+        return Err(format!("not a checkable menu item: {id}"));
+    }
+    app.state::<MenuState>()
+        .checked
+        .lock()
+        .unwrap()
+        .insert(id.to_string(), checked);
+    let item = app
+        .menu()
+        .and_then(|menu| find_item(menu.items().ok()?, id));
+    if let Some(check) = item.as_ref().and_then(|item| item.as_check_menuitem()) {
+        check.set_checked(checked).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Record the state a check item toggled to and tell the frontend.
+fn toggle_checked(app: &AppHandle, id: &str) {
+    let checked = app
+        .menu()
+        .and_then(|menu| find_item(menu.items().ok()?, id))
+        .and_then(|item| item.as_check_menuitem()?.is_checked().ok());
+    let Some(checked) = checked else {
+        return;
+    };
+    app.state::<MenuState>()
+        .checked
+        .lock()
+        .unwrap()
+        .insert(id.to_string(), checked);
+    if let Some(win) = app.get_webview_window("main") {
+        emit_to_window(&win, &format!("menu:{id}"), checked);
+    }
+}
+
+/// Rebuild and re-attach the application menu, e.g. after a keybinding
+/// change.
+pub fn rebuild(app: &AppHandle) -> tauri::Result<()> {
+    let menu = build_menu(app)?;
+    app.set_menu(menu)?;
+    Ok(())
+}
+
+/// Bind `action` to `accel` (an empty string unbinds it) and rebuild the
+/// menu.
+pub fn set_accelerator(app: &AppHandle, action: &str, accel: &str) -> Result<(), String> {
+    if !DEFAULT_ACCELERATORS.iter().any(|(id, _)| *id == action) {
+        return Err(format!("unknown menu action: {action}"));
+    }
+    if !accel.is_empty() {
+        validate_accelerator(accel)?;
+    }
+    let mut keys: HashMap<String, String> =
+        settings::load(app, ACCELERATORS_KEY).unwrap_or_default();
+    keys.insert(action.to_string(), accel.to_string());
+    settings::save(app, ACCELERATORS_KEY, &keys)?;
+    rebuild(app).map_err(|e| e.to_string())
+}
+
+/// Bind several actions at once (an empty accelerator unbinds one) and
+/// rebuild the menu once. Nothing is saved if any entry is invalid.
+pub fn set_accelerators(app: &AppHandle, bindings: &HashMap<String, String>) -> Result<(), String> {
+    for (action, accel) in bindings {
+        if !DEFAULT_ACCELERATORS.iter().any(|(id, _)| id == action) {
+            return Err(format!("unknown menu action: {action}"));
+        }
+        if !accel.is_empty() {
+            validate_accelerator(accel)?;
+        }
+    }
+    let mut keys: HashMap<String, String> =
+        settings::load(app, ACCELERATORS_KEY).unwrap_or_default();
+    keys.extend(bindings.clone());
+    settings::save(app, ACCELERATORS_KEY, &keys)?;
+    rebuild(app).map_err(|e| e.to_string())
+}
+
+/// Drop every override and restore the default shortcuts.
+pub fn reset_accelerators(app: &AppHandle) -> Result<(), String> {
+    settings::remove(app, ACCELERATORS_KEY)?;
+    rebuild(app).map_err(|e| e.to_string())
+}
+
+/// Effective bindings for the keyboard-shortcuts settings page.
+pub fn accelerators(app: &AppHandle) -> HashMap<String, String> {
+    let keys: HashMap<String, String> = settings::load(app, ACCELERATORS_KEY).unwrap_or_default();
+    DEFAULT_ACCELERATORS
+        .iter()
+        .map(|(action, _)| {
+            let accel = accelerator(&keys, action).unwrap_or_default();
+            (action.to_string(), accel)
+        })
+        .collect()
+}
+
+fn recent_channel_accelerator(position: usize) -> String {
+    format!("CmdOrCtrl+{position}")
+}
+
+/// Accelerators of the Go → Recent Channels entries by position, as
+/// (`go-channel-<n>`, accelerator), whether or not the entry exists yet.
+pub fn recent_channel_accelerators() -> Vec<(String, String)> {
+    (1..=MAX_ACCELERATED)
+        .map(|position| {
+            (
+                format!("go-channel-{position}"),
+                recent_channel_accelerator(position),
+            )
+        })
+        .collect()
 }
 
 /// Wire menu event handlers after the menu is attached to the app.
@@ -49,25 +704,160 @@ pub fn handle_menu_event(app: &AppHandle, event_id: &str) {
             if let Some(win) = app.get_webview_window("main") {
                 let _ = win.show();
                 let _ = win.set_focus();
-                let _ = win.emit("menu:new-conversation", ());
+                emit_to_window(&win, "menu:new-conversation", ());
             }
         }
         "preferences" => {
+            if let Err(e) = crate::preferences::open(app, None) {
+                log::warn!("[nchat-desktop] failed to open settings: {e}");
+            }
+        }
+        "toggle-sidebar" | "compact-mode" | "mute-notifications" => {
+            // The item has already flipped; the payload is its new state.
+            toggle_checked(app, event_id);
+        }
+        "dictation" => crate::dictation::toggle(app),
+        "spellcheck" => {
+            let enabled = crate::spellcheck::settings(app).enabled;
+            if crate::spellcheck::set_enabled(app, !enabled).is_ok() {
+                // Language items are only enabled while spellcheck is on.
+                let _ = rebuild(app);
+            }
+        }
+        #[cfg(target_os = "windows")]
+        "spell-language-settings" => crate::spellcheck::open_language_settings(),
+        id if id.starts_with(SPELL_LANGUAGE_PREFIX) => {
+            let code = &id[SPELL_LANGUAGE_PREFIX.len()..];
+            if let Err(e) = crate::spellcheck::toggle_language(app, code) {
+                log::warn!("[nchat-desktop] spellcheck: {e}");
+            }
+        }
+        "find" | "quick-switcher" => {
+            if let Some(win) = app.get_webview_window("main") {
+                let _ = win.show();
+                let _ = win.set_focus();
+                emit_to_window(&win, &format!("menu:{event_id}"), ());
+            }
+        }
+        "report-problem" => {
+            // The frontend asks for consent (screenshot, logs) and then
+            // calls `create_bug_report`.
             if let Some(win) = app.get_webview_window("main") {
                 let _ = win.show();
                 let _ = win.set_focus();
-                let _ = win.emit("menu:preferences", ());
+                emit_to_window(&win, "menu:report-problem", ());
             }
         }
-        "toggle-sidebar" => {
+        "rollback-update" => {
+            // The frontend confirms the target version and then calls
+            // `rollback_update`.
             if let Some(win) = app.get_webview_window("main") {
-                let _ = win.emit("menu:toggle-sidebar", ());
+                let _ = win.show();
+                let _ = win.set_focus();
+                emit_to_window(
+                    &win,
+                    "menu:rollback-update",
+                    crate::updater::rollback_info(app),
+                );
+            }
+        }
+        "about" => {
+            if let Err(e) = crate::about::open(app) {
+                log::warn!("[nchat-desktop] failed to open About window: {e}");
             }
         }
+        "collect-diagnostics" => {
+            let app = app.clone();
+            std::thread::spawn(move || {
+                let result = crate::diagnostics::collect(&app).and_then(|path| {
+                    crate::commands::shell::shell_show_item_in_folder(
+                        path.to_string_lossy().to_string(),
+                    )
+                });
+                if let Err(e) = result {
+                    log::warn!("[nchat-desktop] collecting diagnostics failed: {e}");
+                }
+            });
+        }
         "bring-to-front" => {
+            for win in app.webview_windows().values() {
+                let _ = win.unminimize();
+                let _ = win.show();
+            }
+            if let Some(win) = focused_window(app) {
+                let _ = win.set_focus();
+            }
+        }
+        "zoom-in" | "zoom-out" | "actual-size" => {
+            if let Some(win) = focused_window(app) {
+                let result = match event_id {
+                    "zoom-in" => crate::windows::step_zoom(&win, 1),
+                    "zoom-out" => crate::windows::step_zoom(&win, -1),
+                    _ => crate::windows::set_zoom(&win, 1.0),
+                };
+                if let Err(e) = result {
+                    log::warn!("[nchat-desktop] zoom failed: {e}");
+                }
+            }
+        }
+        "always-on-top" => {
+            if let Some(win) = focused_window(app) {
+                let on = !win.is_always_on_top().unwrap_or(false);
+                if let Err(e) = crate::windows::set_always_on_top(app, win.label(), on) {
+                    log::warn!("[nchat-desktop] always on top failed: {e}");
+                    // Clicking toggled the check mark; put it back.
+                    refresh_always_on_top(app);
+                }
+            }
+        }
+        "zoom" => {
+            if let Some(win) = focused_window(app) {
+                let _ = match win.is_maximized() {
+                    Ok(true) => win.unmaximize(),
+                    _ => win.maximize(),
+                };
+            }
+        }
+        id if id.starts_with(WINDOW_ITEM_PREFIX) => {
+            if let Some(win) = app.get_webview_window(&id[WINDOW_ITEM_PREFIX.len()..]) {
+                let _ = win.unminimize();
+                let _ = win.show();
+                let _ = win.set_focus();
+            }
+            // Clicking toggled the check mark; restore it from real focus.
+            refresh_window_list(app, None);
+        }
+        id if id.starts_with(CUSTOM_PREFIX) => {
+            let rest = &id[CUSTOM_PREFIX.len()..];
+            let Some((section, item)) = rest.split_once(ITEM_SEPARATOR) else {
+                return;
+            };
+            // The menu bar clicked belongs to the focused window (the key
+            // window on macOS).
+            if let Some(win) = focused_window(app) {
+                let event = CustomMenuEvent {
+                    section: section.to_string(),
+                    id: item.to_string(),
+                };
+                emit_to_window(&win, "menu:custom-item", event);
+            }
+        }
+        id if id.starts_with(CONTEXT_ITEM_PREFIX) => {
+            let label = app
+                .state::<MenuState>()
+                .context_window
+                .lock()
+                .unwrap()
+                .take();
+            if let Some(win) = label.and_then(|label| app.get_webview_window(&label)) {
+                emit_to_window(&win, "menu:context-item", &id[CONTEXT_ITEM_PREFIX.len()..]);
+            }
+        }
+        id if id.starts_with(RECENT_ITEM_PREFIX) => {
             if let Some(win) = app.get_webview_window("main") {
                 let _ = win.show();
                 let _ = win.set_focus();
+                emit_to_window(&win, "menu:go-channel", &id[RECENT_ITEM_PREFIX.len()..]);
             }
         }
         _ => {}
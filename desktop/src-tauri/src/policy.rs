@@ -0,0 +1,126 @@
+// nChat Desktop — admin-managed policy for managed deployments
+
+use serde::Deserialize;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// Machine-wide policy file written by IT (MDM, GPO, config management).
+/// Users cannot change it from the app; a missing file means "unmanaged".
+fn policy_path() -> PathBuf {
+    if cfg!(target_os = "macos") {
+        PathBuf::from("/Library/Application Support/nChat/policy.json")
+    } else if cfg!(target_os = "windows") {
+        std::env::var_os("ProgramData")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(r"C:\ProgramData"))
+            .join("nChat")
+            .join("policy.json")
+    } else {
+        PathBuf::from("/etc/nchat/policy.json")
+    }
+}
+
+#[derive(Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Policy {
+    /// Periodic health reporting to an admin endpoint (opt-in via policy).
+    pub heartbeat: Option<HeartbeatPolicy>,
+    /// Identity overlay on outgoing screen shares, for leak tracing.
+    pub screen_share_watermark: Option<WatermarkPolicy>,
+    /// Local speech-to-text program used for dictation.
+    pub dictation: Option<DictationPolicy>,
+    /// Update source for self-hosted deployments.
+    pub updater: Option<UpdaterPolicy>,
+    /// Hosts of self-hosted servers whose `https` links open in the app.
+    pub deep_link_hosts: Vec<String>,
+    /// Kiosk mode settings for shared displays.
+    pub kiosk: Option<KioskPolicy>,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HeartbeatPolicy {
+    pub endpoint: String,
+    #[serde(default = "default_heartbeat_interval")]
+    pub interval_secs: u64,
+}
+
+fn default_heartbeat_interval() -> u64 {
+    300
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WatermarkPolicy {
+    /// Overlay text; `{name}` and `{email}` are replaced with the signed-in
+    /// user's identity.
+    #[serde(default = "default_watermark_template")]
+    pub template: String,
+    /// 0.0–1.0; kept faint so shared content stays readable.
+    #[serde(default = "default_watermark_opacity")]
+    pub opacity: f32,
+}
+
+fn default_watermark_template() -> String {
+    "{name} <{email}>".into()
+}
+
+fn default_watermark_opacity() -> f32 {
+    0.12
+}
+
+/// A local recognizer that captures the microphone itself and prints one
+/// recognized segment per line on stdout (e.g. whisper.cpp's `stream`).
+/// Only settable by policy: the webview can write the settings store, and
+/// it must never be able to choose a program to run.
+#[derive(Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DictationPolicy {
+    pub program: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+/// The exit shortcut is the only way out of kiosk mode, so it is set here
+/// rather than from the webview.
+#[derive(Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct KioskPolicy {
+    /// Accelerator leaving kiosk mode, e.g. `"CmdOrCtrl+Alt+Shift+F12"`.
+    pub exit_shortcut: Option<String>,
+}
+
+/// Where updates come from. The public key decides which packages get
+/// installed, so a key can only come from here unless the policy allows
+/// users to set their own.
+#[derive(Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct UpdaterPolicy {
+    /// Fixed manifest URL; users cannot change it when set.
+    pub endpoint: Option<String>,
+    /// Base64 minisign public key replacing the compiled-in one.
+    pub pubkey: Option<String>,
+    /// Let users set a public key along with a custom endpoint.
+    pub allow_custom_pubkey: bool,
+    /// Let packages installed from disk be older than the running version.
+    pub allow_downgrade: bool,
+}
+
+/// The policy in effect, read once at first use. A malformed file is logged
+/// and treated as unmanaged rather than preventing startup.
+pub fn current() -> &'static Policy {
+    static POLICY: OnceLock<Policy> = OnceLock::new();
+    POLICY.get_or_init(|| {
+        let path = policy_path();
+        let Ok(raw) = std::fs::read_to_string(&path) else {
+            return Policy::default();
+        };
+        serde_json::from_str(&raw).unwrap_or_else(|e| {
+            log::warn!(
+                "[nchat-desktop] ignoring malformed policy {}: {e}",
+                path.display()
+            );
+            Policy::default()
+        })
+    })
+}
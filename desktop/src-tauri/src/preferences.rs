@@ -0,0 +1,89 @@
+// nChat Desktop — the settings window
+//
+// Preferences open in their own window rather than as a route of the main
+// window, so they can stay open beside a conversation. There is at most one;
+// opening it again focuses it and moves it to the requested section. Its
+// size and position are kept by the window-state plugin, and the session
+// snapshot reopens it at launch. Closing it only closes it, whatever the
+// main window's close behavior, and hands focus back to the main window.
+
+use tauri::{AppHandle, Emitter, Manager, WebviewUrl, WebviewWindowBuilder};
+
+use crate::windows::MAIN_WINDOW;
+
+/// Label of the settings window; the `settings` capability matches it.
+pub const SETTINGS_WINDOW: &str = "settings";
+
+/// Frontend route of the settings window; a section follows as
+/// `/settings/<section>`.
+pub const SETTINGS_ROUTE: &str = "/settings";
+
+/// Section names are route segments such as `notifications` or
+/// `keyboard-shortcuts`.
+fn valid_section(section: &str) -> bool {
+    !section.is_empty()
+        && section.len() <= 64
+        && section
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+/// Show the settings window, creating it on first use, optionally at
+/// `section`. An open window is sent `settings:navigate` with the section.
+pub fn open(app: &AppHandle, section: Option<&str>) -> Result<(), String> {
+    if crate::kiosk::is_active() {
+        return Err("not available in kiosk mode".into());
+    }
+    if let Some(section) = section.filter(|s| !valid_section(s)) {
+        return Err(format!("invalid settings section: {section}"));
+    }
+    if let Some(win) = app.get_webview_window(SETTINGS_WINDOW) {
+        if let Some(section) = section {
+            let _ = win.emit("settings:navigate", section);
+        }
+        let result = (|| {
+            win.show()?;
+            win.unminimize()?;
+            win.set_focus()
+        })();
+        return result.map_err(|e| e.to_string());
+    }
+    let route = match section {
+        Some(section) => format!("index.html#{SETTINGS_ROUTE}/{section}"),
+        None => format!("index.html#{SETTINGS_ROUTE}"),
+    };
+    let win = WebviewWindowBuilder::new(app, SETTINGS_WINDOW, WebviewUrl::App(route.into()))
+        .title(crate::i18n::t("menu.preferences"))
+        .inner_size(720.0, 560.0)
+        .min_inner_size(560.0, 420.0)
+        .minimizable(false)
+        .maximizable(false)
+        .center()
+        .build()
+        .map_err(|e| e.to_string())?;
+    crate::tray::apply_tray_only(app);
+    win.set_focus().map_err(|e| e.to_string())
+}
+
+/// Reopen the settings window at launch on the route it showed at quit.
+pub fn restore(app: &AppHandle, route: &str) {
+    let section = route
+        .strip_prefix(SETTINGS_ROUTE)
+        .and_then(|rest| rest.strip_prefix('/'))
+        .filter(|section| valid_section(section));
+    if let Err(e) = open(app, section) {
+        log::warn!("[nchat-desktop] failed to restore the settings window: {e}");
+    }
+}
+
+/// Called when the settings window is asked to close: the main window gets
+/// `settings:closed` and the focus back.
+pub fn on_close(app: &AppHandle) {
+    let Some(main) = app.get_webview_window(MAIN_WINDOW) else {
+        return;
+    };
+    let _ = main.emit("settings:closed", ());
+    if main.is_visible().unwrap_or(false) {
+        let _ = main.set_focus();
+    }
+}
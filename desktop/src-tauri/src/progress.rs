@@ -0,0 +1,113 @@
+// nChat Desktop — taskbar and Dock progress
+//
+// Long transfers show a progress bar on the main window's taskbar button
+// (Windows), on the Dock icon (macOS) and on Unity launcher entries (Linux),
+// so they can be followed without opening the window. Uploads and
+// downloads report through `set_progress`; update downloads report here
+// directly. The bar shows all active sources combined.
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::window::{ProgressBarState, ProgressBarStatus};
+use tauri::{AppHandle, Manager};
+
+use crate::windows::MAIN_WINDOW;
+
+/// Active progress by source.
+static SOURCES: Mutex<Option<HashMap<ProgressSource, Progress>>> = Mutex::new(None);
+
+/// What the progress belongs to.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum ProgressSource {
+    /// Uploads and downloads, reported by the frontend.
+    Transfers,
+    Update,
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum ProgressState {
+    /// No progress; removes the bar.
+    None,
+    #[default]
+    Normal,
+    /// Busy without a known fraction. Shown as a normal bar on macOS and
+    /// Linux.
+    Indeterminate,
+    Paused,
+    Error,
+}
+
+#[derive(Clone, Copy)]
+struct Progress {
+    /// Fraction done, 0–1.
+    value: Option<f64>,
+    state: ProgressState,
+}
+
+/// Report progress for `source`: `value` is the fraction done (0–1).
+/// `ProgressState::None` ends it.
+pub fn set(
+    app: &AppHandle,
+    source: ProgressSource,
+    value: Option<f64>,
+    state: ProgressState,
+) -> Result<(), String> {
+    if value.is_some_and(|value| !(0.0..=1.0).contains(&value)) {
+        return Err("progress must be between 0 and 1".into());
+    }
+    let combined = {
+        let mut sources = SOURCES.lock().unwrap();
+        let sources = sources.get_or_insert_with(HashMap::new);
+        if state == ProgressState::None {
+            sources.remove(&source);
+        } else {
+            // A state change alone keeps the last known fraction.
+            let value = value.or_else(|| sources.get(&source).and_then(|p| p.value));
+            sources.insert(source, Progress { value, state });
+        }
+        combine(sources.values())
+    };
+    let Some(win) = app.get_webview_window(MAIN_WINDOW) else {
+        return Ok(());
+    };
+    win.set_progress_bar(combined).map_err(|e| e.to_string())
+}
+
+/// Update the state of `source` without a new fraction. Failures are only
+/// logged: progress is a hint, not worth failing a transfer over.
+pub fn set_state(app: &AppHandle, source: ProgressSource, state: ProgressState) {
+    if let Err(e) = set(app, source, None, state) {
+        log::warn!("[nchat-desktop] failed to update taskbar progress: {e}");
+    }
+}
+
+/// One bar for all sources: the average of the known fractions, and the
+/// most pressing state (error, then paused).
+fn combine<'a>(sources: impl Iterator<Item = &'a Progress>) -> ProgressBarState {
+    let sources: Vec<&Progress> = sources.collect();
+    if sources.is_empty() {
+        return ProgressBarState {
+            status: Some(ProgressBarStatus::None),
+            progress: None,
+        };
+    }
+    let values: Vec<f64> = sources.iter().filter_map(|p| p.value).collect();
+    let has = |state| sources.iter().any(|p| p.state == state);
+    let status = if has(ProgressState::Error) {
+        ProgressBarStatus::Error
+    } else if has(ProgressState::Paused) {
+        ProgressBarStatus::Paused
+    } else if values.is_empty() {
+        ProgressBarStatus::Indeterminate
+    } else {
+        ProgressBarStatus::Normal
+    };
+    let progress = (!values.is_empty())
+        .then(|| (values.iter().sum::<f64>() / values.len() as f64 * 100.0).round() as u64);
+    ProgressBarState {
+        status: Some(status),
+        progress,
+    }
+}
@@ -0,0 +1,112 @@
+// nChat Desktop — HTTP proxy configuration for native requests
+//
+// By default native requests follow the system proxy (environment
+// variables everywhere, plus the system configuration on macOS and the
+// registry on Windows). Users behind a proxy the system does not describe
+// can set one here; its password is kept in the OS keychain.
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::keychain;
+use crate::state::settings;
+
+const SETTINGS_KEY: &str = "network.proxy";
+const PASSWORD_ACCOUNT: &str = "proxy-password";
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ProxyMode {
+    #[default]
+    System,
+    Manual,
+    /// Connect directly, ignoring any system proxy.
+    None,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct ProxySettings {
+    pub mode: ProxyMode,
+    /// `http://host:port`, `https://…` or `socks5://…`; used in manual mode.
+    pub url: Option<String>,
+    pub username: Option<String>,
+}
+
+/// A resolved proxy choice, ready to apply to any `reqwest` client.
+#[derive(Clone)]
+pub enum ProxyConfig {
+    System,
+    Direct,
+    Manual(reqwest::Proxy),
+}
+
+impl ProxyConfig {
+    pub fn apply(&self, builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+        match self {
+            Self::System => builder,
+            Self::Direct => builder.no_proxy(),
+            Self::Manual(proxy) => builder.proxy(proxy.clone()),
+        }
+    }
+}
+
+pub fn load(app: &AppHandle) -> ProxySettings {
+    settings::load(app, SETTINGS_KEY).unwrap_or_default()
+}
+
+fn build_manual(
+    settings: &ProxySettings,
+    password: Option<&str>,
+) -> Result<reqwest::Proxy, String> {
+    let url = settings
+        .url
+        .as_deref()
+        .filter(|u| !u.trim().is_empty())
+        .ok_or("a proxy URL is required in manual mode")?;
+    let proxy = reqwest::Proxy::all(url.trim()).map_err(|e| e.to_string())?;
+    Ok(
+        match settings.username.as_deref().filter(|u| !u.is_empty()) {
+            Some(user) => proxy.basic_auth(user, password.unwrap_or_default()),
+            None => proxy,
+        },
+    )
+}
+
+/// Save the proxy settings. `password` replaces the stored one when given;
+/// an empty string removes it.
+pub fn configure(
+    app: &AppHandle,
+    new: ProxySettings,
+    password: Option<String>,
+) -> Result<(), String> {
+    if new.mode == ProxyMode::Manual {
+        // Reject unusable settings before they break every request.
+        build_manual(&new, password.as_deref())?;
+    }
+    match password.as_deref() {
+        Some("") => keychain::delete_secret(PASSWORD_ACCOUNT)?,
+        Some(password) => keychain::store_secret(PASSWORD_ACCOUNT, password)?,
+        None => {}
+    }
+    settings::save(app, SETTINGS_KEY, &new)
+}
+
+pub fn current(app: &AppHandle) -> Result<ProxyConfig, String> {
+    let settings = load(app);
+    Ok(match settings.mode {
+        ProxyMode::System => ProxyConfig::System,
+        ProxyMode::None => ProxyConfig::Direct,
+        ProxyMode::Manual => {
+            let password = keychain::read_secret(PASSWORD_ACCOUNT)?;
+            ProxyConfig::Manual(build_manual(&settings, password.as_deref())?)
+        }
+    })
+}
+
+/// An HTTP client that honours the proxy settings.
+pub fn client(app: &AppHandle) -> Result<reqwest::Client, String> {
+    current(app)?
+        .apply(reqwest::Client::builder())
+        .build()
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,114 @@
+// nChat Desktop — webview renderer diagnostics and software fallback
+//
+// Some GPU/driver combinations (notably NVIDIA on Linux) leave the webview
+// black. Hardware acceleration can be turned off persistently, with the
+// `--disable-gpu` launch flag, or automatically after several launches in
+// a row that ended abnormally before the frontend reported that it
+// rendered. Quitting normally before then is not counted.
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::state::settings;
+
+const DISABLE_GPU_KEY: &str = "renderer.disableGpu";
+const AUTO_FALLBACK_KEY: &str = "renderer.autoFallback";
+const LAUNCH_PENDING_KEY: &str = "renderer.launchPending";
+const FAILED_LAUNCHES_KEY: &str = "renderer.failedLaunches";
+
+/// Launches without a ready signal before falling back to software.
+const MAX_FAILED_LAUNCHES: u32 = 3;
+
+/// Command-line switch that turns the persistent toggle on.
+const DISABLE_GPU_FLAG: &str = "--disable-gpu";
+
+#[derive(Serialize)]
+pub struct RendererInfo {
+    /// Webview engine version (WebView2, WebKitGTK or WKWebView).
+    pub webview_version: Option<String>,
+    pub gpu_disabled: bool,
+    /// The toggle was turned on by the crash fallback, not by the user.
+    pub auto_fallback: bool,
+    /// Consecutive launches that crashed before reporting a rendered frame.
+    pub failed_launches: u32,
+    /// Whether this platform honours the toggle.
+    pub supported: bool,
+}
+
+pub fn gpu_disabled(app: &AppHandle) -> bool {
+    settings::load(app, DISABLE_GPU_KEY).unwrap_or(false)
+}
+
+/// Turn hardware acceleration off or on for the next launch. Clearing the
+/// toggle also resets the crash fallback.
+pub fn set_gpu_disabled(app: &AppHandle, disabled: bool) -> Result<(), String> {
+    settings::save(app, DISABLE_GPU_KEY, &disabled)?;
+    settings::save(app, AUTO_FALLBACK_KEY, &false)?;
+    if !disabled {
+        settings::save(app, FAILED_LAUNCHES_KEY, &0u32)?;
+    }
+    Ok(())
+}
+
+/// Record that the webview rendered, so this launch is not counted as a
+/// failure.
+pub fn mark_ready(app: &AppHandle) -> Result<(), String> {
+    settings::save(app, LAUNCH_PENDING_KEY, &false)?;
+    settings::save(app, FAILED_LAUNCHES_KEY, &0u32)
+}
+
+/// Called on a normal quit: a launch the user ended before the first frame
+/// did not crash the renderer.
+pub fn on_exit(app: &AppHandle) {
+    let _ = settings::save(app, LAUNCH_PENDING_KEY, &false);
+}
+
+/// Count failed launches and apply the renderer settings. Must run before
+/// the main window is created.
+pub fn apply_launch_settings(app: &AppHandle) {
+    if std::env::args().any(|arg| arg == DISABLE_GPU_FLAG) {
+        let _ = settings::save(app, DISABLE_GPU_KEY, &true);
+    }
+
+    let mut failed: u32 = settings::load(app, FAILED_LAUNCHES_KEY).unwrap_or(0);
+    if settings::load(app, LAUNCH_PENDING_KEY).unwrap_or(false) {
+        failed += 1;
+        let _ = settings::save(app, FAILED_LAUNCHES_KEY, &failed);
+    }
+    if failed >= MAX_FAILED_LAUNCHES && !gpu_disabled(app) {
+        log::warn!(
+            "[nchat-desktop] renderer did not start {failed} times in a row; disabling GPU acceleration"
+        );
+        let _ = settings::save(app, DISABLE_GPU_KEY, &true);
+        let _ = settings::save(app, AUTO_FALLBACK_KEY, &true);
+    }
+    let _ = settings::save(app, LAUNCH_PENDING_KEY, &true);
+
+    if !gpu_disabled(app) {
+        return;
+    }
+
+    #[cfg(target_os = "windows")]
+    crate::windows::append_browser_arg("--disable-gpu");
+    #[cfg(target_os = "linux")]
+    {
+        // Software compositing avoids the DMA-BUF renderer that breaks on
+        // several proprietary drivers.
+        std::env::set_var("WEBKIT_DISABLE_COMPOSITING_MODE", "1");
+        std::env::set_var("WEBKIT_DISABLE_DMABUF_RENDERER", "1");
+    }
+    #[cfg(target_os = "macos")]
+    log::warn!("[nchat-desktop] WKWebView cannot disable GPU acceleration; ignoring");
+
+    log::info!("[nchat-desktop] GPU acceleration disabled for this launch");
+}
+
+pub fn info(app: &AppHandle) -> RendererInfo {
+    RendererInfo {
+        webview_version: tauri::webview_version().ok(),
+        gpu_disabled: gpu_disabled(app),
+        auto_fallback: settings::load(app, AUTO_FALLBACK_KEY).unwrap_or(false),
+        failed_launches: settings::load(app, FAILED_LAUNCHES_KEY).unwrap_or(0),
+        supported: cfg!(any(target_os = "windows", target_os = "linux")),
+    }
+}
@@ -0,0 +1,239 @@
+// nChat Desktop — screenshot-and-attach
+//
+// A system-wide shortcut starts the platform's region capture: screencapture
+// on macOS (needs the Screen Recording permission), the Snipping Tool overlay
+// on Windows, and slurp with grim, GNOME Screenshot, Spectacle or
+// ImageMagick on Linux. The picked region is saved as a PNG in a temp folder
+// and the main window gets `attach-screenshot` with its path, so the
+// composer can attach it. Cancelling the selection does nothing. Files older
+// than a day are cleared on the next capture.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutEvent, ShortcutState};
+
+use crate::state::settings;
+use crate::windows::MAIN_WINDOW;
+
+/// Settings key holding the global shortcut; an empty string disables it.
+const SHORTCUT_KEY: &str = "screenshot.shortcut";
+
+const DEFAULT_SHORTCUT: &str = "CmdOrCtrl+Alt+S";
+
+/// Folder under the system temp directory the captures are saved in.
+const CAPTURE_DIR: &str = "nchat-screenshots";
+
+/// Captures older than this are removed.
+const KEEP_FOR: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// How long to wait for the Snipping Tool to put a capture on the clipboard.
+#[cfg(target_os = "windows")]
+const CLIPBOARD_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// A capture is in progress; the shortcut is ignored until it ends.
+static CAPTURING: AtomicBool = AtomicBool::new(false);
+
+/// The configured global shortcut, or an empty string when disabled.
+pub fn shortcut(app: &AppHandle) -> String {
+    settings::load(app, SHORTCUT_KEY).unwrap_or_else(|| DEFAULT_SHORTCUT.to_string())
+}
+
+/// Register the configured shortcut. Another app holding the same
+/// combination is not fatal.
+pub fn register_shortcut(app: &AppHandle) {
+    let accel = shortcut(app);
+    if accel.is_empty() {
+        return;
+    }
+    if let Err(e) = app.global_shortcut().register(accel.as_str()) {
+        log::warn!("[nchat-desktop] failed to register screenshot shortcut {accel}: {e}");
+    }
+}
+
+/// Replace the global shortcut; an empty string turns it off.
+pub fn set_shortcut(app: &AppHandle, accel: &str) -> Result<(), String> {
+    let accel = accel.trim();
+    crate::shortcuts::rebind(app, &shortcut(app), accel)?;
+    settings::save(app, SHORTCUT_KEY, &accel)
+}
+
+/// Global shortcut handler. Returns whether the shortcut was the
+/// screenshot one.
+pub fn on_shortcut(app: &AppHandle, shortcut: &Shortcut, event: ShortcutEvent) -> bool {
+    let is_own = self::shortcut(app)
+        .parse::<Shortcut>()
+        .is_ok_and(|own| own.id() == shortcut.id());
+    if !is_own {
+        return false;
+    }
+    if event.state == ShortcutState::Pressed {
+        if let Err(e) = capture(app) {
+            log::warn!("[nchat-desktop] screenshot: {e}");
+        }
+    }
+    true
+}
+
+/// Start a region capture in the background; the result arrives as
+/// `attach-screenshot`.
+pub fn capture(app: &AppHandle) -> Result<(), String> {
+    if crate::kiosk::is_active() {
+        return Err("not available in kiosk mode".into());
+    }
+    if CAPTURING.swap(true, Ordering::SeqCst) {
+        return Err("a screenshot is already being taken".into());
+    }
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let result = capture_dir(&app).and_then(|dir| {
+            let millis = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis();
+            let dest = dir.join(format!("screenshot-{millis}.png"));
+            Ok(grab(&app, &dest)?.then_some(dest))
+        });
+        CAPTURING.store(false, Ordering::SeqCst);
+        match result {
+            Ok(Some(path)) => {
+                if let Some(win) = app.get_webview_window(MAIN_WINDOW) {
+                    let _ = win.show();
+                    let _ = win.unminimize();
+                    let _ = win.set_focus();
+                    let _ = win.emit("attach-screenshot", path.to_string_lossy());
+                }
+            }
+            Ok(None) => {}
+            Err(e) => log::warn!("[nchat-desktop] screenshot failed: {e}"),
+        }
+    });
+    Ok(())
+}
+
+/// The capture folder, cleared of old captures.
+fn capture_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .temp_dir()
+        .map_err(|e| e.to_string())?
+        .join(CAPTURE_DIR);
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    if let Ok(entries) = std::fs::read_dir(&dir) {
+        for entry in entries.flatten() {
+            let old = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|modified| modified.elapsed().ok())
+                .is_some_and(|age| age > KEEP_FOR);
+            if old {
+                let _ = std::fs::remove_file(entry.path());
+            }
+        }
+    }
+    Ok(dir)
+}
+
+/// Let the user pick a region and save it to `dest`. Returns false when the
+/// selection was cancelled.
+#[cfg(target_os = "macos")]
+fn grab(_app: &AppHandle, dest: &Path) -> Result<bool, String> {
+    // Interactive selection; Space switches to window capture, Escape
+    // cancels without writing the file.
+    std::process::Command::new("screencapture")
+        .args(["-i", "-x"])
+        .arg(dest)
+        .status()
+        .map_err(|e| format!("screencapture is not available: {e}"))?;
+    Ok(dest.is_file())
+}
+
+/// The Snipping Tool overlay only copies the capture to the clipboard, so
+/// wait for a new image to appear there.
+#[cfg(target_os = "windows")]
+fn grab(app: &AppHandle, dest: &Path) -> Result<bool, String> {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+    use tauri_plugin_clipboard_manager::ClipboardExt;
+
+    let fingerprint = || {
+        app.clipboard().read_image().ok().map(|image| {
+            let mut hasher = DefaultHasher::new();
+            image.rgba().hash(&mut hasher);
+            (image.width(), image.height(), hasher.finish())
+        })
+    };
+    let before = fingerprint();
+    std::process::Command::new("explorer")
+        .arg("ms-screenclip:")
+        .status()
+        .map_err(|e| format!("the Snipping Tool is not available: {e}"))?;
+    let started = std::time::Instant::now();
+    while started.elapsed() < CLIPBOARD_TIMEOUT {
+        std::thread::sleep(Duration::from_millis(500));
+        if fingerprint() == before {
+            continue;
+        }
+        let image = app.clipboard().read_image().map_err(|e| e.to_string())?;
+        image::save_buffer(
+            dest,
+            image.rgba(),
+            image.width(),
+            image.height(),
+            image::ColorType::Rgba8,
+        )
+        .map_err(|e| e.to_string())?;
+        return Ok(true);
+    }
+    Ok(false)
+}
+
+/// Try the region tools in turn; the first one installed decides. A tool
+/// that ran without writing the file was cancelled.
+#[cfg(target_os = "linux")]
+fn grab(_app: &AppHandle, dest: &Path) -> Result<bool, String> {
+    use std::io::ErrorKind;
+    use std::process::{Command, Stdio};
+
+    // slurp picks the region on wlroots compositors, grim captures it.
+    match Command::new("slurp").stdin(Stdio::null()).output() {
+        Ok(output) if output.status.success() => {
+            let geometry = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            Command::new("grim")
+                .args(["-g", &geometry])
+                .arg(dest)
+                .status()
+                .map_err(|e| format!("grim is not available: {e}"))?;
+            return Ok(dest.is_file());
+        }
+        // Escape in slurp; other failures mean the compositor lacks the
+        // layer-shell protocol (GNOME, KDE), so fall through.
+        Ok(output) if String::from_utf8_lossy(&output.stderr).contains("cancelled") => {
+            return Ok(false)
+        }
+        Err(e) if e.kind() != ErrorKind::NotFound => return Err(e.to_string()),
+        _ => {}
+    }
+    let dest_str = dest.to_string_lossy();
+    let tools: [(&str, Vec<&str>); 3] = [
+        ("gnome-screenshot", vec!["-a", "-f", &dest_str]),
+        ("spectacle", vec!["-b", "-n", "-r", "-o", &dest_str]),
+        // ImageMagick: click a window or drag a region (X11 only).
+        ("import", vec![&dest_str]),
+    ];
+    for (tool, args) in tools {
+        match Command::new(tool).args(&args).status() {
+            Ok(_) => return Ok(dest.is_file()),
+            Err(e) if e.kind() == ErrorKind::NotFound => continue,
+            Err(e) => return Err(format!("{tool}: {e}")),
+        }
+    }
+    Err("no screenshot tool found (install grim and slurp, GNOME Screenshot or Spectacle)".into())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+fn grab(_app: &AppHandle, _dest: &Path) -> Result<bool, String> {
+    Err("screenshots are not supported on this platform".into())
+}
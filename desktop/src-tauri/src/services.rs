@@ -0,0 +1,85 @@
+// nChat Desktop — macOS Services provider
+//
+// "Send to nChat" is declared under NSServices in Info.plist, so it shows
+// up in the Services menu of any app with a text selection. macOS delivers
+// the selection to the provider registered here, which brings nChat to the
+// front and hands the text to the frontend as a new message draft.
+
+use std::ffi::{c_char, CStr};
+use std::sync::OnceLock;
+
+use objc2::runtime::{AnyObject, ClassBuilder, NSObject, Sel};
+use objc2::{class, msg_send, sel, ClassType};
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Objective-C name of the provider class.
+const PROVIDER_CLASS: &str = "NChatServicesProvider";
+
+static APP: OnceLock<AppHandle> = OnceLock::new();
+
+/// Plain-text contents of `pboard`, if any.
+unsafe fn pasteboard_text(pboard: *mut AnyObject) -> Option<String> {
+    if pboard.is_null() {
+        return None;
+    }
+    let utf8_type: *mut AnyObject = msg_send![
+        class!(NSString),
+        stringWithUTF8String: c"public.utf8-plain-text".as_ptr()
+    ];
+    let string: *mut AnyObject = msg_send![pboard, stringForType: utf8_type];
+    if string.is_null() {
+        return None;
+    }
+    let bytes: *const c_char = msg_send![string, UTF8String];
+    if bytes.is_null() {
+        return None;
+    }
+    Some(CStr::from_ptr(bytes).to_string_lossy().into_owned())
+}
+
+/// `-sendToNchat:userData:error:`, the NSMessage named in Info.plist.
+extern "C" fn send_to_nchat(
+    _this: &NSObject,
+    _cmd: Sel,
+    pboard: *mut AnyObject,
+    _user_data: *mut AnyObject,
+    _error: *mut *mut AnyObject,
+) {
+    let Some(app) = APP.get() else {
+        return;
+    };
+    let Some(text) = (unsafe { pasteboard_text(pboard) }) else {
+        return;
+    };
+    if text.trim().is_empty() {
+        return;
+    }
+    if let Some(win) = app.get_webview_window(crate::windows::MAIN_WINDOW) {
+        let _ = win.unminimize();
+        let _ = win.show();
+        let _ = win.set_focus();
+        let _ = win.emit("compose-with-text", text);
+    }
+}
+
+/// Register the Services provider. Must run on the main thread, once.
+pub fn register(app: &AppHandle) {
+    if APP.set(app.clone()).is_err() {
+        return;
+    }
+    let Some(mut builder) = ClassBuilder::new(PROVIDER_CLASS, NSObject::class()) else {
+        log::warn!("[nchat-desktop] services provider class already exists");
+        return;
+    };
+    unsafe {
+        builder.add_method(
+            sel!(sendToNchat:userData:error:),
+            send_to_nchat as extern "C" fn(_, _, _, _, _),
+        );
+        let cls = builder.register();
+        // The provider lives for the rest of the process.
+        let provider: *mut AnyObject = msg_send![cls, new];
+        let ns_app: *mut AnyObject = msg_send![class!(NSApplication), sharedApplication];
+        let _: () = msg_send![ns_app, setServicesProvider: provider];
+    }
+}
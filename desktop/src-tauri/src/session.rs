@@ -0,0 +1,132 @@
+// nChat Desktop — restoring the previous session
+//
+// At quit, records which app windows were open and the route each one
+// showed. The route is read from the webview URL, so the frontend does not
+// have to report it: the main window's router navigates by path, the other
+// windows are loaded on a hash route. At launch, auxiliary windows are recreated on their
+// routes, and the main window's route becomes its `last-used` startup
+// target. The settings window is reopened on its section. Pop-out
+// conversation windows are restored by `windows` under their own setting;
+// transient windows (quick switcher, media viewer, re-authentication) are
+// never restored.
+
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+use crate::preferences::SETTINGS_WINDOW;
+use crate::state::settings;
+use crate::windows::{self, WindowOptions, AUX_WINDOW_PREFIX, MAIN_WINDOW};
+
+/// Settings key holding the windows open at the last quit.
+const SESSION_KEY: &str = "session.windows";
+
+/// Settings key for restoring the session at launch (on by default).
+const RESTORE_KEY: &str = "session.restore";
+
+/// Routes longer than this are not worth restoring.
+const MAX_ROUTE_LEN: usize = 2048;
+
+/// Route the main window showed at the last quit, until the frontend takes
+/// it as its startup target.
+static MAIN_ROUTE: Mutex<Option<String>> = Mutex::new(None);
+
+#[derive(Serialize, Deserialize, Clone)]
+struct WindowSnapshot {
+    label: String,
+    /// Route of the app, e.g. `/channel/general`.
+    route: String,
+}
+
+pub fn enabled(app: &AppHandle) -> bool {
+    settings::load(app, RESTORE_KEY).unwrap_or(true)
+}
+
+pub fn set_enabled(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    settings::save(app, RESTORE_KEY, &enabled)?;
+    if !enabled {
+        settings::remove(app, SESSION_KEY)?;
+    }
+    Ok(())
+}
+
+fn valid_route(route: &str) -> bool {
+    route.starts_with('/')
+        && route.len() <= MAX_ROUTE_LEN
+        && !route.contains("..")
+        && !route.chars().any(char::is_control)
+}
+
+/// The main window's route from its URL path and query; `None` on the
+/// shell's own entry page, which is not a route.
+fn main_route(url: &tauri::Url) -> Option<String> {
+    let path = url.path();
+    if path == "/" || path == "/index.html" {
+        return None;
+    }
+    Some(match url.query() {
+        Some(query) => format!("{path}?{query}"),
+        None => path.to_string(),
+    })
+}
+
+/// Record the open windows and their routes. Runs while the app is quitting
+/// and the windows still exist.
+pub fn snapshot(app: &AppHandle) {
+    if !enabled(app) {
+        return;
+    }
+    let snapshots: Vec<WindowSnapshot> = app
+        .webview_windows()
+        .into_values()
+        .filter(|win| {
+            [MAIN_WINDOW, SETTINGS_WINDOW].contains(&win.label())
+                || win.label().starts_with(AUX_WINDOW_PREFIX)
+        })
+        .filter_map(|win| {
+            let url = win.url().ok()?;
+            let route = if win.label() == MAIN_WINDOW {
+                main_route(&url)?
+            } else {
+                url.fragment()?.to_string()
+            };
+            valid_route(&route).then(|| WindowSnapshot {
+                label: win.label().to_string(),
+                route,
+            })
+        })
+        .collect();
+    if let Err(e) = settings::save(app, SESSION_KEY, &snapshots) {
+        log::warn!("[nchat-desktop] failed to save session: {e}");
+    }
+}
+
+/// Recreate the windows of the previous session. The snapshot is used once,
+/// so a launch that crashes does not keep reopening the same windows.
+pub fn restore(app: &AppHandle) {
+    let snapshots: Vec<WindowSnapshot> = settings::load(app, SESSION_KEY).unwrap_or_default();
+    let _ = settings::remove(app, SESSION_KEY);
+    if !enabled(app) {
+        return;
+    }
+    for snapshot in snapshots.into_iter().filter(|s| valid_route(&s.route)) {
+        if snapshot.label == MAIN_WINDOW {
+            *MAIN_ROUTE.lock().unwrap() = Some(snapshot.route);
+        } else if snapshot.label == SETTINGS_WINDOW {
+            crate::preferences::restore(app, &snapshot.route);
+        } else if let Some(label) = snapshot.label.strip_prefix(AUX_WINDOW_PREFIX) {
+            let url = format!("index.html#{}", snapshot.route);
+            if let Err(e) = windows::create_window(app, label, &url, WindowOptions::default()) {
+                log::warn!("[nchat-desktop] failed to restore window {label}: {e}");
+            }
+        }
+    }
+    if let Some(main) = app.get_webview_window(MAIN_WINDOW) {
+        let _ = main.set_focus();
+    }
+}
+
+/// The main window's route from the previous session, handed out once.
+pub fn take_main_route() -> Option<String> {
+    MAIN_ROUTE.lock().unwrap().take()
+}
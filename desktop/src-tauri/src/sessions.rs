@@ -0,0 +1,379 @@
+// nChat Desktop — signed-in sessions and remote sign-out
+//
+// The frontend hands the native layer its session after sign-in; the
+// access token is kept in the OS keychain. Session management calls go
+// through here so they work from any window. Revocation arrives as a
+// realtime push the frontend forwards (`handle_session_revoked`); a
+// background check is the fallback for when the push was missed. It wipes
+// the token, hides the main window and opens a separate re-auth window, so
+// the user is locked out and prompted even if the main webview is wedged.
+//
+// Only an explicit answer counts as a revocation: a 401 or 403 whose JSON
+// body has `"code": "session_revoked"`. Any other 401 means the access
+// token expired. The frontend owns the refresh token, so it is asked for
+// a fresh access token (`session:token-expired`) and the request is
+// retried once with it.
+
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, WebviewUrl, WebviewWindowBuilder};
+use tauri_plugin_notification::NotificationExt;
+use tokio::sync::Notify;
+
+use crate::clock;
+use crate::i18n::t;
+use crate::keychain;
+use crate::proxy;
+use crate::state::settings;
+use crate::windows::MAIN_WINDOW;
+
+/// Session routes on the nChat server, relative to the server URL.
+const SESSIONS_PATH: &str = "/api/auth/sessions";
+
+/// The signed-in user, relative to the server URL.
+const ME_PATH: &str = "/api/auth/me";
+
+/// Settings key holding the current server and session id.
+const SESSION_KEY: &str = "session.current";
+const TOKEN_ACCOUNT: &str = "session-access-token";
+
+/// Label and route of the re-auth window.
+pub const REAUTH_WINDOW: &str = "reauth";
+const REAUTH_ROUTE: &str = "index.html#/reauth";
+
+/// How often the background check asks the server about this session.
+const CHECK_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// How long the frontend has to hand over a refreshed access token.
+const TOKEN_REFRESH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Error code the server sends when this session was revoked.
+const REVOKED_CODE: &str = "session_revoked";
+
+/// Woken whenever the frontend hands over a session.
+fn token_set() -> &'static Notify {
+    static TOKEN_SET: OnceLock<Notify> = OnceLock::new();
+    TOKEN_SET.get_or_init(Notify::new)
+}
+
+/// How the server judged a request's credentials.
+enum Auth {
+    Valid(reqwest::Response),
+    Expired,
+    Revoked,
+    Denied(reqwest::StatusCode),
+}
+
+#[derive(Deserialize)]
+struct AuthError {
+    code: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+struct CurrentSession {
+    server_url: String,
+    session_id: String,
+}
+
+/// One signed-in device as listed by the server.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Session {
+    pub id: String,
+    pub device_name: Option<String>,
+    pub platform: Option<String>,
+    pub ip_address: Option<String>,
+    pub last_active_at: Option<String>,
+    /// Set by the native layer for the session of this install.
+    #[serde(default)]
+    pub current: bool,
+}
+
+#[derive(Deserialize)]
+struct SessionList {
+    sessions: Vec<Session>,
+}
+
+/// The signed-in user as the server reports it.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Identity {
+    pub display_name: Option<String>,
+    pub email: String,
+}
+
+fn api_url(server_url: &str, path: &str) -> Result<String, String> {
+    let base = server_url.trim_end_matches('/');
+    let local = base.starts_with("http://localhost") || base.starts_with("http://127.0.0.1");
+    if !base.starts_with("https://") && !local {
+        return Err("sessions require an https server URL".into());
+    }
+    Ok(format!("{base}{path}"))
+}
+
+fn sessions_url(server_url: &str) -> Result<String, String> {
+    api_url(server_url, SESSIONS_PATH)
+}
+
+fn credentials(app: &AppHandle) -> Result<(CurrentSession, String), String> {
+    let session: CurrentSession = settings::load(app, SESSION_KEY).ok_or("not signed in")?;
+    let token = keychain::read_secret(TOKEN_ACCOUNT)?.ok_or("not signed in")?;
+    Ok((session, token))
+}
+
+/// URL of the server the user is signed in to.
+pub fn server_url(app: &AppHandle) -> Option<String> {
+    settings::load::<CurrentSession>(app, SESSION_KEY).map(|session| session.server_url)
+}
+
+/// Record the session the frontend signed in with. Also ends a pending
+/// re-auth: the lock window closes and the main window comes back.
+pub fn set_current(
+    app: &AppHandle,
+    server_url: String,
+    session_id: String,
+    access_token: String,
+) -> Result<(), String> {
+    sessions_url(&server_url)?;
+    keychain::store_secret(TOKEN_ACCOUNT, &access_token)?;
+    settings::save(
+        app,
+        SESSION_KEY,
+        &CurrentSession {
+            server_url,
+            session_id,
+        },
+    )?;
+    token_set().notify_waiters();
+
+    if let Some(win) = app.get_webview_window(REAUTH_WINDOW) {
+        let _ = win.close();
+        if let Some(main) = app.get_webview_window(MAIN_WINDOW) {
+            let _ = main.emit("session:restored", ());
+            let _ = main.show();
+            let _ = main.set_focus();
+        }
+    }
+    Ok(())
+}
+
+/// Forget the session locally (sign-out from this device).
+pub fn clear(app: &AppHandle) -> Result<(), String> {
+    keychain::delete_secret(TOKEN_ACCOUNT)?;
+    settings::remove(app, SESSION_KEY)
+}
+
+/// Classify a response: a revocation only when the server says so.
+async fn judge(app: &AppHandle, res: reqwest::Response) -> Auth {
+    clock::observe(app, &res);
+    let status = res.status();
+    if status != reqwest::StatusCode::UNAUTHORIZED && status != reqwest::StatusCode::FORBIDDEN {
+        return Auth::Valid(res);
+    }
+    let code = res.json::<AuthError>().await.ok().and_then(|e| e.code);
+    if code.as_deref() == Some(REVOKED_CODE) {
+        Auth::Revoked
+    } else if status == reqwest::StatusCode::UNAUTHORIZED {
+        Auth::Expired
+    } else {
+        // A plain 403 is a permission error, not a credentials one.
+        Auth::Denied(status)
+    }
+}
+
+/// Ask the frontend for a fresh access token and wait for it. Returns the
+/// new token, or `None` when none arrived in time.
+async fn refreshed_token(app: &AppHandle, stale: &str) -> Option<String> {
+    let waiting = token_set().notified();
+    tokio::pin!(waiting);
+    waiting.as_mut().enable();
+    app.get_webview_window(MAIN_WINDOW)?
+        .emit("session:token-expired", ())
+        .ok()?;
+    tokio::time::timeout(TOKEN_REFRESH_TIMEOUT, waiting)
+        .await
+        .ok()?;
+    let (_, token) = credentials(app).ok()?;
+    (token != stale).then_some(token)
+}
+
+/// Send a request built by `request` with this session's token. An expired
+/// token is refreshed once; a revocation locks the app.
+async fn authorized<F>(app: &AppHandle, request: F) -> Result<reqwest::Response, String>
+where
+    F: Fn(&reqwest::Client, &CurrentSession) -> Result<reqwest::RequestBuilder, String>,
+{
+    let (current, mut token) = credentials(app)?;
+    let client = proxy::client(app)?;
+    let mut refreshed = false;
+    loop {
+        let res = request(&client, &current)?
+            .bearer_auth(&token)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        match judge(app, res).await {
+            Auth::Valid(res) => return Ok(res),
+            Auth::Revoked => {
+                log::warn!("[nchat-desktop] session {} was revoked", current.session_id);
+                revoked(app);
+                return Err("session was revoked".into());
+            }
+            Auth::Expired if !refreshed => {
+                token = refreshed_token(app, &token)
+                    .await
+                    .ok_or("the session token expired and was not refreshed")?;
+                refreshed = true;
+            }
+            Auth::Expired => return Err("the session token was rejected".into()),
+            Auth::Denied(status) => {
+                return Err(format!("the server refused the request: {status}"))
+            }
+        }
+    }
+}
+
+pub async fn list(app: &AppHandle) -> Result<Vec<Session>, String> {
+    let (current, _) = credentials(app)?;
+    let res = authorized(app, |client, current| {
+        Ok(client.get(sessions_url(&current.server_url)?))
+    })
+    .await?;
+    let list: SessionList = res
+        .error_for_status()
+        .map_err(|e| e.to_string())?
+        .json()
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(list
+        .sessions
+        .into_iter()
+        .map(|mut s| {
+            s.current = s.id == current.session_id;
+            s
+        })
+        .collect())
+}
+
+/// Who is signed in, asked of the server with this install's own token so
+/// the page cannot substitute someone else.
+pub async fn identity(app: &AppHandle) -> Result<Identity, String> {
+    authorized(app, |client, current| {
+        Ok(client.get(api_url(&current.server_url, ME_PATH)?))
+    })
+    .await?
+    .error_for_status()
+    .map_err(|e| e.to_string())?
+    .json()
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Sign out another device. Revoking this install's own session signs it
+/// out locally as well.
+pub async fn revoke(app: &AppHandle, id: &str) -> Result<(), String> {
+    let (current, _) = credentials(app)?;
+    authorized(app, |client, current| {
+        Ok(client.delete(format!("{}/{id}", sessions_url(&current.server_url)?)))
+    })
+    .await?
+    .error_for_status()
+    .map_err(|e| e.to_string())?;
+    if id == current.session_id {
+        clear(app)?;
+    }
+    Ok(())
+}
+
+/// This session was revoked (pushed to the frontend, or reported by the
+/// server): wipe the token, lock the main window and prompt for sign-in
+/// natively.
+pub fn revoked(app: &AppHandle) {
+    if let Err(e) = clear(app) {
+        log::warn!("[nchat-desktop] failed to clear revoked session: {e}");
+    }
+    if let Some(main) = app.get_webview_window(MAIN_WINDOW) {
+        let _ = main.emit("session:revoked", ());
+        let _ = main.hide();
+    }
+
+    let _ = app
+        .notification()
+        .builder()
+        .title("nChat")
+        .body(t("session.revoked"))
+        .show();
+
+    let win = match app.get_webview_window(REAUTH_WINDOW) {
+        Some(win) => Ok(win),
+        None => WebviewWindowBuilder::new(app, REAUTH_WINDOW, WebviewUrl::App(REAUTH_ROUTE.into()))
+            .title("nChat")
+            .inner_size(420.0, 560.0)
+            .resizable(false)
+            .center()
+            .build(),
+    };
+    match win {
+        Ok(win) => {
+            // The main window stays hidden until the user chooses to sign
+            // in again, so dismissing the prompt instead quits.
+            let handle = app.clone();
+            win.on_window_event(move |event| {
+                let main_shown = handle
+                    .get_webview_window(MAIN_WINDOW)
+                    .is_some_and(|main| main.is_visible().unwrap_or(false));
+                if matches!(event, tauri::WindowEvent::Destroyed)
+                    && credentials(&handle).is_err()
+                    && !main_shown
+                {
+                    handle.exit(0);
+                }
+            });
+            let _ = win.show();
+            let _ = win.set_focus();
+        }
+        Err(e) => log::warn!("[nchat-desktop] failed to open re-auth window: {e}"),
+    }
+}
+
+/// Sign in again from the re-auth window: the main window is reloaded,
+/// which starts it signed out, and shown in place of the prompt.
+pub fn begin_reauth(app: &AppHandle) -> Result<(), String> {
+    let main = app
+        .get_webview_window(MAIN_WINDOW)
+        .ok_or("the main window is not open")?;
+    main.reload().map_err(|e| e.to_string())?;
+    main.show().map_err(|e| e.to_string())?;
+    let _ = main.set_focus();
+    if let Some(win) = app.get_webview_window(REAUTH_WINDOW) {
+        let _ = win.close();
+    }
+    Ok(())
+}
+
+/// Ask the server whether this session is still valid, in case the
+/// revocation push was missed. Failures are only logged.
+async fn check(app: &AppHandle) -> Result<(), String> {
+    if credentials(app).is_err() {
+        return Ok(());
+    }
+    authorized(app, |client, current| {
+        Ok(client.get(format!("{}/current", sessions_url(&current.server_url)?)))
+    })
+    .await
+    .map(|_| ())
+}
+
+/// Start the fallback revocation check.
+pub fn start(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(CHECK_INTERVAL).await;
+            if let Err(e) = check(&app).await {
+                log::warn!("[nchat-desktop] session check failed: {e}");
+            }
+        }
+    });
+}
@@ -0,0 +1,437 @@
+// nChat Desktop — shortcut profiles
+//
+// Everything the keyboard-shortcuts page can change — menu accelerators,
+// the app's own global shortcuts and the user's global bindings — as one
+// versioned JSON file, so a heavily customized setup can be carried to
+// another machine. Unlike a full configuration import, a profile is checked
+// before anything is applied: entries with unknown actions or malformed
+// accelerators are skipped, and an entry whose keys are already taken is
+// resolved by the caller's `ConflictResolution`. Keys another app holds on
+// the new machine are reported rather than failing the whole import.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use tauri::AppHandle;
+use tauri_plugin_global_shortcut::Shortcut;
+
+use crate::double_tap::{self, DoubleTap};
+use crate::shortcuts::{self, ConflictSource, GlobalBinding, ShortcutConflict};
+use crate::{call_shortcuts, menu, screenshot, switcher, workspace_shortcuts};
+
+/// Bumped when the file layout changes incompatibly.
+const PROFILE_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+pub struct ShortcutProfile {
+    pub version: u32,
+    pub app_version: String,
+    /// Menu accelerators by action; "" for an unbound action.
+    #[serde(default)]
+    pub menu: BTreeMap<String, String>,
+    /// The app's own global shortcuts (`quick-switcher`, `screenshot`,
+    /// `mute`, `deafen`, `next-workspace`, `previous-workspace`); "" when
+    /// turned off.
+    #[serde(default)]
+    pub global: BTreeMap<String, String>,
+    #[serde(default)]
+    pub bindings: Vec<GlobalBinding>,
+    /// The quick switcher's double-tap trigger. Left alone when absent.
+    #[serde(default)]
+    pub switcher_double_tap: Option<DoubleTap>,
+}
+
+/// What to do with a profile entry whose keys are already in use.
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConflictResolution {
+    /// Skip the entry and keep the binding on this machine.
+    #[default]
+    KeepExisting,
+    /// Apply the entry and turn off the binding it clashes with. OS
+    /// shortcuts cannot be turned off and are only reported.
+    UseImported,
+}
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum SkipReason {
+    /// Not an action this version of the app knows.
+    Unknown,
+    /// The accelerator could not be parsed.
+    Invalid,
+    /// The keys are taken; see `conflict`.
+    Conflict,
+    /// Another app holds the keys on this machine.
+    Unavailable,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct SkippedShortcut {
+    pub action: String,
+    pub accelerator: String,
+    pub reason: SkipReason,
+    pub conflict: Option<ShortcutConflict>,
+}
+
+#[derive(Serialize, Default)]
+pub struct ShortcutImportReport {
+    pub imported: Vec<String>,
+    pub skipped: Vec<SkippedShortcut>,
+    /// Bindings on this machine turned off to make room (`use-imported`).
+    pub replaced: Vec<String>,
+    /// Imported shortcuts that clash with an OS shortcut.
+    pub system_conflicts: Vec<ShortcutConflict>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Menu,
+    Global,
+    Binding,
+}
+
+/// A shortcut from the profile or on this machine.
+#[derive(Clone)]
+struct Entry {
+    kind: Kind,
+    /// Menu action, global shortcut id or binding action.
+    action: String,
+    /// Normalized; empty when unbound.
+    accelerator: String,
+    binding: Option<GlobalBinding>,
+}
+
+impl Entry {
+    fn new(
+        kind: Kind,
+        action: String,
+        accelerator: String,
+        binding: Option<GlobalBinding>,
+    ) -> Self {
+        Entry {
+            kind,
+            action,
+            accelerator,
+            binding,
+        }
+    }
+
+    fn same_slot(&self, other: &Entry) -> bool {
+        self.kind == other.kind && self.action == other.action
+    }
+
+    /// The id used in reports, matching the ids of `shortcuts::conflicts`.
+    fn id(&self) -> String {
+        match self.kind {
+            Kind::Global => format!("global:{}", self.action),
+            Kind::Menu | Kind::Binding => self.action.clone(),
+        }
+    }
+
+    fn conflict(&self) -> ShortcutConflict {
+        let source = match self.kind {
+            Kind::Menu => ConflictSource::Menu,
+            Kind::Global | Kind::Binding => ConflictSource::Global,
+        };
+        ShortcutConflict {
+            source,
+            action: self.id(),
+        }
+    }
+}
+
+/// The app's own global shortcuts by id, including the ones turned off.
+fn global_shortcuts(app: &AppHandle) -> BTreeMap<String, String> {
+    let mut global = BTreeMap::from([
+        ("quick-switcher".to_string(), switcher::shortcut(app)),
+        ("screenshot".to_string(), screenshot::shortcut(app)),
+    ]);
+    global.extend(call_shortcuts::shortcuts(app));
+    global.extend(workspace_shortcuts::shortcuts(app));
+    global
+}
+
+fn set_global_shortcut(app: &AppHandle, id: &str, accel: &str) -> Result<(), String> {
+    match id {
+        "quick-switcher" => switcher::set_shortcut(app, accel),
+        "screenshot" => screenshot::set_shortcut(app, accel),
+        "next-workspace" | "previous-workspace" => {
+            workspace_shortcuts::set_shortcut(app, id, accel)
+        }
+        _ => call_shortcuts::set_shortcut(app, id, accel),
+    }
+}
+
+/// Every shortcut currently bound on this machine.
+fn current_entries(app: &AppHandle) -> Vec<Entry> {
+    let mut entries: Vec<Entry> = menu::accelerators(app)
+        .into_iter()
+        .map(|(action, accel)| Entry::new(Kind::Menu, action, accel, None))
+        .collect();
+    entries.extend(
+        global_shortcuts(app)
+            .into_iter()
+            .map(|(id, accel)| Entry::new(Kind::Global, id, accel, None)),
+    );
+    entries.extend(shortcuts::global_bindings(app).into_iter().map(|b| {
+        let (action, accel) = (b.action.clone(), b.accelerator.clone());
+        Entry::new(Kind::Binding, action, accel, Some(b))
+    }));
+    for entry in &mut entries {
+        entry.accelerator = shortcuts::normalize(&entry.accelerator).unwrap_or_default();
+    }
+    entries.retain(|entry| !entry.accelerator.is_empty());
+    entries
+}
+
+pub fn export(app: &AppHandle, path: &Path) -> Result<usize, String> {
+    let profile = ShortcutProfile {
+        version: PROFILE_VERSION,
+        app_version: app.package_info().version.to_string(),
+        menu: menu::accelerators(app).into_iter().collect(),
+        global: global_shortcuts(app),
+        bindings: shortcuts::global_bindings(app),
+        switcher_double_tap: double_tap::switcher_trigger(app),
+    };
+    let json = serde_json::to_string_pretty(&profile).map_err(|e| e.to_string())?;
+    std::fs::write(path, json).map_err(|e| e.to_string())?;
+    let double_tap = usize::from(profile.switcher_double_tap.is_some());
+    Ok(profile.menu.len() + profile.global.len() + profile.bindings.len() + double_tap)
+}
+
+/// Check a profile's entries, returning the usable ones with their
+/// accelerators normalized.
+fn validate(
+    app: &AppHandle,
+    profile: ShortcutProfile,
+    report: &mut ShortcutImportReport,
+) -> Vec<Entry> {
+    let menu_actions = menu::accelerators(app);
+    let global_ids = global_shortcuts(app);
+    let mut candidates: Vec<(Entry, bool)> = Vec::new();
+    for (action, accel) in profile.menu {
+        let known = menu_actions.contains_key(&action);
+        candidates.push((Entry::new(Kind::Menu, action, accel, None), known));
+    }
+    for (id, accel) in profile.global {
+        let known = global_ids.contains_key(&id);
+        candidates.push((Entry::new(Kind::Global, id, accel, None), known));
+    }
+    for binding in profile.bindings {
+        let action = binding.action.trim().to_string();
+        let accel = binding.accelerator.clone();
+        let known = !action.is_empty();
+        candidates.push((
+            Entry::new(Kind::Binding, action, accel, Some(binding)),
+            known,
+        ));
+    }
+
+    let mut entries = Vec::new();
+    for (mut entry, known) in candidates {
+        let raw = entry.accelerator.trim().to_string();
+        let id = entry.id();
+        let skip = |reason| SkippedShortcut {
+            action: id.clone(),
+            accelerator: raw.clone(),
+            reason,
+            conflict: None,
+        };
+        if !known {
+            report.skipped.push(skip(SkipReason::Unknown));
+            continue;
+        }
+        // Only menu actions and the app's own shortcuts can be unbound.
+        let needs_keys = entry.kind == Kind::Binding;
+        if raw.is_empty() && !needs_keys {
+            entry.accelerator = String::new();
+            entries.push(entry);
+            continue;
+        }
+        let normalized = shortcuts::normalize(&raw)
+            .filter(|accel| entry.kind == Kind::Menu || accel.parse::<Shortcut>().is_ok());
+        match normalized {
+            Some(accel) => {
+                entry.accelerator = accel;
+                entries.push(entry);
+            }
+            None => report.skipped.push(skip(SkipReason::Invalid)),
+        }
+    }
+    entries
+}
+
+/// Apply a profile written by `export`. The profile's menu accelerators and
+/// app shortcuts replace the ones here; its bindings are added to the ones
+/// here, replacing a binding of the same action.
+pub fn import(
+    app: &AppHandle,
+    path: &Path,
+    resolution: ConflictResolution,
+) -> Result<ShortcutImportReport, String> {
+    if crate::kiosk::is_active() {
+        return Err("not available in kiosk mode".into());
+    }
+    let raw = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let profile: ShortcutProfile = serde_json::from_str(&raw).map_err(|e| e.to_string())?;
+    if profile.version > PROFILE_VERSION {
+        return Err(format!(
+            "shortcut profile version {} is newer than this app supports ({PROFILE_VERSION})",
+            profile.version
+        ));
+    }
+
+    let mut report = ShortcutImportReport::default();
+    let switcher_double_tap = profile.switcher_double_tap;
+    let entries = validate(app, profile, &mut report);
+
+    // Bindings here that the profile leaves alone keep their keys unless
+    // the resolution says otherwise.
+    let mut existing: Vec<Entry> = current_entries(app)
+        .into_iter()
+        .filter(|current| !entries.iter().any(|entry| entry.same_slot(current)))
+        .collect();
+    let mut accepted: Vec<Entry> = Vec::new();
+    let mut replaced: Vec<Entry> = Vec::new();
+    for entry in entries {
+        if entry.accelerator.is_empty() {
+            accepted.push(entry);
+            continue;
+        }
+        let same_keys = |other: &&Entry| other.accelerator == entry.accelerator;
+        let clash = accepted
+            .iter()
+            .find(same_keys)
+            .map(|other| (other.conflict(), true))
+            .or_else(|| {
+                existing
+                    .iter()
+                    .find(same_keys)
+                    .map(|other| (other.conflict(), false))
+            });
+        if let Some((conflict, within_profile)) = clash {
+            // Two entries of the profile itself: the first one wins.
+            if within_profile || resolution == ConflictResolution::KeepExisting {
+                report.skipped.push(SkippedShortcut {
+                    action: entry.id(),
+                    accelerator: entry.accelerator.clone(),
+                    reason: SkipReason::Conflict,
+                    conflict: Some(conflict),
+                });
+                continue;
+            }
+            let (taken, kept): (Vec<Entry>, Vec<Entry>) = existing
+                .drain(..)
+                .partition(|other| other.accelerator == entry.accelerator);
+            existing = kept;
+            replaced.extend(taken);
+        }
+        let system: Vec<ShortcutConflict> = shortcuts::conflicts(app, &entry.accelerator, None)
+            .into_iter()
+            .filter(|conflict| conflict.source == ConflictSource::System)
+            .collect();
+        if let Some(conflict) = system.first() {
+            if resolution == ConflictResolution::KeepExisting {
+                report.skipped.push(SkippedShortcut {
+                    action: entry.id(),
+                    accelerator: entry.accelerator.clone(),
+                    reason: SkipReason::Conflict,
+                    conflict: Some(conflict.clone()),
+                });
+                continue;
+            }
+            report.system_conflicts.extend(system);
+        }
+        accepted.push(entry);
+    }
+
+    // Make room first, so keys moving between shortcuts are free to take.
+    let mut menu_keys: HashMap<String, String> = HashMap::new();
+    for entry in &replaced {
+        match entry.kind {
+            Kind::Menu => {
+                menu_keys.insert(entry.action.clone(), String::new());
+            }
+            Kind::Global => set_global_shortcut(app, &entry.action, "")?,
+            Kind::Binding => shortcuts::unbind(app, &entry.action)?,
+        }
+        report.replaced.push(entry.id());
+    }
+    for entry in accepted.iter().filter(|entry| entry.kind == Kind::Menu) {
+        menu_keys.insert(entry.action.clone(), entry.accelerator.clone());
+        report.imported.push(entry.id());
+    }
+    menu::set_accelerators(app, &menu_keys)?;
+
+    let current_global = global_shortcuts(app);
+    let mut moving: Vec<(Entry, String)> = Vec::new();
+    for entry in accepted.iter().filter(|entry| entry.kind == Kind::Global) {
+        let previous = current_global
+            .get(&entry.action)
+            .cloned()
+            .unwrap_or_default();
+        set_global_shortcut(app, &entry.action, "")?;
+        moving.push((entry.clone(), previous));
+    }
+    for (entry, previous) in moving {
+        match set_global_shortcut(app, &entry.action, &entry.accelerator) {
+            Ok(()) => report.imported.push(entry.id()),
+            Err(e) => {
+                log::warn!("[nchat-desktop] shortcut profile: {e}");
+                // Keep what worked here rather than leaving it turned off.
+                let _ = set_global_shortcut(app, &entry.action, &previous);
+                report.skipped.push(SkippedShortcut {
+                    action: entry.id(),
+                    accelerator: entry.accelerator,
+                    reason: SkipReason::Unavailable,
+                    conflict: None,
+                });
+            }
+        }
+    }
+
+    for entry in accepted
+        .into_iter()
+        .filter(|entry| entry.kind == Kind::Binding)
+    {
+        let Some(binding) = entry.binding.clone() else {
+            continue;
+        };
+        let binding = GlobalBinding {
+            accelerator: entry.accelerator.clone(),
+            action: entry.action.clone(),
+            ..binding
+        };
+        match shortcuts::bind(app, binding) {
+            Ok(()) => report.imported.push(entry.id()),
+            Err(e) => {
+                log::warn!("[nchat-desktop] shortcut profile: {e}");
+                report.skipped.push(SkippedShortcut {
+                    action: entry.id(),
+                    accelerator: entry.accelerator,
+                    reason: SkipReason::Unavailable,
+                    conflict: None,
+                });
+            }
+        }
+    }
+    // Modifier taps cannot clash with key combinations; the listener may
+    // still be unavailable here, e.g. without the macOS permission.
+    if let Some(trigger) = switcher_double_tap {
+        let id = "switcher:double-tap".to_string();
+        match double_tap::set_switcher_trigger(app, Some(trigger)) {
+            Ok(()) => report.imported.push(id),
+            Err(e) => {
+                log::warn!("[nchat-desktop] shortcut profile: {e}");
+                report.skipped.push(SkippedShortcut {
+                    action: id,
+                    accelerator: String::new(),
+                    reason: SkipReason::Unavailable,
+                    conflict: None,
+                });
+            }
+        }
+    }
+    Ok(report)
+}
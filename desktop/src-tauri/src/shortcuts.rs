@@ -0,0 +1,708 @@
+// nChat Desktop — global shortcuts and recording them
+//
+// Besides the quick switcher's, the user can bind system-wide shortcuts to
+// frontend actions. Pressing one sends `shortcut:action` with the action to
+// the main window, showing it first when the binding asks for that. A
+// binding can step aside while the main window has focus, or only while the
+// user is typing in it, so keys like Ctrl+Shift+V keep working in the
+// composer; it is released then and registered again afterwards.
+//
+// `begin_shortcut_capture` starts recording: global shortcuts are released so
+// pressing one reaches the page instead of triggering it, and the settings
+// page forwards its key presses with `shortcut_capture_key`. The first
+// combination with a modifier (or a function key) ends the capture; Escape
+// cancels it. The result is a normalized accelerator such as
+// `CmdOrCtrl+Shift+K`, plus the app and OS shortcuts it would clash with.
+
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutEvent, ShortcutState};
+use tokio::sync::oneshot;
+
+use crate::state::settings;
+use crate::windows::MAIN_WINDOW;
+
+/// Settings key holding the user's global shortcut bindings.
+const GLOBAL_KEY: &str = "shortcuts.global";
+
+/// How long a capture waits for a key before giving up.
+const CAPTURE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The running capture: receives the accelerator, or `None` on Escape.
+static CAPTURE: Mutex<Option<oneshot::Sender<Option<String>>>> = Mutex::new(None);
+
+/// Codes of keys that only modify others; pressing one alone keeps waiting.
+const MODIFIER_CODES: &[&str] = &[
+    "ShiftLeft",
+    "ShiftRight",
+    "ControlLeft",
+    "ControlRight",
+    "AltLeft",
+    "AltRight",
+    "MetaLeft",
+    "MetaRight",
+    "OSLeft",
+    "OSRight",
+];
+
+/// Non-character keys, by accepted spelling (upper case) and the spelling
+/// used in normalized accelerators.
+const NAMED_KEYS: &[(&[&str], &str)] = &[
+    (&["SPACE"], "Space"),
+    (&["ENTER", "RETURN"], "Enter"),
+    (&["TAB"], "Tab"),
+    (&["BACKSPACE"], "Backspace"),
+    (&["DELETE", "DEL"], "Delete"),
+    (&["ESCAPE", "ESC"], "Escape"),
+    (&["INSERT"], "Insert"),
+    (&["HOME"], "Home"),
+    (&["END"], "End"),
+    (&["PAGEUP"], "PageUp"),
+    (&["PAGEDOWN"], "PageDown"),
+    (&["ARROWUP", "UP"], "Up"),
+    (&["ARROWDOWN", "DOWN"], "Down"),
+    (&["ARROWLEFT", "LEFT"], "Left"),
+    (&["ARROWRIGHT", "RIGHT"], "Right"),
+    (&["PRINTSCREEN"], "PrintScreen"),
+    (&["MINUS", "-"], "-"),
+    (&["EQUAL", "="], "="),
+    (&["COMMA", ","], ","),
+    (&["PERIOD", "."], "."),
+    (&["SLASH", "/"], "/"),
+    (&["BACKSLASH", "\\"], "\\"),
+    (&["SEMICOLON", ";"], ";"),
+    (&["QUOTE", "'"], "'"),
+    (&["BRACKETLEFT", "["], "["),
+    (&["BRACKETRIGHT", "]"], "]"),
+    (&["BACKQUOTE", "`"], "`"),
+];
+
+/// Shortcuts the OS keeps for itself (or that every app uses for editing),
+/// as (accelerator, id). The settings page words the ids.
+#[cfg(target_os = "macos")]
+const SYSTEM_SHORTCUTS: &[(&str, &str)] = &[
+    ("CmdOrCtrl+Space", "spotlight"),
+    ("Ctrl+Space", "input-source"),
+    ("CmdOrCtrl+Tab", "app-switcher"),
+    ("CmdOrCtrl+`", "window-switcher"),
+    ("CmdOrCtrl+Q", "quit"),
+    ("CmdOrCtrl+H", "hide"),
+    ("CmdOrCtrl+Alt+H", "hide-others"),
+    ("CmdOrCtrl+M", "minimize"),
+    ("CmdOrCtrl+W", "close-window"),
+    ("CmdOrCtrl+Ctrl+F", "full-screen"),
+    ("CmdOrCtrl+Ctrl+Q", "lock-screen"),
+    ("CmdOrCtrl+Alt+Escape", "force-quit"),
+    ("CmdOrCtrl+Shift+3", "screenshot"),
+    ("CmdOrCtrl+Shift+4", "screenshot-selection"),
+    ("CmdOrCtrl+Shift+5", "screenshot-tools"),
+    ("Ctrl+Up", "mission-control"),
+    ("Ctrl+Down", "app-windows"),
+    ("CmdOrCtrl+C", "copy"),
+    ("CmdOrCtrl+X", "cut"),
+    ("CmdOrCtrl+V", "paste"),
+    ("CmdOrCtrl+Z", "undo"),
+    ("CmdOrCtrl+Shift+Z", "redo"),
+    ("CmdOrCtrl+A", "select-all"),
+];
+
+#[cfg(target_os = "windows")]
+const SYSTEM_SHORTCUTS: &[(&str, &str)] = &[
+    ("Alt+Tab", "app-switcher"),
+    ("Alt+F4", "close-window"),
+    ("Super+Tab", "task-view"),
+    ("Super+D", "show-desktop"),
+    ("Super+E", "file-explorer"),
+    ("Super+L", "lock-screen"),
+    ("Super+R", "run"),
+    ("Super+V", "clipboard-history"),
+    ("Super+.", "emoji-picker"),
+    ("Super+Space", "input-source"),
+    ("Super+Shift+S", "screenshot-selection"),
+    ("PrintScreen", "screenshot"),
+    ("CmdOrCtrl+Escape", "start-menu"),
+    ("CmdOrCtrl+Shift+Escape", "task-manager"),
+    ("CmdOrCtrl+Alt+Delete", "security-screen"),
+    ("CmdOrCtrl+C", "copy"),
+    ("CmdOrCtrl+X", "cut"),
+    ("CmdOrCtrl+V", "paste"),
+    ("CmdOrCtrl+Z", "undo"),
+    ("CmdOrCtrl+Y", "redo"),
+    ("CmdOrCtrl+A", "select-all"),
+];
+
+/// Defaults shared by GNOME and KDE Plasma.
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+const SYSTEM_SHORTCUTS: &[(&str, &str)] = &[
+    ("Alt+Tab", "app-switcher"),
+    ("Super+Tab", "app-switcher"),
+    ("Alt+F2", "run"),
+    ("Alt+F4", "close-window"),
+    ("Super+D", "show-desktop"),
+    ("Super+L", "lock-screen"),
+    ("Super+Space", "input-source"),
+    ("PrintScreen", "screenshot"),
+    ("CmdOrCtrl+Alt+T", "terminal"),
+    ("CmdOrCtrl+Alt+Delete", "log-out"),
+    ("CmdOrCtrl+Alt+Left", "previous-workspace"),
+    ("CmdOrCtrl+Alt+Right", "next-workspace"),
+    ("CmdOrCtrl+C", "copy"),
+    ("CmdOrCtrl+X", "cut"),
+    ("CmdOrCtrl+V", "paste"),
+    ("CmdOrCtrl+Z", "undo"),
+    ("CmdOrCtrl+Shift+Z", "redo"),
+    ("CmdOrCtrl+A", "select-all"),
+];
+
+/// A key press forwarded from the settings page, named as in the DOM's
+/// `KeyboardEvent`.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CapturedKey {
+    /// Physical key, e.g. `KeyK`; independent of the keyboard layout.
+    pub code: String,
+    #[serde(default)]
+    pub ctrl_key: bool,
+    #[serde(default)]
+    pub alt_key: bool,
+    #[serde(default)]
+    pub shift_key: bool,
+    #[serde(default)]
+    pub meta_key: bool,
+}
+
+/// Main window focus and typing state, and the accelerators released for
+/// them.
+static SUSPENSION: Mutex<Suspension> = Mutex::new(Suspension {
+    main_focused: false,
+    typing: false,
+    released: Vec::new(),
+});
+
+struct Suspension {
+    main_focused: bool,
+    /// A text field in the main window has focus, as the frontend reports.
+    typing: bool,
+    released: Vec<String>,
+}
+
+/// When a global binding steps aside for the app itself.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum SuspendMode {
+    #[default]
+    Never,
+    /// While the main window has focus.
+    WhenFocused,
+    /// While a text field in the main window has focus.
+    WhileTyping,
+}
+
+/// A system-wide shortcut bound to a frontend action.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GlobalBinding {
+    pub accelerator: String,
+    /// Sent to the main window as the `shortcut:action` payload.
+    pub action: String,
+    /// Show and focus the main window before sending the action.
+    #[serde(default)]
+    pub show_window: bool,
+    #[serde(default)]
+    pub suspend: SuspendMode,
+}
+
+/// Where a clashing shortcut is bound.
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictSource {
+    /// A menu accelerator.
+    Menu,
+    /// A system-wide shortcut of the app.
+    Global,
+    /// Reserved by the OS.
+    System,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ShortcutConflict {
+    pub source: ConflictSource,
+    /// Menu action, global shortcut or system shortcut id.
+    pub action: String,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ShortcutCapture {
+    pub accelerator: String,
+    pub conflicts: Vec<ShortcutConflict>,
+}
+
+/// The user's global bindings.
+pub fn global_bindings(app: &AppHandle) -> Vec<GlobalBinding> {
+    settings::load(app, GLOBAL_KEY).unwrap_or_default()
+}
+
+/// Register every global shortcut: the app's own (quick switcher,
+/// screenshot, calls, workspaces, media keys) and the user's.
+/// One taken by another app is logged and skipped.
+pub fn register_all(app: &AppHandle) {
+    crate::switcher::register_shortcut(app);
+    crate::screenshot::register_shortcut(app);
+    crate::call_shortcuts::register_shortcuts(app);
+    crate::workspace_shortcuts::register_shortcuts(app);
+    crate::media_keys::register_shortcuts(app);
+    let global = app.global_shortcut();
+    SUSPENSION.lock().unwrap().released.clear();
+    for binding in global_bindings(app) {
+        if let Err(e) = global.register(binding.accelerator.as_str()) {
+            log::warn!(
+                "[nchat-desktop] failed to register shortcut {} for {}: {e}",
+                binding.accelerator,
+                binding.action
+            );
+        }
+    }
+    apply_suspension(app);
+}
+
+/// Register the stored bindings again, e.g. after a settings import.
+pub fn reload(app: &AppHandle) {
+    if crate::kiosk::is_active() || is_capturing() {
+        return;
+    }
+    let _ = app.global_shortcut().unregister_all();
+    register_all(app);
+}
+
+/// Move one of the app's own global shortcuts from `previous` to `accel`;
+/// either may be empty for none. A failed change leaves `previous` working.
+pub fn rebind(app: &AppHandle, previous: &str, accel: &str) -> Result<(), String> {
+    if !accel.is_empty() {
+        accel
+            .parse::<Shortcut>()
+            .map_err(|e| format!("invalid shortcut {accel}: {e}"))?;
+    }
+    let global = app.global_shortcut();
+    if !previous.is_empty() && global.is_registered(previous) {
+        global.unregister(previous).map_err(|e| e.to_string())?;
+    }
+    if !accel.is_empty() {
+        if let Err(e) = global.register(accel) {
+            if !previous.is_empty() {
+                let _ = global.register(previous);
+            }
+            return Err(format!("shortcut {accel} is not available: {e}"));
+        }
+    }
+    Ok(())
+}
+
+/// Bind `binding.accelerator` system-wide, replacing the action's previous
+/// binding and anything else bound to the same keys.
+pub fn bind(app: &AppHandle, binding: GlobalBinding) -> Result<(), String> {
+    if crate::kiosk::is_active() {
+        return Err("not available in kiosk mode".into());
+    }
+    let action = binding.action.trim();
+    if action.is_empty() {
+        return Err("shortcut action is empty".into());
+    }
+    let accelerator = binding.accelerator.trim();
+    accelerator
+        .parse::<Shortcut>()
+        .map_err(|e| format!("invalid shortcut {accelerator}: {e}"))?;
+    let keys = normalize(accelerator).ok_or_else(|| format!("invalid shortcut {accelerator}"))?;
+    let taken = app_shortcuts(app)
+        .into_iter()
+        .find(|(_, accel)| normalize(accel).as_deref() == Some(keys.as_str()));
+    if let Some((id, _)) = taken {
+        return Err(format!("{accelerator} is already the {id} shortcut"));
+    }
+    let global = app.global_shortcut();
+    let mut bindings = global_bindings(app);
+    let (replaced, mut kept): (Vec<_>, Vec<_>) = bindings.drain(..).partition(|b| {
+        b.action == action || normalize(&b.accelerator).as_deref() == Some(keys.as_str())
+    });
+    for old in &replaced {
+        let _ = global.unregister(old.accelerator.as_str());
+    }
+    if let Err(e) = global.register(accelerator) {
+        // Put the old bindings back so a failed change leaves them working.
+        for old in &replaced {
+            let _ = global.register(old.accelerator.as_str());
+        }
+        return Err(format!("shortcut {accelerator} is not available: {e}"));
+    }
+    SUSPENSION.lock().unwrap().released.retain(|released| {
+        released != accelerator && !replaced.iter().any(|old| old.accelerator == *released)
+    });
+    kept.push(GlobalBinding {
+        accelerator: accelerator.to_string(),
+        action: action.to_string(),
+        show_window: binding.show_window,
+        suspend: binding.suspend,
+    });
+    settings::save(app, GLOBAL_KEY, &kept)?;
+    apply_suspension(app);
+    Ok(())
+}
+
+/// Remove the global binding of `action`, if any.
+pub fn unbind(app: &AppHandle, action: &str) -> Result<(), String> {
+    let (removed, kept): (Vec<_>, Vec<_>) = global_bindings(app)
+        .into_iter()
+        .partition(|b| b.action == action);
+    if removed.is_empty() {
+        return Ok(());
+    }
+    for binding in &removed {
+        let _ = app
+            .global_shortcut()
+            .unregister(binding.accelerator.as_str());
+    }
+    settings::save(app, GLOBAL_KEY, &kept)
+}
+
+/// Whether a shortcut is being recorded.
+pub fn is_capturing() -> bool {
+    CAPTURE.lock().unwrap().is_some()
+}
+
+/// Called when the main window gains or loses focus.
+pub fn on_main_focus_changed(app: &AppHandle, focused: bool) {
+    let changed = {
+        let mut suspension = SUSPENSION.lock().unwrap();
+        let changed = suspension.main_focused != focused;
+        suspension.main_focused = focused;
+        changed
+    };
+    if changed {
+        apply_suspension(app);
+    }
+}
+
+/// The frontend reports whether a text field in the main window has focus.
+pub fn set_typing(app: &AppHandle, typing: bool) {
+    let changed = {
+        let mut suspension = SUSPENSION.lock().unwrap();
+        let changed = suspension.typing != typing;
+        suspension.typing = typing;
+        changed
+    };
+    if changed {
+        apply_suspension(app);
+    }
+}
+
+/// Release the bindings that step aside in the current state and register
+/// the ones whose reason to step aside has passed.
+fn apply_suspension(app: &AppHandle) {
+    if crate::kiosk::is_active() || CAPTURE.lock().unwrap().is_some() {
+        return;
+    }
+    let bindings = global_bindings(app);
+    let global = app.global_shortcut();
+    let mut suspension = SUSPENSION.lock().unwrap();
+    let suspended: Vec<String> = bindings
+        .iter()
+        .filter(|b| match b.suspend {
+            SuspendMode::Never => false,
+            SuspendMode::WhenFocused => suspension.main_focused,
+            SuspendMode::WhileTyping => suspension.main_focused && suspension.typing,
+        })
+        .map(|b| b.accelerator.clone())
+        .collect();
+    for accelerator in &suspended {
+        if !suspension.released.contains(accelerator) {
+            let _ = global.unregister(accelerator.as_str());
+        }
+    }
+    for accelerator in &suspension.released {
+        let still_bound = bindings.iter().any(|b| b.accelerator == *accelerator);
+        if still_bound && !suspended.contains(accelerator) {
+            if let Err(e) = global.register(accelerator.as_str()) {
+                log::warn!("[nchat-desktop] failed to restore shortcut {accelerator}: {e}");
+            }
+        }
+    }
+    suspension.released = suspended;
+}
+
+/// Global shortcut handler for the user's bindings. Returns whether the
+/// shortcut was one of them.
+pub fn on_shortcut(app: &AppHandle, shortcut: &Shortcut, event: ShortcutEvent) -> bool {
+    let binding = global_bindings(app).into_iter().find(|b| {
+        b.accelerator
+            .parse::<Shortcut>()
+            .is_ok_and(|s| s.id() == shortcut.id())
+    });
+    let Some(binding) = binding else {
+        return false;
+    };
+    if event.state != ShortcutState::Pressed {
+        return true;
+    }
+    let Some(win) = app.get_webview_window(MAIN_WINDOW) else {
+        return true;
+    };
+    if binding.show_window {
+        let _ = win.show();
+        let _ = win.unminimize();
+        let _ = win.set_focus();
+    }
+    let _ = win.emit("shortcut:action", binding.action);
+    true
+}
+
+/// Record the next shortcut pressed on the settings page. `action` is the
+/// binding being changed (a menu action, a global binding's action, or one
+/// of the app's own global shortcuts such as `global:quick-switcher`), which
+/// does not count as a conflict with itself.
+/// Returns `None` when the user cancels with Escape.
+pub async fn capture(
+    app: &AppHandle,
+    action: Option<&str>,
+) -> Result<Option<ShortcutCapture>, String> {
+    if crate::kiosk::is_active() {
+        return Err("not available in kiosk mode".into());
+    }
+    app.global_shortcut()
+        .unregister_all()
+        .map_err(|e| e.to_string())?;
+    let (sender, receiver) = oneshot::channel();
+    // A capture already running is cancelled: its sender is dropped.
+    *CAPTURE.lock().unwrap() = Some(sender);
+    let result = tokio::time::timeout(CAPTURE_TIMEOUT, receiver).await;
+    let idle = {
+        let mut current = CAPTURE.lock().unwrap();
+        if result.is_err() {
+            // Timed out: the sender is still ours.
+            current.take();
+        }
+        current.is_none()
+    };
+    // A newer capture keeps the shortcuts released until it finishes.
+    if idle {
+        register_all(app);
+    }
+    match result {
+        Ok(Ok(Some(accelerator))) => Ok(Some(ShortcutCapture {
+            conflicts: conflicts(app, &accelerator, action),
+            accelerator,
+        })),
+        Ok(Ok(None)) => Ok(None),
+        Ok(Err(_)) => Err("shortcut capture was replaced".into()),
+        Err(_) => Err("no shortcut was pressed".into()),
+    }
+}
+
+/// Hand a key press to the running capture. Returns whether it ended the
+/// capture; lone modifiers and keys without a modifier keep it waiting.
+pub fn capture_key(key: &CapturedKey) -> bool {
+    let Some(accelerator) = accelerator_for(key) else {
+        return false;
+    };
+    let Some(sender) = CAPTURE.lock().unwrap().take() else {
+        return false;
+    };
+    let _ = sender.send(accelerator);
+    true
+}
+
+/// The accelerator for a key press: `Some(None)` for a plain Escape, `None`
+/// for presses that are not a shortcut.
+fn accelerator_for(key: &CapturedKey) -> Option<Option<String>> {
+    if MODIFIER_CODES.contains(&key.code.as_str()) {
+        return None;
+    }
+    let name = key_from_code(&key.code)?;
+    let modified = key.ctrl_key || key.alt_key || key.meta_key;
+    if !modified && !key.shift_key && name == "Escape" {
+        return Some(None);
+    }
+    if !modified && !is_function_key(&name) {
+        return None;
+    }
+    let modifiers = Modifiers {
+        primary: if cfg!(target_os = "macos") {
+            key.meta_key
+        } else {
+            key.ctrl_key
+        },
+        ctrl: cfg!(target_os = "macos") && key.ctrl_key,
+        alt: key.alt_key,
+        shift: key.shift_key,
+        super_key: !cfg!(target_os = "macos") && key.meta_key,
+    };
+    Some(Some(modifiers.join(&name)))
+}
+
+/// Modifiers of a normalized accelerator. `primary` is Cmd on macOS and
+/// Ctrl elsewhere.
+#[derive(Default, PartialEq, Eq)]
+struct Modifiers {
+    primary: bool,
+    ctrl: bool,
+    alt: bool,
+    shift: bool,
+    super_key: bool,
+}
+
+impl Modifiers {
+    fn join(&self, key: &str) -> String {
+        let mut parts = Vec::new();
+        for (on, name) in [
+            (self.primary, "CmdOrCtrl"),
+            (self.ctrl, "Ctrl"),
+            (self.alt, "Alt"),
+            (self.shift, "Shift"),
+            (self.super_key, "Super"),
+        ] {
+            if on {
+                parts.push(name);
+            }
+        }
+        parts.push(key);
+        parts.join("+")
+    }
+}
+
+/// Rewrite `accel` in the normalized form, so differently spelled bindings
+/// of the same keys (`Ctrl+k` and `CmdOrCtrl+K` off macOS) compare equal.
+pub fn normalize(accel: &str) -> Option<String> {
+    let mut parts: Vec<&str> = accel.split('+').map(str::trim).collect();
+    let key = canonical_key(parts.pop()?)?;
+    let mut modifiers = Modifiers::default();
+    for part in parts {
+        match part.to_ascii_uppercase().as_str() {
+            "CMDORCTRL" | "CMDORCONTROL" | "COMMANDORCONTROL" | "COMMANDORCTRL" => {
+                modifiers.primary = true
+            }
+            "CTRL" | "CONTROL" if cfg!(target_os = "macos") => modifiers.ctrl = true,
+            "CTRL" | "CONTROL" => modifiers.primary = true,
+            "CMD" | "COMMAND" | "SUPER" | "META" if cfg!(target_os = "macos") => {
+                modifiers.primary = true
+            }
+            "CMD" | "COMMAND" | "SUPER" | "META" => modifiers.super_key = true,
+            "ALT" | "OPTION" => modifiers.alt = true,
+            "SHIFT" => modifiers.shift = true,
+            _ => return None,
+        }
+    }
+    Some(modifiers.join(&key))
+}
+
+/// Normalized name of an accelerator key token.
+fn canonical_key(token: &str) -> Option<String> {
+    let upper = token.to_ascii_uppercase();
+    if upper.len() == 1 && upper.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Some(upper);
+    }
+    if let Some(n) = upper.strip_prefix("NUMPAD").or(upper.strip_prefix("NUM")) {
+        if n.len() == 1 && n.chars().all(|c| c.is_ascii_digit()) {
+            return Some(format!("Num{n}"));
+        }
+    }
+    if is_function_key(&upper) {
+        return Some(upper);
+    }
+    NAMED_KEYS
+        .iter()
+        .find(|(aliases, _)| aliases.contains(&upper.as_str()))
+        .map(|(_, name)| name.to_string())
+}
+
+/// Normalized name of the key a DOM `KeyboardEvent.code` names.
+fn key_from_code(code: &str) -> Option<String> {
+    let key = code
+        .strip_prefix("Key")
+        .or_else(|| code.strip_prefix("Digit"))
+        .filter(|key| key.len() == 1)
+        .unwrap_or(code);
+    canonical_key(key)
+}
+
+/// `F1` to `F24`, which work as shortcuts without a modifier.
+fn is_function_key(name: &str) -> bool {
+    name.strip_prefix('F')
+        .and_then(|n| n.parse::<u8>().ok())
+        .is_some_and(|n| (1..=24).contains(&n))
+}
+
+/// The app's own global shortcuts that are set, by action id. Ids start with
+/// `global:`, apart from the menu's in-app actions of the same name.
+fn app_shortcuts(app: &AppHandle) -> Vec<(String, String)> {
+    let mut shortcuts = vec![
+        (
+            "global:quick-switcher".to_string(),
+            crate::switcher::shortcut(app),
+        ),
+        (
+            "global:screenshot".to_string(),
+            crate::screenshot::shortcut(app),
+        ),
+    ];
+    shortcuts.extend(
+        crate::call_shortcuts::shortcuts(app)
+            .into_iter()
+            .map(|(action, accel)| (format!("global:{action}"), accel)),
+    );
+    shortcuts.extend(
+        crate::workspace_shortcuts::shortcuts(app)
+            .into_iter()
+            .map(|(action, accel)| (format!("global:{action}"), accel)),
+    );
+    shortcuts.extend(
+        crate::workspace_shortcuts::jump_shortcuts(app)
+            .into_iter()
+            .map(|(position, accel)| (format!("global:workspace-{position}"), accel)),
+    );
+    shortcuts.retain(|(_, accel)| !accel.is_empty());
+    shortcuts
+}
+
+/// App and OS shortcuts bound to the same keys as `accelerator`, except
+/// `action` itself.
+pub fn conflicts(
+    app: &AppHandle,
+    accelerator: &str,
+    action: Option<&str>,
+) -> Vec<ShortcutConflict> {
+    let Some(wanted) = normalize(accelerator) else {
+        return Vec::new();
+    };
+    let mut bound: Vec<(ConflictSource, String, String)> = crate::menu::accelerators(app)
+        .into_iter()
+        .map(|(action, accel)| (ConflictSource::Menu, action, accel))
+        .collect();
+    bound.extend(
+        crate::menu::recent_channel_accelerators()
+            .into_iter()
+            .map(|(action, accel)| (ConflictSource::Menu, action, accel)),
+    );
+    bound.extend(
+        app_shortcuts(app)
+            .into_iter()
+            .map(|(id, accel)| (ConflictSource::Global, id, accel)),
+    );
+    bound.extend(
+        global_bindings(app)
+            .into_iter()
+            .map(|b| (ConflictSource::Global, b.action, b.accelerator)),
+    );
+    bound.extend(
+        SYSTEM_SHORTCUTS
+            .iter()
+            .map(|(accel, id)| (ConflictSource::System, id.to_string(), accel.to_string())),
+    );
+    let mut conflicts: Vec<ShortcutConflict> = bound
+        .into_iter()
+        .filter(|(source, id, _)| *source == ConflictSource::System || Some(id.as_str()) != action)
+        .filter(|(_, _, accel)| normalize(accel).as_deref() == Some(wanted.as_str()))
+        .map(|(source, action, _)| ShortcutConflict { source, action })
+        .collect();
+    conflicts.sort_by(|a, b| a.action.cmp(&b.action));
+    conflicts
+}
@@ -0,0 +1,232 @@
+// nChat Desktop — spellchecker configuration
+//
+// The webview engines spellcheck with the platform's dictionaries:
+// WebKitGTK through enchant/hunspell, WKWebView through NSSpellChecker and
+// WebView2 through the Windows spellchecker (which follows the Windows
+// languages and cannot be configured per app, so the menu lists them and
+// links to the system settings). On/off is applied by the renderer of every
+// window, which follows `spellcheck:changed` with the `spellcheck`
+// attribute; that works on every engine.
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::state::settings;
+
+/// Settings key for the spellcheck configuration.
+const SPELLCHECK_KEY: &str = "spellcheck";
+
+/// Where distributions install hunspell/myspell dictionaries.
+#[cfg(target_os = "linux")]
+const DICTIONARY_DIRS: &[&str] = &[
+    "/usr/share/hunspell",
+    "/usr/share/myspell",
+    "/usr/share/myspell/dicts",
+];
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct SpellcheckSettings {
+    pub enabled: bool,
+    /// Dictionary codes such as `en_US`; empty uses the system default.
+    pub languages: Vec<String>,
+}
+
+impl Default for SpellcheckSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            languages: Vec::new(),
+        }
+    }
+}
+
+pub fn settings(app: &AppHandle) -> SpellcheckSettings {
+    settings::load(app, SPELLCHECK_KEY).unwrap_or_default()
+}
+
+/// Dictionaries installed on this system, sorted by code.
+#[cfg(target_os = "linux")]
+pub fn available_languages() -> Vec<String> {
+    let mut languages: Vec<String> = DICTIONARY_DIRS
+        .iter()
+        .filter_map(|dir| std::fs::read_dir(dir).ok())
+        .flat_map(|entries| entries.flatten())
+        .filter_map(|entry| {
+            let path = entry.path();
+            (path.extension()? == "dic")
+                .then(|| path.file_stem()?.to_str().map(str::to_string))
+                .flatten()
+        })
+        // Hyphenation patterns share the extension.
+        .filter(|code| !code.starts_with("hyph_"))
+        .collect();
+    languages.sort();
+    languages.dedup();
+    languages
+}
+
+/// Dictionaries installed on this system, sorted by code.
+#[cfg(target_os = "macos")]
+pub fn available_languages() -> Vec<String> {
+    use objc2::runtime::AnyObject;
+    use objc2::{class, msg_send};
+    use std::ffi::{c_char, CStr};
+
+    let mut languages = Vec::new();
+    unsafe {
+        let checker: *mut AnyObject = msg_send![class!(NSSpellChecker), sharedSpellChecker];
+        let list: *mut AnyObject = msg_send![checker, availableLanguages];
+        let count: usize = msg_send![list, count];
+        for i in 0..count {
+            let code: *mut AnyObject = msg_send![list, objectAtIndex: i];
+            let bytes: *const c_char = msg_send![code, UTF8String];
+            if !bytes.is_null() {
+                languages.push(CStr::from_ptr(bytes).to_string_lossy().into_owned());
+            }
+        }
+    }
+    languages.sort();
+    languages
+}
+
+/// The Windows languages (Settings → Time & language), which WebView2
+/// checks spelling in; they can be listed but not chosen per app.
+#[cfg(target_os = "windows")]
+pub fn available_languages() -> Vec<String> {
+    use windows::core::w;
+    use windows::Win32::Foundation::ERROR_SUCCESS;
+    use windows::Win32::System::Registry::{RegGetValueW, HKEY_CURRENT_USER, RRF_RT_REG_MULTI_SZ};
+
+    let key = w!("Control Panel\\International\\User Profile");
+    let value = w!("Languages");
+    let mut size = 0u32;
+    unsafe {
+        let status = RegGetValueW(
+            HKEY_CURRENT_USER,
+            key,
+            value,
+            RRF_RT_REG_MULTI_SZ,
+            None,
+            None,
+            Some(&mut size),
+        );
+        if status != ERROR_SUCCESS {
+            return Vec::new();
+        }
+        let mut buf = vec![0u16; size as usize / 2];
+        let status = RegGetValueW(
+            HKEY_CURRENT_USER,
+            key,
+            value,
+            RRF_RT_REG_MULTI_SZ,
+            None,
+            Some(buf.as_mut_ptr().cast()),
+            Some(&mut size),
+        );
+        if status != ERROR_SUCCESS {
+            return Vec::new();
+        }
+        buf.split(|&c| c == 0)
+            .filter(|code| !code.is_empty())
+            .map(String::from_utf16_lossy)
+            .collect()
+    }
+}
+
+/// Open the Windows language settings, where WebView2's spellcheck
+/// languages are chosen.
+#[cfg(target_os = "windows")]
+pub fn open_language_settings() {
+    if let Err(e) = std::process::Command::new("explorer.exe")
+        .arg("ms-settings:regionlanguage")
+        .spawn()
+    {
+        log::warn!("[nchat-desktop] failed to open language settings: {e}");
+    }
+}
+
+/// Push the configuration to the webview engines and the frontend.
+pub fn apply(app: &AppHandle) {
+    let config = settings(app);
+
+    #[cfg(target_os = "linux")]
+    {
+        use tauri::Manager;
+        use webkit2gtk::{WebContextExt, WebViewExt};
+
+        for win in app.webview_windows().into_values() {
+            let config = config.clone();
+            let _ = win.with_webview(move |webview| {
+                if let Some(context) = webview.inner().context() {
+                    context.set_spell_checking_enabled(config.enabled);
+                    // An empty list goes back to the default language.
+                    let codes: Vec<&str> = config.languages.iter().map(String::as_str).collect();
+                    context.set_spell_checking_languages(&codes);
+                }
+            });
+        }
+    }
+    #[cfg(target_os = "macos")]
+    {
+        let languages = config.languages.clone();
+        let _ = app.run_on_main_thread(move || unsafe {
+            use objc2::runtime::{AnyObject, Bool};
+            use objc2::{class, msg_send};
+
+            let checker: *mut AnyObject = msg_send![class!(NSSpellChecker), sharedSpellChecker];
+            // A single language is pinned; otherwise macOS picks per word.
+            let automatic = languages.len() != 1;
+            let _: () =
+                msg_send![checker, setAutomaticallyIdentifiesLanguages: Bool::new(automatic)];
+            if let [language] = languages.as_slice() {
+                let Ok(code) = std::ffi::CString::new(language.as_str()) else {
+                    return;
+                };
+                let code: *mut AnyObject =
+                    msg_send![class!(NSString), stringWithUTF8String: code.as_ptr()];
+                let _: Bool = msg_send![checker, setLanguage: code];
+            }
+        });
+    }
+
+    let _ = app.emit("spellcheck:changed", config);
+}
+
+fn save(app: &AppHandle, config: &SpellcheckSettings) -> Result<(), String> {
+    settings::save(app, SPELLCHECK_KEY, config)?;
+    apply(app);
+    Ok(())
+}
+
+pub fn set_enabled(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    let mut config = settings(app);
+    config.enabled = enabled;
+    save(app, &config)
+}
+
+/// Replace the active dictionaries. Codes must be installed.
+pub fn set_languages(app: &AppHandle, languages: Vec<String>) -> Result<(), String> {
+    if cfg!(target_os = "windows") {
+        return Err("on Windows, spellcheck follows the Windows language settings".into());
+    }
+    let available = available_languages();
+    if let Some(missing) = languages.iter().find(|code| !available.contains(code)) {
+        return Err(format!("no dictionary installed for {missing}"));
+    }
+    let mut config = settings(app);
+    config.languages = languages;
+    save(app, &config)
+}
+
+/// Add or remove one dictionary, as the Edit → Spelling items do.
+pub fn toggle_language(app: &AppHandle, code: &str) -> Result<(), String> {
+    let mut languages = settings(app).languages;
+    match languages.iter().position(|l| l == code) {
+        Some(i) => {
+            languages.remove(i);
+        }
+        None => languages.push(code.to_string()),
+    }
+    set_languages(app, languages)
+}
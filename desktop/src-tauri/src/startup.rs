@@ -0,0 +1,138 @@
+// nChat Desktop — where the app opens at launch
+//
+// The user picks a startup view; the frontend asks for the resolved target
+// once on its first load. A link the app was launched with takes
+// precedence, since it is more specific than any preference.
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+use crate::deeplink::{self, DeepLink, Route};
+use crate::state::settings;
+
+/// Settings key for the user's startup preference.
+const VIEW_KEY: &str = "startup.view";
+
+/// Settings key for the last location the frontend reported.
+const LAST_LOCATION_KEY: &str = "startup.lastLocation";
+
+/// Set once the startup target has been handed out; reloads keep their
+/// current place.
+static TAKEN: AtomicBool = AtomicBool::new(false);
+
+/// Target derived from the link the app was launched with, if any.
+static LAUNCH_LINK: Mutex<Option<StartupTarget>> = Mutex::new(None);
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum StartupView {
+    #[default]
+    LastUsed,
+    UnifiedInbox,
+    Channel {
+        id: String,
+    },
+    Workspace {
+        id: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct Location {
+    pub workspace_id: Option<String>,
+    pub channel_id: Option<String>,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum StartupTarget {
+    UnifiedInbox,
+    Channel {
+        id: String,
+        workspace_id: Option<String>,
+    },
+    Workspace {
+        id: String,
+    },
+    Invite {
+        token: String,
+    },
+    /// The app route the main window showed when the app last quit, e.g.
+    /// `/channel/general/thread/42`.
+    Route {
+        path: String,
+    },
+}
+
+pub fn view(app: &AppHandle) -> StartupView {
+    settings::load(app, VIEW_KEY).unwrap_or_default()
+}
+
+pub fn set_view(app: &AppHandle, view: StartupView) -> Result<(), String> {
+    match &view {
+        StartupView::Channel { id } | StartupView::Workspace { id } if id.is_empty() => {
+            Err("startup view needs an id".into())
+        }
+        _ => settings::save(app, VIEW_KEY, &view),
+    }
+}
+
+/// Remember where the user is, for the `last-used` startup view.
+pub fn set_last_location(app: &AppHandle, location: Location) -> Result<(), String> {
+    settings::save(app, LAST_LOCATION_KEY, &location)
+}
+
+/// Record the links the app was launched with; the first routable one
+/// becomes the startup target.
+pub fn set_launch_links(urls: &[String]) {
+    let target = urls.iter().find_map(|url| match deeplink::parse(url)? {
+        link if link.routed_at_launch() => None,
+        DeepLink::Open(link) => match link.route {
+            Route::Channel { id, .. } => Some(StartupTarget::Channel {
+                id,
+                workspace_id: link.workspace_id,
+            }),
+            Route::Workspace { id } => Some(StartupTarget::Workspace { id }),
+            Route::Invite { code } => Some(StartupTarget::Invite { token: code }),
+            Route::User { .. }
+            | Route::Call { .. }
+            | Route::Dial { .. }
+            | Route::Compose { .. }
+            | Route::Status { .. } => None,
+        },
+        DeepLink::Notify(_) => None,
+    });
+    *LAUNCH_LINK.lock().unwrap() = target;
+}
+
+/// Where the frontend should navigate after launch. Returns `None` after
+/// the first call, or when the frontend's own default applies.
+pub fn take_target(app: &AppHandle) -> Option<StartupTarget> {
+    if TAKEN.swap(true, Ordering::SeqCst) {
+        return None;
+    }
+    if let Some(target) = LAUNCH_LINK.lock().unwrap().take() {
+        return Some(target);
+    }
+    match view(app) {
+        StartupView::LastUsed => {
+            if let Some(path) = crate::session::take_main_route() {
+                return Some(StartupTarget::Route { path });
+            }
+            let last: Location = settings::load(app, LAST_LOCATION_KEY)?;
+            match (last.channel_id, last.workspace_id) {
+                (Some(id), workspace_id) => Some(StartupTarget::Channel { id, workspace_id }),
+                (None, Some(id)) => Some(StartupTarget::Workspace { id }),
+                (None, None) => None,
+            }
+        }
+        StartupView::UnifiedInbox => Some(StartupTarget::UnifiedInbox),
+        StartupView::Channel { id } => Some(StartupTarget::Channel {
+            id,
+            workspace_id: None,
+        }),
+        StartupView::Workspace { id } => Some(StartupTarget::Workspace { id }),
+    }
+}
@@ -0,0 +1,30 @@
+// Portable configuration — the user-facing part of the native settings as
+// one versioned JSON file, for IT-distributed defaults and for moving
+// preferences between machines. Which keys travel is decided by
+// `nchat_core::settings`; this reads and writes the file.
+
+use nchat_core::settings::{self as core_settings, AppConfig};
+use std::path::Path;
+use tauri::AppHandle;
+
+pub use nchat_core::settings::ConfigImportReport;
+
+use super::settings;
+
+pub fn export(app: &AppHandle, path: &Path) -> Result<usize, String> {
+    let config = core_settings::export_config(
+        &settings::store(app)?,
+        &app.package_info().version.to_string(),
+    );
+    let json = serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?;
+    std::fs::write(path, json).map_err(|e| e.to_string())?;
+    Ok(config.settings.len())
+}
+
+/// Apply a configuration file over the current settings. Keys that are
+/// machine-local or look like credentials are reported as skipped.
+pub fn import(app: &AppHandle, path: &Path) -> Result<ConfigImportReport, String> {
+    let raw = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let config: AppConfig = serde_json::from_str(&raw).map_err(|e| e.to_string())?;
+    core_settings::import_config(&settings::store(app)?, config)
+}
@@ -1,2 +1,4 @@
-// State module — reserved for future AppState struct
-// Add shared application state here as features require it.
+// State module — shared application state and persisted native settings.
+
+pub mod config;
+pub mod settings;
@@ -0,0 +1,74 @@
+// nChat Desktop — persisted native settings (tauri-plugin-store)
+//
+// The typed accessors and the storage contract are `nchat_core::settings`;
+// this binds them to the app's store file.
+
+use nchat_core::settings::{self as core_settings, Store as _};
+use serde::{de::DeserializeOwned, Serialize};
+use std::sync::Arc;
+use tauri::{AppHandle, Wry};
+use tauri_plugin_store::{Store, StoreExt};
+
+/// Store file holding every setting owned by the native layer.
+pub const SETTINGS_STORE: &str = "settings.json";
+
+/// The settings store file, flushed after every write.
+pub struct AppStore(Arc<Store<Wry>>);
+
+impl core_settings::Store for AppStore {
+    fn get(&self, key: &str) -> Option<serde_json::Value> {
+        self.0.get(key)
+    }
+
+    fn set(&self, key: &str, value: serde_json::Value) -> Result<(), String> {
+        self.0.set(key, value);
+        self.0.save().map_err(|e| e.to_string())
+    }
+
+    fn delete(&self, key: &str) -> Result<(), String> {
+        if self.0.delete(key) {
+            self.0.save().map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+
+    fn entries(&self) -> Vec<(String, serde_json::Value)> {
+        self.0.entries()
+    }
+
+    fn has(&self, key: &str) -> bool {
+        self.0.has(key)
+    }
+}
+
+pub fn store(app: &AppHandle) -> Result<AppStore, String> {
+    app.store(SETTINGS_STORE)
+        .map(AppStore)
+        .map_err(|e| e.to_string())
+}
+
+/// Read and deserialize a setting. Returns `None` if the key is missing or
+/// the stored value no longer matches `T` (e.g. after a schema change).
+pub fn load<T: DeserializeOwned>(app: &AppHandle, key: &str) -> Option<T> {
+    core_settings::load(&store(app).ok()?, key)
+}
+
+/// Serialize a setting and flush the store to disk.
+pub fn save<T: Serialize>(app: &AppHandle, key: &str, value: &T) -> Result<(), String> {
+    core_settings::save(&store(app)?, key, value)
+}
+
+/// All stored settings, used for diagnostics snapshots.
+pub fn entries(app: &AppHandle) -> Vec<(String, serde_json::Value)> {
+    store(app).map(|store| store.entries()).unwrap_or_default()
+}
+
+/// Returns true if the key is present in the store.
+pub fn contains(app: &AppHandle, key: &str) -> bool {
+    store(app).map(|store| store.has(key)).unwrap_or(false)
+}
+
+/// Remove a setting and flush the store. Missing keys are not an error.
+pub fn remove(app: &AppHandle, key: &str) -> Result<(), String> {
+    store(app)?.delete(key)
+}
@@ -0,0 +1,178 @@
+// nChat Desktop — opt-in personal usage statistics
+//
+// Messages sent per day, time spent in calls and per-channel activity are
+// counted in a separate local store for the "Your year in nChat" screen.
+// Nothing here is ever uploaded; turning the feature off deletes it.
+
+use chrono::{Duration, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_store::StoreExt;
+
+use crate::state::settings;
+
+/// Store file for the statistics, kept apart from settings so it can be
+/// wiped as a whole.
+const STATS_STORE: &str = "stats.json";
+const DAYS_KEY: &str = "days";
+const ENABLED_KEY: &str = "stats.enabled";
+
+/// Channels returned in `top_channels`.
+const TOP_CHANNELS: usize = 5;
+
+#[derive(Default)]
+pub struct StatsState {
+    /// Serializes read-modify-write of the store.
+    lock: Mutex<()>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct DayStats {
+    messages: u32,
+    call_secs: u64,
+    /// Messages sent per channel name.
+    channels: HashMap<String, u32>,
+}
+
+/// Something worth counting, reported by the frontend.
+#[derive(Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Activity {
+    MessageSent { channel: String },
+    CallEnded { duration_secs: u64 },
+}
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum StatsPeriod {
+    Week,
+    Month,
+    Year,
+    All,
+}
+
+#[derive(Serialize)]
+pub struct DailyActivity {
+    pub date: String,
+    pub messages: u32,
+}
+
+#[derive(Serialize)]
+pub struct ChannelActivity {
+    pub channel: String,
+    pub messages: u32,
+}
+
+#[derive(Serialize)]
+pub struct PersonalStats {
+    pub enabled: bool,
+    pub messages_sent: u32,
+    pub call_secs: u64,
+    pub active_days: u32,
+    /// One entry per day with activity, oldest first (heatmap cells).
+    pub daily: Vec<DailyActivity>,
+    pub top_channels: Vec<ChannelActivity>,
+}
+
+pub fn is_enabled(app: &AppHandle) -> bool {
+    settings::load(app, ENABLED_KEY).unwrap_or(false)
+}
+
+fn load_days(app: &AppHandle) -> BTreeMap<String, DayStats> {
+    app.store(STATS_STORE)
+        .ok()
+        .and_then(|store| store.get(DAYS_KEY))
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
+
+fn save_days(app: &AppHandle, days: &BTreeMap<String, DayStats>) -> Result<(), String> {
+    let store = app.store(STATS_STORE).map_err(|e| e.to_string())?;
+    let json = serde_json::to_value(days).map_err(|e| e.to_string())?;
+    store.set(DAYS_KEY, json);
+    store.save().map_err(|e| e.to_string())
+}
+
+/// Opt in or out. Opting out deletes everything collected so far.
+pub fn set_enabled(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    settings::save(app, ENABLED_KEY, &enabled)?;
+    if !enabled {
+        let _guard = app.state::<StatsState>().lock.lock().unwrap();
+        let store = app.store(STATS_STORE).map_err(|e| e.to_string())?;
+        store.clear();
+        store.save().map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Count an activity against today. Ignored unless the user opted in.
+pub fn record(app: &AppHandle, activity: Activity) -> Result<(), String> {
+    if !is_enabled(app) {
+        return Ok(());
+    }
+    let _guard = app.state::<StatsState>().lock.lock().unwrap();
+    let mut days = load_days(app);
+    let today = days
+        .entry(Local::now().date_naive().to_string())
+        .or_default();
+    match activity {
+        Activity::MessageSent { channel } => {
+            today.messages += 1;
+            *today.channels.entry(channel).or_default() += 1;
+        }
+        Activity::CallEnded { duration_secs } => today.call_secs += duration_secs,
+    }
+    save_days(app, &days)
+}
+
+/// Aggregate the days that fall into `period`, ending today.
+pub fn summarize(app: &AppHandle, period: StatsPeriod) -> PersonalStats {
+    let today = Local::now().date_naive();
+    let since = match period {
+        StatsPeriod::Week => Some(today - Duration::days(6)),
+        StatsPeriod::Month => Some(today - Duration::days(29)),
+        StatsPeriod::Year => Some(today - Duration::days(364)),
+        StatsPeriod::All => None,
+    };
+
+    let mut stats = PersonalStats {
+        enabled: is_enabled(app),
+        messages_sent: 0,
+        call_secs: 0,
+        active_days: 0,
+        daily: Vec::new(),
+        top_channels: Vec::new(),
+    };
+    let mut channels: HashMap<String, u32> = HashMap::new();
+    for (date, day) in load_days(app) {
+        let Ok(parsed) = date.parse::<NaiveDate>() else {
+            continue;
+        };
+        if since.is_some_and(|since| parsed < since) {
+            continue;
+        }
+        stats.messages_sent += day.messages;
+        stats.call_secs += day.call_secs;
+        if day.messages > 0 || day.call_secs > 0 {
+            stats.active_days += 1;
+        }
+        for (channel, count) in day.channels {
+            *channels.entry(channel).or_default() += count;
+        }
+        stats.daily.push(DailyActivity {
+            date,
+            messages: day.messages,
+        });
+    }
+
+    let mut channels: Vec<_> = channels.into_iter().collect();
+    channels.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    stats.top_channels = channels
+        .into_iter()
+        .take(TOP_CHANNELS)
+        .map(|(channel, messages)| ChannelActivity { channel, messages })
+        .collect();
+    stats
+}
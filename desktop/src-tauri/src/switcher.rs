@@ -0,0 +1,156 @@
+// nChat Desktop — quick switcher overlay
+//
+// A Spotlight-style borderless window hosting the frontend quick switcher,
+// toggled by a system-wide shortcut so channels can be reached from any
+// app. The window is created on first use and then only hidden and shown.
+// It hides itself when it loses focus; Escape and picking a result are
+// handled by the frontend, which calls `hide_quick_switcher`.
+
+use tauri::{
+    AppHandle, Emitter, Manager, PhysicalPosition, WebviewUrl, WebviewWindow, WebviewWindowBuilder,
+};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutEvent, ShortcutState};
+
+use crate::state::settings;
+
+/// Label of the overlay window; there is at most one.
+pub const SWITCHER_WINDOW: &str = "quick-switcher";
+
+/// Frontend route rendered in the overlay.
+const SWITCHER_ROUTE: &str = "index.html#/quick-switcher";
+
+/// Settings key holding the global shortcut; an empty string disables it.
+const SHORTCUT_KEY: &str = "switcher.shortcut";
+
+/// Distinct from the in-app Cmd/Ctrl+K so both can be used.
+const DEFAULT_SHORTCUT: &str = "CmdOrCtrl+Alt+K";
+
+/// Logical size of the overlay.
+const WIDTH: f64 = 640.0;
+const HEIGHT: f64 = 420.0;
+
+/// The configured global shortcut, or an empty string when disabled.
+pub fn shortcut(app: &AppHandle) -> String {
+    settings::load(app, SHORTCUT_KEY).unwrap_or_else(|| DEFAULT_SHORTCUT.to_string())
+}
+
+/// Register the configured shortcut at launch. Another app holding the
+/// same combination is not fatal; the in-app switcher still works.
+pub fn register_shortcut(app: &AppHandle) {
+    let accel = shortcut(app);
+    if accel.is_empty() {
+        return;
+    }
+    if let Err(e) = app.global_shortcut().register(accel.as_str()) {
+        log::warn!("[nchat-desktop] failed to register quick switcher shortcut {accel}: {e}");
+    }
+}
+
+/// Replace the global shortcut, e.g. `set_shortcut("Alt+Space")`. Pass an
+/// empty string to turn it off.
+pub fn set_shortcut(app: &AppHandle, accel: &str) -> Result<(), String> {
+    let accel = accel.trim();
+    crate::shortcuts::rebind(app, &shortcut(app), accel)?;
+    settings::save(app, SHORTCUT_KEY, &accel)
+}
+
+/// Global shortcut handler; only reacts to the key going down. Returns
+/// whether the shortcut was the switcher's.
+pub fn on_shortcut(app: &AppHandle, shortcut: &Shortcut, event: ShortcutEvent) -> bool {
+    let is_switcher = self::shortcut(app)
+        .parse::<Shortcut>()
+        .is_ok_and(|own| own.id() == shortcut.id());
+    if !is_switcher {
+        return false;
+    }
+    if event.state == ShortcutState::Pressed {
+        toggle(app);
+    }
+    true
+}
+
+/// Show the overlay, or hide it if it is already in front.
+pub fn toggle(app: &AppHandle) {
+    let showing = app
+        .get_webview_window(SWITCHER_WINDOW)
+        .is_some_and(|win| win.is_visible().unwrap_or(false) && win.is_focused().unwrap_or(false));
+    let result = if showing { hide(app, None) } else { show(app) };
+    if let Err(e) = result {
+        log::warn!("[nchat-desktop] quick switcher: {e}");
+    }
+}
+
+/// Center the overlay on the monitor under the cursor and focus it. The
+/// frontend gets `quick-switcher:show` to clear the previous query.
+pub fn show(app: &AppHandle) -> Result<(), String> {
+    if crate::kiosk::is_active() {
+        return Err("not available in kiosk mode".into());
+    }
+    let win = match app.get_webview_window(SWITCHER_WINDOW) {
+        Some(win) => win,
+        None => {
+            WebviewWindowBuilder::new(app, SWITCHER_WINDOW, WebviewUrl::App(SWITCHER_ROUTE.into()))
+                .title("nChat")
+                .inner_size(WIDTH, HEIGHT)
+                .decorations(false)
+                .resizable(false)
+                .always_on_top(true)
+                .skip_taskbar(true)
+                .visible(false)
+                .build()
+                .map_err(|e| e.to_string())?
+        }
+    };
+    center_on_active_monitor(app, &win)?;
+    win.show().map_err(|e| e.to_string())?;
+    win.set_focus().map_err(|e| e.to_string())?;
+    let _ = win.emit("quick-switcher:show", ());
+    Ok(())
+}
+
+/// Hide the overlay. With a `target` (the result the user picked), the main
+/// window is brought forward and receives `quick-switcher:open` with it.
+pub fn hide(app: &AppHandle, target: Option<String>) -> Result<(), String> {
+    if let Some(win) = app.get_webview_window(SWITCHER_WINDOW) {
+        win.hide().map_err(|e| e.to_string())?;
+    }
+    if let Some(target) = target {
+        let main = app
+            .get_webview_window(crate::windows::MAIN_WINDOW)
+            .ok_or("main window not found")?;
+        let _ = main.unminimize();
+        main.show().map_err(|e| e.to_string())?;
+        main.set_focus().map_err(|e| e.to_string())?;
+        main.emit("quick-switcher:open", target)
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// The overlay lost focus: put it away like a menu would.
+pub fn on_blur(app: &AppHandle) {
+    if let Err(e) = hide(app, None) {
+        log::warn!("[nchat-desktop] quick switcher: {e}");
+    }
+}
+
+/// The monitor under the cursor, falling back to the main window's and
+/// then the primary display.
+fn center_on_active_monitor(app: &AppHandle, win: &WebviewWindow) -> Result<(), String> {
+    let monitor = app
+        .cursor_position()
+        .ok()
+        .and_then(|p| app.monitor_from_point(p.x, p.y).ok().flatten())
+        .or_else(|| {
+            app.get_webview_window(crate::windows::MAIN_WINDOW)
+                .and_then(|main| main.current_monitor().ok().flatten())
+        })
+        .or_else(|| app.primary_monitor().ok().flatten())
+        .ok_or("no monitor available")?;
+    let area = monitor.work_area();
+    let size = win.outer_size().map_err(|e| e.to_string())?;
+    let x = area.position.x + (area.size.width.saturating_sub(size.width) / 2) as i32;
+    let y = area.position.y + (area.size.height.saturating_sub(size.height) / 2) as i32;
+    win.set_position(PhysicalPosition::new(x, y))
+        .map_err(|e| e.to_string())
+}
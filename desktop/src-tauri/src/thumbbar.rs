@@ -0,0 +1,292 @@
+// nChat Desktop — call controls in the taskbar preview (Windows)
+//
+// While a call is active, the main window's taskbar thumbnail shows mute,
+// hang up and share screen buttons, so a call can be handled from another
+// app. The frontend reports the call with `set_call_controls`; clicks reach
+// the main window as `call:control` (`toggle-mute`, `hang-up` or
+// `toggle-screen-share`). Windows only lets buttons be added once per
+// window, so they are hidden rather than removed when the call ends, and
+// added again if Explorer restarts. Other platforms have no equivalent.
+
+use serde::Deserialize;
+use tauri::AppHandle;
+
+/// State of the active call.
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CallControls {
+    pub muted: bool,
+    pub sharing_screen: bool,
+}
+
+/// Show the call buttons for `call`, or hide them with `None`.
+pub fn set_call_controls(app: &AppHandle, call: Option<CallControls>) -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    {
+        *imp::CURRENT.lock().unwrap() = call;
+        let handle = app.clone();
+        app.run_on_main_thread(move || imp::apply(&handle))
+            .map_err(|e| e.to_string())?;
+    }
+    #[cfg(not(target_os = "windows"))]
+    let _ = (app, call);
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+mod imp {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Mutex, OnceLock};
+    use tauri::{AppHandle, Emitter, Manager};
+    use windows::core::w;
+    use windows::Win32::Foundation::{HWND, LPARAM, LRESULT, WPARAM};
+    use windows::Win32::System::Com::{CoCreateInstance, CLSCTX_INPROC_SERVER};
+    use windows::Win32::UI::Shell::{
+        DefSubclassProc, ITaskbarList3, SetWindowSubclass, TaskbarList, THBF_ENABLED, THBF_HIDDEN,
+        THBN_CLICKED, THB_FLAGS, THB_ICON, THB_TOOLTIP, THUMBBUTTON,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::{
+        CreateIcon, RegisterWindowMessageW, HICON, WM_COMMAND,
+    };
+
+    use super::CallControls;
+    use crate::i18n::t;
+    use crate::windows::MAIN_WINDOW;
+
+    /// The call the buttons show, kept to re-add them after an Explorer
+    /// restart.
+    pub static CURRENT: Mutex<Option<CallControls>> = Mutex::new(None);
+
+    /// Whether the buttons exist on the main window's taskbar button.
+    static ADDED: AtomicBool = AtomicBool::new(false);
+
+    /// Receives the button clicks.
+    static APP: OnceLock<AppHandle> = OnceLock::new();
+
+    /// Button icons (mic, muted mic, hang up, share screen), created once
+    /// and kept for the life of the process. Stored as raw handles so they
+    /// can live in a static.
+    static ICONS: OnceLock<[isize; 4]> = OnceLock::new();
+
+    const MUTE_ID: u32 = 0;
+    const HANG_UP_ID: u32 = 1;
+    const SHARE_ID: u32 = 2;
+
+    const SUBCLASS_ID: usize = 0x6e63_6862;
+
+    /// Message Explorer sends when a taskbar button is (re)created.
+    static TASKBAR_BUTTON_CREATED: OnceLock<u32> = OnceLock::new();
+
+    const WHITE: [u8; 4] = [0xFF, 0xFF, 0xFF, 0xFF];
+    const RED: [u8; 4] = [0xE8, 0x3B, 0x3B, 0xFF];
+
+    const MIC: [&str; 16] = [
+        "                ",
+        "      ####      ",
+        "     ######     ",
+        "     ######     ",
+        "     ######     ",
+        "     ######     ",
+        "  #  ######  #  ",
+        "  #  ######  #  ",
+        "  ##  ####  ##  ",
+        "   ##      ##   ",
+        "    ########    ",
+        "       ##       ",
+        "       ##       ",
+        "     ######     ",
+        "                ",
+        "                ",
+    ];
+
+    const MIC_MUTED: [&str; 16] = [
+        "#               ",
+        " ##   ####      ",
+        "  ## ######     ",
+        "   ## #####     ",
+        "    ## ####     ",
+        "     ## ###     ",
+        "  #  ## ###  #  ",
+        "  #  ### ##  #  ",
+        "  ##  ### # ##  ",
+        "   ##     ###   ",
+        "    ######  ##  ",
+        "       ##    ## ",
+        "       ##     ##",
+        "     ######    #",
+        "                ",
+        "                ",
+    ];
+
+    const HANG_UP: [&str; 16] = [
+        "                ",
+        "                ",
+        "                ",
+        "                ",
+        "                ",
+        "    ########    ",
+        "  ############  ",
+        " ######  ###### ",
+        " ####      #### ",
+        " ####      #### ",
+        "  ##        ##  ",
+        "                ",
+        "                ",
+        "                ",
+        "                ",
+        "                ",
+    ];
+
+    const SCREEN: [&str; 16] = [
+        "                ",
+        " ############## ",
+        " #            # ",
+        " #     ##     # ",
+        " #    ####    # ",
+        " #   ######   # ",
+        " #     ##     # ",
+        " #     ##     # ",
+        " #            # ",
+        " ############## ",
+        "       ##       ",
+        "     ######     ",
+        "                ",
+        "                ",
+        "                ",
+        "                ",
+    ];
+
+    /// Add, update or hide the buttons. Runs on the main thread, where the
+    /// window lives.
+    pub fn apply(app: &AppHandle) {
+        if let Err(e) = apply_inner(app) {
+            log::warn!("[nchat-desktop] failed to update taskbar call buttons: {e}");
+        }
+    }
+
+    fn apply_inner(app: &AppHandle) -> Result<(), String> {
+        let Some(win) = app.get_webview_window(MAIN_WINDOW) else {
+            return Ok(());
+        };
+        let hwnd = win.hwnd().map_err(|e| e.to_string())?;
+        let call = *CURRENT.lock().unwrap();
+        if call.is_none() && !ADDED.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+        let taskbar = unsafe {
+            let taskbar: ITaskbarList3 = CoCreateInstance(&TaskbarList, None, CLSCTX_INPROC_SERVER)
+                .map_err(|e| e.to_string())?;
+            taskbar.HrInit().map_err(|e| e.to_string())?;
+            taskbar
+        };
+        let buttons = buttons(call);
+        let result = if ADDED.swap(true, Ordering::SeqCst) {
+            unsafe { taskbar.ThumbBarUpdateButtons(hwnd, &buttons) }
+        } else {
+            if APP.set(app.clone()).is_ok() {
+                unsafe {
+                    let _ = SetWindowSubclass(hwnd, Some(subclass_proc), SUBCLASS_ID, 0);
+                }
+            }
+            let result = unsafe { taskbar.ThumbBarAddButtons(hwnd, &buttons) };
+            if result.is_err() {
+                ADDED.store(false, Ordering::SeqCst);
+            }
+            result
+        };
+        result.map_err(|e| e.to_string())
+    }
+
+    fn buttons(call: Option<CallControls>) -> [THUMBBUTTON; 3] {
+        let icons = ICONS.get_or_init(|| {
+            [
+                icon(&MIC, WHITE),
+                icon(&MIC_MUTED, WHITE),
+                icon(&HANG_UP, RED),
+                icon(&SCREEN, WHITE),
+            ]
+        });
+        let flags = if call.is_some() {
+            THBF_ENABLED
+        } else {
+            THBF_HIDDEN
+        };
+        let call = call.unwrap_or_default();
+        let button = |id: u32, icon: isize, tip: &str| {
+            let mut button = THUMBBUTTON {
+                dwMask: THB_ICON | THB_TOOLTIP | THB_FLAGS,
+                iId: id,
+                hIcon: HICON(icon as _),
+                dwFlags: flags,
+                ..Default::default()
+            };
+            for (slot, unit) in button.szTip.iter_mut().zip(tip.encode_utf16().take(259)) {
+                *slot = unit;
+            }
+            button
+        };
+        [
+            if call.muted {
+                button(MUTE_ID, icons[1], t("call.unmute"))
+            } else {
+                button(MUTE_ID, icons[0], t("call.mute"))
+            },
+            button(HANG_UP_ID, icons[2], t("call.hang_up")),
+            if call.sharing_screen {
+                button(SHARE_ID, icons[3], t("call.stop_sharing"))
+            } else {
+                button(SHARE_ID, icons[3], t("call.share_screen"))
+            },
+        ]
+    }
+
+    /// A 16×16 icon with `color` where `rows` has a `#`.
+    fn icon(rows: &[&str; 16], color: [u8; 4]) -> isize {
+        let [r, g, b, a] = color;
+        let mut bgra = Vec::with_capacity(16 * 16 * 4);
+        for row in rows {
+            for c in row.bytes() {
+                bgra.extend_from_slice(&if c == b'#' { [b, g, r, a] } else { [0; 4] });
+            }
+        }
+        // The 32-bit image carries its own alpha; the AND mask is unused.
+        let mask = [0u8; 16 * 16 / 8];
+        unsafe { CreateIcon(None, 16, 16, 1, 32, mask.as_ptr(), bgra.as_ptr()) }
+            .map(|icon| icon.0 as isize)
+            .unwrap_or(0)
+    }
+
+    unsafe extern "system" fn subclass_proc(
+        hwnd: HWND,
+        msg: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+        _id: usize,
+        _data: usize,
+    ) -> LRESULT {
+        if msg == WM_COMMAND && (wparam.0 >> 16) as u32 & 0xFFFF == THBN_CLICKED {
+            let action = match wparam.0 as u32 & 0xFFFF {
+                MUTE_ID => Some("toggle-mute"),
+                HANG_UP_ID => Some("hang-up"),
+                SHARE_ID => Some("toggle-screen-share"),
+                _ => None,
+            };
+            if let (Some(action), Some(app)) = (action, APP.get()) {
+                if let Some(win) = app.get_webview_window(MAIN_WINDOW) {
+                    let _ = win.emit("call:control", action);
+                }
+                return LRESULT(0);
+            }
+        }
+        // Explorer restarted and the taskbar button lost its buttons.
+        let button_created = *TASKBAR_BUTTON_CREATED
+            .get_or_init(|| RegisterWindowMessageW(w!("TaskbarButtonCreated")));
+        if msg == button_created {
+            ADDED.store(false, Ordering::SeqCst);
+            if let Some(app) = APP.get() {
+                apply(app);
+            }
+        }
+        DefSubclassProc(hwnd, msg, wparam, lparam)
+    }
+}
@@ -0,0 +1,124 @@
+// nChat Desktop — custom titlebar window controls
+//
+// Support for windows that draw their own titlebar: moving the macOS
+// traffic lights into it, starting native moves and resizes from regions
+// the frontend draws, and toggling the native resize borders. The traffic
+// light inset is a window-creation option in Tauri, so a change applies to
+// the main window from the next launch.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::{AppHandle, WebviewWindow};
+
+use crate::state::settings;
+
+/// Settings key holding the traffic light inset for the main window.
+const TRAFFIC_LIGHTS_KEY: &str = "titlebar.trafficLights";
+
+/// Inset the main window was created with.
+static APPLIED_INSET: Mutex<Option<Inset>> = Mutex::new(None);
+
+/// Position of the macOS window buttons from the window's top-left corner,
+/// in logical pixels.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub struct Inset {
+    pub x: f64,
+    pub y: f64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrafficLightStatus {
+    pub inset: Option<Inset>,
+    /// The main window was created with a different inset.
+    pub restart_required: bool,
+}
+
+/// Edge or corner a custom resize handle sits on.
+#[derive(Deserialize, Clone, Copy, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum ResizeEdge {
+    North,
+    South,
+    East,
+    West,
+    NorthEast,
+    NorthWest,
+    SouthEast,
+    SouthWest,
+}
+
+pub fn traffic_lights(app: &AppHandle) -> TrafficLightStatus {
+    let inset: Option<Inset> = settings::load(app, TRAFFIC_LIGHTS_KEY);
+    TrafficLightStatus {
+        inset,
+        restart_required: inset != *APPLIED_INSET.lock().unwrap(),
+    }
+}
+
+/// Move the traffic lights into a custom titlebar, or put them back with
+/// `None`. Only meaningful on macOS.
+pub fn set_traffic_lights(
+    app: &AppHandle,
+    inset: Option<Inset>,
+) -> Result<TrafficLightStatus, String> {
+    if !cfg!(target_os = "macos") {
+        return Err("traffic lights only exist on macOS".into());
+    }
+    match inset {
+        Some(Inset { x, y }) if !(0.0..=200.0).contains(&x) || !(0.0..=200.0).contains(&y) => {
+            return Err("traffic light inset must be within 0–200 px".into());
+        }
+        Some(inset) => settings::save(app, TRAFFIC_LIGHTS_KEY, &inset)?,
+        None => settings::remove(app, TRAFFIC_LIGHTS_KEY)?,
+    }
+    Ok(traffic_lights(app))
+}
+
+/// Give the main window an overlay titlebar with the traffic lights at the
+/// stored inset. Called by `windows::create_main_window`.
+#[cfg(target_os = "macos")]
+pub fn apply_at_creation<'a>(
+    app: &AppHandle,
+    builder: tauri::WebviewWindowBuilder<'a, tauri::Wry, AppHandle>,
+) -> tauri::WebviewWindowBuilder<'a, tauri::Wry, AppHandle> {
+    let inset: Option<Inset> = settings::load(app, TRAFFIC_LIGHTS_KEY);
+    *APPLIED_INSET.lock().unwrap() = inset;
+    match inset {
+        Some(Inset { x, y }) => builder
+            .title_bar_style(tauri::TitleBarStyle::Overlay)
+            .hidden_title(true)
+            .traffic_light_position(tauri::LogicalPosition::new(x, y)),
+        None => builder,
+    }
+}
+
+/// Begin moving `win` with the mouse, from a mousedown in a drag region.
+pub fn start_drag(win: &WebviewWindow) -> Result<(), String> {
+    win.start_dragging().map_err(|e| e.to_string())
+}
+
+/// Begin resizing `win` from a custom handle on `edge`.
+pub fn start_resize(win: &WebviewWindow, edge: ResizeEdge) -> Result<(), String> {
+    win.start_resize_dragging(resize_direction(edge)?)
+        .map_err(|e| e.to_string())
+}
+
+/// Turn the native resize borders of `win` on or off. Undecorated windows
+/// on Windows only get their resize border along with the system shadow.
+pub fn set_resize_borders(win: &WebviewWindow, enabled: bool) -> Result<(), String> {
+    win.set_resizable(enabled).map_err(|e| e.to_string())?;
+    #[cfg(target_os = "windows")]
+    if !win.is_decorated().unwrap_or(true) {
+        win.set_shadow(enabled).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Tauri's resize direction type is not re-exported, so it is built from
+/// its serialized variant name.
+fn resize_direction<T: DeserializeOwned>(edge: ResizeEdge) -> Result<T, String> {
+    serde_json::from_value(serde_json::Value::String(format!("{edge:?}")))
+        .map_err(|e| e.to_string())
+}
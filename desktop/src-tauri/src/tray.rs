@@ -1,64 +1,644 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
 use tauri::{
-    menu::{Menu, MenuItem, PredefinedMenuItem},
+    image::Image,
+    menu::{CheckMenuItem, Menu, MenuItem, PredefinedMenuItem},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    App, Emitter, Manager,
+    AppHandle, Emitter, Manager, Theme, Wry,
 };
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons};
 
-pub fn build_tray(app: &App) -> tauri::Result<()> {
-    let show = MenuItem::with_id(app, "show", "Show nChat", true, None::<&str>)?;
-    let new_msg =
-        MenuItem::with_id(app, "new_conversation", "New Conversation", true, None::<&str>)?;
-    let prefs =
-        MenuItem::with_id(app, "preferences", "Preferences…", true, None::<&str>)?;
-    let quit = MenuItem::with_id(app, "quit", "Quit nChat", true, None::<&str>)?;
+use crate::i18n::{t, tf};
+use crate::state::settings;
+
+/// Identifier of the app's single tray icon, used to look it up later.
+pub const TRAY_ID: &str = "main";
+
+/// Presence statuses offered in the tray, as (status, label key).
+pub const STATUSES: &[(&str, &str)] = &[
+    ("online", "tray.status.online"),
+    ("away", "tray.status.away"),
+    ("dnd", "tray.status.dnd"),
+    ("invisible", "tray.status.invisible"),
+];
+
+/// Settings key for whether the user wants a tray icon at all.
+const VISIBLE_KEY: &str = "tray.visible";
+
+/// Settings key recording that the user was told the tray is unavailable.
+#[cfg(target_os = "linux")]
+const UNAVAILABLE_NOTIFIED_KEY: &str = "tray.unavailableNotified";
+
+/// Settings key for what a middle click on the tray icon does.
+const MIDDLE_CLICK_KEY: &str = "tray.middleClickAction";
+
+/// Settings key for the user's tray icon theme override.
+const ICON_THEME_KEY: &str = "tray.iconTheme";
+
+/// Settings key for running as a tray-only utility (no Dock/taskbar entry).
+const TRAY_ONLY_KEY: &str = "tray.trayOnly";
+
+/// Settings key for what closing the main window does.
+const CLOSE_BEHAVIOR_KEY: &str = "tray.closeBehavior";
+
+/// Which tray icon variant to show. `Auto` follows the system theme
+/// (and uses a template image on macOS so the menu bar tints it).
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TrayIconTheme {
+    #[default]
+    Auto,
+    Light,
+    Dark,
+}
+
+/// How many channels are listed in the tooltip.
+const TOOLTIP_CHANNELS: usize = 3;
+
+#[derive(Deserialize, Clone)]
+pub struct ChannelUnread {
+    pub name: String,
+    pub unread: u32,
+}
+
+/// Unread/connection summary pushed by the frontend and rendered into the
+/// tray tooltip.
+#[derive(Deserialize, Clone, Default)]
+pub struct TraySummary {
+    pub unread: u32,
+    #[serde(default)]
+    pub top_channels: Vec<ChannelUnread>,
+    /// `connected`, `connecting`, `offline`, ...
+    #[serde(default)]
+    pub connection: Option<String>,
+    /// Unread mentions of the user, across channels.
+    #[serde(default)]
+    pub mentions: u32,
+    /// Unread direct messages.
+    #[serde(default)]
+    pub direct_messages: u32,
+}
+
+/// Action bound to a middle click on the tray icon.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum MiddleClickAction {
+    /// Show the window and open the quick-compose popover.
+    #[default]
+    QuickCompose,
+    /// Toggle notification/call mute without showing the window.
+    ToggleMute,
+    None,
+}
+
+/// Live tray menu handles, kept so commands can update items in place.
+pub struct TrayState {
+    menu: Mutex<Option<Menu<Wry>>>,
+    status: Mutex<String>,
+    summary: Mutex<TraySummary>,
+    debug_port: Mutex<Option<u16>>,
+}
+
+impl Default for TrayState {
+    fn default() -> Self {
+        Self {
+            menu: Mutex::new(None),
+            status: Mutex::new("online".into()),
+            summary: Mutex::new(TraySummary::default()),
+            debug_port: Mutex::new(None),
+        }
+    }
+}
+
+fn render_tooltip(summary: &TraySummary, debug_port: Option<u16>) -> String {
+    let mut lines = vec![match summary.unread {
+        0 => "nChat".to_string(),
+        1 => t("tray.unread_one").to_string(),
+        n => tf("tray.unread_many", &[("n", &n)]),
+    }];
+    let channels: Vec<String> = summary
+        .top_channels
+        .iter()
+        .filter(|c| c.unread > 0)
+        .take(TOOLTIP_CHANNELS)
+        .map(|c| format!("{} ({})", c.name, c.unread))
+        .collect();
+    if !channels.is_empty() {
+        lines.push(channels.join(", "));
+    }
+    match summary.connection.as_deref() {
+        None | Some("connected") => {}
+        Some(other) => lines.push(tf("tray.connection", &[("state", &other)])),
+    }
+    if let Some(port) = debug_port {
+        lines.push(tf("tray.debug", &[("port", &port)]));
+    }
+    lines.join("\n")
+}
+
+/// Re-render the tooltip from the current tray state.
+fn refresh_tooltip(app: &AppHandle) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    let state = app.state::<TrayState>();
+    let tooltip = render_tooltip(
+        &state.summary.lock().unwrap(),
+        *state.debug_port.lock().unwrap(),
+    );
+    let _ = tray.set_tooltip(Some(tooltip));
+}
+
+fn status_item_id(status: &str) -> String {
+    format!("status-{status}")
+}
+
+fn build_tray_menu(app: &AppHandle) -> tauri::Result<Menu<Wry>> {
+    let show = MenuItem::with_id(app, "show", t("tray.show"), true, None::<&str>)?;
+    let new_msg = MenuItem::with_id(
+        app,
+        "new_conversation",
+        t("menu.new_conversation"),
+        true,
+        None::<&str>,
+    )?;
+    let prefs = MenuItem::with_id(
+        app,
+        "preferences",
+        t("menu.preferences"),
+        true,
+        None::<&str>,
+    )?;
+    let quit = MenuItem::with_id(app, "quit", t("menu.quit"), true, None::<&str>)?;
     let sep1 = PredefinedMenuItem::separator(app)?;
     let sep2 = PredefinedMenuItem::separator(app)?;
+    let sep3 = PredefinedMenuItem::separator(app)?;
+
+    let state = app.state::<TrayState>();
+    let current = state.status.lock().unwrap().clone();
+    let status_items = STATUSES
+        .iter()
+        .map(|(status, label)| {
+            CheckMenuItem::with_id(
+                app,
+                status_item_id(status),
+                t(label),
+                true,
+                *status == current,
+                None::<&str>,
+            )
+        })
+        .collect::<tauri::Result<Vec<_>>>()?;
+
+    let menu = Menu::with_items(app, &[&show, &new_msg, &sep1])?;
+    for item in &status_items {
+        menu.append(item)?;
+    }
+    menu.append_items(&[&sep2, &prefs, &sep3, &quit])?;
+    *state.menu.lock().unwrap() = Some(menu.clone());
+    Ok(menu)
+}
+
+/// Rebuild the tray menu and tooltip, e.g. after the language changed.
+pub fn rebuild_menu(app: &AppHandle) -> tauri::Result<()> {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return Ok(());
+    };
+    tray.set_menu(Some(build_tray_menu(app)?))?;
+    refresh_tooltip(app);
+    Ok(())
+}
 
-    let menu = Menu::with_items(app, &[&show, &new_msg, &sep1, &prefs, &sep2, &quit])?;
+pub fn build_tray(app: &AppHandle) -> tauri::Result<()> {
+    let menu = build_tray_menu(app)?;
+    let state = app.state::<TrayState>();
 
-    TrayIconBuilder::new()
+    TrayIconBuilder::with_id(TRAY_ID)
         .menu(&menu)
-        .tooltip("nChat")
-        .on_menu_event(|app, event| {
-            match event.id.as_ref() {
-                "show" => {
-                    if let Some(win) = app.get_webview_window("main") {
-                        let _ = win.show();
-                        let _ = win.set_focus();
-                    }
+        .tooltip(render_tooltip(
+            &state.summary.lock().unwrap(),
+            *state.debug_port.lock().unwrap(),
+        ))
+        .on_menu_event(|app, event| match event.id.as_ref() {
+            "show" => {
+                if let Some(win) = app.get_webview_window("main") {
+                    let _ = win.show();
+                    let _ = win.set_focus();
                 }
-                "new_conversation" => {
-                    if let Some(win) = app.get_webview_window("main") {
-                        let _ = win.show();
-                        let _ = win.set_focus();
-                        let _ = win.emit("menu:new-conversation", ());
-                    }
+            }
+            "new_conversation" => {
+                if let Some(win) = app.get_webview_window("main") {
+                    let _ = win.show();
+                    let _ = win.set_focus();
+                    crate::menu::emit_to_window(&win, "menu:new-conversation", ());
                 }
-                "preferences" => {
-                    if let Some(win) = app.get_webview_window("main") {
-                        let _ = win.show();
-                        let _ = win.set_focus();
-                        let _ = win.emit("menu:preferences", ());
-                    }
+            }
+            "preferences" => {
+                if let Err(e) = crate::preferences::open(app, None) {
+                    log::warn!("[nchat-desktop] failed to open settings: {e}");
+                }
+            }
+            "quit" => app.exit(0),
+            id => {
+                if let Some(status) = id.strip_prefix("status-") {
+                    let _ = set_status(app, status);
+                    // Pop-outs show presence too.
+                    let _ = app.emit("tray:set-status", status.to_string());
                 }
-                "quit" => app.exit(0),
-                _ => {}
             }
         })
-        .on_tray_icon_event(|tray, event| {
-            if let TrayIconEvent::Click {
+        .on_tray_icon_event(|tray, event| match event {
+            TrayIconEvent::Click {
                 button: MouseButton::Left,
                 button_state: MouseButtonState::Up,
                 ..
-            } = event
-            {
+            } => {
                 if let Some(win) = tray.app_handle().get_webview_window("main") {
                     let _ = win.show();
                     let _ = win.set_focus();
                 }
             }
+            TrayIconEvent::Click {
+                button: MouseButton::Middle,
+                button_state: MouseButtonState::Up,
+                ..
+            } => run_middle_click_action(tray.app_handle()),
+            _ => {}
         })
         .build(app)?;
 
     Ok(())
 }
+
+/// Show or clear the remote-debugging indicator on the tray icon.
+/// `port` is the local devtools port while a support session is active.
+pub fn set_debug_indicator(app: &AppHandle, port: Option<u16>) {
+    *app.state::<TrayState>().debug_port.lock().unwrap() = port;
+    refresh_tooltip(app);
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        let _ = tray.set_title(port.map(|_| "DEBUG"));
+    }
+}
+
+/// Replace the unread/connection summary and refresh the tooltip.
+pub fn set_summary(app: &AppHandle, summary: TraySummary) {
+    *app.state::<TrayState>().summary.lock().unwrap() = summary;
+    refresh_tooltip(app);
+}
+
+/// The last summary pushed by the frontend.
+pub fn summary(app: &AppHandle) -> TraySummary {
+    app.state::<TrayState>().summary.lock().unwrap().clone()
+}
+
+/// The status currently checked in the tray.
+pub fn status(app: &AppHandle) -> String {
+    app.state::<TrayState>().status.lock().unwrap().clone()
+}
+
+/// Check the tray item for `status` and uncheck the others.
+pub fn set_status(app: &AppHandle, status: &str) -> Result<(), String> {
+    if !STATUSES.iter().any(|(s, _)| *s == status) {
+        return Err(format!("unknown status: {status}"));
+    }
+    let state = app.state::<TrayState>();
+    *state.status.lock().unwrap() = status.to_string();
+
+    let menu = state.menu.lock().unwrap();
+    let Some(menu) = menu.as_ref() else {
+        return Ok(());
+    };
+    for (s, _) in STATUSES {
+        if let Some(item) = menu.get(status_item_id(s).as_str()) {
+            if let Some(check) = item.as_check_menuitem() {
+                check.set_checked(*s == status).map_err(|e| e.to_string())?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Pick and apply the tray icon for the current theme. Called after the tray
+/// is built, whenever the system theme changes, and when the override changes.
+pub fn apply_icon(app: &AppHandle) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    let theme: TrayIconTheme = settings::load(app, ICON_THEME_KEY).unwrap_or_default();
+
+    let focused = crate::focus::is_active(app);
+    let badged = crate::badge::on_tray(app);
+
+    #[cfg(target_os = "macos")]
+    {
+        if theme == TrayIconTheme::Auto {
+            let icon = tauri::include_image!("icons/tray/tray-template.png");
+            let icon = if focused { dimmed(icon) } else { icon };
+            // A template image would tint the badge mark with the menu bar.
+            if badged {
+                let icon = crate::badge::mark_tray_icon(icon);
+                let _ = tray.set_icon_with_as_template(Some(icon), false);
+            } else {
+                let _ = tray.set_icon_with_as_template(Some(icon), true);
+            }
+            return;
+        }
+    }
+
+    let dark = match theme {
+        TrayIconTheme::Dark => true,
+        TrayIconTheme::Light => false,
+        TrayIconTheme::Auto => app
+            .get_webview_window("main")
+            .and_then(|win| win.theme().ok())
+            .is_some_and(|t| matches!(t, Theme::Dark)),
+    };
+    // The "dark" icon is a light glyph meant for dark taskbars and panels.
+    let icon: Image<'static> = if dark {
+        tauri::include_image!("icons/tray/tray-dark.png")
+    } else {
+        tauri::include_image!("icons/tray/tray-light.png")
+    };
+    let icon = if focused { dimmed(icon) } else { icon };
+    let icon = if badged {
+        crate::badge::mark_tray_icon(icon)
+    } else {
+        icon
+    };
+    let _ = tray.set_icon_with_as_template(Some(icon), false);
+}
+
+/// The icon at reduced opacity, shown during a focus session.
+fn dimmed(icon: Image<'_>) -> Image<'static> {
+    let mut rgba = icon.rgba().to_vec();
+    for alpha in rgba.iter_mut().skip(3).step_by(4) {
+        *alpha /= 2;
+    }
+    Image::new_owned(rgba, icon.width(), icon.height())
+}
+
+/// Persist a tray icon theme override and apply it immediately.
+pub fn set_icon_theme(app: &AppHandle, theme: TrayIconTheme) -> Result<(), String> {
+    settings::save(app, ICON_THEME_KEY, &theme)?;
+    apply_icon(app);
+    Ok(())
+}
+
+/// Whether the user wants the tray icon (defaults to true).
+pub fn is_enabled(app: &AppHandle) -> bool {
+    settings::load(app, VISIBLE_KEY).unwrap_or(true)
+}
+
+/// Whether a tray icon currently exists.
+pub fn is_visible(app: &AppHandle) -> bool {
+    app.tray_by_id(TRAY_ID).is_some()
+}
+
+/// Destroy or recreate the tray icon and remember the choice.
+pub fn set_visible(app: &AppHandle, visible: bool) -> Result<(), String> {
+    if visible {
+        host_available()?;
+    }
+    settings::save(app, VISIBLE_KEY, &visible)?;
+    if visible && !is_visible(app) {
+        build_tray(app).map_err(|e| e.to_string())?;
+        apply_icon(app);
+    } else if !visible {
+        // Without the tray there would be no way back to the app.
+        if is_tray_only(app) {
+            set_tray_only(app, false)?;
+        }
+        app.remove_tray_by_id(TRAY_ID);
+        *app.state::<TrayState>().menu.lock().unwrap() = None;
+    }
+    Ok(())
+}
+
+/// Whether the app runs without a Dock/taskbar entry.
+pub fn is_tray_only(app: &AppHandle) -> bool {
+    settings::load(app, TRAY_ONLY_KEY).unwrap_or(false)
+}
+
+/// Add or remove the app's Dock (macOS) or taskbar entries according to the
+/// stored preference. Called at launch and whenever a window is created.
+pub fn apply_tray_only(app: &AppHandle) {
+    let tray_only = is_tray_only(app) && is_visible(app);
+
+    #[cfg(target_os = "macos")]
+    {
+        let policy = if tray_only {
+            tauri::ActivationPolicy::Accessory
+        } else {
+            tauri::ActivationPolicy::Regular
+        };
+        if let Err(e) = app.set_activation_policy(policy) {
+            log::warn!("[nchat-desktop] failed to set activation policy: {e}");
+        }
+    }
+    #[cfg(not(target_os = "macos"))]
+    for win in app.webview_windows().values() {
+        // The quick switcher overlay never has a taskbar entry.
+        if win.label() != crate::switcher::SWITCHER_WINDOW {
+            let _ = win.set_skip_taskbar(tray_only);
+        }
+    }
+}
+
+/// Hide the app from the Dock and taskbar while keeping the tray icon and
+/// background services running. Requires a visible tray icon.
+pub fn set_tray_only(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    if enabled && !is_visible(app) {
+        return Err("tray-only mode needs a visible tray icon".into());
+    }
+    settings::save(app, TRAY_ONLY_KEY, &enabled)?;
+    apply_tray_only(app);
+    // The badge moves between the Dock or taskbar and the tray.
+    crate::badge::refresh(app);
+    Ok(())
+}
+
+/// What closing the main window does. Hiding needs a tray icon (or, on
+/// macOS, the Dock icon) to get the window back, so without one it quits
+/// instead.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum CloseBehavior {
+    Quit,
+    HideToTray,
+    /// Ask on the next close; the answer becomes the setting.
+    Ask,
+}
+
+/// The stored close behavior. Defaults to hiding on macOS, where apps
+/// conventionally outlive their windows, and to quitting elsewhere.
+pub fn close_behavior(app: &AppHandle) -> CloseBehavior {
+    settings::load(app, CLOSE_BEHAVIOR_KEY).unwrap_or(if cfg!(target_os = "macos") {
+        CloseBehavior::HideToTray
+    } else {
+        CloseBehavior::Quit
+    })
+}
+
+/// Store the close behavior. With `close_now`, the main window is closed
+/// accordingly right away.
+pub fn set_close_behavior(
+    app: &AppHandle,
+    behavior: CloseBehavior,
+    close_now: bool,
+) -> Result<(), String> {
+    settings::save(app, CLOSE_BEHAVIOR_KEY, &behavior)?;
+    if close_now && behavior != CloseBehavior::Ask {
+        close_main(app, behavior);
+    }
+    Ok(())
+}
+
+/// Handle a close request for the main window according to the close
+/// behavior.
+pub fn on_main_close_requested(win: &tauri::Window, api: &tauri::CloseRequestApi) {
+    let app = win.app_handle();
+    let behavior = close_behavior(app);
+    if behavior != CloseBehavior::Quit {
+        api.prevent_close();
+    }
+    close_main(app, behavior);
+}
+
+/// Show and focus the main window, e.g. when the Dock icon is clicked.
+pub fn show_main(app: &AppHandle) {
+    if let Some(win) = app.get_webview_window(crate::windows::MAIN_WINDOW) {
+        let _ = win.show();
+        let _ = win.set_focus();
+    }
+}
+
+fn close_main(app: &AppHandle, behavior: CloseBehavior) {
+    let Some(win) = app.get_webview_window(crate::windows::MAIN_WINDOW) else {
+        return;
+    };
+    // On macOS the Dock icon brings a hidden window back, tray or not.
+    let can_hide = cfg!(target_os = "macos") || is_visible(app);
+    match behavior {
+        CloseBehavior::HideToTray if can_hide => {
+            let _ = win.hide();
+        }
+        CloseBehavior::Ask if can_hide => ask_close_behavior(app),
+        _ => app.exit(0),
+    }
+}
+
+/// Ask whether closing should keep the app running in the tray or quit it,
+/// remember the answer and act on it.
+fn ask_close_behavior(app: &AppHandle) {
+    let handle = app.clone();
+    app.dialog()
+        .message(t("close.prompt"))
+        .title(t("close.prompt_title"))
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            t("close.keep_running").into(),
+            t("close.quit").into(),
+        ))
+        .show(move |keep_running| {
+            let behavior = if keep_running {
+                CloseBehavior::HideToTray
+            } else {
+                CloseBehavior::Quit
+            };
+            if let Err(e) = set_close_behavior(&handle, behavior, true) {
+                log::warn!("[nchat-desktop] failed to save close behavior: {e}");
+            }
+        });
+}
+
+#[derive(Serialize)]
+pub struct TrayAvailability {
+    /// The desktop session can display a tray icon.
+    pub available: bool,
+    /// A tray icon currently exists.
+    pub visible: bool,
+    /// Why the tray is unavailable, if it is.
+    pub reason: Option<String>,
+}
+
+/// Ask the session bus whether a StatusNotifierItem host (the panel side of
+/// AppIndicator) is running. Stock GNOME has none without an extension.
+#[cfg(target_os = "linux")]
+fn sni_host_registered() -> zbus::Result<bool> {
+    let conn = zbus::blocking::Connection::session()?;
+    let reply = conn.call_method(
+        Some("org.kde.StatusNotifierWatcher"),
+        "/StatusNotifierWatcher",
+        Some("org.freedesktop.DBus.Properties"),
+        "Get",
+        &(
+            "org.kde.StatusNotifierWatcher",
+            "IsStatusNotifierHostRegistered",
+        ),
+    )?;
+    let value: zbus::zvariant::OwnedValue = reply.body().deserialize()?;
+    Ok(bool::try_from(value).unwrap_or(false))
+}
+
+/// Check whether this session can show a tray icon.
+pub fn host_available() -> Result<(), String> {
+    #[cfg(target_os = "linux")]
+    {
+        match sni_host_registered() {
+            Ok(true) => Ok(()),
+            Ok(false) => Err("no StatusNotifier host is registered".into()),
+            Err(e) => Err(format!("StatusNotifier watcher not reachable: {e}")),
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        Ok(())
+    }
+}
+
+pub fn availability(app: &AppHandle) -> TrayAvailability {
+    let reason = host_available().err();
+    TrayAvailability {
+        available: reason.is_none(),
+        visible: is_visible(app),
+        reason,
+    }
+}
+
+/// Fallback when there is no tray: tell the user once that closing the
+/// window quits the app, since there is nothing to minimize to.
+#[cfg(target_os = "linux")]
+pub fn notify_unavailable(app: &AppHandle) {
+    use tauri_plugin_notification::NotificationExt;
+
+    if settings::load(app, UNAVAILABLE_NOTIFIED_KEY).unwrap_or(false) {
+        return;
+    }
+    let _ = app
+        .notification()
+        .builder()
+        .title("nChat")
+        .body(t("tray.unavailable"))
+        .show();
+    let _ = settings::save(app, UNAVAILABLE_NOTIFIED_KEY, &true);
+}
+
+fn run_middle_click_action(app: &AppHandle) {
+    let Some(win) = app.get_webview_window("main") else {
+        return;
+    };
+    match settings::load(app, MIDDLE_CLICK_KEY).unwrap_or_default() {
+        MiddleClickAction::QuickCompose => {
+            let _ = win.show();
+            let _ = win.set_focus();
+            crate::menu::emit_to_window(&win, "tray:quick-compose", ());
+        }
+        MiddleClickAction::ToggleMute => {
+            crate::menu::emit_to_window(&win, "tray:toggle-mute", ());
+        }
+        MiddleClickAction::None => {}
+    }
+}
+
+pub fn set_middle_click_action(app: &AppHandle, action: MiddleClickAction) -> Result<(), String> {
+    settings::save(app, MIDDLE_CLICK_KEY, &action)
+}
@@ -0,0 +1,1189 @@
+// nChat Desktop — update channels and checks
+//
+// Every build can follow any channel: the channel only selects which
+// manifest the updater reads, so beta testers opt in without a separate
+// download.
+//
+// Updates are either installed right away or, in background mode,
+// downloaded silently and staged in memory, then installed when the app
+// quits. Progress and state changes are reported to the main window.
+//
+// Delta updates: a manifest may list
+// `"deltas": { "<from version>": { "url", "signature", "size" } }`. A delta
+// is a zstd patch (`zstd --patch-from=<old package> <new package>`) against
+// the package this install came from, which is kept in the cache after
+// every update. The patch carries its own signature, checked before it is
+// applied, and `size` is the rebuilt package's size: decompression stops
+// there, so a bad patch cannot use up memory. The rebuilt package must
+// still match the release signature; any failure falls back to the full
+// package.
+//
+// Release notes: a manifest may add `"changelog": "<url>"`, a JSON list of
+// `{ "version", "notes" }` entries. When the update is more than one
+// release ahead, the notes of every release since the installed version
+// are shown, newest first, instead of only the latest entry.
+//
+// Rollback: when an update replaces the base package, the old one is kept
+// as the previous package, with its release signature, so the user can
+// reinstall the version they updated from. Its signature is checked again
+// before it is installed, and like offline installs it needs no server.
+// The version rolled back from is skipped by later checks.
+//
+// Automatic checks stay quiet about a release the user skipped and while
+// the prompt is snoozed; checks the user starts always report it.
+//
+// Self-hosted deployments can point the updater at their own manifest. A
+// custom endpoint replaces the channel URLs; packages must still be signed
+// with the compiled-in key unless the admin policy supplies another key or
+// lets users set one.
+//
+// Offline installs: a package copied onto the machine (with the `.sig`
+// file the bundler writes next to it) is checked against the same key as
+// downloaded ones. Its version is taken from the file name in the
+// signature's trusted comment (or, for macOS bundles, the Info.plist
+// inside), and it must be newer than the running version unless the admin
+// policy allows downgrades. The updater plugin only installs packages it
+// fetched from a manifest, so these verified bytes (and rollbacks) are
+// installed by `installer.rs` instead; no server is contacted.
+//
+// All update traffic goes through the configured proxy (see `proxy.rs`).
+
+use base64::Engine;
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, Manager, Url};
+use tauri_plugin_updater::{Update, UpdaterExt};
+use tokio::sync::{watch, Notify};
+
+use crate::installer;
+use crate::policy;
+use crate::progress::{self, ProgressSource, ProgressState};
+use crate::proxy;
+use crate::state::settings;
+
+/// Settings key for the selected release channel.
+const CHANNEL_KEY: &str = "updater.channel";
+
+/// Error returned when the user cancels a download.
+const CANCELED: &str = "update download canceled";
+
+/// Settings key for downloading updates in the background.
+const BACKGROUND_KEY: &str = "updater.backgroundDownload";
+
+/// Settings key for a release the user skipped or rolled back from. One
+/// entry is enough: older releases are never offered again anyway.
+const SKIPPED_KEY: &str = "updater.skippedVersion";
+
+/// Settings key for the end of a "Remind me later" snooze (Unix ms).
+const SNOOZED_UNTIL_KEY: &str = "updater.snoozedUntil";
+
+/// Settings key for a user-configured update source.
+const SOURCE_KEY: &str = "updater.source";
+
+/// Longest allowed snooze.
+const MAX_SNOOZE_HOURS: u32 = 30 * 24;
+
+/// How often the background mode checks for a new release.
+const BACKGROUND_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// Minimum spacing between progress events.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateChannel {
+    #[default]
+    Stable,
+    Beta,
+    Nightly,
+}
+
+impl UpdateChannel {
+    /// Manifest URL; the updater fills in `{{target}}` and `{{arch}}`.
+    fn endpoint(self) -> &'static str {
+        match self {
+            Self::Stable => {
+                "https://packages.nself.org/chat-desktop/latest-{{target}}-{{arch}}.json"
+            }
+            Self::Beta => {
+                "https://packages.nself.org/chat-desktop/beta/latest-{{target}}-{{arch}}.json"
+            }
+            Self::Nightly => {
+                "https://packages.nself.org/chat-desktop/nightly/latest-{{target}}-{{arch}}.json"
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Copy)]
+#[serde(rename_all = "kebab-case")]
+pub enum UpdateStatus {
+    Idle,
+    Downloading,
+    Staged,
+    Installing,
+    RollingBack,
+    Paused,
+}
+
+/// Requested state of the download in flight.
+#[derive(Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum DownloadControl {
+    #[default]
+    Run,
+    Pause,
+    Cancel,
+}
+
+#[derive(Serialize, Clone)]
+struct UpdateStateEvent {
+    status: UpdateStatus,
+    version: Option<String>,
+}
+
+/// A downloaded update waiting to be installed on quit.
+#[derive(Default)]
+pub struct UpdaterState {
+    staged: Mutex<Option<(Update, Vec<u8>)>>,
+    downloading: AtomicBool,
+    control: watch::Sender<DownloadControl>,
+    /// Version whose package was last checked, and whether it passed.
+    verified: Mutex<Option<(String, bool)>>,
+    wake: Notify,
+}
+
+#[derive(Serialize, Clone)]
+struct UpdateProgress {
+    downloaded: u64,
+    total: Option<u64>,
+    percent: Option<f64>,
+    bytes_per_sec: u64,
+    eta_secs: Option<u64>,
+}
+
+#[derive(Serialize)]
+pub struct UpdateInfo {
+    pub available: bool,
+    pub version: Option<String>,
+    pub notes: Option<String>,
+    /// `pending` until the package has been downloaded and checked.
+    pub signature: Option<SignatureStatus>,
+    /// Key the package must be signed with.
+    pub signer: Option<Signer>,
+    /// Host the package is downloaded from.
+    pub download_host: Option<String>,
+}
+
+#[derive(Serialize, Clone, Copy)]
+#[serde(rename_all = "kebab-case")]
+pub enum SignatureStatus {
+    Pending,
+    Verified,
+    Failed,
+}
+
+#[derive(Serialize, Clone, Copy)]
+#[serde(rename_all = "kebab-case")]
+pub enum KeySource {
+    Policy,
+    Custom,
+    BuiltIn,
+}
+
+#[derive(Serialize)]
+pub struct Signer {
+    /// minisign key ID, as printed by `minisign -G`.
+    pub key_id: String,
+    pub source: KeySource,
+}
+
+/// Payload of `update-verification-failed`.
+#[derive(Serialize, Clone)]
+struct VerificationFailed {
+    version: Option<String>,
+    key_id: Option<String>,
+    error: String,
+}
+
+/// A self-hosted update source as stored in the settings.
+#[derive(Serialize, Deserialize, Clone, Default)]
+struct CustomSource {
+    endpoint: String,
+    pubkey: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct UpdateSource {
+    /// Manifest URL in use (may contain `{{target}}`/`{{arch}}`).
+    pub endpoint: String,
+    /// A user-configured endpoint replaces the channel's.
+    pub custom: bool,
+    /// The admin policy fixes the endpoint.
+    pub managed: bool,
+    /// Packages are verified with a key other than the compiled-in one.
+    pub custom_pubkey: bool,
+}
+
+#[derive(Serialize, Clone)]
+pub struct RollbackInfo {
+    pub current: String,
+    pub previous: String,
+}
+
+impl UpdateInfo {
+    fn none() -> Self {
+        Self {
+            available: false,
+            version: None,
+            notes: None,
+            signature: None,
+            signer: None,
+            download_host: None,
+        }
+    }
+}
+
+pub fn channel(app: &AppHandle) -> UpdateChannel {
+    settings::load(app, CHANNEL_KEY).unwrap_or_default()
+}
+
+pub fn set_channel(app: &AppHandle, channel: UpdateChannel) -> Result<(), String> {
+    settings::save(app, CHANNEL_KEY, &channel)
+}
+
+fn custom_source(app: &AppHandle) -> Option<CustomSource> {
+    settings::load(app, SOURCE_KEY)
+}
+
+/// Manifest URL to check: the policy's, then the user's, then the
+/// channel's.
+fn endpoint(app: &AppHandle) -> String {
+    let policy = policy::current().updater.as_ref();
+    policy
+        .and_then(|p| p.endpoint.clone())
+        .or_else(|| custom_source(app).map(|s| s.endpoint))
+        .unwrap_or_else(|| channel(app).endpoint().to_string())
+}
+
+/// Key update packages must be signed with: the policy's, then the user's
+/// (if the policy allows it), then the compiled-in one.
+fn signing_key(app: &AppHandle) -> Option<(String, KeySource)> {
+    let policy = policy::current().updater.clone().unwrap_or_default();
+    if let Some(key) = policy.pubkey {
+        return Some((key, KeySource::Policy));
+    }
+    let user_key = custom_source(app)
+        .and_then(|s| s.pubkey)
+        .filter(|_| policy.allow_custom_pubkey);
+    if let Some(key) = user_key {
+        return Some((key, KeySource::Custom));
+    }
+    app.config()
+        .plugins
+        .0
+        .get("updater")
+        .and_then(|updater| updater.get("pubkey"))
+        .and_then(|key| key.as_str())
+        .map(|key| (key.to_string(), KeySource::BuiltIn))
+}
+
+fn pubkey(app: &AppHandle) -> Option<String> {
+    signing_key(app).map(|(key, _)| key)
+}
+
+/// The key ID in a base64-encoded minisign public key file.
+fn key_id(pubkey: &str) -> Option<String> {
+    let b64 = base64::engine::general_purpose::STANDARD;
+    let file = String::from_utf8(b64.decode(pubkey.trim()).ok()?).ok()?;
+    let key = b64.decode(file.lines().nth(1)?.trim()).ok()?;
+    // "Ed", then the 8-byte little-endian key ID, then the key itself.
+    let id = u64::from_le_bytes(key.get(2..10)?.try_into().ok()?);
+    Some(format!("{id:016X}"))
+}
+
+fn signer(app: &AppHandle) -> Option<Signer> {
+    let (key, source) = signing_key(app)?;
+    Some(Signer {
+        key_id: key_id(&key)?,
+        source,
+    })
+}
+
+/// Result of the last signature check of `version`'s package, if any.
+fn signature_status(app: &AppHandle, version: &str) -> SignatureStatus {
+    let state = app.state::<UpdaterState>();
+    let verified = state.verified.lock().unwrap();
+    match verified.as_ref() {
+        Some((v, true)) if v == version => SignatureStatus::Verified,
+        Some((v, false)) if v == version => SignatureStatus::Failed,
+        _ => SignatureStatus::Pending,
+    }
+}
+
+/// An updater for `endpoint`, using the configured proxy and key.
+/// `any_version` disables the plugin's own newer-version filter.
+fn updater(
+    app: &AppHandle,
+    endpoint: &str,
+    pubkey: Option<String>,
+    any_version: bool,
+) -> Result<tauri_plugin_updater::Updater, String> {
+    let endpoint = Url::parse(endpoint).map_err(|e| e.to_string())?;
+    if endpoint.scheme() != "https" {
+        return Err("the update endpoint must use https".into());
+    }
+    let proxy = proxy::current(app)?;
+    let mut builder = app
+        .updater_builder()
+        .configure_client(move |builder| proxy.apply(builder));
+    if let Some(pubkey) = pubkey {
+        builder = builder.pubkey(pubkey);
+    }
+    if any_version {
+        builder = builder.version_comparator(|_, _| true);
+    }
+    builder
+        .endpoints(vec![endpoint])
+        .and_then(|builder| builder.build())
+        .map_err(|e| e.to_string())
+}
+
+pub fn source(app: &AppHandle) -> UpdateSource {
+    let policy = policy::current().updater.clone().unwrap_or_default();
+    let custom = custom_source(app);
+    UpdateSource {
+        endpoint: endpoint(app),
+        custom: policy.endpoint.is_none() && custom.is_some(),
+        managed: policy.endpoint.is_some(),
+        custom_pubkey: policy.pubkey.is_some()
+            || (policy.allow_custom_pubkey && custom.is_some_and(|s| s.pubkey.is_some())),
+    }
+}
+
+/// Point the updater at a self-hosted manifest, or back to the channel
+/// URLs with `None`. The endpoint must serve a valid manifest before it is
+/// saved.
+pub async fn set_source(
+    app: &AppHandle,
+    endpoint: Option<String>,
+    pubkey: Option<String>,
+) -> Result<UpdateSource, String> {
+    let policy = policy::current().updater.clone().unwrap_or_default();
+    if policy.endpoint.is_some() {
+        return Err("the update source is managed by your administrator".into());
+    }
+    let Some(endpoint) = endpoint.map(|e| e.trim().to_string()) else {
+        settings::remove(app, SOURCE_KEY)?;
+        return Ok(source(app));
+    };
+    let pubkey = pubkey
+        .map(|k| k.trim().to_string())
+        .filter(|k| !k.is_empty());
+    if pubkey.is_some() && !policy.allow_custom_pubkey {
+        return Err(
+            "a custom update key must be allowed by the admin policy (updater.allowCustomPubkey)"
+                .into(),
+        );
+    }
+
+    let url = Url::parse(&endpoint).map_err(|e| format!("invalid endpoint: {e}"))?;
+    if url.scheme() != "https" {
+        return Err("the update endpoint must use https".into());
+    }
+    if let Some(key) = &pubkey {
+        let raw = base64::engine::general_purpose::STANDARD
+            .decode(key)
+            .map_err(|e| format!("invalid public key: {e}"))?;
+        let text = String::from_utf8(raw).map_err(|e| format!("invalid public key: {e}"))?;
+        minisign_verify::PublicKey::decode(&text)
+            .map_err(|e| format!("invalid public key: {e}"))?;
+    }
+    // Fetch the manifest once so a typo does not silently stop updates.
+    updater(
+        app,
+        &endpoint,
+        pubkey.clone().or_else(|| self::pubkey(app)),
+        true,
+    )?
+    .check()
+    .await
+    .map_err(|e| format!("the endpoint did not serve a valid update manifest: {e}"))?;
+
+    settings::save(app, SOURCE_KEY, &CustomSource { endpoint, pubkey })?;
+    Ok(source(app))
+}
+
+/// Query the selected channel's manifest. Releases that are not newer than
+/// the running version are ignored, so a manifest can never roll the app
+/// back (including after switching from beta to stable).
+pub async fn find_update(app: &AppHandle) -> Result<Option<Update>, String> {
+    let current =
+        Version::parse(&app.package_info().version.to_string()).map_err(|e| e.to_string())?;
+    let updater = updater(app, &endpoint(app), pubkey(app), false)?;
+
+    match updater.check().await.map_err(|e| e.to_string())? {
+        Some(update) => match Version::parse(&update.version) {
+            Ok(remote) if remote <= current => {
+                log::warn!(
+                    "[nchat-desktop] update check: remote {} <= current {} — ignoring",
+                    remote,
+                    current
+                );
+                Ok(None)
+            }
+            _ => Ok(Some(update)),
+        },
+        None => Ok(None),
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Whether the user asked not to hear about `version` (skipped), or about
+/// any update for now (snoozed).
+fn dismissed(app: &AppHandle, version: &str) -> bool {
+    let skipped = settings::load::<String>(app, SKIPPED_KEY).as_deref() == Some(version);
+    let snoozed =
+        settings::load::<u64>(app, SNOOZED_UNTIL_KEY).is_some_and(|until| until > now_ms());
+    skipped || snoozed
+}
+
+/// "Remind me later": keep automatic checks quiet for `hours`.
+pub fn snooze(app: &AppHandle, hours: u32) -> Result<u64, String> {
+    if hours == 0 || hours > MAX_SNOOZE_HOURS {
+        return Err(format!("snooze for 1 to {MAX_SNOOZE_HOURS} hours"));
+    }
+    let until = now_ms() + u64::from(hours) * 3_600_000;
+    settings::save(app, SNOOZED_UNTIL_KEY, &until)?;
+    Ok(until)
+}
+
+/// "Skip this version": never announce `version` automatically. A staged
+/// download of it is dropped.
+pub fn skip_version(app: &AppHandle, version: &str) -> Result<(), String> {
+    settings::save(app, SKIPPED_KEY, &version)?;
+    let state = app.state::<UpdaterState>();
+    let mut staged = state.staged.lock().unwrap();
+    if staged
+        .as_ref()
+        .is_some_and(|(update, _)| update.version == version)
+    {
+        *staged = None;
+        drop(staged);
+        emit_status(app, UpdateStatus::Idle, None);
+    }
+    Ok(())
+}
+
+/// Check for an update. Automatic checks (`manual == false`) ignore a
+/// skipped or snoozed release.
+pub async fn check(app: &AppHandle, manual: bool) -> Result<UpdateInfo, String> {
+    let update = find_update(app)
+        .await?
+        .filter(|update| manual || !dismissed(app, &update.version));
+    Ok(match update {
+        Some(update) => UpdateInfo {
+            available: true,
+            version: Some(update.version.clone()),
+            notes: release_notes(app, &update).await,
+            signature: Some(signature_status(app, &update.version)),
+            signer: signer(app),
+            download_host: update.download_url.host_str().map(str::to_string),
+        },
+        None => UpdateInfo::none(),
+    })
+}
+
+#[derive(Deserialize)]
+struct ChangelogEntry {
+    version: String,
+    notes: String,
+}
+
+/// Notes for every release between the running version and `update`, from
+/// the manifest's changelog. Falls back to the update's own notes.
+async fn release_notes(app: &AppHandle, update: &Update) -> Option<String> {
+    let Some(url) = update
+        .raw_json
+        .get("changelog")
+        .and_then(|url| url.as_str())
+    else {
+        return update.body.clone();
+    };
+    match changelog_since(app, url, &update.version).await {
+        Ok(Some(notes)) => Some(notes),
+        Ok(None) => update.body.clone(),
+        Err(e) => {
+            log::warn!("[nchat-desktop] failed to fetch changelog: {e}");
+            update.body.clone()
+        }
+    }
+}
+
+async fn changelog_since(
+    app: &AppHandle,
+    url: &str,
+    latest: &str,
+) -> Result<Option<String>, String> {
+    let current =
+        Version::parse(&app.package_info().version.to_string()).map_err(|e| e.to_string())?;
+    let latest = Version::parse(latest).map_err(|e| e.to_string())?;
+    let entries: Vec<ChangelogEntry> = proxy::client(app)?
+        .get(url)
+        .send()
+        .await
+        .and_then(|res| res.error_for_status())
+        .map_err(|e| e.to_string())?
+        .json()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut entries: Vec<(Version, String)> = entries
+        .into_iter()
+        .filter_map(|entry| Some((Version::parse(&entry.version).ok()?, entry.notes)))
+        .filter(|(version, _)| *version > current && *version <= latest)
+        .collect();
+    if entries.is_empty() {
+        return Ok(None);
+    }
+    entries.sort_by(|a, b| b.0.cmp(&a.0));
+    let notes = entries
+        .iter()
+        .map(|(version, notes)| format!("## {version}\n\n{}", notes.trim()))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    Ok(Some(notes))
+}
+
+fn emit_progress(app: &AppHandle, downloaded: u64, total: Option<u64>, started: Instant) {
+    let elapsed = started.elapsed().as_secs_f64();
+    let bytes_per_sec = if elapsed > 0.0 {
+        (downloaded as f64 / elapsed) as u64
+    } else {
+        0
+    };
+    let progress = UpdateProgress {
+        downloaded,
+        total,
+        percent: total
+            .filter(|total| *total > 0)
+            .map(|total| (downloaded as f64 / total as f64 * 100.0).min(100.0)),
+        bytes_per_sec,
+        eta_secs: total
+            .filter(|_| bytes_per_sec > 0)
+            .map(|total| total.saturating_sub(downloaded) / bytes_per_sec),
+    };
+    if let Err(e) = progress::set(
+        app,
+        ProgressSource::Update,
+        progress.percent.map(|percent| percent / 100.0),
+        ProgressState::Normal,
+    ) {
+        log::warn!("[nchat-desktop] failed to update taskbar progress: {e}");
+    }
+    if let Some(win) = app.get_webview_window(crate::windows::MAIN_WINDOW) {
+        let _ = win.emit("update-download-progress", progress);
+    }
+}
+
+/// Files kept for a cached package: the package, its version and its
+/// release signature.
+struct PackageFiles {
+    package: PathBuf,
+    version: PathBuf,
+    signature: PathBuf,
+}
+
+fn package_files(app: &AppHandle, name: &str) -> Option<PackageFiles> {
+    let dir = app.path().app_cache_dir().ok()?.join("updates");
+    Some(PackageFiles {
+        package: dir.join(format!("{name}-package")),
+        version: dir.join(format!("{name}-version")),
+        signature: dir.join(format!("{name}-signature")),
+    })
+}
+
+/// Cached copy of the package the running version was installed from; the
+/// base for the next delta.
+fn base_package_files(app: &AppHandle) -> Option<PackageFiles> {
+    package_files(app, "base")
+}
+
+/// The package of the version before the last update.
+fn previous_package_files(app: &AppHandle) -> Option<PackageFiles> {
+    package_files(app, "previous")
+}
+
+fn save_base_package(app: &AppHandle, version: &str, bytes: &[u8], signature: &str) {
+    let Some(base) = base_package_files(app) else {
+        return;
+    };
+    // The running version's package becomes the rollback target. A base
+    // from an update that was downloaded but never installed is dropped.
+    if let (true, Some(previous)) = (base_is_current(app), previous_package_files(app)) {
+        let kept = std::fs::rename(&base.package, previous.package)
+            .and_then(|_| std::fs::rename(&base.version, previous.version))
+            .and_then(|_| std::fs::rename(&base.signature, previous.signature));
+        if let Err(e) = kept {
+            log::warn!("[nchat-desktop] could not keep previous package for rollback: {e}");
+        }
+    }
+    let saved = base
+        .package
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|_| std::fs::write(&base.package, bytes))
+        .and_then(|_| std::fs::write(&base.version, version))
+        .and_then(|_| std::fs::write(&base.signature, signature));
+    if let Err(e) = saved {
+        log::warn!("[nchat-desktop] could not keep update package for deltas: {e}");
+    }
+}
+
+/// Whether the base package belongs to the running version.
+fn base_is_current(app: &AppHandle) -> bool {
+    base_package_files(app)
+        .and_then(|base| std::fs::read_to_string(base.version).ok())
+        .is_some_and(|version| version.trim() == app.package_info().version.to_string())
+}
+
+/// The base package, if it belongs to the running version.
+fn base_package(app: &AppHandle) -> Option<Vec<u8>> {
+    let base = base_package_files(app)?;
+    base_is_current(app)
+        .then(|| std::fs::read(base.package).ok())
+        .flatten()
+}
+
+/// Check `bytes` against the release signature with the updater's key.
+fn verify_signature(app: &AppHandle, bytes: &[u8], signature: &str) -> Result<(), String> {
+    verified_comment(app, bytes, signature).map(|_| ())
+}
+
+/// Check `bytes` against the release signature and return the signature's
+/// trusted comment, which the signature covers too.
+fn verified_comment(app: &AppHandle, bytes: &[u8], signature: &str) -> Result<String, String> {
+    let decode = |b64: &str| -> Result<String, String> {
+        let raw = base64::engine::general_purpose::STANDARD
+            .decode(b64)
+            .map_err(|e| e.to_string())?;
+        String::from_utf8(raw).map_err(|e| e.to_string())
+    };
+    let pubkey = pubkey(app).ok_or("no updater public key configured")?;
+    let key = minisign_verify::PublicKey::decode(&decode(&pubkey)?).map_err(|e| e.to_string())?;
+    let signature =
+        minisign_verify::Signature::decode(&decode(signature)?).map_err(|e| e.to_string())?;
+    key.verify(bytes, &signature, true)
+        .map_err(|e| e.to_string())?;
+    Ok(signature.trusted_comment().to_string())
+}
+
+/// The version of a signed package: from the file name the bundler signed
+/// (`<product>_<version>_<arch>...`), or for macOS bundles, whose file name
+/// has none, from the Info.plist inside.
+fn package_version(comment: &str, bytes: &[u8]) -> Option<Version> {
+    let file = comment
+        .split('\t')
+        .find_map(|field| field.strip_prefix("file:"))
+        .unwrap_or_default();
+    file.split('_')
+        .find_map(|part| Version::parse(part).ok())
+        .or_else(|| bundle_version(bytes))
+}
+
+#[cfg(target_os = "macos")]
+fn bundle_version(bytes: &[u8]) -> Option<Version> {
+    let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(bytes));
+    let mut entry = archive.entries().ok()?.flatten().find(|entry| {
+        entry
+            .path()
+            .is_ok_and(|path| path.ends_with("Contents/Info.plist"))
+    })?;
+    let mut plist = String::new();
+    entry.read_to_string(&mut plist).ok()?;
+    let (_, rest) = plist.split_once("<key>CFBundleShortVersionString</key>")?;
+    let (_, rest) = rest.split_once("<string>")?;
+    let (version, _) = rest.split_once("</string>")?;
+    Version::parse(version.trim()).ok()
+}
+
+#[cfg(not(target_os = "macos"))]
+fn bundle_version(_bytes: &[u8]) -> Option<Version> {
+    None
+}
+
+/// Record the outcome of checking `version`'s package; failures are also
+/// reported to the main window as `update-verification-failed`.
+fn record_verification(app: &AppHandle, version: Option<&str>, result: &Result<(), String>) {
+    if let Some(version) = version {
+        *app.state::<UpdaterState>().verified.lock().unwrap() =
+            Some((version.to_string(), result.is_ok()));
+    }
+    let Err(error) = result else {
+        return;
+    };
+    log::warn!("[nchat-desktop] update signature verification failed: {error}");
+    let event = VerificationFailed {
+        version: version.map(str::to_string),
+        key_id: pubkey(app).as_deref().and_then(key_id),
+        error: error.clone(),
+    };
+    if let Some(win) = app.get_webview_window(crate::windows::MAIN_WINDOW) {
+        let _ = win.emit("update-verification-failed", event);
+    }
+}
+
+/// A manifest's `deltas` entry for one installed version.
+#[derive(Deserialize)]
+struct Delta {
+    url: String,
+    /// Signature of the patch itself.
+    signature: String,
+    /// Size of the rebuilt package in bytes.
+    size: u64,
+}
+
+/// Rebuild the new package from the base and a delta, if the manifest
+/// offers one for the running version.
+async fn download_delta(app: &AppHandle, update: &Update) -> Result<Option<Vec<u8>>, String> {
+    let current = app.package_info().version.to_string();
+    let Some(entry) = update
+        .raw_json
+        .get("deltas")
+        .and_then(|deltas| deltas.get(&current))
+    else {
+        return Ok(None);
+    };
+    let entry: Delta =
+        serde_json::from_value(entry.clone()).map_err(|e| format!("invalid delta entry: {e}"))?;
+    let Some(base) = base_package(app) else {
+        return Ok(None);
+    };
+
+    let url = Url::parse(&entry.url).map_err(|e| e.to_string())?;
+    let delta = fetch_package(app, update, url).await?;
+    // Only a signed patch is worth decompressing.
+    verify_signature(app, &delta, &entry.signature)?;
+
+    let mut decoder = zstd::stream::read::Decoder::with_dictionary(delta.as_slice(), &base)
+        .map_err(|e| e.to_string())?;
+    // Patches span the base and the whole new package, so the window must
+    // cover the larger of the two, and need not go further.
+    let window_log = (base.len() as u64)
+        .max(entry.size)
+        .next_power_of_two()
+        .trailing_zeros()
+        .clamp(10, 31);
+    decoder
+        .window_log_max(window_log)
+        .map_err(|e| e.to_string())?;
+    let mut package = Vec::new();
+    decoder
+        .take(entry.size + 1)
+        .read_to_end(&mut package)
+        .map_err(|e| e.to_string())?;
+    if package.len() as u64 != entry.size {
+        return Err(format!(
+            "rebuilt package is not the expected {} bytes",
+            entry.size
+        ));
+    }
+
+    verify_signature(app, &package, &update.signature)?;
+    log::info!(
+        "[nchat-desktop] applied {} byte delta for {} ({} bytes)",
+        delta.len(),
+        update.version,
+        package.len()
+    );
+    Ok(Some(package))
+}
+
+/// Fetch the package for `update`: a delta when one applies, otherwise the
+/// full package. The result is kept as the base for the next delta.
+async fn download(app: &AppHandle, update: &Update) -> Result<Vec<u8>, String> {
+    app.state::<UpdaterState>()
+        .control
+        .send_replace(DownloadControl::Run);
+    let bytes = match download_delta(app, update).await {
+        Ok(Some(bytes)) => {
+            record_verification(app, Some(&update.version), &Ok(()));
+            bytes
+        }
+        Ok(None) => download_full(app, update).await?,
+        Err(e) if e == CANCELED => return Err(e),
+        Err(e) => {
+            log::warn!("[nchat-desktop] delta update failed, using full package: {e}");
+            download_full(app, update).await?
+        }
+    };
+    save_base_package(app, &update.version, &bytes, &update.signature);
+    Ok(bytes)
+}
+
+/// Fetch the full package for `update` and check its signature.
+async fn download_full(app: &AppHandle, update: &Update) -> Result<Vec<u8>, String> {
+    let bytes = fetch_package(app, update, update.download_url.clone()).await?;
+    let result = verify_signature(app, &bytes, &update.signature);
+    record_verification(app, Some(&update.version), &result);
+    result.map(|_| bytes)
+}
+
+/// Download `url` into memory, emitting `update-download-progress` (bytes,
+/// percent, speed, ETA) to the main window as it arrives. Follows the
+/// pause/resume/cancel requests from `control_download`: pausing drops the
+/// connection, and resuming continues with a Range request where the
+/// server supports it.
+async fn fetch_package(app: &AppHandle, update: &Update, url: Url) -> Result<Vec<u8>, String> {
+    let client = proxy::client(app)?;
+    let mut control = app.state::<UpdaterState>().control.subscribe();
+    let started = Instant::now();
+    let mut bytes: Vec<u8> = Vec::new();
+    let mut total = None;
+    let mut last_emit: Option<Instant> = None;
+
+    'request: loop {
+        let mut request = client
+            .get(url.clone())
+            .headers(update.headers.clone())
+            .header(reqwest::header::ACCEPT, "application/octet-stream");
+        if !bytes.is_empty() {
+            request = request.header(reqwest::header::RANGE, format!("bytes={}-", bytes.len()));
+        }
+        let mut res = request
+            .send()
+            .await
+            .and_then(|res| res.error_for_status())
+            .map_err(|e| e.to_string())?;
+        if res.status() != reqwest::StatusCode::PARTIAL_CONTENT {
+            // No range support: start over.
+            bytes.clear();
+        }
+        if total.is_none() {
+            total = res.content_length().map(|len| len + bytes.len() as u64);
+        }
+
+        loop {
+            let state = *control.borrow_and_update();
+            match state {
+                DownloadControl::Cancel => return Err(CANCELED.into()),
+                DownloadControl::Pause => {
+                    drop(res);
+                    emit_status(app, UpdateStatus::Paused, Some(&update.version));
+                    control
+                        .wait_for(|c| *c != DownloadControl::Pause)
+                        .await
+                        .map_err(|e| e.to_string())?;
+                    emit_status(app, UpdateStatus::Downloading, Some(&update.version));
+                    continue 'request;
+                }
+                DownloadControl::Run => {}
+            }
+            let chunk = tokio::select! {
+                chunk = res.chunk() => chunk.map_err(|e| e.to_string())?,
+                _ = control.changed() => continue,
+            };
+            let Some(chunk) = chunk else {
+                break 'request;
+            };
+            bytes.extend_from_slice(&chunk);
+            if !matches!(last_emit, Some(at) if at.elapsed() < PROGRESS_INTERVAL) {
+                last_emit = Some(Instant::now());
+                emit_progress(app, bytes.len() as u64, total, started);
+            }
+        }
+    }
+
+    // Always report completion, even if the last chunk was throttled.
+    emit_progress(app, bytes.len() as u64, Some(bytes.len() as u64), started);
+    Ok(bytes)
+}
+
+/// Pause, resume or cancel the update download in flight.
+pub fn control_download(app: &AppHandle, request: DownloadControl) -> Result<(), String> {
+    let state = app.state::<UpdaterState>();
+    if !state.downloading.load(Ordering::SeqCst) {
+        return Err("no update is downloading".into());
+    }
+    state.control.send_replace(request);
+    Ok(())
+}
+
+fn emit_status(app: &AppHandle, status: UpdateStatus, version: Option<&str>) {
+    let progress = match status {
+        UpdateStatus::Downloading => ProgressState::Normal,
+        UpdateStatus::Paused => ProgressState::Paused,
+        _ => ProgressState::None,
+    };
+    progress::set_state(app, ProgressSource::Update, progress);
+    let event = UpdateStateEvent {
+        status,
+        version: version.map(str::to_string),
+    };
+    if let Some(win) = app.get_webview_window(crate::windows::MAIN_WINDOW) {
+        let _ = win.emit("update-state", event);
+    }
+}
+
+/// Download and install the available update right away. The new version
+/// runs after a restart.
+pub async fn install(app: &AppHandle) -> Result<String, String> {
+    let state = app.state::<UpdaterState>();
+    if state.downloading.swap(true, Ordering::SeqCst) {
+        return Err("an update is already downloading".into());
+    }
+    let result = install_inner(app).await;
+    state.downloading.store(false, Ordering::SeqCst);
+    if result.is_err() {
+        emit_status(app, UpdateStatus::Idle, None);
+    }
+    result
+}
+
+async fn install_inner(app: &AppHandle) -> Result<String, String> {
+    let update = find_update(app).await?.ok_or("no update available")?;
+    emit_status(app, UpdateStatus::Downloading, Some(&update.version));
+    let bytes = download(app, &update).await?;
+    emit_status(app, UpdateStatus::Installing, Some(&update.version));
+    update.install(bytes).map_err(|e| e.to_string())?;
+    log::info!("[nchat-desktop] installed update {}", update.version);
+    Ok(update.version)
+}
+
+/// Whether updates are downloaded in the background and staged.
+pub fn background_download(app: &AppHandle) -> bool {
+    settings::load(app, BACKGROUND_KEY).unwrap_or(false)
+}
+
+pub fn set_background_download(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    settings::save(app, BACKGROUND_KEY, &enabled)?;
+    if enabled {
+        app.state::<UpdaterState>().wake.notify_one();
+    }
+    Ok(())
+}
+
+/// Version of the staged update, if one is waiting.
+pub fn staged_version(app: &AppHandle) -> Option<String> {
+    let state = app.state::<UpdaterState>();
+    let staged = state.staged.lock().unwrap();
+    staged.as_ref().map(|(update, _)| update.version.clone())
+}
+
+/// Download the available update without installing it; it is installed
+/// on the next quit or by `install_staged`. Returns the staged version.
+/// Automatic runs (`manual == false`) leave a dismissed release alone.
+pub async fn stage(app: &AppHandle, manual: bool) -> Result<Option<String>, String> {
+    let state = app.state::<UpdaterState>();
+    if state.downloading.swap(true, Ordering::SeqCst) {
+        return Err("an update is already downloading".into());
+    }
+    let result = stage_inner(app, manual).await;
+    state.downloading.store(false, Ordering::SeqCst);
+    if result.is_err() {
+        emit_status(app, UpdateStatus::Idle, None);
+    }
+    result
+}
+
+async fn stage_inner(app: &AppHandle, manual: bool) -> Result<Option<String>, String> {
+    let update = find_update(app)
+        .await?
+        .filter(|update| manual || !dismissed(app, &update.version));
+    let Some(update) = update else {
+        return Ok(staged_version(app));
+    };
+    if staged_version(app).as_deref() == Some(update.version.as_str()) {
+        return Ok(Some(update.version));
+    }
+    emit_status(app, UpdateStatus::Downloading, Some(&update.version));
+    let bytes = download(app, &update).await?;
+    let version = update.version.clone();
+    *app.state::<UpdaterState>().staged.lock().unwrap() = Some((update, bytes));
+    log::info!("[nchat-desktop] staged update {version}");
+    emit_status(app, UpdateStatus::Staged, Some(&version));
+    Ok(Some(version))
+}
+
+/// Install the staged update, if any. Returns whether one was installed.
+pub fn install_staged(app: &AppHandle) -> Result<bool, String> {
+    let staged = app.state::<UpdaterState>().staged.lock().unwrap().take();
+    let Some((update, bytes)) = staged else {
+        return Ok(false);
+    };
+    emit_status(app, UpdateStatus::Installing, Some(&update.version));
+    update.install(bytes).map_err(|e| e.to_string())?;
+    log::info!("[nchat-desktop] installed staged update {}", update.version);
+    Ok(true)
+}
+
+/// The version a rollback would reinstall, if a previous package is kept
+/// along with its signature.
+pub fn rollback_info(app: &AppHandle) -> Option<RollbackInfo> {
+    let files = previous_package_files(app)?;
+    let previous = std::fs::read_to_string(files.version)
+        .ok()?
+        .trim()
+        .to_string();
+    let current = app.package_info().version.to_string();
+    (files.package.is_file() && files.signature.is_file() && previous != current)
+        .then_some(RollbackInfo { current, previous })
+}
+
+/// Install a verified package off the async runtime; the installers wait
+/// for files, dialogs and package managers.
+async fn install_package(bytes: Vec<u8>, version: String) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || installer::install(&bytes, &version))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Reinstall the previous version. `confirm` must name the version being
+/// rolled back to, as shown to the user. The kept package must still match
+/// its release signature and be that version; the update server is not
+/// contacted. Fails while an update is downloading or being staged.
+pub async fn rollback(app: &AppHandle, confirm: &str) -> Result<String, String> {
+    let info = rollback_info(app).ok_or("no previous version is available")?;
+    if confirm != info.previous {
+        return Err(format!("confirm the rollback to {}", info.previous));
+    }
+    // A download or stage finishing meanwhile would stage the release
+    // being rolled back from.
+    let state = app.state::<UpdaterState>();
+    if state.downloading.swap(true, Ordering::SeqCst) {
+        return Err("an update is already downloading".into());
+    }
+    emit_status(app, UpdateStatus::RollingBack, Some(&info.previous));
+
+    let result = async {
+        let files = previous_package_files(app).ok_or("no previous package")?;
+        let bytes = std::fs::read(files.package).map_err(|e| e.to_string())?;
+        let signature = std::fs::read_to_string(files.signature).map_err(|e| e.to_string())?;
+        let signature = signature.trim();
+        emit_progress(
+            app,
+            bytes.len() as u64,
+            Some(bytes.len() as u64),
+            Instant::now(),
+        );
+
+        let result = verified_comment(app, &bytes, signature);
+        record_verification(app, Some(&info.previous), &result.clone().map(|_| ()));
+        let comment = result?;
+        if package_version(&comment, &bytes).map(|v| v.to_string()) != Some(info.previous.clone()) {
+            return Err(format!("the kept package is not version {}", info.previous));
+        }
+        // A staged update would bring the broken release back on quit.
+        state.staged.lock().unwrap().take();
+        settings::save(app, SKIPPED_KEY, &info.current)?;
+        emit_status(app, UpdateStatus::Installing, Some(&info.previous));
+        install_package(bytes, info.previous.clone()).await
+    }
+    .await;
+    state.downloading.store(false, Ordering::SeqCst);
+
+    if let Err(e) = result {
+        emit_status(app, UpdateStatus::Idle, None);
+        return Err(e);
+    }
+    log::warn!(
+        "[nchat-desktop] rolled back from {} to {}",
+        info.current,
+        info.previous
+    );
+    Ok(info.previous)
+}
+
+/// Install a package from disk after checking its signature, given
+/// directly or read from `<path>.sig`, without contacting the update
+/// server. The package must be newer than the running version unless the
+/// admin policy allows downgrades. The new version runs after a restart.
+pub async fn install_from_path(
+    app: &AppHandle,
+    path: &Path,
+    signature: Option<String>,
+) -> Result<(), String> {
+    if !path.is_file() {
+        return Err(format!("{} is not a file", path.display()));
+    }
+    let signature = match signature {
+        Some(signature) => signature,
+        None => {
+            let mut sig_path = path.as_os_str().to_owned();
+            sig_path.push(".sig");
+            std::fs::read_to_string(&sig_path)
+                .map_err(|e| format!("no signature for {}: {e}", path.display()))?
+        }
+    };
+    let signature = signature.trim();
+    let bytes = std::fs::read(path).map_err(|e| e.to_string())?;
+    let result = verified_comment(app, &bytes, signature);
+    record_verification(app, None, &result.clone().map(|_| ()));
+    let comment = result?;
+
+    let version = package_version(&comment, &bytes)
+        .ok_or_else(|| format!("cannot tell which version {} is", path.display()))?;
+    let current =
+        Version::parse(&app.package_info().version.to_string()).map_err(|e| e.to_string())?;
+    let allow_downgrade = policy::current()
+        .updater
+        .as_ref()
+        .is_some_and(|policy| policy.allow_downgrade);
+    if version <= current && !allow_downgrade {
+        return Err(format!(
+            "{} is version {version}, which is not newer than the running {current}",
+            path.display()
+        ));
+    }
+
+    let state = app.state::<UpdaterState>();
+    if state.downloading.swap(true, Ordering::SeqCst) {
+        return Err("an update is already downloading".into());
+    }
+    let version = version.to_string();
+    // The local package replaces whatever was staged.
+    state.staged.lock().unwrap().take();
+    emit_status(app, UpdateStatus::Installing, Some(&version));
+    let result = install_package(bytes, version.clone()).await;
+    state.downloading.store(false, Ordering::SeqCst);
+
+    if let Err(e) = result {
+        emit_status(app, UpdateStatus::Idle, None);
+        return Err(e);
+    }
+    log::info!(
+        "[nchat-desktop] installed update {version} from {}",
+        path.display()
+    );
+    Ok(())
+}
+
+/// Check and stage updates periodically while background download is on.
+pub fn start_background(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            if background_download(&app) {
+                if let Err(e) = stage(&app, false).await {
+                    log::warn!("[nchat-desktop] background update failed: {e}");
+                }
+            }
+            let state = app.state::<UpdaterState>();
+            tokio::select! {
+                _ = tokio::time::sleep(BACKGROUND_INTERVAL) => {}
+                _ = state.wake.notified() => {}
+            }
+        }
+    });
+}
@@ -0,0 +1,1049 @@
+// nChat Desktop — native window creation
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{
+    AppHandle, Emitter, LogicalSize, Manager, PhysicalPosition, PhysicalRect, PhysicalSize,
+    UserAttentionType, WebviewUrl, WebviewWindow, WebviewWindowBuilder,
+};
+
+use crate::state::settings;
+
+/// Label of the primary application window.
+pub const MAIN_WINDOW: &str = "main";
+
+/// Label prefix of pop-out conversation windows; the conversation id
+/// follows.
+pub const CONVERSATION_WINDOW_PREFIX: &str = "conversation-";
+
+/// Label prefix of windows the frontend opens with `create_window`; the
+/// caller's label follows. The `auxiliary` capability matches it.
+pub const AUX_WINDOW_PREFIX: &str = "aux-";
+
+/// Settings key holding the webview zoom factor per window label.
+const ZOOM_KEY: &str = "zoom.levels";
+
+/// Zoom steps and bounds for View → Zoom In/Out.
+const ZOOM_STEP: f64 = 0.1;
+const ZOOM_MIN: f64 = 0.5;
+const ZOOM_MAX: f64 = 3.0;
+
+/// Settings key holding the main window's mode and the bounds to return to.
+const MODE_KEY: &str = "window.mode";
+
+/// Logical size of the mini dock; the height shrinks to fit small screens.
+const MINI_WIDTH: f64 = 88.0;
+const MINI_HEIGHT: f64 = 560.0;
+
+/// Logical distance within which a dragged mini dock snaps to a work area
+/// edge.
+const SNAP_DISTANCE: f64 = 24.0;
+
+/// Settings key holding pop-out windows by label: where they were last
+/// placed and whether they were open when the app quit.
+const POPOUTS_KEY: &str = "windows.popouts";
+
+/// Settings key for reopening pop-out windows at launch.
+const RESTORE_POPOUTS_KEY: &str = "windows.restorePopouts";
+
+/// Remembered pop-outs beyond this are dropped, least recently used and
+/// closed first.
+const MAX_SAVED_POPOUTS: usize = 50;
+
+/// Pop-out conversation windows that are open, by label.
+#[derive(Default)]
+pub struct WindowRegistry {
+    conversations: Mutex<HashMap<String, String>>,
+    /// Serializes read-modify-write of the saved pop-outs.
+    store_lock: Mutex<()>,
+    /// Open modal windows and the parent each one disabled.
+    modals: Mutex<HashMap<String, String>>,
+    /// Unread count shown in front of every window title.
+    unread: Mutex<u32>,
+    /// Whether one of the app's windows had focus at the last focus event.
+    app_focused: Mutex<bool>,
+    /// Where the mini dock was dragged to, until it is saved.
+    mini_moved: Mutex<Option<(DockEdge, PhysicalPosition<i32>)>>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct SavedPopout {
+    conversation_id: String,
+    #[serde(default)]
+    title: Option<String>,
+    /// Physical bounds while last open in the normal state.
+    #[serde(default)]
+    bounds: Option<Rect>,
+    /// Name of the monitor the window was on.
+    #[serde(default)]
+    monitor: Option<String>,
+    #[serde(default)]
+    open: bool,
+    #[serde(default)]
+    last_used: u64,
+}
+
+/// Layout profile of the main window. `Mini` is a narrow, fixed-size dock
+/// without decorations or menu bar, snapped to a screen edge.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum WindowMode {
+    #[default]
+    Normal,
+    Mini,
+}
+
+/// Where the mini dock sits. It snaps to the left or right edge when
+/// dragged close to one, and floats anywhere else.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum DockEdge {
+    Left,
+    #[default]
+    Right,
+    Floating,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct ModeState {
+    mode: WindowMode,
+    #[serde(default)]
+    edge: DockEdge,
+    /// Physical bounds of the normal window, put back when leaving mini
+    /// mode.
+    #[serde(default)]
+    normal: Option<Rect>,
+    /// Physical top-left of the mini dock where it was last dragged; the
+    /// edge decides `x` unless it floats.
+    #[serde(default)]
+    mini_position: Option<PhysicalPosition<i32>>,
+}
+
+#[derive(Deserialize, Default)]
+pub struct ConversationWindowOptions {
+    pub title: Option<String>,
+    /// Logical size; defaults to 480×720.
+    pub width: Option<f64>,
+    pub height: Option<f64>,
+}
+
+/// Options for `create_window`. Sizes are logical pixels.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct WindowOptions {
+    pub title: Option<String>,
+    pub width: f64,
+    pub height: f64,
+    pub min_width: Option<f64>,
+    pub min_height: Option<f64>,
+    pub resizable: bool,
+    pub decorations: bool,
+    /// On macOS this relies on the private API enabled for vibrancy.
+    pub transparent: bool,
+    /// Label of the window that owns this one; it stays above its parent.
+    pub parent: Option<String>,
+    /// Disable the parent until this window closes. Needs `parent`.
+    pub modal: bool,
+}
+
+impl Default for WindowOptions {
+    fn default() -> Self {
+        Self {
+            title: None,
+            width: 800.0,
+            height: 600.0,
+            min_width: None,
+            min_height: None,
+            resizable: true,
+            decorations: true,
+            transparent: false,
+            parent: None,
+            modal: false,
+        }
+    }
+}
+
+/// A connected display. Coordinates are physical pixels in the desktop's
+/// coordinate space; `index` is the position `open_media_viewer` takes.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisplayInfo {
+    pub index: usize,
+    pub name: Option<String>,
+    pub bounds: Rect,
+    /// The area not covered by the taskbar, dock or menu bar.
+    pub work_area: Rect,
+    pub scale_factor: f64,
+    pub primary: bool,
+    pub has_cursor: bool,
+    pub has_main_window: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// How insistently to ask for attention. `Critical` keeps the taskbar
+/// button flashing or the Dock icon bouncing until the app is focused;
+/// `Informational` does it once. `None` stops a pending request.
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum AttentionKind {
+    Informational,
+    Critical,
+    None,
+}
+
+#[derive(Serialize, Clone)]
+pub struct ConversationWindow {
+    pub label: String,
+    pub conversation_id: String,
+}
+
+/// Add a Chromium switch for WebView2. Switches are collected in the
+/// environment so every launch-time feature can contribute without
+/// overwriting the others; must run before the first webview is created.
+#[cfg(target_os = "windows")]
+pub fn append_browser_arg(arg: &str) {
+    const VAR: &str = "WEBVIEW2_ADDITIONAL_BROWSER_ARGUMENTS";
+    let args = match std::env::var(VAR) {
+        Ok(existing) if !existing.is_empty() => format!("{existing} {arg}"),
+        _ => arg.to_string(),
+    };
+    std::env::set_var(VAR, args);
+}
+
+/// Create the main window from its `tauri.conf.json` definition.
+/// The window is declared with `create: false` so launch-time settings
+/// (environment for the webview, persisted toggles) are applied before the
+/// webview exists.
+pub fn create_main_window(app: &AppHandle) -> tauri::Result<WebviewWindow> {
+    if let Some(win) = app.get_webview_window(MAIN_WINDOW) {
+        return Ok(win);
+    }
+    let config = app
+        .config()
+        .app
+        .windows
+        .iter()
+        .find(|w| w.label == MAIN_WINDOW)
+        .cloned()
+        .ok_or(tauri::Error::WindowNotFound)?;
+    let builder = WebviewWindowBuilder::from_config(app, &config)?
+        .transparent(crate::effects::wants_transparency(app));
+    #[cfg(target_os = "macos")]
+    let builder = crate::titlebar::apply_at_creation(app, builder);
+    let win = builder.build()?;
+    restore_zoom(&win);
+    crate::effects::restore(&win);
+    Ok(win)
+}
+
+fn zoom_levels(app: &AppHandle) -> HashMap<String, f64> {
+    settings::load(app, ZOOM_KEY).unwrap_or_default()
+}
+
+/// Current zoom factor of `win`, as last set through `set_zoom`.
+pub fn zoom(win: &WebviewWindow) -> f64 {
+    zoom_levels(win.app_handle())
+        .get(win.label())
+        .copied()
+        .unwrap_or(1.0)
+}
+
+/// Apply and persist a zoom factor for `win`; 1.0 is actual size.
+pub fn set_zoom(win: &WebviewWindow, factor: f64) -> Result<f64, String> {
+    // Round away accumulated float error from repeated steps.
+    let factor = ((factor * 100.0).round() / 100.0).clamp(ZOOM_MIN, ZOOM_MAX);
+    win.set_zoom(factor).map_err(|e| e.to_string())?;
+    let app = win.app_handle();
+    let mut levels = zoom_levels(app);
+    if factor == 1.0 {
+        levels.remove(win.label());
+    } else {
+        levels.insert(win.label().to_string(), factor);
+    }
+    settings::save(app, ZOOM_KEY, &levels)?;
+    Ok(factor)
+}
+
+/// Zoom in (`steps > 0`) or out (`steps < 0`) by whole steps.
+pub fn step_zoom(win: &WebviewWindow, steps: i32) -> Result<f64, String> {
+    set_zoom(win, zoom(win) + ZOOM_STEP * steps as f64)
+}
+
+/// Re-apply the persisted zoom factor to a newly created window.
+pub fn restore_zoom(win: &WebviewWindow) {
+    let factor = zoom(win);
+    if factor != 1.0 {
+        if let Err(e) = win.set_zoom(factor) {
+            log::warn!(
+                "[nchat-desktop] failed to restore zoom for {}: {e}",
+                win.label()
+            );
+        }
+    }
+}
+
+fn main_window(app: &AppHandle) -> Result<WebviewWindow, String> {
+    app.get_webview_window(MAIN_WINDOW)
+        .ok_or_else(|| "main window not found".to_string())
+}
+
+/// The window `label`, or the main window when no label is given.
+pub fn window(app: &AppHandle, label: Option<&str>) -> Result<WebviewWindow, String> {
+    let label = label.unwrap_or(MAIN_WINDOW);
+    app.get_webview_window(label)
+        .ok_or_else(|| format!("window not found: {label}"))
+}
+
+/// Whether any of the app's windows has focus.
+pub fn app_focused(app: &AppHandle) -> bool {
+    app.webview_windows()
+        .values()
+        .any(|win| win.is_focused().unwrap_or(false))
+}
+
+/// Called on every window focus change. When focus enters or leaves the
+/// app as a whole, all windows get `app:focus-changed`; focus moving
+/// between two app windows is not a change.
+pub fn on_focus_changed(app: &AppHandle, focused: bool) {
+    let focused = focused || app_focused(app);
+    let registry = app.state::<WindowRegistry>();
+    let mut was_focused = registry.app_focused.lock().unwrap();
+    if *was_focused != focused {
+        *was_focused = focused;
+        let _ = app.emit("app:focus-changed", focused);
+        crate::workspace_shortcuts::on_app_focus_changed(app, focused);
+    }
+}
+
+/// The stored mode state with any unsaved mini dock move applied.
+fn mode_state(app: &AppHandle) -> ModeState {
+    let mut state: ModeState = settings::load(app, MODE_KEY).unwrap_or_default();
+    if let Some((edge, position)) = *app.state::<WindowRegistry>().mini_moved.lock().unwrap() {
+        state.edge = edge;
+        state.mini_position = Some(position);
+    }
+    state
+}
+
+/// The main window's current mode and dock edge.
+pub fn window_mode(app: &AppHandle) -> (WindowMode, DockEdge) {
+    let state = mode_state(app);
+    (state.mode, state.edge)
+}
+
+/// Switch the main window between the normal layout and the mini dock.
+/// The normal window's bounds are remembered on the way in and restored on
+/// the way out; the mode is persisted and re-applied at launch. The window
+/// is told through `window:mode` so the UI can switch layouts.
+pub fn set_window_mode(
+    app: &AppHandle,
+    mode: WindowMode,
+    edge: Option<DockEdge>,
+) -> Result<(), String> {
+    if crate::kiosk::is_active() {
+        return Err("not available in kiosk mode".into());
+    }
+    let win = main_window(app)?;
+    let mut state = mode_state(app);
+    if let Some(edge) = edge {
+        state.edge = edge;
+    }
+    match mode {
+        WindowMode::Mini => {
+            if state.mode != WindowMode::Mini {
+                state.normal = Some(bounds(&win)?);
+            }
+            apply_mini(&win, state.edge, state.mini_position)?;
+        }
+        WindowMode::Normal => {
+            if state.mode == WindowMode::Mini {
+                apply_normal(app, &win, state.normal.take())?;
+            }
+        }
+    }
+    state.mode = mode;
+    settings::save(app, MODE_KEY, &state)?;
+    *app.state::<WindowRegistry>().mini_moved.lock().unwrap() = None;
+    let _ = win.emit("window:mode", mode);
+    Ok(())
+}
+
+/// Re-enter mini mode at launch if that is how the app was left.
+pub fn restore_window_mode(app: &AppHandle) {
+    let state = mode_state(app);
+    if state.mode != WindowMode::Mini {
+        return;
+    }
+    if let Err(e) =
+        main_window(app).and_then(|win| apply_mini(&win, state.edge, state.mini_position))
+    {
+        log::warn!("[nchat-desktop] failed to restore mini mode: {e}");
+    }
+}
+
+/// Called when the main window moves. A mini dock dragged within
+/// `SNAP_DISTANCE` of a work area edge is pulled onto it; the window gets
+/// `window:dock-edge` when the edge it is docked to changes. Moves are kept
+/// in memory and saved with `save_mini_dock`, not on every event.
+pub fn on_main_moved(app: &AppHandle, position: PhysicalPosition<i32>) {
+    if settings::load::<ModeState>(app, MODE_KEY).is_none_or(|s| s.mode != WindowMode::Mini) {
+        return;
+    }
+    let Ok(win) = main_window(app) else {
+        return;
+    };
+    let (Ok(Some(monitor)), Ok(size)) = (win.current_monitor(), win.outer_size()) else {
+        return;
+    };
+    let threshold = (SNAP_DISTANCE * monitor.scale_factor()).round() as i32;
+    let (edge, snapped) = snap(position, size, monitor.work_area(), threshold);
+    if snapped != position {
+        let _ = win.set_position(snapped);
+    }
+    let previous_edge = window_mode(app).1;
+    *app.state::<WindowRegistry>().mini_moved.lock().unwrap() = Some((edge, snapped));
+    if edge != previous_edge {
+        let _ = win.emit("window:dock-edge", edge);
+    }
+}
+
+/// Persist where the mini dock was dragged. Runs at quit.
+pub fn save_mini_dock(app: &AppHandle) {
+    let Some((edge, position)) = app
+        .state::<WindowRegistry>()
+        .mini_moved
+        .lock()
+        .unwrap()
+        .take()
+    else {
+        return;
+    };
+    let mut state: ModeState = settings::load(app, MODE_KEY).unwrap_or_default();
+    state.edge = edge;
+    state.mini_position = Some(position);
+    if let Err(e) = settings::save(app, MODE_KEY, &state) {
+        log::warn!("[nchat-desktop] failed to save the mini dock position: {e}");
+    }
+}
+
+/// Pull `position` onto any work area edge within `threshold`, and say
+/// which side edge it ended up docked to.
+fn snap(
+    position: PhysicalPosition<i32>,
+    size: PhysicalSize<u32>,
+    area: &PhysicalRect<i32, u32>,
+    threshold: i32,
+) -> (DockEdge, PhysicalPosition<i32>) {
+    let left = area.position.x;
+    let right = left + area.size.width as i32 - size.width as i32;
+    let top = area.position.y;
+    let bottom = top + area.size.height as i32 - size.height as i32;
+    let near = |value: i32, edge: i32| (value - edge).abs() <= threshold;
+    let (edge, x) = if near(position.x, left) {
+        (DockEdge::Left, left)
+    } else if near(position.x, right) {
+        (DockEdge::Right, right)
+    } else {
+        (DockEdge::Floating, position.x)
+    };
+    let y = if near(position.y, top) {
+        top
+    } else if near(position.y, bottom) {
+        bottom
+    } else {
+        position.y
+    };
+    (edge, PhysicalPosition::new(x, y))
+}
+
+fn bounds(win: &WebviewWindow) -> Result<Rect, String> {
+    // Maximized or full-screen bounds would make a poor window to return to.
+    if win.is_fullscreen().unwrap_or(false) || win.is_maximized().unwrap_or(false) {
+        return Err("leave full screen or maximized mode first".into());
+    }
+    let position = win.outer_position().map_err(|e| e.to_string())?;
+    let size = win.inner_size().map_err(|e| e.to_string())?;
+    Ok(Rect {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+    })
+}
+
+fn apply_mini(
+    win: &WebviewWindow,
+    edge: DockEdge,
+    position: Option<PhysicalPosition<i32>>,
+) -> Result<(), String> {
+    // The remembered position picks the monitor, if it is still connected.
+    let remembered = position.and_then(|p| {
+        win.monitor_from_point(p.x as f64, p.y as f64)
+            .ok()
+            .flatten()
+    });
+    let monitor = match remembered {
+        Some(monitor) => monitor,
+        None => win
+            .current_monitor()
+            .map_err(|e| e.to_string())?
+            .ok_or("no monitor for the main window")?,
+    };
+    let area = monitor.work_area();
+    let scale = monitor.scale_factor();
+    let width = (MINI_WIDTH * scale).round() as u32;
+    let height = ((MINI_HEIGHT * scale).round() as u32).min(area.size.height);
+    let left = area.position.x;
+    let right = left + (area.size.width - width.min(area.size.width)) as i32;
+    let top = area.position.y;
+    let bottom = top + (area.size.height - height) as i32;
+    let position = position.filter(|_| remembered.is_some());
+    let x = match (edge, position) {
+        (DockEdge::Left, _) => left,
+        (DockEdge::Right, _) => right,
+        (DockEdge::Floating, Some(p)) => p.x.clamp(left, right),
+        (DockEdge::Floating, None) => (left + right) / 2,
+    };
+    let y = match position {
+        Some(p) => p.y.clamp(top, bottom),
+        None => (top + bottom) / 2,
+    };
+
+    let result = (|| {
+        win.set_decorations(false)?;
+        #[cfg(not(target_os = "macos"))]
+        win.hide_menu()?;
+        win.set_min_size(None::<LogicalSize<f64>>)?;
+        win.set_size(PhysicalSize::new(width, height))?;
+        win.set_resizable(false)?;
+        win.set_position(PhysicalPosition::new(x, y))
+    })();
+    result.map_err(|e| e.to_string())
+}
+
+fn apply_normal(app: &AppHandle, win: &WebviewWindow, bounds: Option<Rect>) -> Result<(), String> {
+    let config = app
+        .config()
+        .app
+        .windows
+        .iter()
+        .find(|w| w.label == MAIN_WINDOW);
+    let min_size = config.and_then(|c| Some(LogicalSize::new(c.min_width?, c.min_height?)));
+    let result = (|| {
+        win.set_decorations(true)?;
+        #[cfg(not(target_os = "macos"))]
+        win.show_menu()?;
+        win.set_resizable(true)?;
+        win.set_min_size(min_size)?;
+        match (bounds, config) {
+            (Some(b), _) => {
+                win.set_size(PhysicalSize::new(b.width, b.height))?;
+                win.set_position(PhysicalPosition::new(b.x, b.y))
+            }
+            (None, Some(c)) => {
+                win.set_size(LogicalSize::new(c.width, c.height))?;
+                win.center()
+            }
+            (None, None) => win.center(),
+        }
+    })();
+    result.map_err(|e| e.to_string())
+}
+
+/// Keep the window `label` above other apps' windows, or release it. The
+/// window is told through `window:always-on-top` so a pin button can follow.
+pub fn set_always_on_top(app: &AppHandle, label: &str, on: bool) -> Result<(), String> {
+    let win = app
+        .get_webview_window(label)
+        .ok_or_else(|| format!("window not found: {label}"))?;
+    win.set_always_on_top(on).map_err(|e| e.to_string())?;
+    let _ = win.emit("window:always-on-top", on);
+    crate::menu::refresh_always_on_top(app);
+    Ok(())
+}
+
+/// Open `conversation_id` in its own window, or focus the window already
+/// showing it. The window loads the app at `#/conversation/<id>` and can
+/// do everything the main window can with that conversation.
+pub fn open_conversation_window(
+    app: &AppHandle,
+    conversation_id: &str,
+    options: ConversationWindowOptions,
+) -> Result<ConversationWindow, String> {
+    // The id becomes part of the window label, which is limited to these.
+    let valid = !conversation_id.is_empty()
+        && conversation_id.len() <= 128
+        && conversation_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':'));
+    if !valid {
+        return Err("invalid conversation id".into());
+    }
+    if crate::kiosk::is_active() {
+        return Err("not available in kiosk mode".into());
+    }
+    let label = format!("{CONVERSATION_WINDOW_PREFIX}{conversation_id}");
+
+    let win = match app.get_webview_window(&label) {
+        Some(win) => win,
+        None => {
+            let saved = saved_popouts(app).remove(&label);
+            let title = options
+                .title
+                .clone()
+                .or_else(|| saved.as_ref().and_then(|s| s.title.clone()));
+            let route = format!("index.html#/conversation/{conversation_id}");
+            let win = WebviewWindowBuilder::new(app, &label, WebviewUrl::App(route.into()))
+                .title(title.as_deref().unwrap_or("nChat"))
+                .inner_size(
+                    options.width.unwrap_or(480.0),
+                    options.height.unwrap_or(720.0),
+                )
+                .min_inner_size(320.0, 400.0)
+                // Shown below, once it has been put back where it was.
+                .visible(false)
+                .build()
+                .map_err(|e| e.to_string())?;
+            // An explicit size from the caller wins over the remembered one.
+            if let Some(saved) = saved.filter(|_| options.width.is_none()) {
+                if let Err(e) = place(&win, &saved) {
+                    log::warn!("[nchat-desktop] failed to restore {label}: {e}");
+                }
+            }
+            restore_zoom(&win);
+            apply_title_badge(&win);
+            crate::tray::apply_tray_only(app);
+            win
+        }
+    };
+    app.state::<WindowRegistry>()
+        .conversations
+        .lock()
+        .unwrap()
+        .insert(label.clone(), conversation_id.to_string());
+    let title = options.title.or_else(|| win.title().ok());
+    update_popout(app, &label, |saved| {
+        saved.conversation_id = conversation_id.to_string();
+        saved.title = title;
+        saved.open = true;
+    });
+    win.show().map_err(|e| e.to_string())?;
+    win.set_focus().map_err(|e| e.to_string())?;
+    Ok(ConversationWindow {
+        label,
+        conversation_id: conversation_id.to_string(),
+    })
+}
+
+/// Conversation windows that are open.
+pub fn conversation_windows(app: &AppHandle) -> Vec<ConversationWindow> {
+    let registry = app.state::<WindowRegistry>();
+    let conversations = registry.conversations.lock().unwrap();
+    conversations
+        .iter()
+        .map(|(label, conversation_id)| ConversationWindow {
+            label: label.clone(),
+            conversation_id: conversation_id.clone(),
+        })
+        .collect()
+}
+
+/// Drop a destroyed window from the registry, re-enabling the parent of
+/// a modal.
+pub fn forget(app: &AppHandle, label: &str) {
+    let registry = app.state::<WindowRegistry>();
+    registry.conversations.lock().unwrap().remove(label);
+    let parent = registry.modals.lock().unwrap().remove(label);
+    if let Some(parent) = parent.and_then(|parent| app.get_webview_window(&parent)) {
+        let _ = parent.set_enabled(true);
+        let _ = parent.set_focus();
+    }
+}
+
+/// Open an auxiliary window (image viewer, settings, call...) showing a
+/// route of the bundled app, or focus it if it is already open. The window
+/// gets the label `aux-<label>`, which is returned.
+pub fn create_window(
+    app: &AppHandle,
+    label: &str,
+    url: &str,
+    options: WindowOptions,
+) -> Result<String, String> {
+    let valid_label = !label.is_empty()
+        && label.len() <= 64
+        && label
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'));
+    if !valid_label {
+        return Err("invalid window label".into());
+    }
+    if crate::kiosk::is_active() {
+        return Err("not available in kiosk mode".into());
+    }
+    let route = app_route(url)?;
+    let label = format!("{AUX_WINDOW_PREFIX}{label}");
+    if let Some(win) = app.get_webview_window(&label) {
+        win.show().map_err(|e| e.to_string())?;
+        win.set_focus().map_err(|e| e.to_string())?;
+        return Ok(label);
+    }
+
+    let parent = match &options.parent {
+        Some(parent) => Some(
+            app.get_webview_window(parent)
+                .ok_or_else(|| format!("parent window not found: {parent}"))?,
+        ),
+        None if options.modal => return Err("a modal window needs a parent".into()),
+        None => None,
+    };
+    let mut builder = WebviewWindowBuilder::new(app, &label, WebviewUrl::App(route.into()))
+        .title(options.title.as_deref().unwrap_or("nChat"))
+        .inner_size(options.width, options.height)
+        .resizable(options.resizable)
+        .decorations(options.decorations)
+        .center()
+        .visible(false);
+    if let (Some(w), Some(h)) = (options.min_width, options.min_height) {
+        builder = builder.min_inner_size(w, h);
+    }
+    builder = builder.transparent(options.transparent);
+    if let Some(parent) = &parent {
+        builder = builder.parent(parent).map_err(|e| e.to_string())?;
+    }
+    let win = builder.build().map_err(|e| e.to_string())?;
+    restore_zoom(&win);
+    apply_title_badge(&win);
+    crate::tray::apply_tray_only(app);
+
+    if let (true, Some(parent)) = (options.modal, &parent) {
+        parent.set_enabled(false).map_err(|e| e.to_string())?;
+        app.state::<WindowRegistry>()
+            .modals
+            .lock()
+            .unwrap()
+            .insert(label.clone(), parent.label().to_string());
+    }
+    win.show().map_err(|e| e.to_string())?;
+    win.set_focus().map_err(|e| e.to_string())?;
+    Ok(label)
+}
+
+/// Only routes of the bundled frontend (`index.html#/...`) may be loaded,
+/// so a window opened this way never shows remote content with IPC access.
+fn app_route(url: &str) -> Result<String, String> {
+    let route = url.strip_prefix('/').unwrap_or(url);
+    let valid = route.len() <= 2048
+        && route.starts_with("index.html#/")
+        && !route.contains("..")
+        && !route.contains('\\')
+        && !route.chars().any(char::is_control);
+    if !valid {
+        return Err(format!("not an app route: {url}"));
+    }
+    Ok(route.to_string())
+}
+
+/// Whether pop-out windows left open at quit are reopened at launch.
+pub fn restore_popouts_enabled(app: &AppHandle) -> bool {
+    settings::load(app, RESTORE_POPOUTS_KEY).unwrap_or(false)
+}
+
+pub fn set_restore_popouts(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    settings::save(app, RESTORE_POPOUTS_KEY, &enabled)
+}
+
+/// Reopen the pop-outs that were open at quit, if enabled, leaving the
+/// main window focused.
+pub fn restore_popouts(app: &AppHandle) {
+    let open: Vec<SavedPopout> = saved_popouts(app)
+        .into_values()
+        .filter(|saved| saved.open)
+        .collect();
+    if !restore_popouts_enabled(app) {
+        // Otherwise they would all come back once the option is turned on.
+        for saved in &open {
+            let label = format!("{CONVERSATION_WINDOW_PREFIX}{}", saved.conversation_id);
+            update_popout(app, &label, |saved| saved.open = false);
+        }
+        return;
+    }
+    for saved in open {
+        let options = ConversationWindowOptions {
+            title: saved.title.clone(),
+            ..Default::default()
+        };
+        if let Err(e) = open_conversation_window(app, &saved.conversation_id, options) {
+            log::warn!(
+                "[nchat-desktop] failed to reopen conversation window {}: {e}",
+                saved.conversation_id
+            );
+        }
+    }
+    if let Some(win) = app.get_webview_window(MAIN_WINDOW) {
+        let _ = win.set_focus();
+    }
+}
+
+/// The user closed a pop-out: remember where it was and that it is closed.
+pub fn popout_closed(app: &AppHandle, label: &str) {
+    let placement = app
+        .get_webview_window(label)
+        .and_then(|win| placement(&win));
+    update_popout(app, label, |saved| {
+        if let Some((bounds, monitor)) = placement {
+            saved.bounds = Some(bounds);
+            saved.monitor = monitor;
+        }
+        saved.open = false;
+    });
+}
+
+/// The app is quitting: remember where each open pop-out is, keeping it
+/// marked open so it can be restored.
+pub fn save_popouts(app: &AppHandle) {
+    for ConversationWindow { label, .. } in conversation_windows(app) {
+        let Some(win) = app.get_webview_window(&label) else {
+            continue;
+        };
+        if let Some((bounds, monitor)) = placement(&win) {
+            update_popout(app, &label, |saved| {
+                saved.bounds = Some(bounds);
+                saved.monitor = monitor;
+            });
+        }
+    }
+}
+
+fn saved_popouts(app: &AppHandle) -> HashMap<String, SavedPopout> {
+    settings::load(app, POPOUTS_KEY).unwrap_or_default()
+}
+
+fn update_popout(app: &AppHandle, label: &str, f: impl FnOnce(&mut SavedPopout)) {
+    let Some(conversation_id) = label.strip_prefix(CONVERSATION_WINDOW_PREFIX) else {
+        return;
+    };
+    let registry = app.state::<WindowRegistry>();
+    let _guard = registry.store_lock.lock().unwrap();
+    let mut popouts = saved_popouts(app);
+    let saved = popouts
+        .entry(label.to_string())
+        .or_insert_with(|| SavedPopout {
+            conversation_id: conversation_id.to_string(),
+            title: None,
+            bounds: None,
+            monitor: None,
+            open: false,
+            last_used: 0,
+        });
+    f(saved);
+    saved.last_used = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+    while popouts.len() > MAX_SAVED_POPOUTS {
+        let oldest = popouts
+            .iter()
+            .min_by_key(|(_, saved)| (saved.open, saved.last_used))
+            .map(|(label, _)| label.clone());
+        match oldest {
+            Some(label) => popouts.remove(&label),
+            None => break,
+        };
+    }
+    if let Err(e) = settings::save(app, POPOUTS_KEY, &popouts) {
+        log::warn!("[nchat-desktop] failed to save window state for {label}: {e}");
+    }
+}
+
+/// Bounds and monitor of `win`, unless it is minimized, maximized or full
+/// screen (in which case the last normal placement is kept).
+fn placement(win: &WebviewWindow) -> Option<(Rect, Option<String>)> {
+    let unusual = win.is_minimized().unwrap_or(false)
+        || win.is_maximized().unwrap_or(false)
+        || win.is_fullscreen().unwrap_or(false);
+    if unusual {
+        return None;
+    }
+    let position = win.outer_position().ok()?;
+    let size = win.inner_size().ok()?;
+    let monitor = win
+        .current_monitor()
+        .ok()
+        .flatten()
+        .and_then(|m| m.name().cloned());
+    let bounds = Rect {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+    };
+    Some((bounds, monitor))
+}
+
+/// Put a pop-out back where it was. The size is always restored; the
+/// position only if the same monitor is still attached and the spot is on
+/// it, so a window from an unplugged display is placed by the OS instead.
+fn place(win: &WebviewWindow, saved: &SavedPopout) -> tauri::Result<()> {
+    let Some(b) = saved.bounds else {
+        return Ok(());
+    };
+    win.set_size(PhysicalSize::new(b.width, b.height))?;
+    let on_screen = win.available_monitors()?.iter().any(|m| {
+        let (pos, size) = (m.position(), m.size());
+        let same = saved.monitor.is_none() || m.name() == saved.monitor.as_ref();
+        same && b.x >= pos.x
+            && b.y >= pos.y
+            && b.x < pos.x + size.width as i32
+            && b.y < pos.y + size.height as i32
+    });
+    if on_screen {
+        win.set_position(PhysicalPosition::new(b.x, b.y))?;
+    }
+    Ok(())
+}
+
+/// Show `count` unread messages in front of every window title, e.g.
+/// "(3) nChat — #general", so it is visible in Alt-Tab and the taskbar.
+/// Zero removes the prefix.
+pub fn set_title_badge(app: &AppHandle, count: u32) -> Result<(), String> {
+    *app.state::<WindowRegistry>().unread.lock().unwrap() = count;
+    for win in app.webview_windows().values() {
+        let title = win.title().map_err(|e| e.to_string())?;
+        win.set_title(&badged(app, strip_badge(&title)))
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Title `win` after what it shows, e.g. `#general` gives "nChat —
+/// #general", keeping the unread prefix.
+pub fn set_title(win: &WebviewWindow, context: Option<&str>) -> Result<(), String> {
+    let base = match context.map(str::trim) {
+        Some(context) if !context.is_empty() => format!("nChat — {context}"),
+        _ => "nChat".to_string(),
+    };
+    win.set_title(&badged(win.app_handle(), &base))
+        .map_err(|e| e.to_string())
+}
+
+/// Give a newly created window the current unread prefix.
+fn apply_title_badge(win: &WebviewWindow) {
+    if let Ok(title) = win.title() {
+        let _ = win.set_title(&badged(win.app_handle(), strip_badge(&title)));
+    }
+}
+
+fn badged(app: &AppHandle, title: &str) -> String {
+    match *app.state::<WindowRegistry>().unread.lock().unwrap() {
+        0 => title.to_string(),
+        n if n > 99 => format!("(99+) {title}"),
+        n => format!("({n}) {title}"),
+    }
+}
+
+/// `title` without a leading "(3) " or "(99+) " unread prefix.
+fn strip_badge(title: &str) -> &str {
+    let Some((count, rest)) = title
+        .strip_prefix('(')
+        .and_then(|title| title.split_once(") "))
+    else {
+        return title;
+    };
+    let count = count.strip_suffix('+').unwrap_or(count);
+    if !count.is_empty() && count.chars().all(|c| c.is_ascii_digit()) {
+        rest
+    } else {
+        title
+    }
+}
+
+/// The connected displays, marking the primary one and the ones under the
+/// cursor and the main window, for placement decisions such as where a
+/// picture-in-picture window or a snapped dock goes.
+pub fn displays(app: &AppHandle) -> Result<Vec<DisplayInfo>, String> {
+    // Monitors have no identity to compare, so match them by their bounds.
+    let bounds_of = |m: &tauri::Monitor| Rect {
+        x: m.position().x,
+        y: m.position().y,
+        width: m.size().width,
+        height: m.size().height,
+    };
+    let same = |a: Option<Rect>, b: Rect| {
+        a.is_some_and(|a| (a.x, a.y, a.width, a.height) == (b.x, b.y, b.width, b.height))
+    };
+    let primary = app.primary_monitor().ok().flatten().map(|m| bounds_of(&m));
+    let under_cursor = app
+        .cursor_position()
+        .ok()
+        .and_then(|p| app.monitor_from_point(p.x, p.y).ok().flatten())
+        .map(|m| bounds_of(&m));
+    let with_main = app
+        .get_webview_window(MAIN_WINDOW)
+        .and_then(|win| win.current_monitor().ok().flatten())
+        .map(|m| bounds_of(&m));
+
+    let monitors = app.available_monitors().map_err(|e| e.to_string())?;
+    Ok(monitors
+        .iter()
+        .enumerate()
+        .map(|(index, m)| {
+            let bounds = bounds_of(m);
+            let area = m.work_area();
+            DisplayInfo {
+                index,
+                name: m.name().cloned(),
+                bounds,
+                work_area: Rect {
+                    x: area.position.x,
+                    y: area.position.y,
+                    width: area.size.width,
+                    height: area.size.height,
+                },
+                scale_factor: m.scale_factor(),
+                primary: same(primary, bounds),
+                has_cursor: same(under_cursor, bounds),
+                has_main_window: same(with_main, bounds),
+            }
+        })
+        .collect())
+}
+
+/// Flash the taskbar button (Windows), bounce the Dock icon (macOS) or set
+/// the urgency hint (Linux) for `label`, e.g. when a mention arrives. Does
+/// nothing if the window is focused, or during a focus session unless the
+/// request is critical. Returns whether attention was requested.
+pub fn request_attention(
+    app: &AppHandle,
+    label: &str,
+    kind: AttentionKind,
+) -> Result<bool, String> {
+    let win = app
+        .get_webview_window(label)
+        .ok_or_else(|| format!("window not found: {label}"))?;
+    let attention = match kind {
+        AttentionKind::None => {
+            win.request_user_attention(None)
+                .map_err(|e| e.to_string())?;
+            return Ok(false);
+        }
+        AttentionKind::Informational if crate::focus::is_active(app) => return Ok(false),
+        AttentionKind::Informational => UserAttentionType::Informational,
+        AttentionKind::Critical => UserAttentionType::Critical,
+    };
+    if win.is_focused().unwrap_or(false) {
+        return Ok(false);
+    }
+    win.request_user_attention(Some(attention))
+        .map_err(|e| e.to_string())?;
+    Ok(true)
+}
@@ -0,0 +1,253 @@
+// nChat Desktop — workspace switching shortcuts
+//
+// CmdOrCtrl+Shift+] and CmdOrCtrl+Shift+[ move to the next and previous
+// workspace, and CmdOrCtrl+Alt+1 to 9 jump to a workspace by position
+// (CmdOrCtrl+1 to 9 belong to Go → Recent Channels). They are
+// registered natively so they work in every app window, pop-outs included,
+// whatever has focus inside the page; each press is broadcast as
+// `shortcut-next-workspace`, `shortcut-previous-workspace` or
+// `shortcut-jump-to-workspace` (with the 1-based position) for the main
+// window to switch. Since browsers and editors use the same keys for tabs,
+// they are only held while one of the app's windows has focus, unless the
+// user makes them system-wide.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutEvent, ShortcutState};
+
+use crate::state::settings;
+
+/// Cycling shortcuts as (action, settings key, default accelerator, event).
+/// An empty stored accelerator turns the shortcut off.
+const WORKSPACE_SHORTCUTS: &[(&str, &str, &str, &str)] = &[
+    (
+        "next-workspace",
+        "workspaces.nextShortcut",
+        "CmdOrCtrl+Shift+]",
+        "shortcut-next-workspace",
+    ),
+    (
+        "previous-workspace",
+        "workspaces.previousShortcut",
+        "CmdOrCtrl+Shift+[",
+        "shortcut-previous-workspace",
+    ),
+];
+
+/// Settings key holding the modifiers combined with 1 to 9 to jump to a
+/// workspace; an empty string turns jumping off.
+const JUMP_MODIFIERS_KEY: &str = "workspaces.jumpModifiers";
+
+const DEFAULT_JUMP_MODIFIERS: &str = "CmdOrCtrl+Alt";
+
+const JUMP_EVENT: &str = "shortcut-jump-to-workspace";
+
+/// Settings key; true keeps the shortcuts while other apps have focus.
+const SYSTEM_WIDE_KEY: &str = "workspaces.systemWide";
+
+/// Whether the shortcuts are currently registered.
+static REGISTERED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceShortcuts {
+    /// `next-workspace` and `previous-workspace`; "" when off.
+    pub shortcuts: HashMap<String, String>,
+    pub jump_modifiers: String,
+    pub system_wide: bool,
+}
+
+/// The configured cycling shortcut of each action (`next-workspace`,
+/// `previous-workspace`); empty when turned off.
+pub fn shortcuts(app: &AppHandle) -> HashMap<String, String> {
+    WORKSPACE_SHORTCUTS
+        .iter()
+        .map(|(action, key, default, _)| {
+            let accel = settings::load(app, key).unwrap_or_else(|| default.to_string());
+            (action.to_string(), accel)
+        })
+        .collect()
+}
+
+/// The modifiers of the jump shortcuts, e.g. `CmdOrCtrl+Alt`; empty when off.
+pub fn jump_modifiers(app: &AppHandle) -> String {
+    settings::load(app, JUMP_MODIFIERS_KEY).unwrap_or_else(|| DEFAULT_JUMP_MODIFIERS.to_string())
+}
+
+/// The jump shortcuts by workspace position, e.g. (1, `CmdOrCtrl+Alt+1`).
+pub fn jump_shortcuts(app: &AppHandle) -> Vec<(u8, String)> {
+    jump_accelerators(&jump_modifiers(app))
+}
+
+fn jump_accelerators(modifiers: &str) -> Vec<(u8, String)> {
+    if modifiers.is_empty() {
+        return Vec::new();
+    }
+    (1..=9)
+        .map(|position| (position, format!("{modifiers}+{position}")))
+        .collect()
+}
+
+pub fn system_wide(app: &AppHandle) -> bool {
+    settings::load(app, SYSTEM_WIDE_KEY).unwrap_or(false)
+}
+
+pub fn config(app: &AppHandle) -> WorkspaceShortcuts {
+    WorkspaceShortcuts {
+        shortcuts: shortcuts(app),
+        jump_modifiers: jump_modifiers(app),
+        system_wide: system_wide(app),
+    }
+}
+
+/// Every configured accelerator, cycling and jump ones.
+fn accelerators(app: &AppHandle) -> Vec<String> {
+    let mut accels: Vec<String> = shortcuts(app)
+        .into_values()
+        .filter(|accel| !accel.is_empty())
+        .collect();
+    accels.extend(jump_shortcuts(app).into_iter().map(|(_, accel)| accel));
+    accels
+}
+
+/// Register the shortcuts if they should be held now: always when
+/// system-wide, otherwise while an app window has focus. Another app
+/// holding one is not fatal.
+pub fn register_shortcuts(app: &AppHandle) {
+    let wanted = system_wide(app) || crate::windows::app_focused(app);
+    REGISTERED.store(wanted, Ordering::SeqCst);
+    if !wanted {
+        return;
+    }
+    let global = app.global_shortcut();
+    for accel in accelerators(app) {
+        if global.is_registered(accel.as_str()) {
+            continue;
+        }
+        if let Err(e) = global.register(accel.as_str()) {
+            log::warn!("[nchat-desktop] failed to register workspace shortcut {accel}: {e}");
+        }
+    }
+}
+
+fn unregister_shortcuts(app: &AppHandle) {
+    REGISTERED.store(false, Ordering::SeqCst);
+    let global = app.global_shortcut();
+    for accel in accelerators(app) {
+        let _ = global.unregister(accel.as_str());
+    }
+}
+
+/// Called when focus enters or leaves the app as a whole.
+pub fn on_app_focus_changed(app: &AppHandle, focused: bool) {
+    // Kiosk mode and shortcut recording have released every shortcut and
+    // restore them themselves.
+    if system_wide(app) || crate::kiosk::is_active() || crate::shortcuts::is_capturing() {
+        return;
+    }
+    if focused {
+        register_shortcuts(app);
+    } else {
+        unregister_shortcuts(app);
+    }
+}
+
+/// Change the shortcut of `action`; an empty string turns it off.
+pub fn set_shortcut(app: &AppHandle, action: &str, accel: &str) -> Result<(), String> {
+    let (_, key, _, _) = WORKSPACE_SHORTCUTS
+        .iter()
+        .find(|(a, ..)| *a == action)
+        .ok_or_else(|| format!("unknown workspace shortcut: {action}"))?;
+    let accel = accel.trim();
+    if REGISTERED.load(Ordering::SeqCst) {
+        let previous = shortcuts(app).remove(action).unwrap_or_default();
+        crate::shortcuts::rebind(app, &previous, accel)?;
+    } else if !accel.is_empty() {
+        accel
+            .parse::<Shortcut>()
+            .map_err(|e| format!("invalid shortcut {accel}: {e}"))?;
+    }
+    settings::save(app, key, &accel)
+}
+
+/// Change the modifiers of the jump shortcuts, e.g. `Alt`; an empty string
+/// turns them off. Modifiers that would take keys from menu items are
+/// refused, since a global shortcut keeps the menu item from ever firing.
+pub fn set_jump_modifiers(app: &AppHandle, modifiers: &str) -> Result<(), String> {
+    let modifiers = modifiers.trim();
+    let accels = jump_accelerators(modifiers);
+    for (_, accel) in &accels {
+        accel
+            .parse::<Shortcut>()
+            .map_err(|e| format!("invalid shortcut {accel}: {e}"))?;
+        let menu_conflict = crate::shortcuts::conflicts(app, accel, None)
+            .into_iter()
+            .find(|c| c.source == crate::shortcuts::ConflictSource::Menu);
+        if let Some(conflict) = menu_conflict {
+            return Err(format!(
+                "{accel} is already used by the menu ({})",
+                conflict.action
+            ));
+        }
+    }
+    if REGISTERED.load(Ordering::SeqCst) {
+        let global = app.global_shortcut();
+        let previous = jump_shortcuts(app);
+        for (_, accel) in &previous {
+            let _ = global.unregister(accel.as_str());
+        }
+        // Unlike a single shortcut, some of the nine may be taken; keep the
+        // ones that are free.
+        for (_, accel) in &accels {
+            if let Err(e) = global.register(accel.as_str()) {
+                log::warn!("[nchat-desktop] failed to register workspace shortcut {accel}: {e}");
+            }
+        }
+    }
+    settings::save(app, JUMP_MODIFIERS_KEY, &modifiers)
+}
+
+/// Keep the shortcuts while other apps have focus, or only while the app
+/// has it.
+pub fn set_system_wide(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    settings::save(app, SYSTEM_WIDE_KEY, &enabled)?;
+    if crate::kiosk::is_active() || crate::shortcuts::is_capturing() {
+        return Ok(());
+    }
+    if enabled || crate::windows::app_focused(app) {
+        register_shortcuts(app);
+    } else {
+        unregister_shortcuts(app);
+    }
+    Ok(())
+}
+
+/// Global shortcut handler. Returns whether the shortcut was a workspace
+/// one.
+pub fn on_shortcut(app: &AppHandle, shortcut: &Shortcut, event: ShortcutEvent) -> bool {
+    let is = |accel: &str| {
+        accel
+            .parse::<Shortcut>()
+            .is_ok_and(|own| own.id() == shortcut.id())
+    };
+    let configured = shortcuts(app);
+    let cycle = WORKSPACE_SHORTCUTS
+        .iter()
+        .find(|(action, ..)| configured.get(*action).is_some_and(|accel| is(accel)));
+    if let Some((_, _, _, event_name)) = cycle {
+        if event.state == ShortcutState::Pressed {
+            let _ = app.emit(event_name, ());
+        }
+        return true;
+    }
+    let jump = jump_shortcuts(app).into_iter().find(|(_, accel)| is(accel));
+    let Some((position, _)) = jump else {
+        return false;
+    };
+    if event.state == ShortcutState::Pressed {
+        let _ = app.emit(JUMP_EVENT, position);
+    }
+    true
+}
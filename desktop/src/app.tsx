@@ -7,6 +7,7 @@ import React from 'react';
 import { Sidebar } from '@nself-chat/ui/layout';
 import type { SidebarAdapter } from '@nself-chat/ui/layout';
 import { Spinner } from '@nself-chat/ui/primitives';
+import { reportRendererReady } from './lib/ipc';
 import { tauriAdapters } from './lib/ui-adapters';
 
 // ---------------------------------------------------------------------------
@@ -60,6 +61,14 @@ export function DesktopApp(): React.ReactElement {
       .catch(() => setReady(true));
   }, []);
 
+  React.useEffect(() => {
+    // The shell rendered; otherwise a crash here would count towards
+    // turning GPU acceleration off.
+    if (ready) {
+      reportRendererReady().catch(() => undefined);
+    }
+  }, [ready]);
+
   if (!ready) {
     return (
       <div className="flex h-screen items-center justify-center bg-background">
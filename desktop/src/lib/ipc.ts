@@ -9,6 +9,19 @@ export interface AppInfo {
   version: string;
 }
 
+export interface MediaIndex {
+  /** Absolute paths of the conversation's cached media, oldest first. */
+  items: string[];
+  /** Position of the item shown. */
+  index: number;
+}
+
+export interface SpellcheckSettings {
+  enabled: boolean;
+  /** Dictionary codes such as `en_US`; empty uses the system default. */
+  languages: string[];
+}
+
 export interface UpdateInfo {
   available: boolean;
   version: string | null;
@@ -23,6 +36,35 @@ export async function getAppInfo(): Promise<AppInfo> {
   return { name, version };
 }
 
+/**
+ * Tell the shell the first view rendered, so this launch does not count
+ * towards the software-rendering fallback.
+ */
+export async function reportRendererReady(): Promise<void> {
+  return invoke("report_renderer_ready");
+}
+
+/**
+ * From the re-auth window: show the main window again, reloaded at sign-in.
+ */
+export async function beginReauth(): Promise<void> {
+  return invoke("begin_reauth");
+}
+
+/** Media list and position the viewer window should show. */
+export async function getMediaViewerItems(): Promise<MediaIndex> {
+  return invoke<MediaIndex>("get_media_viewer_items");
+}
+
+/** Store the item the viewer moved to, so reopening it resumes there. */
+export async function setMediaViewerPosition(index: number): Promise<void> {
+  return invoke("set_media_viewer_position", { index });
+}
+
+export async function getSpellcheckSettings(): Promise<SpellcheckSettings> {
+  return invoke<SpellcheckSettings>("get_spellcheck_settings");
+}
+
 export async function windowMinimize(): Promise<void> {
   return invoke("window_minimize");
 }
@@ -0,0 +1,16 @@
+/**
+ * Spellcheck on/off for this window. The shell owns the setting (Edit →
+ * Spelling, or the settings page) and broadcasts `spellcheck:changed`;
+ * editable fields inherit the `spellcheck` attribute from the root element.
+ */
+import { listen } from "@tauri-apps/api/event";
+import { getSpellcheckSettings, type SpellcheckSettings } from "./ipc";
+
+function apply(settings: SpellcheckSettings): void {
+  document.documentElement.spellcheck = settings.enabled;
+}
+
+export function followSpellcheckSetting(): void {
+  getSpellcheckSettings().then(apply).catch(() => {});
+  void listen<SpellcheckSettings>("spellcheck:changed", (e) => apply(e.payload));
+}
@@ -2,13 +2,30 @@ import React from "react";
 import { createRoot } from "react-dom/client";
 import { DesktopRouterProvider } from "./adapters/router";
 import { DesktopApp } from "./app";
+import { followSpellcheckSetting } from "./lib/spellcheck";
+import { MediaViewerView } from "./views/media-viewer";
+import { ReauthView } from "./views/reauth";
+
+/** Secondary native windows load the shell with a hash route. */
+function rootView(): React.ReactElement {
+  switch (window.location.hash.split("?")[0]) {
+    case "#/media-viewer":
+      return <MediaViewerView />;
+    case "#/reauth":
+      return <ReauthView />;
+    default:
+      return <DesktopApp />;
+  }
+}
+
+followSpellcheckSetting();
 
 const container = document.getElementById("root");
 if (!container) throw new Error("root element not found");
 createRoot(container).render(
   <React.StrictMode>
     <DesktopRouterProvider>
-      <DesktopApp />
+      {rootView()}
     </DesktopRouterProvider>
   </React.StrictMode>
 );
@@ -0,0 +1,60 @@
+/**
+ * Fullscreen media viewer window — pages through the media cached for one
+ * conversation. Files are loaded through the asset protocol, which is scoped
+ * to the attachments cache.
+ */
+
+import React from 'react';
+import { convertFileSrc } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { getCurrentWindow } from '@tauri-apps/api/window';
+import { getMediaViewerItems, setMediaViewerPosition, type MediaIndex } from '../lib/ipc';
+
+const VIDEO_EXTENSIONS = ['mp4', 'm4v', 'mov', 'webm', 'mkv'];
+
+function isVideo(path: string): boolean {
+  const ext = path.split('.').pop()?.toLowerCase() ?? '';
+  return VIDEO_EXTENSIONS.includes(ext);
+}
+
+export function MediaViewerView(): React.ReactElement {
+  const [media, setMedia] = React.useState<MediaIndex>({ items: [], index: 0 });
+
+  React.useEffect(() => {
+    getMediaViewerItems().then(setMedia).catch(() => {});
+    const unlisten = listen<MediaIndex>('media-viewer:update', (e) => setMedia(e.payload));
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  React.useEffect(() => {
+    const onKey = (e: KeyboardEvent) => {
+      if (e.key === 'Escape') {
+        getCurrentWindow().close();
+        return;
+      }
+      const step = e.key === 'ArrowRight' ? 1 : e.key === 'ArrowLeft' ? -1 : 0;
+      const index = media.index + step;
+      if (step === 0 || index < 0 || index >= media.items.length) return;
+      setMedia({ ...media, index });
+      setMediaViewerPosition(index).catch(() => {});
+    };
+    window.addEventListener('keydown', onKey);
+    return () => window.removeEventListener('keydown', onKey);
+  }, [media]);
+
+  const path = media.items[media.index];
+  const src = path ? convertFileSrc(path) : null;
+
+  return (
+    <div className="flex h-screen items-center justify-center bg-black">
+      {src &&
+        (isVideo(path) ? (
+          <video key={src} src={src} className="max-h-full max-w-full" controls autoPlay />
+        ) : (
+          <img src={src} alt="" className="max-h-full max-w-full object-contain" />
+        ))}
+    </div>
+  );
+}
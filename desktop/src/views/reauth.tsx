@@ -0,0 +1,29 @@
+/**
+ * Re-authentication window — shown by the shell after this device was
+ * signed out remotely, while the main window is hidden.
+ */
+
+import React from 'react';
+import { EmptyState } from '@nself-chat/ui/primitives';
+import { beginReauth } from '../lib/ipc';
+
+export function ReauthView(): React.ReactElement {
+  const [error, setError] = React.useState<string | null>(null);
+
+  return (
+    <div className="flex h-screen items-center justify-center bg-background text-foreground">
+      <EmptyState
+        title="You were signed out"
+        description={
+          error ?? 'This device was signed out from another session. Sign in again to continue.'
+        }
+        action={{
+          label: 'Sign in again',
+          onClick: () => {
+            beginReauth().catch((e: unknown) => setError(String(e)));
+          },
+        }}
+      />
+    </div>
+  );
+}
@@ -0,0 +1,72 @@
+/**
+ * @jest-environment node
+ */
+
+/**
+ * Key Backup API Route Tests
+ *
+ * A backup uploaded to /api/keys/backup must come back from GET with every
+ * field a client needs to restore it, in the format POST accepts.
+ */
+
+import { NextRequest } from "next/server";
+import { GET, POST } from "../keys/backup/route";
+
+const upload = {
+  deviceId: "desktop-1",
+  keyVersion: 1,
+  encryptedData: "Y2lwaGVydGV4dA==",
+  salt: "c2FsdHNhbHRzYWx0c2FsdA==",
+  iv: "bm9uY2Vub25jZW5vbmNlbm9uY2Vub25j",
+  hmac: "bWFjbWFjbWFjbWFjbWFjbWFjbWFjbWFjbWFjbWE=",
+  kdf: { mCostKib: 65536, tCost: 3, pCost: 1 },
+};
+
+function post(body: unknown, userId = "user-1") {
+  return POST(
+    new NextRequest("http://localhost:3000/api/keys/backup", {
+      method: "POST",
+      headers: { "content-type": "application/json", "x-user-id": userId },
+      body: JSON.stringify(body),
+    }),
+  );
+}
+
+function list(query: string, userId = "user-1") {
+  return GET(
+    new NextRequest(`http://localhost:3000/api/keys/backup?${query}`, {
+      headers: { "x-user-id": userId },
+    }),
+  );
+}
+
+describe("/api/keys/backup", () => {
+  it("returns an uploaded backup in the format it was uploaded in", async () => {
+    const created = await post(upload);
+    expect(created.status).toBe(201);
+
+    const response = await list("limit=1&deviceId=desktop-1");
+    const data = await response.json();
+
+    expect(response.status).toBe(200);
+    expect(data.backups).toHaveLength(1);
+    expect(data.backups[0]).toMatchObject(upload);
+    expect(data.backups[0]).toHaveProperty("id");
+    expect(data.backups[0]).toHaveProperty("createdAt");
+  });
+
+  it("requires the KDF parameters and the HMAC", async () => {
+    const { kdf: _kdf, ...withoutKdf } = upload;
+    const { hmac: _hmac, ...withoutHmac } = upload;
+
+    expect((await post(withoutKdf)).status).toBe(400);
+    expect((await post(withoutHmac)).status).toBe(400);
+  });
+
+  it("only lists the caller's backups", async () => {
+    const response = await list("deviceId=desktop-1", "user-2");
+    const data = await response.json();
+
+    expect(data.backups).toHaveLength(0);
+  });
+});
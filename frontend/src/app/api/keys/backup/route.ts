@@ -20,8 +20,26 @@ const createBackupSchema = z.object({
   salt: z.string().min(1, "Salt is required"),
   iv: z.string().min(1, "IV is required"),
   hmac: z.string().min(1, "HMAC is required"),
+  // Argon2id parameters the backup key was derived with
+  kdf: z.object({
+    mCostKib: z.number().int().positive(),
+    tCost: z.number().int().positive(),
+    pCost: z.number().int().positive(),
+  }),
 });
 
+type CreateBackupInput = z.infer<typeof createBackupSchema>;
+
+interface StoredBackup extends CreateBackupInput {
+  id: string;
+  createdAt: string;
+  size: number;
+  checksum: string;
+}
+
+// In-memory store for demo (would be database and blob storage in production)
+const backupsByUser = new Map<string, StoredBackup[]>();
+
 const verifyBackupSchema = z.object({
   backupId: z.string().min(1, "Backup ID is required"),
   hmac: z.string().min(1, "HMAC is required"),
@@ -44,21 +62,16 @@ export async function POST(request: NextRequest) {
       );
     }
 
-    // Create backup record
-    const backup = {
+    // Create backup record; the blob is stored exactly as uploaded so a
+    // client can restore it with the same fields
+    const backup: StoredBackup = {
+      ...validated,
       id: `bak_${Date.now().toString(36)}_${randomBytes(4).toString("hex")}`,
-      userId,
-      deviceId: validated.deviceId,
-      keyVersion: validated.keyVersion,
-      algorithm: "AES-256-GCM",
-      version: 1,
       createdAt: new Date().toISOString(),
       size: Buffer.from(validated.encryptedData, "base64").length,
       checksum: computeChecksum(validated.encryptedData),
     };
-
-    // Would store encrypted backup data in secure storage
-    // The actual encrypted data would go to blob storage, not database
+    backupsByUser.set(userId, [...(backupsByUser.get(userId) ?? []), backup]);
 
     logger.info("Key backup created", {
       userId,
@@ -123,23 +136,17 @@ export async function GET(request: NextRequest) {
 
     const { searchParams } = new URL(request.url);
     const deviceId = searchParams.get("deviceId");
-    const limit = parseInt(searchParams.get("limit") || "10", 10);
+    const limit = parseInt(searchParams.get("limit") || "10", 10) || 10;
 
-    // Would fetch from database
-    const backups = [
-      {
-        id: "bak_sample1",
-        deviceId: deviceId || "device-1",
-        keyVersion: 2,
-        createdAt: new Date(Date.now() - 86400000).toISOString(),
-        size: 1024,
-        checksum: "abc123",
-      },
-    ];
+    // Newest first, in the format POST accepts
+    const matching = (backupsByUser.get(userId) ?? [])
+      .filter((backup) => !deviceId || backup.deviceId === deviceId)
+      .reverse();
+    const backups = matching.slice(0, Math.max(limit, 1));
 
     return NextResponse.json({
       backups,
-      total: backups.length,
+      total: matching.length,
     });
   } catch (error) {
     logger.error("Failed to list backups", {
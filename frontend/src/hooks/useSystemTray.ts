@@ -4,12 +4,11 @@ import { useEffect, useCallback } from "react";
 import { useRouter } from "next/navigation";
 import {
   setupTrayListeners,
-  updateTrayIcon,
-  updateTrayTooltip,
+  updateTrayState,
   setUnreadCount,
   setTrayStatus,
   type TrayEventHandlers,
-  type TrayIconType,
+  type TrayState,
   type UserStatus,
 } from "@/lib/tauri";
 import { useTauriCheck } from "./useTauri";
@@ -58,18 +57,10 @@ export function useSystemTray(options: UseSystemTrayOptions = {}) {
     router,
   ]);
 
-  const setIcon = useCallback(
-    async (iconType: TrayIconType) => {
+  const setState = useCallback(
+    async (state: TrayState) => {
       if (!isTauri) return;
-      await updateTrayIcon(iconType);
-    },
-    [isTauri],
-  );
-
-  const setTooltip = useCallback(
-    async (tooltip: string) => {
-      if (!isTauri) return;
-      await updateTrayTooltip(tooltip);
+      await updateTrayState(state);
     },
     [isTauri],
   );
@@ -91,8 +82,7 @@ export function useSystemTray(options: UseSystemTrayOptions = {}) {
   );
 
   return {
-    setIcon,
-    setTooltip,
+    setState,
     setUnread,
     setStatus,
     isAvailable: isTauri,
@@ -30,11 +30,11 @@ export {
 // System tray
 export {
   setupTrayListeners,
-  updateTrayIcon,
-  updateTrayTooltip,
+  updateTrayState,
   setUnreadCount,
   setTrayStatus,
-  type TrayIconType,
+  type TrayState,
+  type TrayChannelUnread,
   type UserStatus,
   type TrayEventHandlers,
 } from "./tray";
@@ -62,6 +62,7 @@ export {
   closeWindow,
   focusWindow,
   isWindowFocused,
+  setAppBadge,
   setBadgeCount,
   clearBadge,
   enterFullscreen,
@@ -99,6 +100,13 @@ export {
   type UpdateEventHandlers,
 } from "./updater";
 
+// Session
+export {
+  setCurrentSession,
+  setupSessionListeners,
+  type SessionEventHandlers,
+} from "./session";
+
 // Default export with all modules
 import tauriBridge from "./tauri-bridge";
 import nativeMenu from "./native-menu";
@@ -108,6 +116,7 @@ import window from "./window";
 import deeplinks from "./deeplinks";
 import autostart from "./autostart";
 import updater from "./updater";
+import session from "./session";
 
 export default {
   ...tauriBridge,
@@ -118,4 +127,5 @@ export default {
   deeplinks,
   autostart,
   updater,
+  session,
 };
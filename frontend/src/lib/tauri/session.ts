@@ -0,0 +1,64 @@
+/**
+ * Session - Shares the signed-in session with the desktop shell
+ *
+ * The shell keeps a copy of the access token for its own session checks.
+ * When that token expires it emits `session:token-expired` and waits for a
+ * refreshed one; answer by refreshing and calling setCurrentSession again.
+ * `session:revoked` means this device was signed out elsewhere.
+ */
+
+import { invoke, isTauri, listen } from "./tauri-bridge";
+
+export interface SessionEventHandlers {
+  onTokenExpired?: () => void;
+  onRevoked?: () => void;
+}
+
+/**
+ * Hand the current session to the shell
+ */
+export async function setCurrentSession(
+  serverUrl: string,
+  sessionId: string,
+  accessToken: string,
+): Promise<void> {
+  if (!isTauri()) {
+    return;
+  }
+  return invoke("set_current_session", { serverUrl, sessionId, accessToken });
+}
+
+/**
+ * Set up session event listeners
+ */
+export async function setupSessionListeners(
+  handlers: SessionEventHandlers,
+): Promise<() => void> {
+  if (!isTauri()) {
+    return () => {};
+  }
+
+  const unsubscribers: Array<() => void> = [];
+
+  if (handlers.onTokenExpired) {
+    const unsub = await listen<void>(
+      "session:token-expired",
+      handlers.onTokenExpired,
+    );
+    unsubscribers.push(unsub);
+  }
+
+  if (handlers.onRevoked) {
+    const unsub = await listen<void>("session:revoked", handlers.onRevoked);
+    unsubscribers.push(unsub);
+  }
+
+  return () => {
+    unsubscribers.forEach((unsub) => unsub());
+  };
+}
+
+export default {
+  setCurrentSession,
+  setupSessionListeners,
+};
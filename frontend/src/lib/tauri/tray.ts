@@ -7,8 +7,6 @@
 
 import { invoke, invokeOrFallback, listen, isTauri } from "./tauri-bridge";
 
-export type TrayIconType = "default" | "unread" | "muted" | "dnd";
-
 export type UserStatus = "online" | "away" | "dnd" | "invisible";
 
 export interface TrayEventHandlers {
@@ -58,64 +56,49 @@ export async function setupTrayListeners(
   };
 }
 
+/** Unread messages in one channel, as listed in the tray tooltip. */
+export interface TrayChannelUnread {
+  name: string;
+  unread: number;
+}
+
 /**
- * Update the tray icon
+ * Unread and connection summary. The tray tooltip is rendered from it
+ * natively.
  */
-export async function updateTrayIcon(iconType: TrayIconType): Promise<void> {
-  return invokeOrFallback(
-    "update_tray_icon",
-    { iconType: iconType === "default" ? "normal" : iconType },
-    undefined,
-  );
+export interface TrayState {
+  unread: number;
+  top_channels?: TrayChannelUnread[];
+  /** `connected`, `connecting`, `offline`, ... */
+  connection?: string;
+  mentions?: number;
+  direct_messages?: number;
 }
 
 /**
- * Update the tray tooltip
+ * Push the unread summary to the tray
  */
-export async function updateTrayTooltip(tooltip: string): Promise<void> {
-  return invokeOrFallback("update_tray_tooltip", { tooltip }, undefined);
+export async function updateTrayState(state: TrayState): Promise<void> {
+  return invokeOrFallback("update_tray_state", { payload: state }, undefined);
 }
 
 /**
- * Set tray icon to show unread messages count
+ * Set the unread messages count shown in the tray tooltip
  */
 export async function setUnreadCount(count: number): Promise<void> {
-  if (count > 0) {
-    await updateTrayIcon("unread");
-    await updateTrayTooltip(
-      `nchat - ${count} unread message${count > 1 ? "s" : ""}`,
-    );
-  } else {
-    await updateTrayIcon("default");
-    await updateTrayTooltip("nchat");
-  }
+  await updateTrayState({ unread: count });
 }
 
 /**
- * Set tray icon based on user status
+ * Check the tray menu item for the user's status
  */
 export async function setTrayStatus(status: UserStatus): Promise<void> {
-  switch (status) {
-    case "dnd":
-      await updateTrayIcon("dnd");
-      await updateTrayTooltip("nchat - Do Not Disturb");
-      break;
-    case "away":
-      await updateTrayTooltip("nchat - Away");
-      break;
-    case "invisible":
-      await updateTrayTooltip("nchat - Invisible");
-      break;
-    default:
-      await updateTrayIcon("default");
-      await updateTrayTooltip("nchat - Online");
-  }
+  return invokeOrFallback("set_tray_status", { status }, undefined);
 }
 
 export default {
   setupTrayListeners,
-  updateTrayIcon,
-  updateTrayTooltip,
+  updateTrayState,
   setUnreadCount,
   setTrayStatus,
 };
@@ -79,6 +79,17 @@ export async function isWindowFocused(): Promise<boolean> {
   return false;
 }
 
+/**
+ * Set the app badge: the unread count, or a plain dot. Shown on the Dock,
+ * taskbar, Linux launcher or tray icon, whichever the platform supports.
+ */
+export async function setAppBadge(
+  count: number,
+  kind: "count" | "dot" = "count",
+): Promise<void> {
+  return invokeOrFallback("set_app_badge", { count, kind }, undefined);
+}
+
 /**
  * Set the dock/taskbar badge count (macOS/Windows)
  */
@@ -158,6 +169,7 @@ export default {
   closeWindow,
   focusWindow,
   isWindowFocused,
+  setAppBadge,
   setBadgeCount,
   clearBadge,
   enterFullscreen,